@@ -0,0 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API for Web2 backends to check AuthGate access keys.
+//!
+//! A backend that receives a request signed by `key` calls
+//! `key_status(app_id, key)` against a finalized block and serves the request
+//! only for `KeyStatus::Active`.
+
+use codec::Codec;
+pub use pallet_access_keys::KeyStatus;
+
+sp_api::decl_runtime_apis! {
+	pub trait AccessKeysApi<BlockNumber> where BlockNumber: Codec {
+		/// Status of `key` for `app_id`.
+		fn key_status(app_id: [u8; 32], key: [u8; 32]) -> KeyStatus<BlockNumber>;
+		/// `(app_id, expires_at)` of the grant recorded for a lane `tx_id`;
+		/// `expires_at` is `None` unless the grant is active.
+		fn grant_of_tx(tx_id: [u8; 16]) -> Option<([u8; 32], Option<BlockNumber>)>;
+	}
+}
@@ -0,0 +1,566 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! AuthGate access keys.
+//!
+//! The private lanes sell Web2 access privately (`purchase_access`,
+//! `purchase_access_v2`, `purchase_access_coin`) and relay every accepted
+//! purchase here over XCM as `xcm_record_access_grant(app_id, nullifier, tx_id,
+//! access_key_commitment)` (pallet index 50, call index 2). Only lane sovereign
+//! accounts in `AllowedSovereigns` may record grants.
+//!
+//! A grant is keyed by the lane nullifier and carries the buyer's
+//! `access_key_commitment = BLAKE3("nulla_access_key_v1" ‖ app_id ‖ blinding)`.
+//! The buyer activates it by opening the commitment and binding a 32-byte key
+//! of their choice (e.g. an ed25519 public key) in two steps:
+//! 1. `commit_activation(BLAKE3("nulla_access_activate_v1" ‖ nullifier ‖ blinding ‖ key))`;
+//! 2. `activate_key(nullifier, blinding, key)` in a later block.
+//!
+//! The opening is only revealed in step 2, after the binding to `key` is
+//! already on-chain, so a watcher of the transaction pool cannot claim the
+//! grant for a key of its own.
+//!
+//! A purchase of a subscription plan carries `AccessTerms` (duration, tier,
+//! maximum uses); a plan-less purchase gets the app's `key_ttl`, tier 0 and
+//! unlimited uses. The app admin reports uses with `record_use` and can revoke
+//! keys. A subscription is renewed by buying another plan on any lane and
+//! applying that grant to the existing key with `renew_key`, through the same
+//! commit-then-reveal step. The renewal purchase on the lane is an ordinary
+//! access purchase and does not reference the original one. Web2 backends look
+//! keys up through `pallet-access-keys-runtime-api`.
+
+extern crate alloc;
+
+pub use pallet::*;
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+/// Domain of the access-key commitment computed by the lanes' buyers.
+pub const ACCESS_KEY_DOMAIN: &[u8] = b"nulla_access_key_v1";
+/// Domain of the activation commitment binding an opening to a key.
+pub const ACTIVATION_DOMAIN: &[u8] = b"nulla_access_activate_v1";
+
+/// BLAKE3("nulla_access_key_v1" ‖ app_id ‖ blinding).
+pub fn access_key_commitment(app_id: &[u8; 32], blinding: &[u8; 32]) -> [u8; 32] {
+	let mut h = blake3::Hasher::new();
+	h.update(ACCESS_KEY_DOMAIN);
+	h.update(app_id);
+	h.update(blinding);
+	*h.finalize().as_bytes()
+}
+
+/// BLAKE3("nulla_access_activate_v1" ‖ nullifier ‖ blinding ‖ key).
+pub fn activation_commitment(nullifier: &[u8; 32], blinding: &[u8; 32], key: &[u8; 32]) -> [u8; 32] {
+	let mut h = blake3::Hasher::new();
+	h.update(ACTIVATION_DOMAIN);
+	h.update(nullifier);
+	h.update(blinding);
+	h.update(key);
+	*h.finalize().as_bytes()
+}
+
+/// Subscription terms relayed by a lane with a plan purchase.
+/// Same SCALE layout as the lanes' `AccessTerms`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct AccessTerms {
+	/// Key lifetime in blocks of this chain, counted from activation.
+	pub duration: u32,
+	/// App-defined service tier.
+	pub tier: u8,
+	/// Uses the key allows; `0` = unlimited.
+	pub max_uses: u32,
+}
+
+/// State of an access key as seen by a Web2 backend.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum KeyStatus<BlockNumber> {
+	/// No grant of this app is bound to the key.
+	Unknown,
+	/// The key grants access at `tier` until `expires_at` (inclusive).
+	/// `uses_left` is `None` for unlimited keys.
+	Active { expires_at: BlockNumber, tier: u8, uses_left: Option<u32> },
+	Expired { expired_at: BlockNumber },
+	/// Every use allowed by the plan was recorded.
+	Exhausted,
+	Revoked,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{activation_commitment, access_key_commitment, AccessTerms, KeyStatus};
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
+
+	/// A Web2 app known to AuthGate.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub struct AppInfo<AccountId, BlockNumber> {
+		/// Account allowed to revoke keys and change `key_ttl`.
+		pub admin: AccountId,
+		/// Blocks an activated plan-less key stays valid.
+		pub key_ttl: BlockNumber,
+	}
+
+	/// Lifecycle of a grant.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub enum GrantState<BlockNumber> {
+		/// Recorded, not yet activated.
+		Pending,
+		Active { key: [u8; 32], expires_at: BlockNumber },
+		Revoked,
+		/// A renewal grant applied to the grant with this nullifier.
+		Renewed { into: [u8; 32] },
+	}
+
+	/// One private access purchase relayed by a lane.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub struct AccessGrant<AccountId, BlockNumber> {
+		pub app_id: [u8; 32],
+		pub tx_id: [u8; 16],
+		/// BLAKE3("nulla_access_key_v1" ‖ app_id ‖ blinding).
+		pub access_key_commitment: [u8; 32],
+		/// Sovereign account of the lane that recorded the grant.
+		pub lane: AccountId,
+		pub granted_at: BlockNumber,
+		/// Plan terms; `None` for a plan-less purchase.
+		pub terms: Option<AccessTerms>,
+		/// Uses recorded by the app since activation or the last renewal.
+		pub uses: u32,
+		pub state: GrantState<BlockNumber>,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin that manages lane sovereigns and apps.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum number of lane sovereigns allowed to record grants.
+		#[pallet::constant]
+		type MaxSovereigns: Get<u32>;
+	}
+
+	/// Lane sovereign accounts allowed to call `xcm_record_access_grant`.
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_sovereigns)]
+	pub type AllowedSovereigns<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxSovereigns>, ValueQuery>;
+
+	/// app_id → AppInfo.
+	#[pallet::storage]
+	#[pallet::getter(fn apps)]
+	pub type Apps<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], AppInfo<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	/// Lane nullifier → AccessGrant.
+	#[pallet::storage]
+	#[pallet::getter(fn grants)]
+	pub type Grants<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		[u8; 32],
+		AccessGrant<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Lane tx_id → nullifier of its grant.
+	#[pallet::storage]
+	#[pallet::getter(fn grant_of_tx)]
+	pub type GrantByTx<T: Config> = StorageMap<_, Blake2_128Concat, [u8; 16], [u8; 32], OptionQuery>;
+
+	/// Activation commitment → block it was submitted in.
+	#[pallet::storage]
+	pub type ActivationCommits<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], BlockNumberFor<T>, OptionQuery>;
+
+	/// (app_id, key) → nullifier of the grant the key is bound to.
+	#[pallet::storage]
+	#[pallet::getter(fn key_grant)]
+	pub type KeyIndex<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, [u8; 32], Blake2_128Concat, [u8; 32], [u8; 32], OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub allowed_sovereigns: alloc::vec::Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let sovereigns: BoundedVec<T::AccountId, T::MaxSovereigns> =
+				self.allowed_sovereigns.clone().try_into().expect("too many genesis sovereigns");
+			AllowedSovereigns::<T>::put(sovereigns);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		SovereignAdded { sovereign: T::AccountId },
+		SovereignRemoved { sovereign: T::AccountId },
+		/// A lane relayed an access purchase.
+		AccessGranted { app_id: [u8; 32], nullifier: [u8; 32], tx_id: [u8; 16] },
+		AppRegistered { app_id: [u8; 32], admin: T::AccountId, key_ttl: BlockNumberFor<T> },
+		AppKeyTtlSet { app_id: [u8; 32], key_ttl: BlockNumberFor<T> },
+		ActivationCommitted { commitment: [u8; 32] },
+		KeyActivated { app_id: [u8; 32], key: [u8; 32], expires_at: BlockNumberFor<T> },
+		KeyRevoked { app_id: [u8; 32], nullifier: [u8; 32] },
+		KeyUsed { app_id: [u8; 32], key: [u8; 32], uses: u32 },
+		/// The grant `renewal` extended the key.
+		KeyRenewed { app_id: [u8; 32], key: [u8; 32], renewal: [u8; 32], expires_at: BlockNumberFor<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Caller is not an allowed lane sovereign.
+		NotAllowedSovereign,
+		AlreadySovereign,
+		TooManySovereigns,
+		/// A grant with this nullifier or tx_id was already recorded.
+		DuplicateGrant,
+		AppAlreadyRegistered,
+		AppNotRegistered,
+		/// `key_ttl` must be non-zero.
+		ZeroKeyTtl,
+		/// Caller is neither the app admin nor `AdminOrigin`.
+		NotAppAdmin,
+		GrantNotFound,
+		/// The grant was already activated or revoked.
+		GrantNotPending,
+		ActivationAlreadyCommitted,
+		/// No activation commitment matches the opening and key.
+		ActivationNotCommitted,
+		/// The activation commitment must be from an earlier block.
+		ActivationTooEarly,
+		/// The blinding does not open the grant's access-key commitment.
+		InvalidOpening,
+		/// The key is already bound to a grant of this app.
+		KeyInUse,
+		AlreadyRevoked,
+		/// The key is not bound to an active, unexpired grant of this app.
+		KeyNotActive,
+		/// Every use allowed by the plan was recorded.
+		UsesExhausted,
+		/// Only a plan purchase can renew a key.
+		RenewalWithoutPlan,
+		/// The renewal grant is for another app.
+		RenewalAppMismatch,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow a lane sovereign account to record grants (Admin only).
+		#[pallet::call_index(0)]
+		#[pallet::weight(5_000)]
+		pub fn add_sovereign(origin: OriginFor<T>, sovereign: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			AllowedSovereigns::<T>::try_mutate(|list| -> DispatchResult {
+				ensure!(!list.contains(&sovereign), Error::<T>::AlreadySovereign);
+				list.try_push(sovereign.clone()).map_err(|_| Error::<T>::TooManySovereigns)?;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::SovereignAdded { sovereign });
+			Ok(())
+		}
+
+		/// Remove a lane sovereign account (Admin only). Its grants are kept.
+		#[pallet::call_index(1)]
+		#[pallet::weight(5_000)]
+		pub fn remove_sovereign(origin: OriginFor<T>, sovereign: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			AllowedSovereigns::<T>::try_mutate(|list| -> DispatchResult {
+				let before = list.len();
+				list.retain(|s| s != &sovereign);
+				ensure!(list.len() < before, Error::<T>::NotAllowedSovereign);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::SovereignRemoved { sovereign });
+			Ok(())
+		}
+
+		/// Record an access purchase — XCM-only from an allowed lane sovereign.
+		///
+		/// Called by `AccessKeyDispatch` on ProofHub (para 2000) and ScanProof
+		/// (para 2002). `terms` carries the plan bought, `None` for a plan-less
+		/// key. Grants of apps not yet registered here are recorded and can be
+		/// activated once the app is registered.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000)]
+		pub fn xcm_record_access_grant(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			nullifier: [u8; 32],
+			tx_id: [u8; 16],
+			access_key_commitment: [u8; 32],
+			terms: Option<AccessTerms>,
+		) -> DispatchResult {
+			let lane = ensure_signed(origin)?;
+			ensure!(AllowedSovereigns::<T>::get().contains(&lane), Error::<T>::NotAllowedSovereign);
+			ensure!(!Grants::<T>::contains_key(nullifier), Error::<T>::DuplicateGrant);
+			ensure!(!GrantByTx::<T>::contains_key(tx_id), Error::<T>::DuplicateGrant);
+
+			Grants::<T>::insert(nullifier, AccessGrant {
+				app_id,
+				tx_id,
+				access_key_commitment,
+				lane,
+				granted_at: frame_system::Pallet::<T>::block_number(),
+				terms,
+				uses: 0,
+				state: GrantState::Pending,
+			});
+			GrantByTx::<T>::insert(tx_id, nullifier);
+			Self::deposit_event(Event::AccessGranted { app_id, nullifier, tx_id });
+			Ok(())
+		}
+
+		/// Register a Web2 app with the lifetime of its plan-less keys (Admin only).
+		#[pallet::call_index(3)]
+		#[pallet::weight(5_000)]
+		pub fn register_app(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			admin: T::AccountId,
+			key_ttl: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Apps::<T>::contains_key(app_id), Error::<T>::AppAlreadyRegistered);
+			ensure!(!key_ttl.is_zero(), Error::<T>::ZeroKeyTtl);
+			Apps::<T>::insert(app_id, AppInfo { admin: admin.clone(), key_ttl });
+			Self::deposit_event(Event::AppRegistered { app_id, admin, key_ttl });
+			Ok(())
+		}
+
+		/// Change the lifetime of plan-less keys activated from now on
+		/// (app admin or Admin).
+		#[pallet::call_index(4)]
+		#[pallet::weight(5_000)]
+		pub fn set_app_key_ttl(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			key_ttl: BlockNumberFor<T>,
+		) -> DispatchResult {
+			Self::ensure_app_admin(origin, &app_id)?;
+			ensure!(!key_ttl.is_zero(), Error::<T>::ZeroKeyTtl);
+			Apps::<T>::mutate(app_id, |app| {
+				if let Some(app) = app.as_mut() {
+					app.key_ttl = key_ttl;
+				}
+			});
+			Self::deposit_event(Event::AppKeyTtlSet { app_id, key_ttl });
+			Ok(())
+		}
+
+		/// Step 1 of activation: commit to
+		/// BLAKE3("nulla_access_activate_v1" ‖ nullifier ‖ blinding ‖ key).
+		///
+		/// Any signed account may submit it; use one that is not linked to the
+		/// buyer.
+		#[pallet::call_index(5)]
+		#[pallet::weight(5_000)]
+		pub fn commit_activation(origin: OriginFor<T>, commitment: [u8; 32]) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(!ActivationCommits::<T>::contains_key(commitment), Error::<T>::ActivationAlreadyCommitted);
+			ActivationCommits::<T>::insert(commitment, frame_system::Pallet::<T>::block_number());
+			Self::deposit_event(Event::ActivationCommitted { commitment });
+			Ok(())
+		}
+
+		/// Step 2 of activation: open the grant's access-key commitment and
+		/// bind `key` to it. The matching `commit_activation` must be from an
+		/// earlier block.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000)]
+		pub fn activate_key(
+			origin: OriginFor<T>,
+			nullifier: [u8; 32],
+			blinding: [u8; 32],
+			key: [u8; 32],
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let (mut grant, commitment) = Self::open_pending(&nullifier, &blinding, &key, now)?;
+			let app = Apps::<T>::get(grant.app_id).ok_or(Error::<T>::AppNotRegistered)?;
+			ensure!(!KeyIndex::<T>::contains_key(grant.app_id, key), Error::<T>::KeyInUse);
+
+			let lifetime = match grant.terms {
+				Some(terms) => terms.duration.into(),
+				None => app.key_ttl,
+			};
+			let expires_at = now.saturating_add(lifetime);
+			grant.state = GrantState::Active { key, expires_at };
+			let app_id = grant.app_id;
+			Grants::<T>::insert(nullifier, grant);
+			KeyIndex::<T>::insert(app_id, key, nullifier);
+			ActivationCommits::<T>::remove(commitment);
+			Self::deposit_event(Event::KeyActivated { app_id, key, expires_at });
+			Ok(())
+		}
+
+		/// Revoke a grant, active or pending (app admin or Admin).
+		#[pallet::call_index(7)]
+		#[pallet::weight(5_000)]
+		pub fn revoke_key(origin: OriginFor<T>, nullifier: [u8; 32]) -> DispatchResult {
+			let mut grant = Grants::<T>::get(nullifier).ok_or(Error::<T>::GrantNotFound)?;
+			Self::ensure_app_admin(origin, &grant.app_id)?;
+			ensure!(grant.state != GrantState::Revoked, Error::<T>::AlreadyRevoked);
+			// The key stays in `KeyIndex` so it cannot be bound again.
+			grant.state = GrantState::Revoked;
+			let app_id = grant.app_id;
+			Grants::<T>::insert(nullifier, grant);
+			Self::deposit_event(Event::KeyRevoked { app_id, nullifier });
+			Ok(())
+		}
+
+		/// Record one use of an active key (app admin only).
+		///
+		/// Fails once a plan's `max_uses` is reached; unlimited keys just count.
+		#[pallet::call_index(8)]
+		#[pallet::weight(5_000)]
+		pub fn record_use(origin: OriginFor<T>, app_id: [u8; 32], key: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let app = Apps::<T>::get(app_id).ok_or(Error::<T>::AppNotRegistered)?;
+			ensure!(app.admin == who, Error::<T>::NotAppAdmin);
+			let nullifier = KeyIndex::<T>::get(app_id, key).ok_or(Error::<T>::KeyNotActive)?;
+			let uses = Grants::<T>::try_mutate(nullifier, |grant| -> Result<u32, DispatchError> {
+				let grant = grant.as_mut().ok_or(Error::<T>::GrantNotFound)?;
+				let GrantState::Active { expires_at, .. } = grant.state else {
+					return Err(Error::<T>::KeyNotActive.into());
+				};
+				ensure!(frame_system::Pallet::<T>::block_number() <= expires_at, Error::<T>::KeyNotActive);
+				if let Some(terms) = grant.terms {
+					ensure!(terms.max_uses == 0 || grant.uses < terms.max_uses, Error::<T>::UsesExhausted);
+				}
+				grant.uses = grant.uses.saturating_add(1);
+				Ok(grant.uses)
+			})?;
+			Self::deposit_event(Event::KeyUsed { app_id, key, uses });
+			Ok(())
+		}
+
+		/// Apply a pending plan grant to an existing key of the same app.
+		///
+		/// Opens `renewal`'s access-key commitment like `activate_key`; the
+		/// matching `commit_activation(BLAKE3(.. ‖ renewal ‖ blinding ‖ key))`
+		/// must be from an earlier block. The key's expiry is extended by the
+		/// new plan's duration from the later of now and its current expiry,
+		/// and it takes the new plan's tier and use limit with a fresh count.
+		/// Expired keys can be renewed; revoked ones cannot.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000)]
+		pub fn renew_key(
+			origin: OriginFor<T>,
+			renewal: [u8; 32],
+			blinding: [u8; 32],
+			key: [u8; 32],
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let (mut renewal_grant, commitment) = Self::open_pending(&renewal, &blinding, &key, now)?;
+			let terms = renewal_grant.terms.ok_or(Error::<T>::RenewalWithoutPlan)?;
+			let app_id = renewal_grant.app_id;
+			let nullifier = KeyIndex::<T>::get(app_id, key).ok_or(Error::<T>::KeyNotActive)?;
+
+			let mut grant = Grants::<T>::get(nullifier).ok_or(Error::<T>::GrantNotFound)?;
+			ensure!(grant.app_id == app_id, Error::<T>::RenewalAppMismatch);
+			let GrantState::Active { expires_at, .. } = grant.state else {
+				return Err(Error::<T>::KeyNotActive.into());
+			};
+			let expires_at = expires_at.max(now).saturating_add(terms.duration.into());
+			grant.state = GrantState::Active { key, expires_at };
+			grant.terms = Some(terms);
+			grant.uses = 0;
+			renewal_grant.state = GrantState::Renewed { into: nullifier };
+
+			Grants::<T>::insert(nullifier, grant);
+			Grants::<T>::insert(renewal, renewal_grant);
+			ActivationCommits::<T>::remove(commitment);
+			Self::deposit_event(Event::KeyRenewed { app_id, key, renewal, expires_at });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks the activation commitment of (`nullifier`, `blinding`, `key`)
+		/// is from an earlier block and `blinding` opens the pending grant.
+		#[allow(clippy::type_complexity)]
+		fn open_pending(
+			nullifier: &[u8; 32],
+			blinding: &[u8; 32],
+			key: &[u8; 32],
+			now: BlockNumberFor<T>,
+		) -> Result<(AccessGrant<T::AccountId, BlockNumberFor<T>>, [u8; 32]), DispatchError> {
+			let commitment = activation_commitment(nullifier, blinding, key);
+			let committed_at =
+				ActivationCommits::<T>::get(commitment).ok_or(Error::<T>::ActivationNotCommitted)?;
+			ensure!(committed_at < now, Error::<T>::ActivationTooEarly);
+
+			let grant = Grants::<T>::get(nullifier).ok_or(Error::<T>::GrantNotFound)?;
+			ensure!(grant.state == GrantState::Pending, Error::<T>::GrantNotPending);
+			ensure!(
+				access_key_commitment(&grant.app_id, blinding) == grant.access_key_commitment,
+				Error::<T>::InvalidOpening
+			);
+			Ok((grant, commitment))
+		}
+
+		/// Accepts `AdminOrigin` or the signed admin of a registered app.
+		fn ensure_app_admin(origin: OriginFor<T>, app_id: &[u8; 32]) -> DispatchResult {
+			let origin = match T::AdminOrigin::try_origin(origin) {
+				Ok(_) => {
+					ensure!(Apps::<T>::contains_key(app_id), Error::<T>::AppNotRegistered);
+					return Ok(());
+				}
+				Err(origin) => origin,
+			};
+			let who = ensure_signed(origin)?;
+			let app = Apps::<T>::get(app_id).ok_or(Error::<T>::AppNotRegistered)?;
+			ensure!(app.admin == who, Error::<T>::NotAppAdmin);
+			Ok(())
+		}
+
+		/// Status of `key` for `app_id` at the current block.
+		pub fn key_status(app_id: [u8; 32], key: [u8; 32]) -> KeyStatus<BlockNumberFor<T>> {
+			let Some(grant) = KeyIndex::<T>::get(app_id, key).and_then(Grants::<T>::get) else {
+				return KeyStatus::Unknown;
+			};
+			match grant.state {
+				GrantState::Active { expires_at, .. } => {
+					if frame_system::Pallet::<T>::block_number() > expires_at {
+						return KeyStatus::Expired { expired_at: expires_at };
+					}
+					let (tier, uses_left) = match grant.terms {
+						Some(terms) if terms.max_uses > 0 => {
+							(terms.tier, Some(terms.max_uses.saturating_sub(grant.uses)))
+						}
+						Some(terms) => (terms.tier, None),
+						None => (0, None),
+					};
+					if uses_left == Some(0) {
+						return KeyStatus::Exhausted;
+					}
+					KeyStatus::Active { expires_at, tier, uses_left }
+				}
+				GrantState::Revoked => KeyStatus::Revoked,
+				GrantState::Pending | GrantState::Renewed { .. } => KeyStatus::Unknown,
+			}
+		}
+
+		/// `(app_id, expires_at)` of the grant recorded for a lane `tx_id`;
+		/// `expires_at` is `None` unless the grant is active.
+		pub fn tx_grant(tx_id: [u8; 16]) -> Option<([u8; 32], Option<BlockNumberFor<T>>)> {
+			let grant = GrantByTx::<T>::get(tx_id).and_then(Grants::<T>::get)?;
+			let expires_at = match grant.state {
+				GrantState::Active { expires_at, .. } => Some(expires_at),
+				_ => None,
+			};
+			Some((grant.app_id, expires_at))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
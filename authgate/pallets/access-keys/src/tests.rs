@@ -0,0 +1,494 @@
+use crate as pallet_access_keys;
+use crate::{
+	access_key_commitment, activation_commitment, AccessTerms, ActivationCommits, Error, Event, GrantState, Grants,
+	KeyIndex, KeyStatus,
+};
+use frame_support::{assert_noop, assert_ok, derive_impl, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_runtime::{traits::BadOrigin, BuildStorage, DispatchResult};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		AccessKeys: pallet_access_keys,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+impl pallet_access_keys::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxSovereigns = ConstU32<2>;
+}
+
+const LANE: u64 = 1;
+const OTHER_LANE: u64 = 2;
+const APP_ADMIN: u64 = 3;
+const RELAYER: u64 = 4;
+const WATCHER: u64 = 5;
+const APP: [u8; 32] = [0xa1; 32];
+const KEY_TTL: u64 = 100;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_access_keys::GenesisConfig::<Test> { allowed_sovereigns: vec![LANE] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// `new_test_ext` with `APP` registered, administered by `APP_ADMIN`.
+fn app_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(AccessKeys::register_app(RuntimeOrigin::root(), APP, APP_ADMIN, KEY_TTL));
+	});
+	ext
+}
+
+fn nullifier(n: u8) -> [u8; 32] {
+	[n; 32]
+}
+
+fn blinding(n: u8) -> [u8; 32] {
+	[n.wrapping_add(0x40); 32]
+}
+
+fn key(n: u8) -> [u8; 32] {
+	[n.wrapping_add(0x80); 32]
+}
+
+/// Lane purchase `n` of `APP`, recorded by `LANE`.
+fn grant(n: u8, terms: Option<AccessTerms>) {
+	assert_ok!(AccessKeys::xcm_record_access_grant(
+		RuntimeOrigin::signed(LANE),
+		APP,
+		nullifier(n),
+		[n; 16],
+		access_key_commitment(&APP, &blinding(n)),
+		terms,
+	));
+}
+
+/// Plan-less grant `n` relayed by `lane` under `tx_id`.
+fn record(lane: u64, n: u8, tx_id: [u8; 16]) -> DispatchResult {
+	AccessKeys::xcm_record_access_grant(
+		RuntimeOrigin::signed(lane),
+		APP,
+		nullifier(n),
+		tx_id,
+		access_key_commitment(&APP, &blinding(n)),
+		None,
+	)
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+}
+
+/// Commits in this block and activates grant `n` for `key(n)` in the next.
+fn activate(n: u8) {
+	assert_ok!(AccessKeys::commit_activation(
+		RuntimeOrigin::signed(RELAYER),
+		activation_commitment(&nullifier(n), &blinding(n), &key(n)),
+	));
+	run_to(System::block_number() + 1);
+	assert_ok!(AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(n), blinding(n), key(n)));
+}
+
+#[test]
+fn only_allowed_sovereigns_record_grants() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(record(OTHER_LANE, 1, [1; 16]), Error::<Test>::NotAllowedSovereign);
+
+		// Recorded before the app is registered.
+		grant(1, None);
+		System::assert_last_event(Event::AccessGranted { app_id: APP, nullifier: nullifier(1), tx_id: [1; 16] }.into());
+		assert_eq!(Grants::<Test>::get(nullifier(1)).unwrap().state, GrantState::Pending);
+		assert_eq!(AccessKeys::tx_grant([1; 16]), Some((APP, None)));
+
+		assert_noop!(record(LANE, 1, [2; 16]), Error::<Test>::DuplicateGrant);
+		assert_noop!(record(LANE, 2, [1; 16]), Error::<Test>::DuplicateGrant);
+
+		assert_noop!(AccessKeys::add_sovereign(RuntimeOrigin::signed(LANE), OTHER_LANE), BadOrigin);
+		assert_noop!(AccessKeys::add_sovereign(RuntimeOrigin::root(), LANE), Error::<Test>::AlreadySovereign);
+		assert_ok!(AccessKeys::add_sovereign(RuntimeOrigin::root(), OTHER_LANE));
+		assert_noop!(AccessKeys::add_sovereign(RuntimeOrigin::root(), 9), Error::<Test>::TooManySovereigns);
+
+		assert_ok!(AccessKeys::remove_sovereign(RuntimeOrigin::root(), LANE));
+		assert_noop!(record(LANE, 3, [3; 16]), Error::<Test>::NotAllowedSovereign);
+		// Grants of a removed lane are kept.
+		assert!(Grants::<Test>::contains_key(nullifier(1)));
+	});
+}
+
+#[test]
+fn activation_opens_the_commitment_a_block_later() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		let commitment = activation_commitment(&nullifier(1), &blinding(1), &key(1));
+		assert_ok!(AccessKeys::commit_activation(RuntimeOrigin::signed(RELAYER), commitment));
+		assert_noop!(
+			AccessKeys::commit_activation(RuntimeOrigin::signed(RELAYER), commitment),
+			Error::<Test>::ActivationAlreadyCommitted
+		);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(1)),
+			Error::<Test>::ActivationTooEarly
+		);
+
+		run_to(2);
+		// An opening or key other than the committed one has no commitment.
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(2)),
+			Error::<Test>::ActivationNotCommitted
+		);
+		assert_ok!(AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(1)));
+		System::assert_last_event(Event::KeyActivated { app_id: APP, key: key(1), expires_at: 2 + KEY_TTL }.into());
+		assert!(!ActivationCommits::<Test>::contains_key(commitment));
+		assert_eq!(KeyIndex::<Test>::get(APP, key(1)), Some(nullifier(1)));
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 2 + KEY_TTL, tier: 0, uses_left: None }
+		);
+		assert_eq!(AccessKeys::tx_grant([1; 16]), Some((APP, Some(2 + KEY_TTL))));
+		assert_eq!(AccessKeys::key_status(APP, key(2)), KeyStatus::Unknown);
+	});
+}
+
+#[test]
+fn activation_needs_the_commitment_opening() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		// Committed to a blinding that does not open the grant.
+		let wrong = blinding(9);
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(1), &wrong, &key(1)),
+		));
+		run_to(2);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), wrong, key(1)),
+			Error::<Test>::InvalidOpening
+		);
+	});
+}
+
+#[test]
+fn revealed_opening_cannot_be_claimed_for_another_key() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		activate(1);
+		// A watcher who saw the opening commits to its own key: too late.
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(WATCHER),
+			activation_commitment(&nullifier(1), &blinding(1), &key(5)),
+		));
+		run_to(3);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(WATCHER), nullifier(1), blinding(1), key(5)),
+			Error::<Test>::GrantNotPending
+		);
+	});
+}
+
+#[test]
+fn grant_of_an_unregistered_app_waits_for_registration() {
+	new_test_ext().execute_with(|| {
+		grant(1, None);
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(1), &blinding(1), &key(1)),
+		));
+		run_to(2);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(1)),
+			Error::<Test>::AppNotRegistered
+		);
+		assert_noop!(
+			AccessKeys::register_app(RuntimeOrigin::root(), APP, APP_ADMIN, 0),
+			Error::<Test>::ZeroKeyTtl
+		);
+		assert_ok!(AccessKeys::register_app(RuntimeOrigin::root(), APP, APP_ADMIN, KEY_TTL));
+		assert_ok!(AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(1)));
+	});
+}
+
+#[test]
+fn key_is_bound_to_one_grant_per_app() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		grant(2, None);
+		activate(1);
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(2), &blinding(2), &key(1)),
+		));
+		run_to(3);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(2), blinding(2), key(1)),
+			Error::<Test>::KeyInUse
+		);
+	});
+}
+
+#[test]
+fn key_expires_after_its_lifetime() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		activate(1);
+		let expires_at = 2 + KEY_TTL;
+		// A new lifetime only applies to keys activated afterwards.
+		assert_noop!(
+			AccessKeys::set_app_key_ttl(RuntimeOrigin::signed(RELAYER), APP, 10),
+			Error::<Test>::NotAppAdmin
+		);
+		assert_ok!(AccessKeys::set_app_key_ttl(RuntimeOrigin::signed(APP_ADMIN), APP, 10));
+
+		run_to(expires_at);
+		assert_ok!(AccessKeys::record_use(RuntimeOrigin::signed(APP_ADMIN), APP, key(1)));
+		assert!(matches!(AccessKeys::key_status(APP, key(1)), KeyStatus::Active { .. }));
+
+		run_to(expires_at + 1);
+		assert_eq!(AccessKeys::key_status(APP, key(1)), KeyStatus::Expired { expired_at: expires_at });
+		assert_noop!(
+			AccessKeys::record_use(RuntimeOrigin::signed(APP_ADMIN), APP, key(1)),
+			Error::<Test>::KeyNotActive
+		);
+
+		grant(2, None);
+		activate(2);
+		assert_eq!(
+			AccessKeys::key_status(APP, key(2)),
+			KeyStatus::Active { expires_at: expires_at + 2 + 10, tier: 0, uses_left: None }
+		);
+	});
+}
+
+#[test]
+fn revoked_key_stays_bound() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		activate(1);
+		assert_noop!(AccessKeys::revoke_key(RuntimeOrigin::signed(RELAYER), nullifier(1)), Error::<Test>::NotAppAdmin);
+		assert_noop!(AccessKeys::revoke_key(RuntimeOrigin::root(), nullifier(9)), Error::<Test>::GrantNotFound);
+		assert_ok!(AccessKeys::revoke_key(RuntimeOrigin::signed(APP_ADMIN), nullifier(1)));
+		System::assert_last_event(Event::KeyRevoked { app_id: APP, nullifier: nullifier(1) }.into());
+		assert_eq!(AccessKeys::key_status(APP, key(1)), KeyStatus::Revoked);
+		assert_eq!(AccessKeys::tx_grant([1; 16]), Some((APP, None)));
+		assert_noop!(AccessKeys::revoke_key(RuntimeOrigin::root(), nullifier(1)), Error::<Test>::AlreadyRevoked);
+		assert_noop!(
+			AccessKeys::record_use(RuntimeOrigin::signed(APP_ADMIN), APP, key(1)),
+			Error::<Test>::KeyNotActive
+		);
+
+		// The revoked key cannot be bound to a fresh grant.
+		grant(2, None);
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(2), &blinding(2), &key(1)),
+		));
+		run_to(3);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(2), blinding(2), key(1)),
+			Error::<Test>::KeyInUse
+		);
+	});
+}
+
+#[test]
+fn pending_grant_can_be_revoked() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		assert_ok!(AccessKeys::revoke_key(RuntimeOrigin::root(), nullifier(1)));
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(1), &blinding(1), &key(1)),
+		));
+		run_to(2);
+		assert_noop!(
+			AccessKeys::activate_key(RuntimeOrigin::signed(RELAYER), nullifier(1), blinding(1), key(1)),
+			Error::<Test>::GrantNotPending
+		);
+	});
+}
+
+fn plan(duration: u32, tier: u8, max_uses: u32) -> Option<AccessTerms> {
+	Some(AccessTerms { duration, tier, max_uses })
+}
+
+fn record_uses(n: u8, uses: u32) {
+	for _ in 0..uses {
+		assert_ok!(AccessKeys::record_use(RuntimeOrigin::signed(APP_ADMIN), APP, key(n)));
+	}
+}
+
+/// Commits in this block and applies renewal grant `renewal` to `key(n)` in the next.
+fn renew(renewal: u8, n: u8) {
+	assert_ok!(AccessKeys::commit_activation(
+		RuntimeOrigin::signed(RELAYER),
+		activation_commitment(&nullifier(renewal), &blinding(renewal), &key(n)),
+	));
+	run_to(System::block_number() + 1);
+	assert_ok!(AccessKeys::renew_key(RuntimeOrigin::signed(RELAYER), nullifier(renewal), blinding(renewal), key(n)));
+}
+
+#[test]
+fn use_limit_runs_out() {
+	app_ext().execute_with(|| {
+		grant(1, plan(50, 2, 3));
+		activate(1);
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 52, tier: 2, uses_left: Some(3) }
+		);
+		assert_noop!(
+			AccessKeys::record_use(RuntimeOrigin::signed(RELAYER), APP, key(1)),
+			Error::<Test>::NotAppAdmin
+		);
+
+		record_uses(1, 2);
+		System::assert_last_event(Event::KeyUsed { app_id: APP, key: key(1), uses: 2 }.into());
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 52, tier: 2, uses_left: Some(1) }
+		);
+		record_uses(1, 1);
+		assert_eq!(AccessKeys::key_status(APP, key(1)), KeyStatus::Exhausted);
+		assert_noop!(
+			AccessKeys::record_use(RuntimeOrigin::signed(APP_ADMIN), APP, key(1)),
+			Error::<Test>::UsesExhausted
+		);
+		// Exhausted, not expired: the key is still active until its expiry.
+		assert_eq!(AccessKeys::tx_grant([1; 16]), Some((APP, Some(52))));
+	});
+}
+
+#[test]
+fn plan_without_a_use_limit_just_counts() {
+	app_ext().execute_with(|| {
+		grant(1, plan(50, 1, 0));
+		activate(1);
+		record_uses(1, 5);
+		assert_eq!(Grants::<Test>::get(nullifier(1)).unwrap().uses, 5);
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 52, tier: 1, uses_left: None }
+		);
+	});
+}
+
+#[test]
+fn renewal_extends_the_key_under_the_new_plan() {
+	app_ext().execute_with(|| {
+		grant(1, plan(50, 1, 2));
+		activate(1);
+		record_uses(1, 2);
+		assert_eq!(AccessKeys::key_status(APP, key(1)), KeyStatus::Exhausted);
+
+		// Renewed before expiry: extended from the current expiry.
+		grant(2, plan(30, 3, 10));
+		renew(2, 1);
+		System::assert_last_event(
+			Event::KeyRenewed { app_id: APP, key: key(1), renewal: nullifier(2), expires_at: 52 + 30 }.into(),
+		);
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 82, tier: 3, uses_left: Some(10) }
+		);
+		assert_eq!(Grants::<Test>::get(nullifier(2)).unwrap().state, GrantState::Renewed { into: nullifier(1) });
+		// The renewal grant's own tx resolves, but to no active key.
+		assert_eq!(AccessKeys::tx_grant([2; 16]), Some((APP, None)));
+		assert_eq!(AccessKeys::tx_grant([1; 16]), Some((APP, Some(82))));
+
+		// Renewed after expiry: extended from now.
+		run_to(100);
+		assert_eq!(AccessKeys::key_status(APP, key(1)), KeyStatus::Expired { expired_at: 82 });
+		grant(3, plan(30, 3, 0));
+		renew(3, 1);
+		assert_eq!(
+			AccessKeys::key_status(APP, key(1)),
+			KeyStatus::Active { expires_at: 101 + 30, tier: 3, uses_left: None }
+		);
+	});
+}
+
+#[test]
+fn renewal_needs_a_plan_and_a_live_grant() {
+	app_ext().execute_with(|| {
+		grant(1, None);
+		activate(1);
+		grant(2, None);
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(2), &blinding(2), &key(1)),
+		));
+		run_to(3);
+		assert_noop!(
+			AccessKeys::renew_key(RuntimeOrigin::signed(RELAYER), nullifier(2), blinding(2), key(1)),
+			Error::<Test>::RenewalWithoutPlan
+		);
+
+		grant(3, plan(30, 1, 0));
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(3), &blinding(3), &key(1)),
+		));
+		assert_ok!(AccessKeys::revoke_key(RuntimeOrigin::signed(APP_ADMIN), nullifier(1)));
+		run_to(4);
+		assert_noop!(
+			AccessKeys::renew_key(RuntimeOrigin::signed(RELAYER), nullifier(3), blinding(3), key(1)),
+			Error::<Test>::KeyNotActive
+		);
+		// A renewal cannot be applied to a key that was never activated either.
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(RELAYER),
+			activation_commitment(&nullifier(3), &blinding(3), &key(7)),
+		));
+		run_to(5);
+		assert_noop!(
+			AccessKeys::renew_key(RuntimeOrigin::signed(RELAYER), nullifier(3), blinding(3), key(7)),
+			Error::<Test>::KeyNotActive
+		);
+	});
+}
+
+#[test]
+fn renewal_is_unlinked_until_the_key_holder_applies_it() {
+	app_ext().execute_with(|| {
+		grant(1, plan(50, 1, 0));
+		activate(1);
+		let original = Grants::<Test>::get(nullifier(1)).unwrap();
+
+		// The renewal purchase shares nothing with the original grant.
+		grant(2, plan(50, 1, 0));
+		let renewal = Grants::<Test>::get(nullifier(2)).unwrap();
+		assert_ne!(renewal.tx_id, original.tx_id);
+		assert_ne!(renewal.access_key_commitment, original.access_key_commitment);
+		assert_eq!(renewal.state, GrantState::Pending);
+		assert_eq!(AccessKeys::tx_grant([2; 16]), Some((APP, None)));
+
+		// Knowing the original grant's opening does not open the renewal.
+		assert_ok!(AccessKeys::commit_activation(
+			RuntimeOrigin::signed(WATCHER),
+			activation_commitment(&nullifier(2), &blinding(1), &key(1)),
+		));
+		run_to(3);
+		assert_noop!(
+			AccessKeys::renew_key(RuntimeOrigin::signed(WATCHER), nullifier(2), blinding(1), key(1)),
+			Error::<Test>::InvalidOpening
+		);
+		assert_eq!(Grants::<Test>::get(nullifier(1)), Some(original));
+
+		renew(2, 1);
+		assert_eq!(Grants::<Test>::get(nullifier(2)).unwrap().state, GrantState::Renewed { into: nullifier(1) });
+	});
+}
@@ -0,0 +1,170 @@
+//! ScanProof one-of-many coins: `coin deposit-open` and `coin spend`.
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use nulla_wallet::public::{CoinSpendPublic, CoinWithdrawPublic};
+use parity_scale_codec::Encode;
+use scanproof_verifier::one_of_many::{
+    blinding_sub, coin_commit, pad_group, pedersen_commit, prove, prove_deposit_open, prove_g1_pok, NO_CHANGE,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::json::{seed_or_random, Bytes, H16, H32};
+
+/// Proof context of a coin spend: BLAKE2-256 of its SCALE public inputs.
+pub fn context(public_inputs: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(public_inputs).into()
+}
+
+#[derive(Deserialize)]
+pub struct DepositOpenInput {
+    serial: H32,
+    value: u64,
+    blinding: H32,
+    /// AccountId of the `deposit_coin` signer; the proof is bound to it.
+    depositor: H32,
+    #[serde(default)]
+    seed: Option<H32>,
+}
+
+#[derive(Serialize)]
+pub struct DepositOpenOutput {
+    coin: H32,
+    amount: u64,
+    open_proof: Bytes,
+}
+
+/// Coin and opening proof for `deposit_coin`.
+pub fn deposit_open(input: DepositOpenInput) -> Result<DepositOpenOutput, String> {
+    let coin = coin_commit(&input.serial.0, input.value, &input.blinding.0);
+    let proof = prove_deposit_open(
+        &coin,
+        input.value,
+        &input.serial.0,
+        &input.blinding.0,
+        &input.depositor.0,
+        seed_or_random(input.seed),
+    );
+    Ok(DepositOpenOutput { coin: H32::from(coin), amount: input.value, open_proof: Bytes(proof) })
+}
+
+/// Secrets of the change coin.
+#[derive(Deserialize)]
+pub struct ChangeCoin {
+    serial: H32,
+    blinding: H32,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum CoinAction {
+    /// `purchase_coin`, or `purchase_access_coin` with the app id as `rwa_id`
+    /// and the access-key commitment as `ownership_commitment`.
+    Purchase {
+        price: u64,
+        rwa_id: H32,
+        quantity: u64,
+        #[serde(default)]
+        rate_round: Option<u32>,
+        #[serde(default)]
+        access_plan: Option<u8>,
+        ownership_commitment: H32,
+        #[serde(default)]
+        change: Option<ChangeCoin>,
+    },
+    /// `withdraw_coin` of the whole coin.
+    Withdraw { destination: H32 },
+}
+
+#[derive(Deserialize)]
+pub struct SpendInput {
+    /// Coins of the group as stored in `CoinGroups`; padded here.
+    coins: Vec<H32>,
+    group_id: u32,
+    index: usize,
+    serial: H32,
+    value: u64,
+    blinding: H32,
+    tx_id: H16,
+    #[serde(flatten)]
+    action: CoinAction,
+    #[serde(default)]
+    seed: Option<H32>,
+}
+
+#[derive(Serialize)]
+pub struct SpendOutput {
+    public_inputs: Bytes,
+    one_of_many_proof: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    g1_pok: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change_coin: Option<H32>,
+}
+
+/// One-of-many spend of the coin at `index`.
+pub fn spend(input: SpendInput) -> Result<SpendOutput, String> {
+    let coins: Vec<[u8; 32]> = input.coins.iter().map(|c| c.0).collect();
+    let serial = input.serial.0;
+    if coins.get(input.index) != Some(&coin_commit(&serial, input.value, &input.blinding.0)) {
+        return Err("coin at `index` does not open to (serial, value, blinding)".into());
+    }
+    let seed = seed_or_random(input.seed);
+
+    let (public_inputs, price, change, witness_blinding, change_secrets) = match input.action {
+        CoinAction::Purchase { price, rwa_id, quantity, rate_round, access_plan, ownership_commitment, change } => {
+            let cv = input
+                .value
+                .checked_sub(price)
+                .ok_or_else(|| format!("coin value {} does not cover {price}", input.value))?;
+            let (change_pt, change_coin, witness_blinding, secrets) = match (cv, change) {
+                (0, _) => (NO_CHANGE, [0u8; 32], input.blinding.0, None),
+                (_, Some(c)) => (
+                    pedersen_commit(cv, &c.blinding.0),
+                    coin_commit(&c.serial.0, cv, &c.blinding.0),
+                    blinding_sub(&input.blinding.0, &c.blinding.0),
+                    Some(c.serial.0),
+                ),
+                (_, None) => return Err(format!("{cv} left over: `change` secrets are required")),
+            };
+            let public = CoinSpendPublic {
+                group_id: input.group_id,
+                serial,
+                rwa_id: rwa_id.0,
+                quantity,
+                rate_round,
+                access_plan,
+                tx_id: input.tx_id.0,
+                ownership_commitment: ownership_commitment.0,
+                change: change_pt,
+                change_coin,
+            };
+            (public.encode(), price, change_pt, witness_blinding, secrets.map(|s| (s, change_coin)))
+        }
+        CoinAction::Withdraw { destination } => {
+            let public = CoinWithdrawPublic {
+                group_id: input.group_id,
+                serial,
+                amount: input.value,
+                destination: destination.0,
+                tx_id: input.tx_id.0,
+            };
+            (public.encode(), input.value, NO_CHANGE, input.blinding.0, None)
+        }
+    };
+
+    let ctx = context(&public_inputs);
+    let padded = pad_group(&coins, input.group_id);
+    let proof = prove(&padded, input.index, &serial, price, &change, &witness_blinding, &ctx, seed)
+        .ok_or("one-of-many witness does not hold")?;
+    let (g1_pok, change_coin) = match change_secrets {
+        Some((new_serial, change_coin)) => {
+            // A distinct nonce seed for the second proof.
+            let pok_seed: [u8; 32] = blake2::Blake2s256::digest(seed).into();
+            let pok = prove_g1_pok(&change_coin, &change, &new_serial, &ctx, pok_seed);
+            (Some(Bytes(pok)), Some(H32::from(change_coin)))
+        }
+        None => (None, None),
+    };
+    Ok(SpendOutput { public_inputs: Bytes(public_inputs), one_of_many_proof: Bytes(proof), g1_pok, change_coin })
+}
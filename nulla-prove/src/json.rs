@@ -0,0 +1,153 @@
+//! JSON value types shared by the commands. Byte strings are hex, with or
+//! without a `0x` prefix on input and without one on output.
+
+use nulla_wallet::KeyPair;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Fixed-length byte string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hex<const N: usize>(pub [u8; N]);
+
+pub type H32 = Hex<32>;
+pub type H16 = Hex<16>;
+
+/// Variable-length byte string.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl<const N: usize> From<[u8; N]> for Hex<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Hex(bytes)
+    }
+}
+
+fn decode_hex<E: serde::de::Error>(s: &str) -> Result<Vec<u8>, E> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(E::custom)
+}
+
+impl<const N: usize> Serialize for Hex<N> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(self.0))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Hex<N> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let bytes = decode_hex::<D::Error>(&String::deserialize(d)?)?;
+        let len = bytes.len();
+        bytes
+            .try_into()
+            .map(Hex)
+            .map_err(|_| D::Error::custom(format!("expected {N} bytes, got {len}")))
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&hex::encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        decode_hex::<D::Error>(&String::deserialize(d)?).map(Bytes)
+    }
+}
+
+/// Blinding and owner of a note: the pk digest directly, or the 32-byte
+/// seed of the owner's ML-DSA-44 key.
+#[derive(Deserialize)]
+pub struct Keyed {
+    pub blinding: H32,
+    #[serde(default)]
+    pub pkd: Option<H32>,
+    #[serde(default)]
+    pub key_seed: Option<H32>,
+}
+
+impl Keyed {
+    pub fn pkd(&self) -> Result<[u8; 32], String> {
+        match (self.pkd, self.key_seed) {
+            (Some(pkd), _) => Ok(pkd.0),
+            (None, Some(seed)) => Ok(KeyPair::from_seed(&seed.0).pk_digest()),
+            (None, None) => Err("note needs `pkd` or `key_seed`".into()),
+        }
+    }
+}
+
+/// A v2 note opening.
+#[derive(Deserialize)]
+pub struct Note {
+    pub value: u64,
+    #[serde(flatten)]
+    pub keyed: Keyed,
+}
+
+/// Merkle path of a leaf, as produced by `nulla_wallet::TreeMirror::path`.
+#[derive(Deserialize)]
+pub struct Path {
+    pub leaf_index: u32,
+    pub siblings: Vec<H32>,
+}
+
+impl Path {
+    pub fn siblings(&self) -> Result<[[u8; 32]; nulla_wallet::tree::MERKLE_DEPTH], String> {
+        let siblings: Vec<[u8; 32]> = self.siblings.iter().map(|s| s.0).collect();
+        let len = siblings.len();
+        siblings
+            .try_into()
+            .map_err(|_| format!("expected {} siblings, got {len}", nulla_wallet::tree::MERKLE_DEPTH))
+    }
+}
+
+/// ProofHub paymaster fee credit: its opening and its path in the fee
+/// credit tree. `value` is the lane's base fee.
+#[derive(Deserialize)]
+pub struct Fee {
+    pub value: u64,
+    pub fee_blinding: H32,
+    pub path: Path,
+}
+
+impl Fee {
+    /// Ticket for the spend with SCALE `public_inputs`.
+    pub fn ticket(&self, public_inputs: &[u8]) -> Result<nulla_wallet::public::FeeTicket, String> {
+        let path = nulla_wallet::MerklePath { leaf_index: self.path.leaf_index, siblings: self.path.siblings()? };
+        Ok(nulla_wallet::public::FeeTicket::for_spend(self.value, &self.fee_blinding.0, &path, public_inputs))
+    }
+}
+
+/// A ProofHub fee ticket as passed in the call's `fee`.
+#[derive(Serialize, Deserialize)]
+pub struct FeeTicket {
+    pub fee_root: H32,
+    pub fee_nullifier: H32,
+    pub proof: Bytes,
+}
+
+impl From<nulla_wallet::public::FeeTicket> for FeeTicket {
+    fn from(t: nulla_wallet::public::FeeTicket) -> Self {
+        FeeTicket { fee_root: H32::from(t.fee_root), fee_nullifier: H32::from(t.fee_nullifier), proof: Bytes(t.proof) }
+    }
+}
+
+impl FeeTicket {
+    pub fn to_public(&self) -> nulla_wallet::public::FeeTicket {
+        nulla_wallet::public::FeeTicket {
+            fee_root: self.fee_root.0,
+            fee_nullifier: self.fee_nullifier.0,
+            proof: self.proof.0.clone(),
+        }
+    }
+}
+
+/// Fresh 32-byte proof seed unless the input pins one.
+pub fn seed_or_random(seed: Option<H32>) -> [u8; 32] {
+    use rand_core::RngCore;
+    seed.map(|s| s.0).unwrap_or_else(|| {
+        let mut seed = [0u8; 32];
+        rand_core::OsRng.fill_bytes(&mut seed);
+        seed
+    })
+}
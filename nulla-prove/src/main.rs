@@ -0,0 +1,82 @@
+//! nulla-prove: offline prover for the NULLA privacy lanes.
+//!
+//! Usage: nulla-prove <command> [input.json]
+//!
+//! Reads a JSON object from `input.json` (or stdin when omitted or `-`) and
+//! prints a JSON object to stdout. Byte strings are hex. On error the exit
+//! code is 1 and stderr carries the message.
+//!
+//! Commands:
+//!   deposit-v2          DepositV2Air proof and leaf for `deposit_v2`
+//!   spend purchase      SpendAir + ownership + refund (+ credential) proofs, `purchase_rwa_v2`
+//!   spend access        SpendAir proof for `purchase_access_v2`
+//!   spend withdraw      SpendAir proof for `withdraw_v2`
+//!   auth                ML-DSA-44 `auth` over SCALE public inputs (‖ fee ticket)
+//!   coin deposit-open   coin and opening proof for ScanProof `deposit_coin`
+//!   coin spend          one-of-many proof (+ G1 PoK) for `purchase_coin`,
+//!                       `purchase_access_coin` or `withdraw_coin`
+//!   verify              run the lane's checks on a proof set locally
+
+mod coin;
+mod json;
+mod v2;
+mod verify;
+
+use std::io::Read;
+use std::{env, fs, io, process};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const USAGE: &str = "usage: nulla-prove <deposit-v2 | spend purchase|access|withdraw | auth | \
+                     coin deposit-open|spend | verify> [input.json]";
+
+fn read_input(path: Option<&String>) -> Result<String, String> {
+    match path.map(String::as_str) {
+        None | Some("-") => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s).map_err(|e| e.to_string())?;
+            Ok(s)
+        }
+        Some(p) => fs::read_to_string(p).map_err(|e| format!("{p}: {e}")),
+    }
+}
+
+fn run_with<I: DeserializeOwned, O: Serialize>(
+    input: &str,
+    f: impl FnOnce(I) -> Result<O, String>,
+) -> Result<String, String> {
+    let input = serde_json::from_str(input).map_err(|e| format!("invalid input: {e}"))?;
+    serde_json::to_string_pretty(&f(input)?).map_err(|e| e.to_string())
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, path) = match args {
+        [group, sub, rest @ ..] if group == "spend" || group == "coin" => (format!("{group} {sub}"), rest.first()),
+        [command, rest @ ..] => (command.clone(), rest.first()),
+        [] => return Err(USAGE.into()),
+    };
+    let runner: fn(&str) -> Result<String, String> = match command.as_str() {
+        "deposit-v2" => |i| run_with(i, v2::deposit),
+        "spend purchase" => |i| run_with(i, v2::purchase),
+        "spend access" => |i| run_with(i, v2::access),
+        "spend withdraw" => |i| run_with(i, v2::withdraw),
+        "auth" => |i| run_with(i, v2::auth),
+        "coin deposit-open" => |i| run_with(i, coin::deposit_open),
+        "coin spend" => |i| run_with(i, coin::spend),
+        "verify" => |i| run_with(i, verify::verify),
+        _ => return Err(USAGE.into()),
+    };
+    runner(&read_input(path)?)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(out) => println!("{out}"),
+        Err(e) => {
+            eprintln!("error: {e}");
+            process::exit(1);
+        }
+    }
+}
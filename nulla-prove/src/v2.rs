@@ -0,0 +1,292 @@
+//! ProofHub v2: `deposit-v2`, `spend purchase|access|withdraw` and `auth`.
+
+use nulla_wallet::public::{access_key_commitment, CredentialPublicV2, SpendPublicV2, WithdrawPublicV2};
+use nulla_wallet::KeyPair;
+use parity_scale_codec::Encode;
+use proofhub_verifier::prover::{
+    note_hash, nullifier_hash, prove_credential, prove_deposit, prove_spend, root_from_path, CredentialWitness,
+    SpendWitness, SPEND_AUTH_DOMAIN, WITHDRAW_AUTH_DOMAIN,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::json::{Bytes, Fee, FeeTicket, Keyed, Note, Path, H16, H32};
+
+#[derive(Deserialize)]
+pub struct DepositInput {
+    #[serde(flatten)]
+    note: Note,
+}
+
+#[derive(Serialize)]
+pub struct DepositOutput {
+    leaf: H32,
+    amount: u64,
+    deposit_proof: Bytes,
+}
+
+/// DepositV2Air proof for `deposit_v2`.
+pub fn deposit(input: DepositInput) -> Result<DepositOutput, String> {
+    let (leaf, proof) = prove_deposit(input.note.value, &input.note.keyed.blinding.0, &input.note.keyed.pkd()?);
+    Ok(DepositOutput { leaf: H32::from(leaf), amount: input.note.value, deposit_proof: Bytes(proof) })
+}
+
+/// Credential presented with a purchase.
+#[derive(Deserialize)]
+pub struct Credential {
+    registry_id: u32,
+    class: u64,
+    blinding: H32,
+    path: Path,
+}
+
+#[derive(Deserialize)]
+pub struct PurchaseInput {
+    note: Note,
+    path: Path,
+    /// v2 tree epoch the note was inserted under (`LeafEpochV2`).
+    #[serde(default)]
+    tree_epoch: u32,
+    /// Total price in planck: the lane's unit price × quantity.
+    price: u64,
+    change: Keyed,
+    rwa_id: H32,
+    quantity: u64,
+    #[serde(default)]
+    rate_round: Option<u32>,
+    tx_id: H16,
+    /// Buyer's ownership note; its value is `quantity`.
+    ownership: Keyed,
+    /// Buyer's escrow refund note; its value is `price`.
+    refund: Keyed,
+    #[serde(default)]
+    credential: Option<Credential>,
+}
+
+#[derive(Deserialize)]
+pub struct AccessInput {
+    note: Note,
+    path: Path,
+    /// v2 tree epoch the note was inserted under (`LeafEpochV2`).
+    #[serde(default)]
+    tree_epoch: u32,
+    price: u64,
+    change: Keyed,
+    app_id: H32,
+    #[serde(default)]
+    access_plan: Option<u8>,
+    tx_id: H16,
+    access_key_blinding: H32,
+}
+
+#[derive(Deserialize)]
+pub struct WithdrawInput {
+    note: Note,
+    path: Path,
+    /// v2 tree epoch the note was inserted under (`LeafEpochV2`).
+    #[serde(default)]
+    tree_epoch: u32,
+    destination: H32,
+    tx_id: H16,
+}
+
+#[derive(Serialize)]
+pub struct SpendOutput {
+    /// SCALE public inputs, to sign with `auth` and submit.
+    public_inputs: Bytes,
+    spend_proof: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ownership_proof: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refund_proof: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credential_proof: Option<Bytes>,
+    merkle_root: H32,
+    nullifier: H32,
+    change_leaf: H32,
+    change_pkd: H32,
+}
+
+struct Spent {
+    proof: Vec<u8>,
+    root: [u8; 32],
+    nullifier: [u8; 32],
+    change_leaf: [u8; 32],
+    change_pkd: [u8; 32],
+}
+
+/// SpendAir proof in purchase mode (`change` given) or withdraw mode.
+fn prove(note: &Note, path: &Path, price_or_amount: u64, change: Option<&Keyed>) -> Result<Spent, String> {
+    let b = note.keyed.blinding.0;
+    let pkd = note.keyed.pkd()?;
+    let siblings = path.siblings()?;
+    let (cv, cb, cpkd) = match change {
+        Some(c) => {
+            let cv = note
+                .value
+                .checked_sub(price_or_amount)
+                .ok_or_else(|| format!("note value {} does not cover {price_or_amount}", note.value))?;
+            (cv, c.blinding.0, c.pkd()?)
+        }
+        None if note.value == price_or_amount => (0, [0u8; 32], [0u8; 32]),
+        None => return Err("a withdrawal takes the whole note".into()),
+    };
+    let witness = SpendWitness { v: note.value, b, pkd, index: path.leaf_index, siblings, cv, cb, cpkd };
+    let mode = change.is_some() as u64;
+    let proof = prove_spend(&witness, price_or_amount, mode);
+    let root = root_from_path(&note_hash(note.value, &b, &pkd), path.leaf_index, &siblings);
+    let (change_leaf, change_pkd) = match change {
+        Some(_) => (note_hash(cv, &cb, &cpkd), cpkd),
+        None => (proofhub_verifier::spend_v2::zero_change_leaf(), [0u8; 32]),
+    };
+    Ok(Spent { proof, root, nullifier: nullifier_hash(&b), change_leaf, change_pkd })
+}
+
+fn output(
+    spent: Spent,
+    public_inputs: Vec<u8>,
+    ownership: Option<Vec<u8>>,
+    refund: Option<Vec<u8>>,
+    credential: Option<Vec<u8>>,
+) -> SpendOutput {
+    SpendOutput {
+        public_inputs: Bytes(public_inputs),
+        spend_proof: Bytes(spent.proof),
+        ownership_proof: ownership.map(Bytes),
+        refund_proof: refund.map(Bytes),
+        credential_proof: credential.map(Bytes),
+        merkle_root: H32::from(spent.root),
+        nullifier: H32::from(spent.nullifier),
+        change_leaf: H32::from(spent.change_leaf),
+        change_pkd: H32::from(spent.change_pkd),
+    }
+}
+
+/// `purchase_rwa_v2`: spend, ownership, refund and (optional) credential proofs.
+pub fn purchase(input: PurchaseInput) -> Result<SpendOutput, String> {
+    let spent = prove(&input.note, &input.path, input.price, Some(&input.change))?;
+    let (ownership_leaf, ownership_proof) =
+        prove_deposit(input.quantity, &input.ownership.blinding.0, &input.ownership.pkd()?);
+    let (refund_leaf, refund_proof) = prove_deposit(input.price, &input.refund.blinding.0, &input.refund.pkd()?);
+    let (credential, credential_proof) = match &input.credential {
+        Some(c) => {
+            let witness = CredentialWitness {
+                class: c.class,
+                b: c.blinding.0,
+                index: c.path.leaf_index,
+                siblings: c.path.siblings()?,
+            };
+            let p = prove_credential(&witness, &input.rwa_id.0, &spent.nullifier)
+                .ok_or("rwa_id is not a canonical asset id")?;
+            let public = CredentialPublicV2 { registry_id: c.registry_id, root: p.root, class: p.class, tag: p.tag };
+            (Some(public), Some(p.proof))
+        }
+        None => (None, None),
+    };
+    let public_inputs = SpendPublicV2 {
+        tree_epoch: input.tree_epoch,
+        merkle_root: spent.root,
+        nullifier: spent.nullifier,
+        rwa_id: input.rwa_id.0,
+        quantity: input.quantity,
+        rate_round: input.rate_round,
+        credential,
+        access_plan: None,
+        tx_id: input.tx_id.0,
+        ownership_commitment: ownership_leaf,
+        change_leaf: spent.change_leaf,
+        change_pkd: spent.change_pkd,
+        refund_leaf,
+    }
+    .encode();
+    // An empty credential proof is what the lane expects when none is needed.
+    Ok(output(
+        spent,
+        public_inputs,
+        Some(ownership_proof),
+        Some(refund_proof),
+        Some(credential_proof.unwrap_or_default()),
+    ))
+}
+
+/// `purchase_access_v2`: a purchase-mode spend for one access key.
+pub fn access(input: AccessInput) -> Result<SpendOutput, String> {
+    let spent = prove(&input.note, &input.path, input.price, Some(&input.change))?;
+    let public_inputs = SpendPublicV2 {
+        tree_epoch: input.tree_epoch,
+        merkle_root: spent.root,
+        nullifier: spent.nullifier,
+        rwa_id: input.app_id.0,
+        quantity: 1,
+        rate_round: None,
+        credential: None,
+        access_plan: input.access_plan,
+        tx_id: input.tx_id.0,
+        ownership_commitment: access_key_commitment(&input.app_id.0, &input.access_key_blinding.0),
+        change_leaf: spent.change_leaf,
+        change_pkd: spent.change_pkd,
+        refund_leaf: [0u8; 32],
+    }
+    .encode();
+    Ok(output(spent, public_inputs, None, None, None))
+}
+
+/// `withdraw_v2`: a withdraw-mode spend of the whole note.
+pub fn withdraw(input: WithdrawInput) -> Result<SpendOutput, String> {
+    let spent = prove(&input.note, &input.path, input.note.value, None)?;
+    let public_inputs = WithdrawPublicV2 {
+        tree_epoch: input.tree_epoch,
+        merkle_root: spent.root,
+        nullifier: spent.nullifier,
+        amount: input.note.value,
+        destination: input.destination.0,
+        tx_id: input.tx_id.0,
+    }
+    .encode();
+    Ok(output(spent, public_inputs, None, None, None))
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Domain {
+    /// Purchases, access, relist and auction calls.
+    #[default]
+    Spend,
+    /// `withdraw_v2` and `redeem_ownership_v2`.
+    Withdraw,
+}
+
+#[derive(Deserialize)]
+pub struct AuthInput {
+    key_seed: H32,
+    public_inputs: Bytes,
+    #[serde(default)]
+    fee: Option<Fee>,
+    #[serde(default)]
+    domain: Domain,
+}
+
+#[derive(Serialize)]
+pub struct AuthOutput {
+    auth: Bytes,
+    pkd: H32,
+    /// The fee ticket `auth` signs, to pass as the call's `fee`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee: Option<FeeTicket>,
+}
+
+/// ML-DSA-44 `auth` over SCALE(public inputs) ‖ SCALE(fee ticket).
+pub fn auth(input: AuthInput) -> Result<AuthOutput, String> {
+    let key = KeyPair::from_seed(&input.key_seed.0);
+    let ticket = input.fee.as_ref().map(|fee| fee.ticket(&input.public_inputs.0)).transpose()?;
+    let mut message = input.public_inputs.0;
+    if let Some(ticket) = &ticket {
+        ticket.encode_to(&mut message);
+    }
+    let domain = match input.domain {
+        Domain::Spend => SPEND_AUTH_DOMAIN,
+        Domain::Withdraw => WITHDRAW_AUTH_DOMAIN,
+    };
+    let auth = key.sign_auth(domain, &message).map_err(|e| e.to_string())?;
+    let fee = ticket.map(FeeTicket::from);
+    Ok(AuthOutput { auth: Bytes(auth), pkd: H32::from(key.pk_digest()), fee })
+}
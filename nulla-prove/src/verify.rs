@@ -0,0 +1,195 @@
+//! `verify`: the lane's checks, run locally.
+
+use nulla_wallet::public::{CoinSpendPublic, CoinWithdrawPublic, SpendPublicV2, WithdrawPublicV2};
+use parity_scale_codec::{Decode, Encode};
+use proofhub_verifier::deposit_v2::verify_deposit_v2;
+use proofhub_verifier::fee::{self, verify_fee_proof};
+use proofhub_verifier::spend_v2::verify_spend_v2;
+use proofhub_verifier::v2::pk_digest;
+use proofhub_verifier::verify_spend_auth_v2;
+use scanproof_verifier::one_of_many;
+use serde::{Deserialize, Serialize};
+
+use crate::coin::context;
+use crate::json::{Bytes, FeeTicket, H32};
+
+const ML_DSA_PK_LEN: usize = 1312;
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum VerifyInput {
+    DepositV2 {
+        leaf: H32,
+        amount: u64,
+        deposit_proof: Bytes,
+    },
+    /// A `purchase_rwa_v2` / `purchase_access_v2` spend; `price` is the total
+    /// the lane would charge.
+    Purchase {
+        public_inputs: Bytes,
+        auth: Bytes,
+        spend_proof: Bytes,
+        price: u64,
+        #[serde(default)]
+        ownership_proof: Option<Bytes>,
+        #[serde(default)]
+        refund_proof: Option<Bytes>,
+        #[serde(default)]
+        fee: Option<FeeInput>,
+    },
+    Withdraw {
+        public_inputs: Bytes,
+        auth: Bytes,
+        spend_proof: Bytes,
+        #[serde(default)]
+        fee: Option<FeeInput>,
+    },
+    CoinDepositOpen {
+        coin: H32,
+        amount: u64,
+        open_proof: Bytes,
+        depositor: H32,
+    },
+    CoinPurchase {
+        coins: Vec<H32>,
+        public_inputs: Bytes,
+        one_of_many_proof: Bytes,
+        #[serde(default)]
+        g1_pok: Bytes,
+        price: u64,
+    },
+    CoinWithdraw {
+        coins: Vec<H32>,
+        public_inputs: Bytes,
+        one_of_many_proof: Bytes,
+    },
+}
+
+/// A fee ticket and the base fee it must pay.
+#[derive(Deserialize)]
+pub struct FeeInput {
+    value: u64,
+    #[serde(flatten)]
+    ticket: FeeTicket,
+}
+
+#[derive(Serialize)]
+pub struct VerifyOutput {
+    valid: bool,
+    /// The first check that failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed: Option<&'static str>,
+}
+
+fn checks(results: &[(&'static str, bool)]) -> VerifyOutput {
+    let failed = results.iter().find(|(_, ok)| !ok).map(|(name, _)| *name);
+    VerifyOutput { valid: failed.is_none(), failed }
+}
+
+fn auth_ok(auth: &[u8], public_inputs: &[u8], fee: &Option<FeeInput>, withdraw: bool) -> bool {
+    let mut message = public_inputs.to_vec();
+    if let Some(fee) = fee {
+        fee.ticket.to_public().encode_to(&mut message);
+    }
+    verify_spend_auth_v2(auth, &message, withdraw)
+}
+
+fn fee_ok(public_inputs: &[u8], fee: &Option<FeeInput>) -> bool {
+    fee.as_ref().map_or(true, |fee| {
+        let t = &fee.ticket;
+        verify_fee_proof(&t.proof.0, &t.fee_root.0, fee.value, &t.fee_nullifier.0, &fee::context(public_inputs))
+    })
+}
+
+fn auth_pkd(auth: &[u8]) -> [u8; 32] {
+    pk_digest(&auth[..ML_DSA_PK_LEN.min(auth.len())])
+}
+
+fn coins(group: &[H32], group_id: u32) -> Vec<[u8; 32]> {
+    let coins: Vec<[u8; 32]> = group.iter().map(|c| c.0).collect();
+    one_of_many::pad_group(&coins, group_id)
+}
+
+pub fn verify(input: VerifyInput) -> Result<VerifyOutput, String> {
+    Ok(match input {
+        VerifyInput::DepositV2 { leaf, amount, deposit_proof } => {
+            checks(&[("deposit_proof", verify_deposit_v2(&deposit_proof.0, amount, &leaf.0))])
+        }
+        VerifyInput::Purchase { public_inputs, auth, spend_proof, price, ownership_proof, refund_proof, fee } => {
+            let p = SpendPublicV2::decode(&mut &public_inputs.0[..]).map_err(|e| e.to_string())?;
+            let spend = verify_spend_v2(
+                &spend_proof.0,
+                &p.merkle_root,
+                &p.nullifier,
+                &auth_pkd(&auth.0),
+                price,
+                &p.change_leaf,
+                &p.change_pkd,
+                true,
+            );
+            let ownership = ownership_proof
+                .map_or(true, |proof| verify_deposit_v2(&proof.0, p.quantity, &p.ownership_commitment));
+            let refund = refund_proof.map_or(true, |proof| verify_deposit_v2(&proof.0, price, &p.refund_leaf));
+            checks(&[
+                ("auth", auth_ok(&auth.0, &public_inputs.0, &fee, false)),
+                ("fee_proof", fee_ok(&public_inputs.0, &fee)),
+                ("spend_proof", spend),
+                ("ownership_proof", ownership),
+                ("refund_proof", refund),
+            ])
+        }
+        VerifyInput::Withdraw { public_inputs, auth, spend_proof, fee } => {
+            let p = WithdrawPublicV2::decode(&mut &public_inputs.0[..]).map_err(|e| e.to_string())?;
+            let spend = verify_spend_v2(
+                &spend_proof.0,
+                &p.merkle_root,
+                &p.nullifier,
+                &auth_pkd(&auth.0),
+                p.amount,
+                &proofhub_verifier::spend_v2::zero_change_leaf(),
+                &[0u8; 32],
+                false,
+            );
+            checks(&[
+                ("auth", auth_ok(&auth.0, &public_inputs.0, &fee, true)),
+                ("fee_proof", fee_ok(&public_inputs.0, &fee)),
+                ("spend_proof", spend),
+            ])
+        }
+        VerifyInput::CoinDepositOpen { coin, amount, open_proof, depositor } => checks(&[(
+            "open_proof",
+            one_of_many::verify_deposit_open(&coin.0, amount, &open_proof.0, &depositor.0),
+        )]),
+        VerifyInput::CoinPurchase { coins: group, public_inputs, one_of_many_proof, g1_pok, price } => {
+            let p = CoinSpendPublic::decode(&mut &public_inputs.0[..]).map_err(|e| e.to_string())?;
+            let ctx = context(&public_inputs.0);
+            let spend = one_of_many::verify(
+                &one_of_many_proof.0,
+                &coins(&group, p.group_id),
+                &p.serial,
+                price,
+                &p.change,
+                &ctx,
+            );
+            let has_change = p.change != one_of_many::NO_CHANGE;
+            let pok = !has_change || one_of_many::verify_g1_pok(&p.change_coin, &p.change, &g1_pok.0, &ctx);
+            checks(&[
+                ("change", has_change == (p.change_coin != [0u8; 32])),
+                ("one_of_many_proof", spend),
+                ("g1_pok", pok),
+            ])
+        }
+        VerifyInput::CoinWithdraw { coins: group, public_inputs, one_of_many_proof } => {
+            let p = CoinWithdrawPublic::decode(&mut &public_inputs.0[..]).map_err(|e| e.to_string())?;
+            let spend = one_of_many::verify(
+                &one_of_many_proof.0,
+                &coins(&group, p.group_id),
+                &p.serial,
+                p.amount,
+                &one_of_many::NO_CHANGE,
+                &context(&public_inputs.0),
+            );
+            checks(&[("one_of_many_proof", spend)])
+        }
+    })
+}
@@ -8,7 +8,7 @@
  *  1. spec_version on both chains
  *  2. ProofHub: SpendTagValues count, SpendTagUsed count, NullifierUsed count,
  *               CommitmentAmounts count, Leaves length, MerkleRoot
- *  3. RWA chain: ProofHubPurchases count, OwnershipRedeemed count
+ *  3. RWA chain: ProofHubPurchases count, OwnershipUnitsRedeemed count
  *  4. Consistency: every spend_tag in SpendTagUsed must also be in SpendTagValues
  *  5. Pool account balance (must hold all deposited funds)
  */
@@ -108,7 +108,7 @@ async function auditRwa(proofHubStats) {
   }
 
   const purchases       = await api.query.rwaMarketplace.proofHubPurchases.entries();
-  const ownershipRed    = await api.query.rwaMarketplace.ownershipRedeemed.entries();
+  const ownershipRed    = await api.query.rwaMarketplace.ownershipUnitsRedeemed.entries();
   const listings        = await api.query.rwaMarketplace.listings.entries();
   const nullifiersUsed  = await api.query.rwaMarketplace.nullifierUsed.entries();
 
@@ -0,0 +1,215 @@
+//! Call data for the lane extrinsics.
+//!
+//! `call_data()` returns `[pallet index][call index] ‖ SCALE(args)`, the
+//! encoding of the runtime call, ready to wrap in a signed or unsigned
+//! extrinsic. The lane pallets are `dev_mode`, so call indices follow their
+//! declaration order.
+//!
+//! Left out: the XCM-only calls (origin is a sibling sovereign), and on
+//! ProofHub the disabled v1 `deposit_public` (0) and `relist_private` (3).
+
+use parity_scale_codec::Encode;
+// Same SCALE layout as the ProofHub pallet's `StarkSecurity`.
+use proofhub_verifier::envelope::StarkSecurity;
+
+use crate::public::{AccessPlan, CoinFeeTicket, FeeTicket};
+
+/// `Proofs` pallet index in both lane runtimes.
+pub const PROOFS_PALLET_INDEX: u8 = 51;
+
+/// ProofHub `pallet_proofs` calls.
+#[derive(Encode, Clone, Debug, PartialEq, Eq)]
+pub enum ProofHubCall {
+    #[codec(index = 1)]
+    PurchaseRwa { proof: Vec<u8>, public_inputs: Vec<u8> },
+    #[codec(index = 2)]
+    WithdrawPrivate { proof: Vec<u8>, public_inputs: Vec<u8> },
+    #[codec(index = 4)]
+    SetRwaPrice { rwa_id: [u8; 32], price: u64 },
+    #[codec(index = 5)]
+    SetAccessConfig { app_id: [u8; 32], price: u64, payment_account: [u8; 32] },
+    #[codec(index = 6)]
+    PurchaseAccess { proof: Vec<u8>, public_inputs: Vec<u8> },
+    #[codec(index = 7)]
+    PurchaseAccessV2 { auth: Vec<u8>, public_inputs: Vec<u8>, spend_proof: Vec<u8>, fee: Option<FeeTicket> },
+    #[codec(index = 8)]
+    DepositV2 { leaf: [u8; 32], amount: u64, deposit_proof: Vec<u8>, hints_blob: Vec<u8> },
+    #[codec(index = 9)]
+    PurchaseRwaV2 {
+        auth: Vec<u8>,
+        public_inputs: Vec<u8>,
+        spend_proof: Vec<u8>,
+        ownership_proof: Vec<u8>,
+        refund_proof: Vec<u8>,
+        credential_proof: Vec<u8>,
+        fee: Option<FeeTicket>,
+    },
+    #[codec(index = 10)]
+    WithdrawV2 { auth: Vec<u8>, public_inputs: Vec<u8>, spend_proof: Vec<u8>, fee: Option<FeeTicket> },
+    #[codec(index = 11)]
+    DepositFee { leaf: [u8; 32], deposit_proof: Vec<u8> },
+    #[codec(index = 12)]
+    RelistV2 {
+        seller_auth: Vec<u8>,
+        buyer_auth: Vec<u8>,
+        public_inputs: Vec<u8>,
+        ownership_proof: Vec<u8>,
+        spend_proof: Vec<u8>,
+        new_ownership_proof: Vec<u8>,
+        payment_proof: Vec<u8>,
+        credential_proof: Vec<u8>,
+        fee: Option<FeeTicket>,
+    },
+    #[codec(index = 13)]
+    RedeemOwnershipV2 { auth: Vec<u8>, public_inputs: Vec<u8>, ownership_proof: Vec<u8>, fee: Option<FeeTicket> },
+    #[codec(index = 14)]
+    SetRwaUnits { rwa_id: [u8; 32], units: Option<u64> },
+    #[codec(index = 15)]
+    CreateAuction { rwa_id: [u8; 32], quantity: u64, reserve_price: u64, bidding_period: u32, reveal_period: u32 },
+    #[codec(index = 16)]
+    PlaceBidV2 { auth: Vec<u8>, public_inputs: Vec<u8>, spend_proof: Vec<u8>, fee: Option<FeeTicket> },
+    #[codec(index = 17)]
+    RevealBidV2 {
+        auth: Vec<u8>,
+        public_inputs: Vec<u8>,
+        lock_proof: Vec<u8>,
+        refund_proof: Vec<u8>,
+        ownership_proof: Vec<u8>,
+        fee: Option<FeeTicket>,
+    },
+    #[codec(index = 18)]
+    SettleAuction { auction_id: u32 },
+    #[codec(index = 19)]
+    ReleaseBidV2 { auth: Vec<u8>, public_inputs: Vec<u8>, lock_proof: Vec<u8>, fee: Option<FeeTicket> },
+    #[codec(index = 22)]
+    RefundEscrow { tx_id: [u8; 16] },
+    #[codec(index = 24)]
+    SetRwaUsdPrice { rwa_id: [u8; 32], usd_cents: u64 },
+    #[codec(index = 25)]
+    CreateCredentialRegistry { issuer: [u8; 32] },
+    #[codec(index = 26)]
+    IssueCredential { registry_id: u32, leaf: [u8; 32] },
+    #[codec(index = 28)]
+    SetAccessPlans { app_id: [u8; 32], plans: Vec<AccessPlan> },
+    #[codec(index = 29)]
+    SetProofVersionRetired { air: u8, version: u16, retired: bool },
+    #[codec(index = 30)]
+    SetStarkSecurity { security: StarkSecurity },
+    #[codec(index = 31)]
+    SetRootRetention { blocks: u32 },
+}
+
+/// ScanProof `pallet_proofs` calls.
+#[derive(Encode, Clone, Debug, PartialEq, Eq)]
+pub enum ScanProofCall {
+    #[codec(index = 0)]
+    DepositPublic { commitment: Vec<u8>, range_proof: Vec<u8>, hints_blob: Vec<u8> },
+    #[codec(index = 1)]
+    SubmitProof {
+        proof: Vec<u8>,
+        range_proof: Vec<u8>,
+        public_inputs: Vec<u8>,
+        hints_blob: Vec<u8>,
+        fee: Option<CoinFeeTicket>,
+    },
+    #[codec(index = 2)]
+    PurchaseRwa {
+        input_commitment: [u8; 32],
+        opening_proof: Vec<u8>,
+        nullifier: [u8; 32],
+        rwa_id: [u8; 32],
+        tx_id: [u8; 16],
+        ownership_commitment: [u8; 32],
+        change_commitment: Option<[u8; 32]>,
+    },
+    #[codec(index = 3)]
+    WithdrawPrivate {
+        commitment: [u8; 32],
+        nullifier: [u8; 32],
+        opening_proof: Vec<u8>,
+        destination: Vec<u8>,
+        value: u64,
+    },
+    #[codec(index = 4)]
+    SetRwaPrice { rwa_id: [u8; 32], price: u64 },
+    #[codec(index = 5)]
+    SetAccessConfig { app_id: [u8; 32], price: u64, payment_account: [u8; 32] },
+    #[codec(index = 6)]
+    PurchaseAccess {
+        input_commitment: [u8; 32],
+        opening_proof: Vec<u8>,
+        nullifier: [u8; 32],
+        app_id: [u8; 32],
+        tx_id: [u8; 16],
+        access_key_commitment: [u8; 32],
+        change_commitment: Option<[u8; 32]>,
+    },
+    #[codec(index = 7)]
+    DepositCoin { coin: [u8; 32], amount: u64, open_proof: Vec<u8> },
+    #[codec(index = 8)]
+    PurchaseCoin { public_inputs: Vec<u8>, one_of_many_proof: Vec<u8>, g1_pok: Vec<u8>, fee: Option<CoinFeeTicket> },
+    #[codec(index = 9)]
+    WithdrawCoin { public_inputs: Vec<u8>, one_of_many_proof: Vec<u8>, fee: Option<CoinFeeTicket> },
+    #[codec(index = 10)]
+    PurchaseAccessCoin {
+        public_inputs: Vec<u8>,
+        one_of_many_proof: Vec<u8>,
+        g1_pok: Vec<u8>,
+        fee: Option<CoinFeeTicket>,
+    },
+    #[codec(index = 11)]
+    DepositFee { coin: [u8; 32], open_proof: Vec<u8> },
+    #[codec(index = 12)]
+    SetRwaUnits { rwa_id: [u8; 32], units: Option<u64> },
+    #[codec(index = 14)]
+    SetRwaUsdPrice { rwa_id: [u8; 32], usd_cents: u64 },
+    #[codec(index = 16)]
+    SetAccessPlans { app_id: [u8; 32], plans: Vec<AccessPlan> },
+    #[codec(index = 17)]
+    SetProofVersionRetired { air: u8, version: u16, retired: bool },
+    #[codec(index = 18)]
+    SetRootRetention { blocks: u32 },
+}
+
+impl ProofHubCall {
+    pub fn call_data(&self) -> Vec<u8> {
+        let mut data = vec![PROOFS_PALLET_INDEX];
+        self.encode_to(&mut data);
+        data
+    }
+}
+
+impl ScanProofCall {
+    pub fn call_data(&self) -> Vec<u8> {
+        let mut data = vec![PROOFS_PALLET_INDEX];
+        self.encode_to(&mut data);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_data_starts_with_pallet_and_call_index() {
+        let data = ProofHubCall::SettleAuction { auction_id: 7 }.call_data();
+        assert_eq!(data, vec![51, 18, 7, 0, 0, 0]);
+
+        // Vec args carry a compact length prefix, BoundedVec-compatible.
+        let data = ProofHubCall::DepositV2 {
+            leaf: [1u8; 32],
+            amount: 5,
+            deposit_proof: vec![9, 9],
+            hints_blob: Vec::new(),
+        }
+        .call_data();
+        assert_eq!(&data[..2], &[51, 8]);
+        assert_eq!(&data[34..42], &5u64.to_le_bytes());
+        assert_eq!(&data[42..], &[8, 9, 9, 0]);
+
+        let data = ScanProofCall::DepositFee { coin: [2u8; 32], open_proof: vec![7; 96] }.call_data();
+        assert_eq!(&data[..2], &[51, 11]);
+        assert_eq!(data.len(), 2 + 32 + 2 + 96);
+    }
+}
@@ -0,0 +1,190 @@
+//! Wallet seed and key derivation.
+//!
+//! Every secret is derived from the 32-byte seed with BLAKE3 `derive_key`,
+//! one context string per [`Purpose`], over `seed ‖ index LE`. A wallet
+//! therefore restores completely from the seed and its next key index.
+//!
+//! Each note gets its own index and so its own blinding and ML-DSA-44
+//! keypair: spends reveal the pk, so a key is never reused across notes.
+
+use fips204::ml_dsa_44;
+use fips204::traits::{KeyGen, SerDes, Signer};
+use proofhub_verifier::v2::pk_digest;
+use rand_core::{CryptoRng, OsRng, RngCore};
+
+use crate::Error;
+
+/// ML-DSA-44 public key length.
+pub const ML_DSA_PK_LEN: usize = 1312;
+/// ML-DSA-44 signature length.
+pub const ML_DSA_SIG_LEN: usize = 2420;
+
+/// What a derived secret is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Purpose {
+    /// Blinding `b` of a v2 note; its nullifier is Poseidon(b).
+    NoteBlinding,
+    /// Seed of the note's ML-DSA-44 keypair.
+    SigningKey,
+    /// Blinding of an RWA ownership note.
+    OwnershipBlinding,
+    /// Blinding of an AuthGate access key.
+    AccessKeyBlinding,
+    /// Salt of a sealed auction bid.
+    BidSalt,
+    /// Serial `s` of a ScanProof coin.
+    CoinSerial,
+    /// Blinding `r` of a ScanProof coin or change output.
+    CoinBlinding,
+}
+
+impl Purpose {
+    fn context(self) -> &'static str {
+        match self {
+            Purpose::NoteBlinding => "nulla-wallet v1 note blinding",
+            Purpose::SigningKey => "nulla-wallet v1 ml-dsa-44 key",
+            Purpose::OwnershipBlinding => "nulla-wallet v1 ownership blinding",
+            Purpose::AccessKeyBlinding => "nulla-wallet v1 access key blinding",
+            Purpose::BidSalt => "nulla-wallet v1 bid salt",
+            Purpose::CoinSerial => "nulla-wallet v1 coin serial",
+            Purpose::CoinBlinding => "nulla-wallet v1 coin blinding",
+        }
+    }
+
+    /// Coin scalars are kept below 2^252 < ℓ, so the bytes are already the
+    /// canonical encoding the ScanProof lane compares serials by.
+    fn is_scalar(self) -> bool {
+        matches!(self, Purpose::CoinSerial | Purpose::CoinBlinding)
+    }
+}
+
+/// The wallet master seed.
+#[derive(Clone)]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Seed(bytes)
+    }
+
+    /// Fresh seed from the OS RNG.
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        Seed(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Secret for `purpose` at `index`.
+    pub fn derive(&self, purpose: Purpose, index: u32) -> [u8; 32] {
+        let mut material = [0u8; 36];
+        material[..32].copy_from_slice(&self.0);
+        material[32..].copy_from_slice(&index.to_le_bytes());
+        let mut out = blake3::derive_key(purpose.context(), &material);
+        if purpose.is_scalar() {
+            out[31] &= 0x0f;
+        }
+        out
+    }
+
+    /// ML-DSA-44 keypair at `index`.
+    pub fn keypair(&self, index: u32) -> KeyPair {
+        KeyPair::from_seed(&self.derive(Purpose::SigningKey, index))
+    }
+}
+
+impl core::fmt::Debug for Seed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+/// An ML-DSA-44 keypair authorising the spend of one note.
+pub struct KeyPair {
+    sk: ml_dsa_44::PrivateKey,
+    pk: [u8; ML_DSA_PK_LEN],
+}
+
+impl KeyPair {
+    /// Deterministic keypair: key generation reads its randomness from the
+    /// BLAKE3 XOF of `seed`.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let mut rng = XofRng(blake3::Hasher::new().update(seed).finalize_xof());
+        let (pk, sk) = ml_dsa_44::KG::try_keygen_with_rng(&mut rng).expect("XOF reads never fail");
+        KeyPair { sk, pk: pk.into_bytes() }
+    }
+
+    pub fn public_key(&self) -> &[u8; ML_DSA_PK_LEN] {
+        &self.pk
+    }
+
+    /// pkd = BLAKE3("nulla_pk_digest_v2" ‖ pk), the note's public key digest.
+    pub fn pk_digest(&self) -> [u8; 32] {
+        pk_digest(&self.pk)
+    }
+
+    /// `auth` parameter of a v2 extrinsic: pk ‖ sig, the signature over
+    /// BLAKE3(domain ‖ message).
+    pub fn sign_auth(&self, domain: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut h = blake3::Hasher::new();
+        h.update(domain);
+        h.update(message);
+        let sig = self.sk.try_sign(h.finalize().as_bytes(), &[]).map_err(|_| Error::Signing)?;
+        let mut auth = Vec::with_capacity(ML_DSA_PK_LEN + ML_DSA_SIG_LEN);
+        auth.extend_from_slice(&self.pk);
+        auth.extend_from_slice(&sig);
+        Ok(auth)
+    }
+}
+
+struct XofRng(blake3::OutputReader);
+
+impl RngCore for XofRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut b = [0u8; 4];
+        self.0.fill(&mut b);
+        u32::from_le_bytes(b)
+    }
+    fn next_u64(&mut self) -> u64 {
+        let mut b = [0u8; 8];
+        self.0.fill(&mut b);
+        u64::from_le_bytes(b)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill(dest);
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.0.fill(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for XofRng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivation_is_deterministic_and_domain_separated() {
+        let seed = Seed::from_bytes([7u8; 32]);
+        assert_eq!(seed.derive(Purpose::NoteBlinding, 3), seed.derive(Purpose::NoteBlinding, 3));
+        assert_ne!(seed.derive(Purpose::NoteBlinding, 3), seed.derive(Purpose::NoteBlinding, 4));
+        assert_ne!(seed.derive(Purpose::NoteBlinding, 3), seed.derive(Purpose::OwnershipBlinding, 3));
+        assert!(seed.derive(Purpose::CoinSerial, 3)[31] < 0x10);
+        assert_eq!(seed.keypair(1).public_key(), seed.keypair(1).public_key());
+        assert_ne!(seed.keypair(1).pk_digest(), seed.keypair(2).pk_digest());
+    }
+
+    #[test]
+    fn auth_verifies_on_the_lane() {
+        let key = Seed::from_bytes([9u8; 32]).keypair(0);
+        let inputs = b"public-inputs".to_vec();
+        let auth = key.sign_auth(proofhub_verifier::v2::SPEND_AUTH_DOMAIN, &inputs).unwrap();
+        assert!(proofhub_verifier::verify_spend_auth_v2(&auth, &inputs, false));
+        assert!(!proofhub_verifier::verify_spend_auth_v2(&auth, &inputs, true));
+    }
+}
@@ -0,0 +1,69 @@
+//! NULLA wallet SDK for the privacy lanes.
+//!
+//! - [`keys`]   — the wallet seed and everything derived from it: note
+//!   blindings, per-note ML-DSA-44 keypairs, coin secrets;
+//! - [`tree`]   — a local mirror of the ProofHub v2 note tree, fed from the
+//!   leaf-inserting events, that produces Merkle paths;
+//! - [`public`] — SCALE public inputs with the exact layouts the lanes decode;
+//! - [`calls`]  — ready-to-submit call data for the lane extrinsics;
+//! - [`Wallet`] — ties them together: owned notes, sync and spend preparation.
+//!
+//! The crate has no Substrate dependencies. Submitting call data (as a signed
+//! or unsigned extrinsic) and decoding events are left to the client.
+
+pub mod calls;
+pub mod keys;
+pub mod public;
+pub mod tree;
+mod wallet;
+
+pub use keys::{KeyPair, Purpose, Seed};
+pub use tree::{MerklePath, TreeMirror};
+pub use wallet::{random_tx_id, OwnedNote, PreparedSpend, PreparedWithdraw, SpendTerms, TreeEvent, Wallet};
+
+use core::fmt;
+
+/// Wallet errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A leaf arrived ahead of the mirror: leaves `expected..got` are missing.
+    LeafOutOfOrder { expected: u32, got: u32 },
+    /// A replayed leaf index carries a different leaf than the mirror holds.
+    LeafConflict { index: u32 },
+    /// The mirror root differs from the `new_root` reported by the chain.
+    RootMismatch { chain: [u8; 32], local: [u8; 32] },
+    /// The tree holds 2^20 leaves.
+    TreeFull,
+    /// No note with this key index.
+    UnknownNote,
+    /// The note is marked spent.
+    NoteSpent,
+    /// The note's leaf has not been seen in the tree yet.
+    NoteNotInserted,
+    /// The note value does not cover the spend.
+    InsufficientValue { value: u64, required: u64 },
+    /// ML-DSA-44 signing failed.
+    Signing,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LeafOutOfOrder { expected, got } => {
+                write!(f, "leaf {got} arrived before leaf {expected}; resync the tree")
+            }
+            Error::LeafConflict { index } => write!(f, "conflicting leaf at index {index}"),
+            Error::RootMismatch { .. } => write!(f, "local tree root differs from the chain root"),
+            Error::TreeFull => write!(f, "note tree is full"),
+            Error::UnknownNote => write!(f, "unknown note"),
+            Error::NoteSpent => write!(f, "note already spent"),
+            Error::NoteNotInserted => write!(f, "note leaf not in the tree yet"),
+            Error::InsufficientValue { value, required } => {
+                write!(f, "note value {value} does not cover {required}")
+            }
+            Error::Signing => write!(f, "ML-DSA-44 signing failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
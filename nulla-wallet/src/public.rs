@@ -0,0 +1,230 @@
+//! SCALE public inputs of the lane extrinsics.
+//!
+//! Each struct mirrors the lane pallet type of the same name field for
+//! field, so its encoding is byte-identical to what the pallet decodes and
+//! what the v2 `auth` signs. Keep them in step with `pallet_proofs`.
+
+use parity_scale_codec::{Decode, Encode};
+
+/// Access-key commitment, BLAKE3("nulla_access_key_v1" ‖ app_id ‖ blinding).
+/// Carried in `ownership_commitment` by access-lane purchases.
+pub fn access_key_commitment(app_id: &[u8; 32], blinding: &[u8; 32]) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(b"nulla_access_key_v1");
+    h.update(app_id);
+    h.update(blinding);
+    *h.finalize().as_bytes()
+}
+
+/// Sealed bid commitment, BLAKE3("nulla_auction_bid_v1" ‖ auction_id LE ‖ bid LE ‖ salt).
+pub fn auction_bid_commitment(auction_id: u32, bid: u64, salt: &[u8; 32]) -> [u8; 32] {
+    let mut h = blake3::Hasher::new();
+    h.update(b"nulla_auction_bid_v1");
+    h.update(&auction_id.to_le_bytes());
+    h.update(&bid.to_le_bytes());
+    h.update(salt);
+    *h.finalize().as_bytes()
+}
+
+/// Canonical RWA id of an RWA chain asset: asset_id LE u32, rest zero.
+pub fn rwa_id(asset_id: u32) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id[..4].copy_from_slice(&asset_id.to_le_bytes());
+    id
+}
+
+// ---- ProofHub ----
+
+/// Paymaster fee credit leaf on ProofHub, NoteHash(value, b, 0). Deposited
+/// by `deposit_fee` with a DepositV2Air proof for pkd = 0.
+pub fn fee_credit_leaf(value: u64, fee_blinding: &[u8; 32]) -> [u8; 32] {
+    proofhub_verifier::fee::credit_leaf(value, fee_blinding)
+}
+
+/// Paymaster fee credit spend on ProofHub (`pallet_proofs::FeeTicket`): a
+/// STARK that a credit under `fee_root` is spent with `fee_nullifier`,
+/// bound to BLAKE2-256(public inputs). The credit leaf is never revealed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct FeeTicket {
+    pub fee_root: [u8; 32],
+    pub fee_nullifier: [u8; 32],
+    pub proof: Vec<u8>,
+}
+
+impl FeeTicket {
+    /// Ticket spending the credit of `value` opened by `fee_blinding`, at
+    /// `path` in the fee credit tree, in the spend with SCALE `public_inputs`.
+    #[cfg(feature = "prover")]
+    pub fn for_spend(
+        value: u64,
+        fee_blinding: &[u8; 32],
+        path: &crate::tree::MerklePath,
+        public_inputs: &[u8],
+    ) -> Self {
+        let witness = proofhub_verifier::prover::FeeWitness {
+            value,
+            b: *fee_blinding,
+            index: path.leaf_index,
+            siblings: path.siblings,
+        };
+        let spend = proofhub_verifier::prover::prove_fee(&witness, public_inputs);
+        Self { fee_root: spend.root, fee_nullifier: spend.nullifier, proof: spend.proof }
+    }
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CredentialPublicV2 {
+    pub registry_id: u32,
+    pub root: [u8; 32],
+    pub class: u64,
+    pub tag: [u8; 32],
+}
+
+/// `purchase_rwa_v2` and `purchase_access_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct SpendPublicV2 {
+    pub tree_epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub rwa_id: [u8; 32],
+    pub quantity: u64,
+    pub rate_round: Option<u32>,
+    pub credential: Option<CredentialPublicV2>,
+    pub access_plan: Option<u8>,
+    pub tx_id: [u8; 16],
+    pub ownership_commitment: [u8; 32],
+    pub change_leaf: [u8; 32],
+    pub change_pkd: [u8; 32],
+    /// Refund note for an escrowed RWA purchase; ignored on the access lane.
+    pub refund_leaf: [u8; 32],
+}
+
+/// `withdraw_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct WithdrawPublicV2 {
+    pub tree_epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub amount: u64,
+    pub destination: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// `relist_v2`, co-signed by seller and buyer.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct RelistPublicV2 {
+    pub rwa_id: [u8; 32],
+    pub ownership_root: [u8; 32],
+    pub ownership_nullifier: [u8; 32],
+    pub quantity: u64,
+    pub ownership_change_leaf: [u8; 32],
+    pub ownership_change_pkd: [u8; 32],
+    pub new_ownership_leaf: [u8; 32],
+    pub tree_epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub price: u64,
+    pub change_leaf: [u8; 32],
+    pub change_pkd: [u8; 32],
+    pub payment_leaf: [u8; 32],
+    pub credential: Option<CredentialPublicV2>,
+    pub tx_id: [u8; 16],
+}
+
+/// `redeem_ownership_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct RedeemPublicV2 {
+    pub rwa_id: [u8; 32],
+    pub ownership_root: [u8; 32],
+    pub ownership_nullifier: [u8; 32],
+    pub quantity: u64,
+    pub ownership_change_leaf: [u8; 32],
+    pub ownership_change_pkd: [u8; 32],
+    pub claimant: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// `place_bid_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionBidPublicV2 {
+    pub auction_id: u32,
+    pub tree_epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub lock_leaf: [u8; 32],
+    pub lock_pkd: [u8; 32],
+    pub bid_commitment: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// `reveal_bid_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionRevealPublicV2 {
+    pub auction_id: u32,
+    pub bid_index: u32,
+    pub bid: u64,
+    pub salt: [u8; 32],
+    pub lock_nullifier: [u8; 32],
+    pub change_leaf: [u8; 32],
+    pub change_pkd: [u8; 32],
+    pub refund_leaf: [u8; 32],
+    pub ownership_leaf: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// `release_bid_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionReleasePublicV2 {
+    pub auction_id: u32,
+    pub bid_index: u32,
+    pub lock_nullifier: [u8; 32],
+    pub change_leaf: [u8; 32],
+    pub change_pkd: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// A subscription plan of an access-key app. Same layout on both lanes.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccessPlan {
+    pub price: u64,
+    pub duration: u32,
+    pub tier: u8,
+    pub max_uses: u32,
+}
+
+// ---- ScanProof ----
+
+/// Paymaster fee credit spend on ScanProof (`pallet_proofs::FeeTicket`): a
+/// one-of-many proof over fee coin group `group_id` revealing only `serial`,
+/// bound to BLAKE2-256(public inputs).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct CoinFeeTicket {
+    pub group_id: u32,
+    pub serial: [u8; 32],
+    pub proof: Vec<u8>,
+}
+
+/// `purchase_coin` and `purchase_access_coin`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct CoinSpendPublic {
+    pub group_id: u32,
+    pub serial: [u8; 32],
+    pub rwa_id: [u8; 32],
+    pub quantity: u64,
+    pub rate_round: Option<u32>,
+    pub access_plan: Option<u8>,
+    pub tx_id: [u8; 16],
+    pub ownership_commitment: [u8; 32],
+    pub change: [u8; 32],
+    pub change_coin: [u8; 32],
+}
+
+/// `withdraw_coin`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct CoinWithdrawPublic {
+    pub group_id: u32,
+    pub serial: [u8; 32],
+    pub amount: u64,
+    pub destination: [u8; 32],
+    pub tx_id: [u8; 16],
+}
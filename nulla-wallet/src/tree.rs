@@ -0,0 +1,189 @@
+//! Local mirror of a depth-20 Poseidon note tree.
+//!
+//! The chain keeps only the frontier (`FrontierNodesV2`), which cannot
+//! produce paths. The mirror keeps every node instead: a right edge still
+//! waiting for its sibling is hashed against the zero subtree, exactly as
+//! `v2::Frontier::root` folds it, so the mirror root always equals the chain
+//! root after the same inserts. Inserting costs 20 hashes; paths are lookups.
+//!
+//! The same mirror serves the per-RWA ownership trees and the auction lock
+//! trees, which share the hash and depth.
+
+use proofhub_verifier::v2::{merkle_hash2, zero_subtrees};
+
+pub use proofhub_verifier::v2::MERKLE_DEPTH;
+
+use crate::Error;
+
+/// Sibling path from a leaf to the root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerklePath {
+    pub leaf_index: u32,
+    /// `siblings[d]` is the sibling at depth d (leaf level = 0).
+    pub siblings: [[u8; 32]; MERKLE_DEPTH],
+}
+
+impl MerklePath {
+    /// Root reached by folding `leaf` up the path.
+    pub fn root(&self, leaf: &[u8; 32]) -> [u8; 32] {
+        let mut node = *leaf;
+        let mut idx = self.leaf_index;
+        for sibling in &self.siblings {
+            node = if idx & 1 == 0 { merkle_hash2(&node, sibling) } else { merkle_hash2(sibling, &node) };
+            idx >>= 1;
+        }
+        node
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TreeMirror {
+    /// `levels[d][i]` is node i at depth d; `levels[MERKLE_DEPTH]` holds the root.
+    levels: Vec<Vec<[u8; 32]>>,
+    zeros: Vec<[u8; 32]>,
+}
+
+impl Default for TreeMirror {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TreeMirror {
+    pub fn new() -> Self {
+        TreeMirror { levels: vec![Vec::new(); MERKLE_DEPTH + 1], zeros: zero_subtrees() }
+    }
+
+    /// Mirror rebuilt from every leaf in insertion order, e.g. after a
+    /// [`Error::LeafOutOfOrder`] resync from chain storage.
+    pub fn from_leaves(leaves: &[[u8; 32]]) -> Result<Self, Error> {
+        let mut tree = Self::new();
+        for leaf in leaves {
+            tree.push(*leaf)?;
+        }
+        Ok(tree)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.levels[0].len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    pub fn leaf(&self, index: u32) -> Option<[u8; 32]> {
+        self.levels[0].get(index as usize).copied()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[MERKLE_DEPTH].first().copied().unwrap_or(self.zeros[MERKLE_DEPTH])
+    }
+
+    /// Append `leaf`; returns its index.
+    pub fn push(&mut self, leaf: [u8; 32]) -> Result<u32, Error> {
+        let index = self.len();
+        if index as u64 >= 1u64 << MERKLE_DEPTH {
+            return Err(Error::TreeFull);
+        }
+        self.levels[0].push(leaf);
+        let mut node = leaf;
+        let mut idx = index as usize;
+        for d in 0..MERKLE_DEPTH {
+            node = if idx & 1 == 0 {
+                let right = self.levels[d].get(idx + 1).copied().unwrap_or(self.zeros[d]);
+                merkle_hash2(&node, &right)
+            } else {
+                merkle_hash2(&self.levels[d][idx - 1], &node)
+            };
+            idx >>= 1;
+            let parents = &mut self.levels[d + 1];
+            if idx < parents.len() {
+                parents[idx] = node;
+            } else {
+                parents.push(node);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Insert `leaf` at the index the chain reported. Leaves must arrive in
+    /// order; a replayed leaf is accepted once more and returns `false`.
+    pub fn insert(&mut self, index: u32, leaf: [u8; 32]) -> Result<bool, Error> {
+        let len = self.len();
+        if index < len {
+            return match self.leaf(index) == Some(leaf) {
+                true => Ok(false),
+                false => Err(Error::LeafConflict { index }),
+            };
+        }
+        if index > len {
+            return Err(Error::LeafOutOfOrder { expected: len, got: index });
+        }
+        self.push(leaf).map(|_| true)
+    }
+
+    /// Path of the leaf at `index` against the current root.
+    pub fn path(&self, index: u32) -> Option<MerklePath> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut siblings = [[0u8; 32]; MERKLE_DEPTH];
+        let mut idx = index as usize;
+        for (d, sibling) in siblings.iter_mut().enumerate() {
+            *sibling = self.levels[d].get(idx ^ 1).copied().unwrap_or(self.zeros[d]);
+            idx >>= 1;
+        }
+        Some(MerklePath { leaf_index: index, siblings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proofhub_verifier::v2::{reference_root, verify_merkle_path, Frontier};
+
+    fn leaf(i: u8) -> [u8; 32] {
+        [i.wrapping_add(1); 32]
+    }
+
+    #[test]
+    fn root_tracks_the_chain_frontier() {
+        let mut tree = TreeMirror::new();
+        let mut frontier = Frontier::default();
+        assert_eq!(tree.root(), frontier.root());
+        for i in 0..9u8 {
+            tree.push(leaf(i)).unwrap();
+            frontier.insert(leaf(i)).unwrap();
+            assert_eq!(tree.root(), frontier.root());
+        }
+        let leaves: Vec<_> = (0..9u8).map(leaf).collect();
+        assert_eq!(tree.root(), reference_root(&leaves));
+    }
+
+    #[test]
+    fn every_path_verifies_against_the_current_root() {
+        let mut tree = TreeMirror::new();
+        for i in 0..6u8 {
+            tree.push(leaf(i)).unwrap();
+        }
+        let root = tree.root();
+        for i in 0..6u32 {
+            let path = tree.path(i).unwrap();
+            assert_eq!(path.root(&leaf(i as u8)), root);
+            assert!(verify_merkle_path(&leaf(i as u8), i, &path.siblings, &root));
+        }
+        assert!(tree.path(6).is_none());
+    }
+
+    #[test]
+    fn inserts_must_arrive_in_order() {
+        let mut tree = TreeMirror::new();
+        assert_eq!(tree.insert(0, leaf(0)), Ok(true));
+        assert_eq!(tree.insert(0, leaf(0)), Ok(false));
+        assert_eq!(tree.insert(0, leaf(1)), Err(Error::LeafConflict { index: 0 }));
+        assert_eq!(tree.insert(2, leaf(2)), Err(Error::LeafOutOfOrder { expected: 1, got: 2 }));
+        assert_eq!(tree.insert(1, leaf(1)), Ok(true));
+        assert_eq!(TreeMirror::from_leaves(&[leaf(0), leaf(1)]).unwrap().root(), tree.root());
+    }
+}
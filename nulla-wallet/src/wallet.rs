@@ -0,0 +1,541 @@
+//! Owned notes, tree sync and spend preparation.
+
+use std::collections::BTreeMap;
+
+use parity_scale_codec::Encode;
+use proofhub_verifier::v2::{note_hash, nullifier_hash, SPEND_AUTH_DOMAIN, WITHDRAW_AUTH_DOMAIN};
+use rand_core::{OsRng, RngCore};
+use scanproof_verifier::one_of_many::{coin_commit, pedersen_commit};
+
+use crate::keys::{KeyPair, Purpose, Seed};
+use crate::public::{CoinSpendPublic, CoinWithdrawPublic, CredentialPublicV2, SpendPublicV2, WithdrawPublicV2};
+use crate::tree::{MerklePath, TreeMirror};
+use crate::Error;
+
+/// A v2 note derived by the wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedNote {
+    /// Derivation index of the note's blinding and keypair.
+    pub key_index: u32,
+    pub value: u64,
+    /// NoteHash(value, b, pkd).
+    pub leaf: [u8; 32],
+    /// Set once the leaf is seen in the tree.
+    pub leaf_index: Option<u32>,
+    /// Tree epoch `leaf_index` refers to.
+    pub tree_epoch: u32,
+    pub spent: bool,
+}
+
+/// A lane event that inserted leaves into the v2 tree, or started a new tree
+/// epoch, as the client decoded it. Every such event must be applied, in
+/// block order, to keep the mirror in step with `CurrentRootV2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreeEvent {
+    TreeEpochV2Started { epoch: u32, final_root: [u8; 32] },
+    DepositV2Accepted { leaf: [u8; 32], leaf_index: u32, new_root: [u8; 32] },
+    PurchaseV2Authorized { change_leaf: [u8; 32], change_leaf_index: u32, new_root: [u8; 32] },
+    AccessPurchaseV2Authorized { change_leaf: [u8; 32], change_leaf_index: u32, new_root: [u8; 32] },
+    RelistV2Completed {
+        payment_leaf: [u8; 32],
+        payment_leaf_index: u32,
+        change_leaf: [u8; 32],
+        change_leaf_index: u32,
+        new_root: [u8; 32],
+    },
+    EscrowRefunded { refund_leaf: [u8; 32], refund_leaf_index: u32, new_root: [u8; 32] },
+    AuctionBidRevealed { change_leaf: [u8; 32], change_leaf_index: u32, new_root: [u8; 32] },
+    AuctionBidReleased { change_leaf: [u8; 32], change_leaf_index: u32, new_root: [u8; 32] },
+    AuctionRefundInserted { refund_leaf: [u8; 32], refund_leaf_index: u32, new_root: [u8; 32] },
+}
+
+impl TreeEvent {
+    /// (leaf_index, leaf) pairs in insertion order, and the root after them.
+    fn leaves(&self) -> (Vec<(u32, [u8; 32])>, [u8; 32]) {
+        match *self {
+            TreeEvent::TreeEpochV2Started { final_root, .. } => (Vec::new(), final_root),
+            TreeEvent::DepositV2Accepted { leaf, leaf_index, new_root } => (vec![(leaf_index, leaf)], new_root),
+            TreeEvent::PurchaseV2Authorized { change_leaf, change_leaf_index, new_root }
+            | TreeEvent::AccessPurchaseV2Authorized { change_leaf, change_leaf_index, new_root }
+            | TreeEvent::AuctionBidRevealed { change_leaf, change_leaf_index, new_root }
+            | TreeEvent::AuctionBidReleased { change_leaf, change_leaf_index, new_root } => {
+                (vec![(change_leaf_index, change_leaf)], new_root)
+            }
+            TreeEvent::RelistV2Completed {
+                payment_leaf,
+                payment_leaf_index,
+                change_leaf,
+                change_leaf_index,
+                new_root,
+            } => (vec![(payment_leaf_index, payment_leaf), (change_leaf_index, change_leaf)], new_root),
+            TreeEvent::EscrowRefunded { refund_leaf, refund_leaf_index, new_root }
+            | TreeEvent::AuctionRefundInserted { refund_leaf, refund_leaf_index, new_root } => {
+                (vec![(refund_leaf_index, refund_leaf)], new_root)
+            }
+        }
+    }
+}
+
+/// What a purchase buys; the lane checks `price` against its own pricing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpendTerms {
+    /// RWA id, or the app id on the access lane.
+    pub rwa_id: [u8; 32],
+    /// Units bought; 1 on the access lane.
+    pub quantity: u64,
+    /// Total price in planck taken out of the note.
+    pub price: u64,
+    pub rate_round: Option<u32>,
+    pub credential: Option<CredentialPublicV2>,
+    pub access_plan: Option<u8>,
+    /// Ownership leaf, or the access-key commitment on the access lane.
+    pub ownership_commitment: [u8; 32],
+    pub tx_id: [u8; 16],
+}
+
+/// A v2 purchase ready to prove and sign.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedSpend {
+    pub note: OwnedNote,
+    pub path: MerklePath,
+    pub public_inputs: SpendPublicV2,
+    /// The change note; tracked by the wallet until its leaf is inserted.
+    pub change: OwnedNote,
+    /// The refund note of `price`, inserted only if an RWA escrow is
+    /// cancelled or times out.
+    pub refund: OwnedNote,
+}
+
+/// A v2 withdrawal ready to prove and sign.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedWithdraw {
+    pub note: OwnedNote,
+    pub path: MerklePath,
+    pub public_inputs: WithdrawPublicV2,
+}
+
+/// Random 16-byte transaction id.
+pub fn random_tx_id() -> [u8; 16] {
+    let mut tx_id = [0u8; 16];
+    OsRng.fill_bytes(&mut tx_id);
+    tx_id
+}
+
+pub struct Wallet {
+    seed: Seed,
+    next_key: u32,
+    notes: Vec<OwnedNote>,
+    /// Current tree epoch and its mirror.
+    epoch: u32,
+    tree: TreeMirror,
+    /// Mirrors of closed epochs that still hold an unspent owned note.
+    closed: BTreeMap<u32, TreeMirror>,
+}
+
+impl Wallet {
+    pub fn new(seed: Seed) -> Self {
+        Wallet { seed, next_key: 0, notes: Vec::new(), epoch: 0, tree: TreeMirror::new(), closed: BTreeMap::new() }
+    }
+
+    /// Wallet restored from persisted state.
+    pub fn restore(
+        seed: Seed,
+        next_key: u32,
+        notes: Vec<OwnedNote>,
+        epoch: u32,
+        tree: TreeMirror,
+        closed: BTreeMap<u32, TreeMirror>,
+    ) -> Self {
+        Wallet { seed, next_key, notes, epoch, tree, closed }
+    }
+
+    pub fn seed(&self) -> &Seed {
+        &self.seed
+    }
+
+    pub fn next_key(&self) -> u32 {
+        self.next_key
+    }
+
+    pub fn notes(&self) -> &[OwnedNote] {
+        &self.notes
+    }
+
+    pub fn tree(&self) -> &TreeMirror {
+        &self.tree
+    }
+
+    /// Current tree epoch (`TreeEpochV2`).
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Mirrors of the closed epochs kept for unspent notes.
+    pub fn closed_trees(&self) -> &BTreeMap<u32, TreeMirror> {
+        &self.closed
+    }
+
+    /// Sum of the unspent notes already in the tree.
+    pub fn balance(&self) -> u64 {
+        self.notes
+            .iter()
+            .filter(|n| !n.spent && n.leaf_index.is_some())
+            .fold(0u64, |acc, n| acc.saturating_add(n.value))
+    }
+
+    /// Take the next derivation index.
+    pub fn fresh_index(&mut self) -> u32 {
+        let index = self.next_key;
+        self.next_key += 1;
+        index
+    }
+
+    pub fn derive(&self, purpose: Purpose, index: u32) -> [u8; 32] {
+        self.seed.derive(purpose, index)
+    }
+
+    pub fn keypair(&self, index: u32) -> KeyPair {
+        self.seed.keypair(index)
+    }
+
+    /// (blinding, pkd) of the note at `key_index`.
+    pub fn note_secrets(&self, key_index: u32) -> ([u8; 32], [u8; 32]) {
+        (self.derive(Purpose::NoteBlinding, key_index), self.keypair(key_index).pk_digest())
+    }
+
+    /// Derive and track a new note of `value`: the leaf of a `deposit_v2`,
+    /// or a change, refund or payment note.
+    pub fn new_note(&mut self, value: u64) -> OwnedNote {
+        let key_index = self.fresh_index();
+        let (b, pkd) = self.note_secrets(key_index);
+        let note = OwnedNote { key_index, value, leaf: note_hash(value, &b, &pkd), leaf_index: None, tree_epoch: 0, spent: false };
+        self.notes.push(note.clone());
+        note
+    }
+
+    pub fn note(&self, key_index: u32) -> Option<&OwnedNote> {
+        self.notes.iter().find(|n| n.key_index == key_index)
+    }
+
+    /// Nullifier the note reveals when spent.
+    pub fn nullifier(&self, key_index: u32) -> [u8; 32] {
+        nullifier_hash(&self.derive(Purpose::NoteBlinding, key_index))
+    }
+
+    /// Apply one tree event. Owned leaves get their index; the mirror root
+    /// is checked against the chain's `new_root`. A new epoch closes the
+    /// mirror, which is kept only while it holds an unspent owned note.
+    pub fn apply(&mut self, event: &TreeEvent) -> Result<(), Error> {
+        if let TreeEvent::TreeEpochV2Started { epoch, final_root } = *event {
+            if epoch <= self.epoch {
+                return Ok(());
+            }
+            if epoch != self.epoch + 1 || self.tree.root() != final_root {
+                return Err(Error::RootMismatch { chain: final_root, local: self.tree.root() });
+            }
+            let closed = std::mem::take(&mut self.tree);
+            let closing = self.epoch;
+            if self.notes.iter().any(|n| !n.spent && n.leaf_index.is_some() && n.tree_epoch == closing) {
+                self.closed.insert(closing, closed);
+            }
+            self.epoch = epoch;
+            return Ok(());
+        }
+        let (leaves, new_root) = event.leaves();
+        let mut inserted = false;
+        for (index, leaf) in leaves {
+            inserted |= self.tree.insert(index, leaf)?;
+            if let Some(note) = self.notes.iter_mut().find(|n| n.leaf == leaf && n.leaf_index.is_none()) {
+                note.leaf_index = Some(index);
+                note.tree_epoch = self.epoch;
+            }
+        }
+        // A replayed event is accepted as is; its root is no longer current.
+        if inserted && self.tree.root() != new_root {
+            return Err(Error::RootMismatch { chain: new_root, local: self.tree.root() });
+        }
+        Ok(())
+    }
+
+    /// Mark a note spent once its spend is included. A closed epoch's
+    /// mirror is dropped with its last unspent owned note.
+    pub fn mark_spent(&mut self, key_index: u32) {
+        let Some(note) = self.notes.iter_mut().find(|n| n.key_index == key_index) else {
+            return;
+        };
+        note.spent = true;
+        let epoch = note.tree_epoch;
+        if !self.notes.iter().any(|n| !n.spent && n.leaf_index.is_some() && n.tree_epoch == epoch) {
+            self.closed.remove(&epoch);
+        }
+    }
+
+    /// Mirror of a tree epoch: the current tree or a kept closed one.
+    fn epoch_tree(&self, epoch: u32) -> Option<&TreeMirror> {
+        if epoch == self.epoch {
+            Some(&self.tree)
+        } else {
+            self.closed.get(&epoch)
+        }
+    }
+
+    /// Path of an unspent, inserted note against its epoch's mirror root:
+    /// the current root, or the final root of a closed epoch.
+    pub fn path(&self, key_index: u32) -> Result<(OwnedNote, MerklePath), Error> {
+        let note = self.note(key_index).ok_or(Error::UnknownNote)?;
+        if note.spent {
+            return Err(Error::NoteSpent);
+        }
+        let index = note.leaf_index.ok_or(Error::NoteNotInserted)?;
+        let tree = self.epoch_tree(note.tree_epoch).ok_or(Error::NoteNotInserted)?;
+        let path = tree.path(index).ok_or(Error::NoteNotInserted)?;
+        Ok((note.clone(), path))
+    }
+
+    /// Public inputs of a `purchase_rwa_v2` / `purchase_access_v2` spending
+    /// the note at `key_index`; the change and refund notes are derived and
+    /// tracked.
+    pub fn prepare_spend(&mut self, key_index: u32, terms: SpendTerms) -> Result<PreparedSpend, Error> {
+        let (note, path) = self.path(key_index)?;
+        let change_value = note
+            .value
+            .checked_sub(terms.price)
+            .ok_or(Error::InsufficientValue { value: note.value, required: terms.price })?;
+        let change = self.new_note(change_value);
+        let change_pkd = self.keypair(change.key_index).pk_digest();
+        let refund = self.new_note(terms.price);
+        let public_inputs = SpendPublicV2 {
+            tree_epoch: note.tree_epoch,
+            merkle_root: path.root(&note.leaf),
+            nullifier: self.nullifier(key_index),
+            rwa_id: terms.rwa_id,
+            quantity: terms.quantity,
+            rate_round: terms.rate_round,
+            credential: terms.credential,
+            access_plan: terms.access_plan,
+            tx_id: terms.tx_id,
+            ownership_commitment: terms.ownership_commitment,
+            change_leaf: change.leaf,
+            change_pkd,
+            refund_leaf: refund.leaf,
+        };
+        Ok(PreparedSpend { note, path, public_inputs, change, refund })
+    }
+
+    /// Public inputs of a `withdraw_v2` of the whole note to `destination`.
+    pub fn prepare_withdraw(
+        &self,
+        key_index: u32,
+        destination: [u8; 32],
+        tx_id: [u8; 16],
+    ) -> Result<PreparedWithdraw, Error> {
+        let (note, path) = self.path(key_index)?;
+        let public_inputs = WithdrawPublicV2 {
+            tree_epoch: note.tree_epoch,
+            merkle_root: path.root(&note.leaf),
+            nullifier: self.nullifier(key_index),
+            amount: note.value,
+            destination,
+            tx_id,
+        };
+        Ok(PreparedWithdraw { note, path, public_inputs })
+    }
+
+    /// `auth` of a v2 extrinsic signed by the note at `key_index`: the
+    /// message is SCALE(public inputs) followed by SCALE(fee ticket) when one
+    /// is attached. `withdraw` selects the withdraw domain, used by
+    /// `withdraw_v2` and `redeem_ownership_v2`.
+    pub fn sign<P: Encode, F: Encode>(
+        &self,
+        key_index: u32,
+        public_inputs: &P,
+        fee: &Option<F>,
+        withdraw: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let mut message = public_inputs.encode();
+        if let Some(ticket) = fee {
+            ticket.encode_to(&mut message);
+        }
+        let domain = if withdraw { WITHDRAW_AUTH_DOMAIN } else { SPEND_AUTH_DOMAIN };
+        self.keypair(key_index).sign_auth(domain, &message)
+    }
+
+    /// STARK witness of a prepared spend; `change` is `None` for a withdrawal.
+    #[cfg(feature = "prover")]
+    pub fn spend_witness(
+        &self,
+        note: &OwnedNote,
+        path: &MerklePath,
+        change: Option<&OwnedNote>,
+    ) -> proofhub_verifier::prover::SpendWitness {
+        let (b, pkd) = self.note_secrets(note.key_index);
+        let (cv, cb, cpkd) = match change {
+            Some(c) => {
+                let (cb, cpkd) = self.note_secrets(c.key_index);
+                (c.value, cb, cpkd)
+            }
+            None => (0, [0u8; 32], [0u8; 32]),
+        };
+        proofhub_verifier::prover::SpendWitness {
+            v: note.value,
+            b,
+            pkd,
+            index: path.leaf_index,
+            siblings: path.siblings,
+            cv,
+            cb,
+            cpkd,
+        }
+    }
+
+    // ---- ScanProof coins ----
+
+    /// (serial, blinding) of the coin at `key_index`.
+    pub fn coin_secrets(&self, key_index: u32) -> ([u8; 32], [u8; 32]) {
+        (self.derive(Purpose::CoinSerial, key_index), self.derive(Purpose::CoinBlinding, key_index))
+    }
+
+    /// Coin C = s·G1 + v·G + r·H at `key_index`, as passed to `deposit_coin`.
+    pub fn coin(&self, key_index: u32, value: u64) -> [u8; 32] {
+        let (s, r) = self.coin_secrets(key_index);
+        coin_commit(&s, value, &r)
+    }
+
+    /// Public inputs of a `purchase_coin` / `purchase_access_coin` spending
+    /// the coin at `key_index` (of `value`) from group `group_id`. Returns
+    /// the key index of the change coin, or `None` when nothing is left.
+    pub fn prepare_coin_spend(
+        &mut self,
+        key_index: u32,
+        value: u64,
+        group_id: u32,
+        terms: SpendTerms,
+    ) -> Result<(CoinSpendPublic, Option<u32>), Error> {
+        let change_value =
+            value.checked_sub(terms.price).ok_or(Error::InsufficientValue { value, required: terms.price })?;
+        let (change, change_coin, change_index) = if change_value == 0 {
+            ([0u8; 32], [0u8; 32], None)
+        } else {
+            let index = self.fresh_index();
+            let (_, r) = self.coin_secrets(index);
+            (pedersen_commit(change_value, &r), self.coin(index, change_value), Some(index))
+        };
+        let public_inputs = CoinSpendPublic {
+            group_id,
+            serial: self.coin_secrets(key_index).0,
+            rwa_id: terms.rwa_id,
+            quantity: terms.quantity,
+            rate_round: terms.rate_round,
+            access_plan: terms.access_plan,
+            tx_id: terms.tx_id,
+            ownership_commitment: terms.ownership_commitment,
+            change,
+            change_coin,
+        };
+        Ok((public_inputs, change_index))
+    }
+
+    /// Public inputs of a `withdraw_coin` of the whole coin to `destination`.
+    pub fn prepare_coin_withdraw(
+        &self,
+        key_index: u32,
+        value: u64,
+        group_id: u32,
+        destination: [u8; 32],
+        tx_id: [u8; 16],
+    ) -> CoinWithdrawPublic {
+        CoinWithdrawPublic { group_id, serial: self.coin_secrets(key_index).0, amount: value, destination, tx_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(wallet: &mut Wallet, leaf: [u8; 32]) -> Result<(), Error> {
+        let mut mirror = wallet.tree().clone();
+        let leaf_index = mirror.push(leaf)?;
+        wallet.apply(&TreeEvent::DepositV2Accepted { leaf, leaf_index, new_root: mirror.root() })
+    }
+
+    #[test]
+    fn deposits_are_tracked_and_spent_with_change() {
+        let mut wallet = Wallet::new(Seed::from_bytes([1u8; 32]));
+        deposit(&mut wallet, [0xaa; 32]).unwrap();
+        let note = wallet.new_note(1_000);
+        assert_eq!(wallet.balance(), 0);
+        deposit(&mut wallet, note.leaf).unwrap();
+        assert_eq!(wallet.note(note.key_index).unwrap().leaf_index, Some(1));
+        assert_eq!(wallet.balance(), 1_000);
+
+        let terms = SpendTerms {
+            rwa_id: crate::public::rwa_id(3),
+            quantity: 2,
+            price: 600,
+            rate_round: None,
+            credential: None,
+            access_plan: None,
+            ownership_commitment: [5u8; 32],
+            tx_id: [6u8; 16],
+        };
+        let spend = wallet.prepare_spend(note.key_index, terms.clone()).unwrap();
+        assert_eq!(spend.path.root(&note.leaf), spend.public_inputs.merkle_root);
+        assert_eq!(spend.change.value, 400);
+        let (cb, cpkd) = wallet.note_secrets(spend.change.key_index);
+        assert_eq!(spend.public_inputs.change_leaf, note_hash(400, &cb, &cpkd));
+        let (rb, rpkd) = wallet.note_secrets(spend.refund.key_index);
+        assert_eq!(spend.public_inputs.refund_leaf, note_hash(600, &rb, &rpkd));
+
+        let auth = wallet.sign(note.key_index, &spend.public_inputs, &None::<()>, false).unwrap();
+        assert!(proofhub_verifier::verify_spend_auth_v2(&auth, &spend.public_inputs.encode(), false));
+
+        let mut mirror = wallet.tree().clone();
+        let change_leaf_index = mirror.push(spend.change.leaf).unwrap();
+        wallet
+            .apply(&TreeEvent::PurchaseV2Authorized {
+                change_leaf: spend.change.leaf,
+                change_leaf_index,
+                new_root: mirror.root(),
+            })
+            .unwrap();
+        wallet.mark_spent(note.key_index);
+        assert_eq!(wallet.balance(), 400);
+        assert_eq!(wallet.prepare_spend(note.key_index, terms).unwrap_err(), Error::NoteSpent);
+    }
+
+    #[test]
+    fn closed_epochs_stay_spendable() {
+        let mut wallet = Wallet::new(Seed::from_bytes([3u8; 32]));
+        let old = wallet.new_note(500);
+        deposit(&mut wallet, old.leaf).unwrap();
+        let final_root = wallet.tree().root();
+        let bad = TreeEvent::TreeEpochV2Started { epoch: 1, final_root: [0u8; 32] };
+        assert!(matches!(wallet.apply(&bad), Err(Error::RootMismatch { .. })));
+        wallet.apply(&TreeEvent::TreeEpochV2Started { epoch: 1, final_root }).unwrap();
+        assert_eq!(wallet.epoch(), 1);
+        assert!(wallet.tree().is_empty());
+
+        let new = wallet.new_note(700);
+        deposit(&mut wallet, new.leaf).unwrap();
+        assert_eq!(wallet.note(new.key_index).unwrap().leaf_index, Some(0));
+        assert_eq!(wallet.note(new.key_index).unwrap().tree_epoch, 1);
+
+        let withdraw = wallet.prepare_withdraw(old.key_index, [9u8; 32], [1u8; 16]).unwrap();
+        assert_eq!(withdraw.public_inputs.tree_epoch, 0);
+        assert_eq!(withdraw.public_inputs.merkle_root, final_root);
+        let withdraw = wallet.prepare_withdraw(new.key_index, [9u8; 32], [2u8; 16]).unwrap();
+        assert_eq!(withdraw.public_inputs.tree_epoch, 1);
+        assert_eq!(withdraw.public_inputs.merkle_root, wallet.tree().root());
+
+        wallet.mark_spent(old.key_index);
+        assert!(wallet.closed_trees().is_empty());
+        assert_eq!(wallet.balance(), 700);
+    }
+
+    #[test]
+    fn apply_rejects_a_diverging_root() {
+        let mut wallet = Wallet::new(Seed::from_bytes([2u8; 32]));
+        let event = TreeEvent::DepositV2Accepted { leaf: [1u8; 32], leaf_index: 0, new_root: [0u8; 32] };
+        assert!(matches!(wallet.apply(&event), Err(Error::RootMismatch { .. })));
+    }
+}
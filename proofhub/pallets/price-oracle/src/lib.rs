@@ -0,0 +1,246 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! NULLA/USD price feed for the private lanes.
+//!
+//! Whitelisted reporters submit a rate in planck per USD cent. Every report
+//! re-aggregates the median of the reports younger than `MaxReportAge`; once at
+//! least `MinReports` are fresh, a changed median opens a new rate round and an
+//! unchanged one re-confirms the current round.
+//!
+//! Lanes price RWAs in USD cents and convert at spend time against the round a
+//! spend names in its public inputs. A proof built against one round is not
+//! invalidated by a report landing in the same block: a superseded round stays
+//! usable for `RoundGracePeriod` blocks. The current round becomes stale, and
+//! unusable, when it has not been confirmed for `MaxRoundAge` blocks.
+
+extern crate alloc;
+
+pub use pallet::*;
+
+/// Rounds kept in storage; older rounds are pruned as new ones open.
+pub const ROUND_HISTORY: u32 = 64;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::ROUND_HISTORY;
+	use alloc::vec::Vec;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+
+	/// Latest rate submitted by one reporter.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub struct RateReport<BlockNumber> {
+		pub planck_per_cent: u64,
+		pub reported_at: BlockNumber,
+	}
+
+	/// An aggregated rate round.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+	pub struct RateRound<BlockNumber> {
+		/// Median of the fresh reports, in planck per USD cent.
+		pub planck_per_cent: u64,
+		/// Block at which the round was opened or last re-confirmed.
+		pub updated_at: BlockNumber,
+		/// Block at which a newer round replaced this one.
+		pub superseded_at: Option<BlockNumber>,
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin that manages the reporter whitelist.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum number of whitelisted reporters.
+		#[pallet::constant]
+		type MaxReporters: Get<u32>;
+		/// Fresh reports required before a round is opened or confirmed.
+		#[pallet::constant]
+		type MinReports: Get<u32>;
+		/// Reports older than this many blocks are left out of the median.
+		#[pallet::constant]
+		type MaxReportAge: Get<BlockNumberFor<Self>>;
+		/// The current round is stale once unconfirmed for this many blocks.
+		#[pallet::constant]
+		type MaxRoundAge: Get<BlockNumberFor<Self>>;
+		/// Blocks a superseded round stays usable for in-flight spends.
+		#[pallet::constant]
+		type RoundGracePeriod: Get<BlockNumberFor<Self>>;
+	}
+
+	/// Whitelisted reporters.
+	#[pallet::storage]
+	#[pallet::getter(fn reporters)]
+	pub type Reporters<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxReporters>, ValueQuery>;
+
+	/// Latest report per reporter.
+	#[pallet::storage]
+	#[pallet::getter(fn reports)]
+	pub type Reports<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RateReport<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Id of the current rate round; `None` until the first aggregation.
+	#[pallet::storage]
+	#[pallet::getter(fn current_round)]
+	pub type CurrentRound<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// round id → RateRound. The last `ROUND_HISTORY` rounds are kept.
+	#[pallet::storage]
+	#[pallet::getter(fn rounds)]
+	pub type Rounds<T: Config> =
+		StorageMap<_, Twox64Concat, u32, RateRound<BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub reporters: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let reporters: BoundedVec<T::AccountId, T::MaxReporters> =
+				self.reporters.clone().try_into().expect("too many genesis reporters");
+			Reporters::<T>::put(reporters);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		ReporterAdded { reporter: T::AccountId },
+		ReporterRemoved { reporter: T::AccountId },
+		RateReported { reporter: T::AccountId, planck_per_cent: u64 },
+		/// A new median opened rate round `round`.
+		RoundOpened { round: u32, planck_per_cent: u64 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Caller is not a whitelisted reporter.
+		NotReporter,
+		/// Account is already a reporter.
+		AlreadyReporter,
+		/// The reporter whitelist is full.
+		TooManyReporters,
+		/// A rate of zero is never valid.
+		ZeroRate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add a reporter to the whitelist (Admin only).
+		#[pallet::call_index(0)]
+		#[pallet::weight(5_000)]
+		pub fn add_reporter(origin: OriginFor<T>, reporter: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Reporters::<T>::try_mutate(|reporters| -> DispatchResult {
+				ensure!(!reporters.contains(&reporter), Error::<T>::AlreadyReporter);
+				reporters.try_push(reporter.clone()).map_err(|_| Error::<T>::TooManyReporters)?;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::ReporterAdded { reporter });
+			Ok(())
+		}
+
+		/// Remove a reporter and its latest report (Admin only).
+		#[pallet::call_index(1)]
+		#[pallet::weight(5_000)]
+		pub fn remove_reporter(origin: OriginFor<T>, reporter: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Reporters::<T>::try_mutate(|reporters| -> DispatchResult {
+				let before = reporters.len();
+				reporters.retain(|r| r != &reporter);
+				ensure!(reporters.len() < before, Error::<T>::NotReporter);
+				Ok(())
+			})?;
+			Reports::<T>::remove(&reporter);
+			Self::deposit_event(Event::ReporterRemoved { reporter });
+			Ok(())
+		}
+
+		/// Submit a NULLA/USD rate in planck per USD cent (whitelisted reporters only).
+		///
+		/// Re-aggregates the median of the fresh reports.
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000)]
+		pub fn report(origin: OriginFor<T>, planck_per_cent: u64) -> DispatchResult {
+			let reporter = ensure_signed(origin)?;
+			ensure!(Reporters::<T>::get().contains(&reporter), Error::<T>::NotReporter);
+			ensure!(planck_per_cent > 0, Error::<T>::ZeroRate);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			Reports::<T>::insert(&reporter, RateReport { planck_per_cent, reported_at: now });
+			Self::deposit_event(Event::RateReported { reporter, planck_per_cent });
+			Self::aggregate(now);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Median of the fresh reports; opens a new round when it changed,
+		/// re-confirms the current round otherwise.
+		fn aggregate(now: BlockNumberFor<T>) {
+			let oldest = now.saturating_sub(T::MaxReportAge::get());
+			let mut rates: Vec<u64> = Reporters::<T>::get()
+				.iter()
+				.filter_map(Reports::<T>::get)
+				.filter(|r| r.reported_at >= oldest)
+				.map(|r| r.planck_per_cent)
+				.collect();
+			if rates.is_empty() || (rates.len() as u32) < T::MinReports::get() {
+				return;
+			}
+			rates.sort_unstable();
+			let mid = rates.len() / 2;
+			let median = if rates.len() % 2 == 0 {
+				((rates[mid - 1] as u128 + rates[mid] as u128) / 2) as u64
+			} else {
+				rates[mid]
+			};
+
+			let current = CurrentRound::<T>::get();
+			if let Some(id) = current {
+				let mut confirmed = false;
+				Rounds::<T>::mutate(id, |round| {
+					if let Some(round) = round.as_mut() {
+						if round.planck_per_cent == median {
+							round.updated_at = now;
+							confirmed = true;
+						} else {
+							round.superseded_at = Some(now);
+						}
+					}
+				});
+				if confirmed {
+					return;
+				}
+			}
+
+			let id = current.map_or(0, |id| id.wrapping_add(1));
+			Rounds::<T>::insert(id, RateRound { planck_per_cent: median, updated_at: now, superseded_at: None });
+			Rounds::<T>::remove(id.wrapping_sub(ROUND_HISTORY));
+			CurrentRound::<T>::put(id);
+			Self::deposit_event(Event::RoundOpened { round: id, planck_per_cent: median });
+		}
+
+		/// Planck per USD cent of `round`, or `None` when the round is unknown,
+		/// superseded for longer than `RoundGracePeriod`, or stale.
+		pub fn rate(round: u32) -> Option<u64> {
+			let r = Rounds::<T>::get(round)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let usable = match r.superseded_at {
+				Some(at) => now <= at.saturating_add(T::RoundGracePeriod::get()),
+				None => now <= r.updated_at.saturating_add(T::MaxRoundAge::get()),
+			};
+			usable.then_some(r.planck_per_cent)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests;
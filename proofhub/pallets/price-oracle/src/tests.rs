@@ -0,0 +1,196 @@
+use crate as pallet_price_oracle;
+use crate::{Error, Event, RateRound, Rounds, ROUND_HISTORY};
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		PriceOracle: pallet_price_oracle,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+impl pallet_price_oracle::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxReporters = ConstU32<4>;
+	type MinReports = ConstU32<2>;
+	type MaxReportAge = ConstU64<10>;
+	type MaxRoundAge = ConstU64<20>;
+	type RoundGracePeriod = ConstU64<3>;
+}
+
+const A: u64 = 1;
+const B: u64 = 2;
+const C: u64 = 3;
+const OUTSIDER: u64 = 9;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_price_oracle::GenesisConfig::<Test> { reporters: vec![A, B, C] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn report(reporter: u64, planck_per_cent: u64) {
+	assert_ok!(PriceOracle::report(RuntimeOrigin::signed(reporter), planck_per_cent));
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+}
+
+#[test]
+fn round_opens_once_min_reports_are_fresh() {
+	new_test_ext().execute_with(|| {
+		report(A, 100);
+		assert_eq!(PriceOracle::current_round(), None);
+		assert_eq!(PriceOracle::rate(0), None);
+
+		// Two reports: the median is their mean.
+		report(B, 301);
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		assert_eq!(PriceOracle::rate(0), Some(200));
+		System::assert_last_event(Event::RoundOpened { round: 0, planck_per_cent: 200 }.into());
+
+		// Three reports: the middle one.
+		report(C, 250);
+		assert_eq!(PriceOracle::current_round(), Some(1));
+		assert_eq!(PriceOracle::rate(1), Some(250));
+		assert_eq!(
+			Rounds::<Test>::get(0),
+			Some(RateRound { planck_per_cent: 200, updated_at: 1, superseded_at: Some(1) })
+		);
+	});
+}
+
+#[test]
+fn median_ignores_a_single_outlier() {
+	new_test_ext().execute_with(|| {
+		report(A, 100);
+		report(B, 100);
+		report(C, 100);
+		report(C, u64::MAX);
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		assert_eq!(PriceOracle::rate(0), Some(100));
+	});
+}
+
+#[test]
+fn even_median_does_not_overflow() {
+	new_test_ext().execute_with(|| {
+		report(A, u64::MAX);
+		report(B, u64::MAX - 1);
+		assert_eq!(PriceOracle::rate(0), Some(u64::MAX - 1));
+	});
+}
+
+#[test]
+fn unchanged_median_reconfirms_the_round() {
+	new_test_ext().execute_with(|| {
+		report(A, 100);
+		report(B, 100);
+		run_to(21);
+		assert_eq!(PriceOracle::rate(0), Some(100));
+		// Unconfirmed for more than MaxRoundAge blocks: stale.
+		run_to(22);
+		assert_eq!(PriceOracle::rate(0), None);
+
+		report(A, 100);
+		report(B, 100);
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		assert_eq!(Rounds::<Test>::get(0).unwrap().updated_at, 22);
+		assert_eq!(PriceOracle::rate(0), Some(100));
+	});
+}
+
+#[test]
+fn superseded_round_is_usable_for_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		report(A, 100);
+		report(B, 100);
+		run_to(5);
+		report(A, 300);
+		assert_eq!(PriceOracle::current_round(), Some(1));
+		assert_eq!(PriceOracle::rate(1), Some(200));
+
+		run_to(8);
+		assert_eq!(PriceOracle::rate(0), Some(100));
+		run_to(9);
+		assert_eq!(PriceOracle::rate(0), None);
+		assert_eq!(PriceOracle::rate(1), Some(200));
+
+		// Reporting the old rate again opens a new round; it does not revive round 0.
+		report(A, 100);
+		assert_eq!(PriceOracle::current_round(), Some(2));
+		assert_eq!(PriceOracle::rate(0), None);
+	});
+}
+
+#[test]
+fn stale_reports_leave_the_median() {
+	new_test_ext().execute_with(|| {
+		report(A, 100);
+		report(B, 100);
+		run_to(12);
+		// A and B are older than MaxReportAge: one fresh report is not enough.
+		report(C, 500);
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		report(B, 500);
+		assert_eq!(PriceOracle::current_round(), Some(1));
+		assert_eq!(PriceOracle::rate(1), Some(500));
+	});
+}
+
+#[test]
+fn old_rounds_are_pruned() {
+	new_test_ext().execute_with(|| {
+		report(A, 1);
+		for rate in 2..=ROUND_HISTORY as u64 + 2 {
+			report(B, rate * 2);
+		}
+		let current = PriceOracle::current_round().unwrap();
+		assert_eq!(current, ROUND_HISTORY);
+		assert!(Rounds::<Test>::get(current - ROUND_HISTORY).is_none());
+		assert!(Rounds::<Test>::get(current - ROUND_HISTORY + 1).is_some());
+	});
+}
+
+#[test]
+fn only_whitelisted_reporters_count() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(PriceOracle::report(RuntimeOrigin::signed(OUTSIDER), 100), Error::<Test>::NotReporter);
+		assert_noop!(PriceOracle::report(RuntimeOrigin::signed(A), 0), Error::<Test>::ZeroRate);
+		assert_noop!(
+			PriceOracle::add_reporter(RuntimeOrigin::signed(A), OUTSIDER),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(PriceOracle::add_reporter(RuntimeOrigin::root(), A), Error::<Test>::AlreadyReporter);
+		assert_ok!(PriceOracle::add_reporter(RuntimeOrigin::root(), OUTSIDER));
+		assert_noop!(PriceOracle::add_reporter(RuntimeOrigin::root(), 10), Error::<Test>::TooManyReporters);
+
+		// A removed reporter's last report leaves the median.
+		report(A, 100);
+		report(B, 300);
+		assert_ok!(PriceOracle::remove_reporter(RuntimeOrigin::root(), B));
+		assert!(PriceOracle::reports(B).is_none());
+		report(C, 100);
+		assert_eq!(PriceOracle::current_round(), Some(1));
+		assert_eq!(PriceOracle::rate(1), Some(100));
+		assert_noop!(PriceOracle::remove_reporter(RuntimeOrigin::root(), B), Error::<Test>::NotReporter);
+	});
+}
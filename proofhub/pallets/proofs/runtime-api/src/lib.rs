@@ -0,0 +1,48 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API for wallets to query ProofHub privacy-lane state.
+//!
+//! A v2 spend anchors to a `(tree_epoch, merkle_root)` pair. A wallet that
+//! builds a proof against an older root checks it with `root_acceptable` (or
+//! against `oldest_acceptable_root`) first: roots recorded before the
+//! retention window have expired. `dry_run` runs an unsigned lane call on the
+//! block's state and returns the pallet error it would fail with, without
+//! submitting it.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+pub use pallet_proofs::{AppConfig, RwaListing};
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	pub trait PrivacyLaneApi<BlockNumber, LaneCall> where BlockNumber: Codec, LaneCall: Codec {
+		/// Current `(tree_epoch, root)` of the v2 note tree.
+		fn current_root() -> (u32, [u8; 32]);
+		/// `(leaf_count, frontier)` of the current v2 tree epoch.
+		fn frontier() -> (u32, [[u8; 32]; 20]);
+		/// v2 roots recorded within the retention window, oldest first, as
+		/// `(block, tree_epoch, root)`.
+		fn recent_roots() -> Vec<(BlockNumber, u32, [u8; 32])>;
+		/// Oldest `(tree_epoch, root)` a v2 spend may still anchor to, apart
+		/// from the final roots of closed epochs, which never expire.
+		fn oldest_acceptable_root() -> (u32, [u8; 32]);
+		/// Whether a v2 spend may anchor to `root` in `tree_epoch` now.
+		fn root_acceptable(tree_epoch: u32, root: [u8; 32]) -> bool;
+		/// `(tree_epoch, index)` of a v2 leaf.
+		fn leaf_index(leaf: [u8; 32]) -> Option<(u32, u32)>;
+		/// Whether a spend nullifier has been used.
+		fn nullifier_used(nullifier: [u8; 32]) -> bool;
+		/// Current ownership spent-set root and the siblings of slot `key`,
+		/// leaf level first: the unspent witness of an ownership
+		/// presentation. The queried node learns the slot.
+		fn ownership_spent_path(key: u64) -> ([u8; 32], [[u8; 32]; 64]);
+		/// Prices, supply and eligibility of an RWA on this lane.
+		fn rwa_listing(rwa_id: [u8; 32]) -> RwaListing;
+		/// Access-key config of an app.
+		fn access_config(app_id: [u8; 32]) -> Option<AppConfig>;
+		/// Dispatch `call` unsigned and roll it back; returns the pallet error.
+		fn dry_run(call: LaneCall) -> Result<(), DispatchError>;
+	}
+}
@@ -10,7 +10,8 @@ use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
 use sp_io::hashing::blake2_256;
 use sp_runtime::transaction_validity::{
-	InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+	InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+	ValidTransaction, ValidTransactionBuilder,
 };
 
 /// Public inputs for an RWA purchase.
@@ -69,9 +70,10 @@ pub struct WithdrawInputs {
 
 /// Public inputs for a private peer-to-peer RWA ownership transfer (resell).
 ///
-/// The current owner proves ownership via BLAKE3 preimage of ownership_commitment;
-/// no ML-DSA-44 signature needed — the blinding IS the proof of ownership.
-/// The new buyer's spend_tag must already be in SpendTagValues.
+/// LEGACY (v1): `relist_private` is disabled — naming `old_tx_id` publicly
+/// linked every resale to the original purchase. Use `relist_v2`, which spends
+/// an ownership note from the ownership tree in zero knowledge.
+/// Kept so the `relist_private` call signature stays decodable.
 #[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub struct RelistInputs {
 	/// tx_id of the original purchase — used to look up ownership_commitment.
@@ -96,7 +98,7 @@ pub struct RelistInputs {
 	/// New ownership commitment for the new buyer:
 	/// BLAKE3("nulla_rwa_ownership_v1" || rwa_id || new_blinding)
 	pub new_ownership_commitment: [u8; 32],
-	/// Unique tx ID for this transfer (used as key in OwnershipCommitments).
+	/// Unique tx ID for this transfer.
 	pub new_tx_id: [u8; 16],
 	/// Phase 7: commitment opening witnesses for the new buyer's note.
 	/// buyer_value/buyer_blinding prove SpendTagCommitments[new_buyer_spend_tag] opens correctly.
@@ -110,6 +112,18 @@ pub struct RelistInputs {
 	pub change_blinding: Option<[u8; 32]>,
 }
 
+/// A SpendAir statement, as checked by `ProofVerify::verify_spend_v2`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpendStatementV2 {
+	pub root: [u8; 32],
+	pub nullifier: [u8; 32],
+	pub pkd: [u8; 32],
+	pub price_or_amount: u64,
+	pub change_leaf: [u8; 32],
+	pub change_pkd: [u8; 32],
+	pub purchase_mode: bool,
+}
+
 pub trait ProofVerify {
 	fn verify_commitment(value: u64, blinding: [u8; 32], commitment: [u8; 32]) -> bool;
 	fn verify_purchase(proof: &[u8], public_inputs: &[u8]) -> bool;
@@ -126,7 +140,8 @@ pub trait ProofVerify {
 	// --- Phase 9 (v2 zk-membership, ZK_MEMBERSHIP_SPEC_V2.md) ---
 
 	/// Verify DepositV2Air: leaf = NoteHash(amount, b, pkd) for private (b, pkd).
-	fn verify_deposit_v2(proof: &[u8], amount: u64, leaf: &[u8; 32]) -> bool;
+	/// Every STARK check takes the lane's `StarkSecurity` policy.
+	fn verify_deposit_v2(proof: &[u8], amount: u64, leaf: &[u8; 32], security: &StarkSecurity) -> bool;
 	/// Verify SpendAir: Merkle membership + nullifier derivation + value conservation.
 	/// purchase_mode true: v - cv == price_or_amount; false: v == price_or_amount, cv == 0.
 	fn verify_spend_v2(
@@ -138,6 +153,7 @@ pub trait ProofVerify {
 		change_leaf: &[u8; 32],
 		change_pkd: &[u8; 32],
 		purchase_mode: bool,
+		security: &StarkSecurity,
 	) -> bool;
 	/// Verify ML-DSA-44 spend authorization over BLAKE3(domain ‖ public_inputs).
 	fn verify_spend_auth_v2(auth: &[u8], public_inputs: &[u8], withdraw: bool) -> bool;
@@ -150,6 +166,87 @@ pub trait ProofVerify {
 	) -> Option<([[u8; 32]; 20], u32, [u8; 32])>;
 	/// Canonical zero-change leaf: NoteHash(0, [0;32], [0;32]).
 	fn v2_zero_change_leaf() -> [u8; 32];
+	/// Poseidon note leaf NoteHash(value, blinding, pkd), for leaves minted by the lane.
+	fn v2_note_leaf(value: u64, blinding: &[u8; 32], pkd: &[u8; 32]) -> [u8; 32];
+	/// Zero subtrees of the ownership spent set, Z[0..=64]; Z[64] is the empty root.
+	fn v2_spent_zero_subtrees() -> Vec<[u8; 32]>;
+	/// Nodes on the spent-set path of slot `key` holding `leaf`, leaf first
+	/// and root last (65 entries).
+	fn v2_spent_path(key: u64, leaf: &[u8; 32], siblings: &[[u8; 32]; 64]) -> Vec<[u8; 32]>;
+	/// Verify a credential presentation: a leaf NoteHash(class, b, 0) under
+	/// `root`, with `tag` the credential nullifier scoped to `rwa_id`, tied to
+	/// the spend nullifier `binding`.
+	fn verify_credential(
+		proof: &[u8],
+		root: &[u8; 32],
+		rwa_id: &[u8; 32],
+		class: u64,
+		tag: &[u8; 32],
+		binding: &[u8; 32],
+		security: &StarkSecurity,
+	) -> bool;
+	/// Paymaster: verify a fee credit spend — a leaf NoteHash(value, b, 0)
+	/// under `root`, with `nullifier` the fee-scoped nullifier of `b`, for
+	/// the spend whose public inputs hash to `context`.
+	fn verify_fee_credit(
+		proof: &[u8],
+		root: &[u8; 32],
+		value: u64,
+		nullifier: &[u8; 32],
+		context: &[u8; 32],
+		security: &StarkSecurity,
+	) -> bool;
+}
+
+/// Which security bound `StarkSecurity::min_bits` applies to.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum SecurityModel {
+	Conjectured,
+	Proven,
+}
+
+/// Acceptance policy for v2 STARK proofs, set by `set_stark_security` and
+/// passed to every `ProofVerify` STARK check.
+///
+/// `blowup_factors` and `fri_folding_factors` are bit sets: bit k allows a
+/// factor of 2^k, and 0 allows any factor.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct StarkSecurity {
+	pub model: SecurityModel,
+	pub min_bits: u32,
+	pub blowup_factors: u8,
+	pub fri_folding_factors: u8,
+}
+
+impl Default for StarkSecurity {
+	/// 80 conjectured bits with any parameters, the lane's original floor.
+	fn default() -> Self {
+		Self { model: SecurityModel::Conjectured, min_bits: 80, blowup_factors: 0, fri_folding_factors: 0 }
+	}
+}
+
+/// Buyer eligibility for an RWA, configured on the RWA chain registry and
+/// synced here by `xcm_sync_rwa_eligibility`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct EligibilityRequirement {
+	/// Credential registry whose tree the buyer must prove membership in.
+	pub registry_id: u32,
+	/// Eligibility flags the credential's class must contain.
+	pub required_class: u64,
+}
+
+/// A buyer's credential presentation, carried in the spend public inputs so
+/// the ML-DSA auth binds it. The STARK travels in `credential_proof`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct CredentialPublicV2 {
+	pub registry_id: u32,
+	/// Must equal the registry's current or a recent credential root.
+	pub root: [u8; 32],
+	/// Eligibility flags of the presented credential.
+	pub class: u64,
+	/// Credential nullifier scoped to the asset: one purchase per credential
+	/// and asset.
+	pub tag: [u8; 32],
 }
 
 /// Public inputs for a v2 zk-membership RWA purchase (Phase 9).
@@ -160,23 +257,114 @@ pub trait ProofVerify {
 /// into the STARK as a public input, binding the signing key to the spent note.
 #[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub struct SpendPublicV2 {
-	/// Must equal CurrentRootV2 or one of RecentRootsV2.
+	/// v2 tree epoch the spent note was inserted under.
+	pub tree_epoch: u32,
+	/// Must be anchored in `tree_epoch`: its current or final root, or a
+	/// root recorded in RootHistoryV2 within RootRetention blocks.
 	pub merkle_root: [u8; 32],
 	/// Poseidon nullifier derived in-circuit from the note blinding.
 	pub nullifier: [u8; 32],
 	pub rwa_id: [u8; 32],
+	/// RWA lane: units bought; the spend is proven against
+	/// `RwaPrices[rwa_id] × quantity`. Access lane: must be 1.
+	pub quantity: u64,
+	/// Oracle round converting a USD-priced RWA (`RwaUsdPrices`) to planck;
+	/// binding it here keeps the proof valid if a newer round opens before
+	/// inclusion. Ignored for planck-priced RWAs and on the access lane.
+	pub rate_round: Option<u32>,
+	/// RWA lane: eligibility credential, required when `RwaEligibility[rwa_id]`
+	/// is set. Ignored on the access lane.
+	pub credential: Option<CredentialPublicV2>,
+	/// Access lane: index of the plan bought in the app's `AppConfig::plans`,
+	/// or `None` for a plan-less key at `AppConfig::price`. Ignored on the RWA lane.
+	pub access_plan: Option<u8>,
 	pub tx_id: [u8; 16],
-	/// BLAKE3("nulla_rwa_ownership_v1" ‖ rwa_id ‖ ownership_blinding).
+	/// RWA lane: the buyer's ownership note leaf,
+	/// NoteHash(quantity, ownership_blinding, owner_pkd), inserted into the
+	/// ownership tree of `rwa_id` and proven well-formed by `ownership_proof`.
+	/// Access lane: BLAKE3("nulla_access_key_v1" ‖ app_id ‖ blinding).
 	pub ownership_commitment: [u8; 32],
 	/// Change note leaf — always present (zero-value change when v == price).
 	pub change_leaf: [u8; 32],
 	/// pk digest of the change note's fresh ML-DSA keypair.
 	pub change_pkd: [u8; 32],
+	/// RWA lane: NoteHash(price, rb, refund_pkd) for private (rb, refund_pkd),
+	/// proven by `refund_proof` and inserted into the v2 tree only if the
+	/// escrow is cancelled or times out. Ignored on the access lane.
+	///
+	/// Not bound to `change_pkd`: `change_pkd` is public here, and a refund
+	/// note under it would be linked to this purchase when spent. The spend
+	/// auth signs this field, so only the buyer chooses the refund note.
+	pub refund_leaf: [u8; 32],
+}
+
+/// Public inputs for a v2 private resale of an RWA ownership note.
+///
+/// Two parties co-sign the same SCALE encoding: the seller (`seller_auth`)
+/// spends an ownership note from the asset's ownership tree, the buyer
+/// (`buyer_auth`) spends a value note from the v2 tree. No purchase tx_id or
+/// earlier owner is named — the seller reveals only a recent ownership root
+/// and the ownership nullifier, so successive owners of an asset are
+/// unlinkable. A partial sale returns the seller's remaining units in
+/// `ownership_change_leaf`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RelistPublicV2 {
+	/// Canonical RWA id (asset_id LE u32, rest zero) — selects the ownership tree.
+	pub rwa_id: [u8; 32],
+	/// Must equal the current or a recent ownership root of `rwa_id`.
+	pub ownership_root: [u8; 32],
+	/// Poseidon nullifier of the seller's ownership note.
+	pub ownership_nullifier: [u8; 32],
+	/// Units sold — at most the units held by the seller's note.
+	pub quantity: u64,
+	/// Seller's remaining units: NoteHash(units − quantity, cb, ownership_change_pkd).
+	/// Always inserted; zero units when the whole holding is sold.
+	pub ownership_change_leaf: [u8; 32],
+	pub ownership_change_pkd: [u8; 32],
+	/// Buyer's new ownership note leaf: NoteHash(quantity, b', pkd').
+	pub new_ownership_leaf: [u8; 32],
+	/// Buyer's v2 value-note spend: tree epoch, root, nullifier, resale
+	/// price for `quantity` units, change.
+	pub tree_epoch: u32,
+	pub merkle_root: [u8; 32],
+	pub nullifier: [u8; 32],
+	pub price: u64,
+	pub change_leaf: [u8; 32],
+	pub change_pkd: [u8; 32],
+	/// Seller's payment note leaf: NoteHash(price, pb, seller_pkd').
+	pub payment_leaf: [u8; 32],
+	/// Buyer's eligibility credential, required when `RwaEligibility[rwa_id]` is set.
+	pub credential: Option<CredentialPublicV2>,
+	pub tx_id: [u8; 16],
+}
+
+/// Public inputs for redeeming a v2 ownership note for the physical asset.
+///
+/// The ownership note is spent in zero knowledge; the RWA chain learns only
+/// the asset, the units redeemed, the ownership nullifier and the claimant
+/// account. Remaining units go back into the ownership tree as a change note.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RedeemPublicV2 {
+	/// Canonical RWA id (asset_id LE u32, rest zero).
+	pub rwa_id: [u8; 32],
+	pub ownership_root: [u8; 32],
+	pub ownership_nullifier: [u8; 32],
+	/// Units redeemed — at most the units held by the note.
+	pub quantity: u64,
+	/// Holder's remaining units (zero units on a full redemption).
+	pub ownership_change_leaf: [u8; 32],
+	pub ownership_change_pkd: [u8; 32],
+	/// AccountId (32 bytes) on the RWA chain that receives the asset.
+	/// Redemption is the public exit — the claimant is revealed by design.
+	pub claimant: [u8; 32],
+	pub tx_id: [u8; 16],
 }
 
 /// Public inputs for a v2 zk-membership withdrawal (Phase 9).
 #[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
 pub struct WithdrawPublicV2 {
+	/// Tree epoch and root the spent note is anchored in, as for SpendPublicV2.
+	pub tree_epoch: u32,
 	pub merkle_root: [u8; 32],
 	pub nullifier: [u8; 32],
 	/// Revealed amount — the STARK enforces v == amount exactly.
@@ -186,6 +374,68 @@ pub struct WithdrawPublicV2 {
 	pub tx_id: [u8; 16],
 }
 
+/// Public inputs for a sealed bid on an RWA auction (`place_bid_v2`).
+///
+/// The bidder spends a v2 note in purchase mode with `price_or_amount = 0`,
+/// so its whole (hidden) value moves into `lock_leaf`. The lock leaf goes into
+/// the auction's own lock tree rather than the v2 tree, where it can only be
+/// spent by `reveal_bid_v2` or, for a bid never revealed, `release_bid_v2`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AuctionBidPublicV2 {
+	pub auction_id: u32,
+	/// Tree epoch and root the spent note is anchored in, as for SpendPublicV2.
+	pub tree_epoch: u32,
+	pub merkle_root: [u8; 32],
+	pub nullifier: [u8; 32],
+	/// Locked note: NoteHash(v, lb, lock_pkd), v = the spent note's value.
+	pub lock_leaf: [u8; 32],
+	/// pk digest of the lock note's fresh ML-DSA keypair; the reveal or
+	/// release must be signed by the matching key.
+	pub lock_pkd: [u8; 32],
+	/// Sealed bid: `Pallet::auction_bid_commitment(auction_id, bid, salt)`.
+	pub bid_commitment: [u8; 32],
+	pub tx_id: [u8; 16],
+}
+
+/// Public inputs for opening a sealed bid during the reveal window (`reveal_bid_v2`).
+///
+/// The bid amount and salt open `bid_commitment`; the lock note is spent in
+/// purchase mode (lock value − change == bid), which proves in zero knowledge
+/// that the lock covers the bid without revealing the lock value. The bid
+/// itself is held in escrow until `settle_auction`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AuctionRevealPublicV2 {
+	pub auction_id: u32,
+	pub bid_index: u32,
+	pub bid: u64,
+	pub salt: [u8; 32],
+	/// Poseidon nullifier of the lock note in the auction's lock tree.
+	pub lock_nullifier: [u8; 32],
+	/// Lock value above the bid: NoteHash(v − bid, cb, change_pkd), inserted
+	/// into the v2 tree immediately.
+	pub change_leaf: [u8; 32],
+	pub change_pkd: [u8; 32],
+	/// NoteHash(bid, rb, refund_pkd) — inserted into the v2 tree if the bid loses.
+	pub refund_leaf: [u8; 32],
+	/// NoteHash(quantity, ob, owner_pkd) — inserted into the ownership tree if the bid wins.
+	pub ownership_leaf: [u8; 32],
+	pub tx_id: [u8; 16],
+}
+
+/// Public inputs for releasing a bid that was never revealed (`release_bid_v2`).
+///
+/// After the reveal window the lock note is spent with `price_or_amount = 0`
+/// and its whole value returns to the v2 tree as `change_leaf`.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AuctionReleasePublicV2 {
+	pub auction_id: u32,
+	pub bid_index: u32,
+	pub lock_nullifier: [u8; 32],
+	pub change_leaf: [u8; 32],
+	pub change_pkd: [u8; 32],
+	pub tx_id: [u8; 16],
+}
+
 /// Trait implemented by the runtime to send an XCM `Transact` to the RWA
 /// parachain whenever a `purchaseRwa` is accepted on the ProofHub chain.
 ///
@@ -204,21 +454,35 @@ pub trait RwaPurchaseDispatch {
 		note_value: u64,
 		tx_id: [u8; 16],
 		ownership_commitment: [u8; 32],
+		units: u64,
+	);
+	/// Send XCM to record a redemption on the RWA chain.
+	/// Called by `redeem_ownership_v2` after the ownership note is spent.
+	/// Resales stay inside the ownership tree and are never sent cross-chain.
+	fn send_redemption(
+		rwa_id: [u8; 32],
+		ownership_nullifier: [u8; 32],
+		claimant: [u8; 32],
+		tx_id: [u8; 16],
+		units: u64,
 	);
-	/// Send XCM to transfer an existing ownership record on the RWA chain.
-	/// Called by `relist_private` after verifying the reseller's ownership proof.
-	fn send_transfer(
-		old_tx_id: [u8; 16],
-		new_ownership_commitment: [u8; 32],
-		new_tx_id: [u8; 16],
+	/// Send XCM to record a settled sealed-bid auction on the RWA chain.
+	/// Called by `settle_auction`; the winning price is public, the winner is not.
+	fn send_auction_result(
+		rwa_id: [u8; 32],
+		auction_id: u32,
+		price: u64,
+		units: u64,
+		ownership_commitment: [u8; 32],
 	);
 }
 
 /// No-op implementation used when XCM is not wired (e.g. tests).
 pub struct NoopRwaDispatch;
 impl RwaPurchaseDispatch for NoopRwaDispatch {
-	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 32], _: u64, _: [u8; 16], _: [u8; 32]) {}
-	fn send_transfer(_: [u8; 16], _: [u8; 32], _: [u8; 16]) {}
+	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 32], _: u64, _: [u8; 16], _: [u8; 32], _: u64) {}
+	fn send_redemption(_: [u8; 32], _: [u8; 32], _: [u8; 32], _: [u8; 16], _: u64) {}
+	fn send_auction_result(_: [u8; 32], _: u32, _: u64, _: u64, _: [u8; 32]) {}
 }
 
 /// Trait implemented by the runtime to send an XCM `Transact` to the AuthGate
@@ -230,33 +494,212 @@ pub trait AccessKeyDispatch {
 		nullifier: [u8; 32],
 		tx_id: [u8; 16],
 		access_key_commitment: [u8; 32],
+		terms: Option<AccessTerms>,
 	);
 }
 
 /// No-op implementation used when XCM is not wired (e.g. tests).
 pub struct NoopAccessDispatch;
 impl AccessKeyDispatch for NoopAccessDispatch {
-	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 16], _: [u8; 32]) {}
+	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 16], _: [u8; 32], _: Option<AccessTerms>) {}
+}
+
+/// NULLA/USD conversion for RWAs priced in USD cents, implemented by the
+/// runtime over `pallet_price_oracle`.
+pub trait FiatRate {
+	/// Planck per USD cent in oracle round `round`, or `None` when the round
+	/// is unknown or no longer usable.
+	fn planck_per_cent(round: u32) -> Option<u64>;
+}
+
+/// No oracle wired: USD-priced RWAs cannot be bought.
+pub struct NoFiatRate;
+impl FiatRate for NoFiatRate {
+	fn planck_per_cent(_: u32) -> Option<u64> { None }
+}
+
+/// Maximum number of subscription plans per access-key app.
+pub const MAX_ACCESS_PLANS: u32 = 8;
+
+/// A subscription plan of a Web2 app. Same SCALE layout on both lanes.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, codec::MaxEncodedLen)]
+pub struct AccessPlan {
+	/// Price in planck the spend must cover. Zero withdraws the plan from sale.
+	pub price: u64,
+	/// Key lifetime in AuthGate blocks, counted from activation.
+	pub duration: u32,
+	/// App-defined service tier.
+	pub tier: u8,
+	/// Uses the key allows; `0` = unlimited.
+	pub max_uses: u32,
+}
+
+impl AccessPlan {
+	pub fn terms(&self) -> AccessTerms {
+		AccessTerms { duration: self.duration, tier: self.tier, max_uses: self.max_uses }
+	}
+}
+
+/// Plan terms relayed to AuthGate with a grant; `pallet_access_keys::AccessTerms`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AccessTerms {
+	pub duration: u32,
+	pub tier: u8,
+	pub max_uses: u32,
 }
 
 /// Configuration for a registered Web2 app on DistProofHub.
-/// Set by sudo via `set_access_config`.
+/// Set by sudo via `set_access_config` and `set_access_plans`.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, codec::MaxEncodedLen)]
 pub struct AppConfig {
-	/// Price in planck that the note must cover.
+	/// Price in planck of a plan-less key. Zero stops plan-less sales.
 	pub price: u64,
 	/// AccountId (raw 32 bytes) on DistProofHub that receives the payment.
 	pub payment_account: [u8; 32],
+	/// Subscription plans, bought by index. Set via `set_access_plans`.
+	pub plans: BoundedVec<AccessPlan, ConstU32<MAX_ACCESS_PLANS>>,
+}
+
+impl AppConfig {
+	/// Price and AuthGate terms of a purchase: `None` buys a plan-less key at
+	/// `price`, `Some(i)` buys `plans[i]`. `None` when not for sale.
+	pub fn quote(&self, plan: Option<u8>) -> Option<(u64, Option<AccessTerms>)> {
+		match plan {
+			None => (self.price > 0).then_some((self.price, None)),
+			Some(i) => self
+				.plans
+				.get(i as usize)
+				.filter(|p| p.price > 0)
+				.map(|p| (p.price, Some(p.terms()))),
+		}
+	}
+}
+
+/// An RWA's terms on this lane, as `PrivacyLaneApi::rwa_listing` returns
+/// them. Same SCALE layout on both lanes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RwaListing {
+	/// Price per unit in planck (`RwaPrices`).
+	pub price: u64,
+	/// Price per unit in USD cents (`RwaUsdPrices`); non-zero overrides `price`.
+	pub usd_cents: u64,
+	/// Units still for sale here, when the lane tracks supply.
+	pub units: Option<u64>,
+	/// Credential buyers must present, when restricted.
+	pub eligibility: Option<EligibilityRequirement>,
+}
+
+/// Paymaster fee credit presented by an unsigned v2 spend.
+///
+/// `proof` is a STARK that some leaf NoteHash(BaseFee, b, 0) of the fee
+/// credit tree lies under `fee_root` and that `fee_nullifier` is the
+/// fee-scoped nullifier of `b`, for the spend whose public inputs hash
+/// (BLAKE2-256) to the proof's context. The spent leaf is never named, so
+/// the spend cannot be traced to the `deposit_fee` that funded it, and a
+/// ticket copied into another spend fails against that spend's context.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct FeeTicket {
+	pub fee_root: [u8; 32],
+	pub fee_nullifier: [u8; 32],
+	pub proof: Vec<u8>,
+}
+
+/// Lifecycle of an escrowed v2 RWA purchase.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub enum EscrowStatus {
+	/// Awaiting confirmation from the RWA chain.
+	Pending,
+	/// Confirmed by the RWA chain; the ownership note was inserted.
+	Fulfilled,
+	/// Timed out or cancelled; a refund leaf was inserted into the v2 tree.
+	Refunded,
+}
+
+/// A `purchase_rwa_v2` held in escrow, keyed by tx_id in `RwaEscrows`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RwaEscrow<BlockNumber> {
+	pub rwa_id: [u8; 32],
+	pub quantity: u64,
+	/// Total price paid out of the spent note.
+	pub price: u64,
+	/// Nullifier of the spent note.
+	pub nullifier: [u8; 32],
+	/// Buyer's ownership note leaf, inserted once the purchase is fulfilled.
+	pub ownership_commitment: [u8; 32],
+	/// Buyer's refund note leaf holding `price`, inserted on cancellation or
+	/// timeout.
+	pub refund_leaf: [u8; 32],
+	/// First block at which `refund_escrow` is accepted.
+	pub deadline: BlockNumber,
+	pub status: EscrowStatus,
+}
+
+/// Maximum sealed bids per auction — bounds the loop in `settle_auction`.
+pub const MAX_AUCTION_BIDS: u32 = 256;
+
+/// Lifecycle of a sealed-bid RWA auction.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub enum AuctionStatus {
+	/// Bidding or reveal window open, or closed and awaiting `settle_auction`.
+	Open,
+	/// Settled with a winner; the result was sent to the RWA chain.
+	Settled,
+	/// Closed without a revealed bid at or above the reserve price.
+	NoSale,
+}
+
+/// A sealed-bid auction of `quantity` units of `rwa_id`.
+/// Created by sudo via `create_auction`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct Auction<BlockNumber> {
+	pub rwa_id: [u8; 32],
+	pub quantity: u64,
+	/// Lowest bid that can win.
+	pub reserve_price: u64,
+	/// Last block at which `place_bid_v2` is accepted.
+	pub bid_end: BlockNumber,
+	/// Last block at which `reveal_bid_v2` is accepted.
+	pub reveal_end: BlockNumber,
+	pub bid_count: u32,
+	/// (bid_index, amount) of the highest revealed bid; the earlier reveal wins a tie.
+	pub best_bid: Option<(u32, u64)>,
+	pub status: AuctionStatus,
+}
+
+/// State of a sealed bid.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum BidState {
+	/// Placed; the lock note is still in the auction's lock tree.
+	Sealed,
+	/// Opened by `reveal_bid_v2`; `amount` is held until settlement.
+	Revealed { amount: u64, refund_leaf: [u8; 32], ownership_leaf: [u8; 32] },
+	/// Never revealed; the lock note was returned by `release_bid_v2`.
+	Released,
+}
+
+/// A bid placed by `place_bid_v2`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AuctionBid {
+	pub bid_commitment: [u8; 32],
+	/// pk digest of the lock note — the reveal or release auth must match it.
+	pub lock_pkd: [u8; 32],
+	pub state: BidState,
 }
 
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::ConstU32;
-	use frame_support::traits::{Currency, ExistenceRequirement};
-	use sp_runtime::traits::UniqueSaturatedInto;
+	use frame_support::traits::{
+		Currency, ExistenceRequirement, FindAuthor, UnfilteredDispatchable, WithdrawReasons,
+	};
+	use sp_runtime::traits::{One, Saturating, UniqueSaturatedInto, Zero};
+	use sp_runtime::Perbill;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -273,6 +716,33 @@ pub mod pallet {
 	/// after `purchase_access` or `purchase_access_v2`.
 	/// Use `NoopAccessDispatch` when XCM is not needed.
 	type AccessDispatch: super::AccessKeyDispatch;
+	/// Paymaster: base fee charged per unsigned v2 spend, prepaid via `deposit_fee`.
+	/// Zero disables the fee lane (spends need no `FeeTicket`).
+	#[pallet::constant]
+	type BaseFee: Get<BalanceOf<Self>>;
+	/// Paymaster: share of each consumed base fee that is burned; the rest
+	/// is paid to the block author.
+	#[pallet::constant]
+	type FeeBurn: Get<Perbill>;
+	/// Paymaster account holding prepaid fee credits.
+	type FeePayer: Get<<Self as frame_system::Config>::AccountId>;
+	type FindAuthor: FindAuthor<Self::AccountId>;
+	/// AccountId of the RWA chain sovereign account on this chain.
+	/// Only this account may call `xcm_confirm_escrow`, `xcm_cancel_escrow`
+	/// and `xcm_sync_rwa_price`.
+	type RwaChainSovereign: Get<<Self as frame_system::Config>::AccountId>;
+	/// Blocks a v2 purchase stays in escrow before the buyer can be refunded.
+	#[pallet::constant]
+	type EscrowPeriod: Get<BlockNumberFor<Self>>;
+	/// NULLA/USD rate for USD-priced RWAs. Use `NoFiatRate` when no oracle is wired.
+	type FiatRate: super::FiatRate;
+	/// Blocks a v2 root stays acceptable after it stops being current, until
+	/// governance sets `RootRetention`.
+	#[pallet::constant]
+	type DefaultRootRetention: Get<BlockNumberFor<Self>>;
+	/// Upper bound for `set_root_retention`.
+	#[pallet::constant]
+	type MaxRootRetention: Get<BlockNumberFor<Self>>;
 }
 
 pub type BalanceOf<T> =
@@ -296,18 +766,6 @@ pub type CurrentRoot<T: Config> = StorageValue<_, [u8; 32], ValueQuery>;
 pub type RecentRoots<T: Config> =
 	StorageValue<_, BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
 
-	/// Maps purchase tx_id → ownership_commitment (stored at purchase_rwa time).
-	/// Used by relist_private to verify the reseller holds the ownership note.
-	#[pallet::storage]
-	pub type OwnershipCommitments<T: Config> =
-		StorageMap<_, Blake2_128Concat, [u8; 16], [u8; 32], OptionQuery>;
-
-	/// Guards against double-relist: once a tx_id is relisted, the original
-	/// ownership_commitment is consumed and cannot be used again.
-	#[pallet::storage]
-	pub type OwnershipUsed<T: Config> =
-		StorageMap<_, Blake2_128Concat, [u8; 16], bool, ValueQuery>;
-
 	/// Phase 7: Maps spend_tag → note commitment (registered at deposit time).
 	/// No plaintext amounts stored on-chain — amounts are provided as witness at spend time.
 	/// spend_tag = BLAKE3("nulla_spend_tag_v1" || deposit_pk_bytes)
@@ -334,14 +792,32 @@ pub type RecentRoots<T: Config> =
 	pub type SpendTagUsed<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
 
-	/// RWA price registry: rwa_id → price in planck.
+	/// RWA price registry: rwa_id → price per unit in planck.
 	/// Set by sudo. Zero means the RWA is not available for purchase.
+	/// Indivisible assets have a single unit, so this is the whole-asset price.
 	#[pallet::storage]
 	#[pallet::getter(fn rwa_prices)]
 	pub type RwaPrices<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], u64, ValueQuery>;
 
-	/// Access-key config: app_id → AppConfig { price, payment_account }.
+	/// USD-priced RWAs: rwa_id → price per unit in USD cents.
+	/// Set by sudo via `set_rwa_usd_price`, cleared when the RWA chain de-lists
+	/// the RWA (`xcm_sync_rwa_price` with price 0). Non-zero overrides `RwaPrices`:
+	/// the price is converted to planck at spend time via `FiatRate`.
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_usd_prices)]
+	pub type RwaUsdPrices<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u64, ValueQuery>;
+
+	/// Units of an RWA still for sale on this lane: rwa_id → units.
+	/// Set by sudo via `set_rwa_units`; v2 purchases draw it down.
+	/// Absent means supply is not tracked here (the RWA chain remains authoritative).
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_units)]
+	pub type RwaUnits<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u64, OptionQuery>;
+
+	/// Access-key config: app_id → AppConfig { price, payment_account, plans }.
 	/// Set by sudo via `set_access_config`. Zero price = app not available.
 	#[pallet::storage]
 	#[pallet::getter(fn access_key_configs)]
@@ -375,7 +851,7 @@ pub type RecentRoots<T: Config> =
 	pub type FrontierNodesV2<T: Config> =
 		StorageValue<_, [[u8; 32]; 20], ValueQuery>;
 
-	/// Number of leaves inserted into the v2 tree.
+	/// Number of leaves inserted into the current v2 tree epoch.
 	#[pallet::storage]
 	#[pallet::getter(fn leaf_count_v2)]
 	pub type LeafCountV2<T: Config> = StorageValue<_, u32, ValueQuery>;
@@ -385,17 +861,258 @@ pub type RecentRoots<T: Config> =
 	#[pallet::getter(fn current_root_v2)]
 	pub type CurrentRootV2<T: Config> = StorageValue<_, [u8; 32], ValueQuery>;
 
-	/// Recent v2 roots window — spend proofs may anchor to any of these.
+	/// Current v2 tree epoch. When a tree fills, its frontier is frozen and
+	/// a fresh tree starts under the next epoch.
+	#[pallet::storage]
+	#[pallet::getter(fn tree_epoch_v2)]
+	pub type TreeEpochV2<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Final root of each closed v2 tree epoch. Notes of a closed epoch stay
+	/// spendable against it indefinitely.
+	#[pallet::storage]
+	#[pallet::getter(fn final_root_v2)]
+	pub type FinalRootsV2<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, [u8; 32], OptionQuery>;
+
+	/// v2 root history: block → (tree epoch, root current at the end of
+	/// that block). Only blocks after which the root changed have an entry,
+	/// and entries older than `RootRetention` are pruned.
 	#[pallet::storage]
-	#[pallet::getter(fn recent_roots_v2)]
-	pub type RecentRootsV2<T: Config> =
-		StorageValue<_, BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
+	pub type RootHistoryV2<T: Config> =
+		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, (u32, [u8; 32]), OptionQuery>;
 
-	/// v2 leaf → index (duplicate prevention + wallet sync convenience).
+	/// (tree epoch, root) → its `RootHistoryV2` block. Spend proofs may anchor
+	/// to a root for `RootRetention` blocks after that block.
+	#[pallet::storage]
+	#[pallet::getter(fn root_recorded_at_v2)]
+	pub type RootRecordedAtV2<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Blake2_128Concat,
+		[u8; 32],
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Blocks a v2 root stays acceptable after it stops being current.
+	#[pallet::storage]
+	#[pallet::getter(fn root_retention)]
+	pub type RootRetention<T: Config> =
+		StorageValue<_, BlockNumberFor<T>, ValueQuery, T::DefaultRootRetention>;
+
+	/// v2 leaf → index within its tree epoch (duplicate prevention + wallet
+	/// sync convenience).
 	#[pallet::storage]
 	pub type LeafIndexV2<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], u32, OptionQuery>;
 
+	/// v2 leaf → tree epoch. Leaves of epoch 0 have no entry.
+	#[pallet::storage]
+	pub type LeafEpochV2<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u32, ValueQuery>;
+
+	// --- Ownership notes: one incremental Poseidon tree (depth 20) per rwa_id ---
+	//
+	// The tree binds the asset, so an ownership note's value is its unit count.
+
+	/// Frontier nodes of each ownership tree (one per level), keyed by rwa_id.
+	#[pallet::storage]
+	pub type OwnershipFrontierNodes<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], [[u8; 32]; 20], ValueQuery>;
+
+	/// Number of ownership notes inserted, keyed by rwa_id.
+	#[pallet::storage]
+	#[pallet::getter(fn ownership_leaf_count)]
+	pub type OwnershipLeafCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u32, ValueQuery>;
+
+	/// Current root of each ownership tree, keyed by rwa_id.
+	#[pallet::storage]
+	#[pallet::getter(fn current_ownership_root)]
+	pub type CurrentOwnershipRoot<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], [u8; 32], ValueQuery>;
+
+	/// Recent ownership roots window per rwa_id — relist/redeem proofs may anchor to any of these.
+	#[pallet::storage]
+	#[pallet::getter(fn recent_ownership_roots)]
+	pub type RecentOwnershipRoots<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
+
+	/// (rwa_id, ownership leaf) → index (duplicate prevention + wallet sync convenience).
+	#[pallet::storage]
+	pub type OwnershipLeafIndex<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, [u8; 32], Blake2_128Concat, [u8; 32], u32, OptionQuery,
+	>;
+
+	/// Spent ownership notes, keyed by Poseidon nullifier.
+	/// Separate from `NullifierUsed`: the two trees have independent nullifier sets.
+	#[pallet::storage]
+	#[pallet::getter(fn ownership_nullifier_used)]
+	pub type OwnershipNullifierUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
+
+	/// Ownership spent set: the non-empty nodes of a depth-64 sparse Poseidon
+	/// tree, keyed by (level, index). Level 0 slot `spent_key(nf)` holds the
+	/// nullifier of a spent ownership note; a missing node is a zero subtree
+	/// and the root is node (64, 0). Ownership presentations prove their
+	/// note's slot empty under the current root.
+	#[pallet::storage]
+	pub type OwnershipSpentNodes<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, u8, Blake2_128Concat, u64, [u8; 32], OptionQuery,
+	>;
+
+	/// Escrowed v2 RWA purchases: tx_id → RwaEscrow.
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_escrows)]
+	pub type RwaEscrows<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 16], RwaEscrow<BlockNumberFor<T>>, OptionQuery>;
+
+	// --- Sealed-bid auctions: one lock tree (depth 20) per auction ---
+
+	/// Next auction id assigned by `create_auction`.
+	#[pallet::storage]
+	pub type NextAuctionId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// auction_id → Auction.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, Auction<BlockNumberFor<T>>, OptionQuery>;
+
+	/// (auction_id, bid_index) → AuctionBid.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_bids)]
+	pub type AuctionBids<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, u32, Blake2_128Concat, u32, AuctionBid, OptionQuery,
+	>;
+
+	/// Frontier nodes of each auction's lock tree, keyed by auction_id.
+	#[pallet::storage]
+	pub type AuctionLockFrontierNodes<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, [[u8; 32]; 20], ValueQuery>;
+
+	/// Number of lock notes inserted, keyed by auction_id.
+	#[pallet::storage]
+	pub type AuctionLockLeafCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+	/// Root of each auction's lock tree. Bidding closes before any reveal, so
+	/// reveals and releases anchor to this final root only.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_lock_root)]
+	pub type AuctionLockRoot<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, [u8; 32], ValueQuery>;
+
+	/// (auction_id, lock leaf) → index (duplicate prevention + wallet sync convenience).
+	#[pallet::storage]
+	pub type AuctionLockLeafIndex<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, u32, Blake2_128Concat, [u8; 32], u32, OptionQuery,
+	>;
+
+	/// Spent lock notes, keyed by Poseidon nullifier.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_lock_nullifier_used)]
+	pub type AuctionLockNullifierUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
+
+	// --- Buyer eligibility: one credential tree (depth 20) per issuer registry ---
+
+	/// Credential registries: registry_id → issuer account allowed to add leaves.
+	#[pallet::storage]
+	#[pallet::getter(fn credential_issuers)]
+	pub type CredentialIssuers<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, <T as frame_system::Config>::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	pub type NextCredentialRegistryId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Frontier nodes of each credential tree, keyed by registry_id.
+	#[pallet::storage]
+	pub type CredentialFrontierNodes<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, [[u8; 32]; 20], ValueQuery>;
+
+	/// Number of credentials issued, keyed by registry_id.
+	#[pallet::storage]
+	#[pallet::getter(fn credential_leaf_count)]
+	pub type CredentialLeafCount<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+	/// Current root of each credential tree, keyed by registry_id.
+	#[pallet::storage]
+	#[pallet::getter(fn current_credential_root)]
+	pub type CurrentCredentialRoot<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, [u8; 32], ValueQuery>;
+
+	/// Recent credential roots per registry — presentations may anchor to any of these.
+	#[pallet::storage]
+	#[pallet::getter(fn recent_credential_roots)]
+	pub type RecentCredentialRoots<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
+
+	/// (registry_id, credential leaf) → index (duplicate prevention + wallet sync).
+	#[pallet::storage]
+	pub type CredentialLeafIndex<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, u32, Blake2_128Concat, [u8; 32], u32, OptionQuery,
+	>;
+
+	/// Used credential tags (one per credential and asset).
+	#[pallet::storage]
+	#[pallet::getter(fn credential_tag_used)]
+	pub type CredentialTagUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
+
+	/// Buyer eligibility per RWA, synced from the RWA chain registry.
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_eligibility)]
+	pub type RwaEligibility<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], EligibilityRequirement, OptionQuery>;
+
+	// --- Paymaster fee lane ---
+
+	/// Frontier nodes of the fee credit tree.
+	#[pallet::storage]
+	pub type FeeFrontierNodes<T: Config> = StorageValue<_, [[u8; 32]; 20], ValueQuery>;
+
+	/// Number of fee credits deposited.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_leaf_count)]
+	pub type FeeLeafCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Current root of the fee credit tree.
+	#[pallet::storage]
+	#[pallet::getter(fn current_fee_root)]
+	pub type CurrentFeeRoot<T: Config> = StorageValue<_, [u8; 32], ValueQuery>;
+
+	/// Recent fee credit roots — fee tickets may anchor to any of these.
+	#[pallet::storage]
+	#[pallet::getter(fn recent_fee_roots)]
+	pub type RecentFeeRoots<T: Config> = StorageValue<_, BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
+
+	/// Fee credit leaf → index (duplicate prevention + wallet sync).
+	#[pallet::storage]
+	pub type FeeLeafIndex<T: Config> = StorageMap<_, Blake2_128Concat, [u8; 32], u32, OptionQuery>;
+
+	/// Consumed fee credits, keyed by their fee nullifier.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_nullifier_used)]
+	pub type FeeNullifierUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
+
+	// --- Proof versions ---
+
+	/// Proof versions governance has retired: (AIR id, version) → true.
+	/// The verifier's envelope registry lists what can be verified at all;
+	/// the runtime rejects proofs of a retired version before verifying.
+	#[pallet::storage]
+	#[pallet::getter(fn proof_version_retired)]
+	pub type RetiredProofVersions<T: Config> =
+		StorageMap<_, Blake2_128Concat, (u8, u16), bool, ValueQuery>;
+
+	/// Acceptance policy for v2 STARK proofs.
+	#[pallet::storage]
+	#[pallet::getter(fn stark_security)]
+	pub type StarkSecurityPolicy<T: Config> = StorageValue<_, StarkSecurity, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -417,12 +1134,59 @@ pub type RecentRoots<T: Config> =
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			// REMOVED: Faucet reinitialization logic
 			if !GenesisInitialized::<T>::get() {
 				GenesisInitialized::<T>::put(true);
 			}
-			Weight::zero()
+			// The root recorded at `n - retention - 1` expires in this block.
+			let retention = RootRetention::<T>::get();
+			if n > retention {
+				Self::prune_root_v2(n - retention - One::one());
+			}
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = StorageVersion::get::<Pallet<T>>();
+			if on_chain >= STORAGE_VERSION {
+				return Weight::zero();
+			}
+			let mut migrated = 0u64;
+			let prefix = <Pallet<T> as frame_support::traits::PalletInfoAccess>::name();
+			// v0 → v1: `AppConfig` gained `plans`.
+			if on_chain < 1 {
+				AccessKeyConfigs::<T>::translate::<(u64, [u8; 32]), _>(|_, (price, payment_account)| {
+					migrated += 1;
+					Some(super::AppConfig { price, payment_account, plans: BoundedVec::default() })
+				});
+			}
+			// v0 → v1: the v1 ownership records `OwnershipCommitments` /
+			// `OwnershipUsed` are dropped, not imported. A v1 commitment is a
+			// BLAKE3 opening of (rwa_id, blinding), not a NoteHash leaf, and
+			// the RWA chain's purchase record already redeems it by tx_id
+			// (`redeem_rwa_ownership`); a tree leaf would let it be redeemed
+			// twice. Used records were never in a tree, so nothing enters the
+			// spent set.
+			if on_chain < 1 {
+				use frame_support::storage::migration::clear_storage_prefix;
+				for item in [&b"OwnershipCommitments"[..], b"OwnershipUsed"] {
+					migrated += clear_storage_prefix(prefix.as_bytes(), item, &[], None, None).unique as u64;
+				}
+			}
+			// → v3: the released 64-root `RecentRootsV2` value gave way to the
+			// block-keyed `RootHistoryV2`. The window's roots move into the
+			// history so in-flight proofs stay valid.
+			if on_chain < 3 {
+				let window = frame_support::storage::migration::take_storage_value::<
+					BoundedVec<[u8; 32], ConstU32<64>>,
+				>(prefix.as_bytes(), b"RecentRootsV2", &[])
+				.unwrap_or_default();
+				migrated += window.len() as u64 + 1;
+				Self::import_roots_v2(window.into_iter().map(|root| (0, root)).collect());
+			}
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
 		}
 	}
 
@@ -459,21 +1223,7 @@ pub type RecentRoots<T: Config> =
 			// NOTE: amount intentionally omitted (Phase 7 privacy model).
 			tx_id: [u8; 16],
 		},
-		/// A private relist was initiated: new buyer's payment routed to reseller note.
-		RelistInitiated {
-			old_tx_id: [u8; 16],
-			new_tx_id: [u8; 16],
-			rwa_id: [u8; 32],
-			// NOTE: price intentionally omitted (Phase 7 privacy model).
-		},
-		/// A payment note was created for the reseller during relist_private.
-		PaymentNoteCreated {
-			payment_commitment: [u8; 32],
-			payment_spend_tag: [u8; 32],
-			// NOTE: payment_amount intentionally omitted (Phase 7 privacy model).
-			new_merkle_root: [u8; 32],
-		},
-		/// Sudo set a new price for an RWA.
+		/// Sudo set a new per-unit price for an RWA.
 		RwaPriceSet { rwa_id: [u8; 32], price: u64 },
 		/// A private note was used to authorise an access-key grant (v1 ML-DSA path).
 		AccessPurchaseAuthorized {
@@ -481,12 +1231,18 @@ pub type RecentRoots<T: Config> =
 			tx_id: [u8; 16],
 		},
 		/// A v2 note was used to authorise an access-key grant (zk-membership path).
+		/// The change leaf is always inserted, as for `PurchaseV2Authorized`.
 		AccessPurchaseV2Authorized {
 			app_id: [u8; 32],
 			tx_id: [u8; 16],
+			change_leaf: [u8; 32],
+			change_leaf_index: u32,
+			new_root: [u8; 32],
 		},
 		/// Sudo set or updated an access-key app config.
 		AccessConfigSet { app_id: [u8; 32], price: u64 },
+		/// Sudo replaced the subscription plans of an access-key app.
+		AccessPlansSet { app_id: [u8; 32], plans: u32 },
 		/// Phase 9: a v2 note leaf was inserted via deposit_v2.
 		/// Wallets sync (leaf, leaf_index) from this event stream to build Merkle paths.
 		DepositV2Accepted {
@@ -499,6 +1255,7 @@ pub type RecentRoots<T: Config> =
 		/// The change leaf is always inserted (zero-value change keeps uniform shape).
 		PurchaseV2Authorized {
 			rwa_id: [u8; 32],
+			quantity: u64,
 			tx_id: [u8; 16],
 			change_leaf: [u8; 32],
 			change_leaf_index: u32,
@@ -506,44 +1263,270 @@ pub type RecentRoots<T: Config> =
 		},
 		/// Phase 9: a v2 note was withdrawn back to public balance.
 		WithdrawV2Completed { tx_id: [u8; 16] },
-	}
-
-	#[pallet::error]
-	pub enum Error<T> {
-		ProofVerificationFailed,
-		NullifierAlreadyUsed,
-		MlDsaFailed,
-		/// The spend_tag was not registered at deposit time — deposit may not have occurred.
-		SpendTagNotFound,
-		/// This spend_tag has already been used in a previous purchase or withdrawal.
-		SpendTagAlreadyUsed,
-		/// The requested RWA has no price set — not available for purchase.
-		RwaPriceNotSet,
-		/// The note's deposited value is less than the RWA price.
-		InsufficientFunds,
-		/// purchase_rwa: note_value > price but change_commitment / change_spend_tag are missing.
-		MissingChangeOutput,
-		/// relist_private: the supplied ownership_blinding does not match the stored commitment.
-		InvalidOwnershipProof,
-		/// relist_private: this ownership (tx_id) has already been relisted or transferred.
-		OwnershipAlreadyUsed,
-		/// relist_private: no ownership record found for the given tx_id.
-		OwnershipNotFound,
-		/// relist_private: the new buyer's note is smaller than the asking price.
-		BuyerInsufficientFunds,
-		/// Phase 9: legacy v1 deposits are disabled — use deposit_v2.
-		LegacyDisabled,
-		/// Phase 9: the v2 Merkle tree is full (2^20 leaves).
-		TreeFullV2,
-		/// Phase 9: the anchored merkle_root is neither current nor recent.
-		RootNotRecent,
-		/// Phase 9: this leaf is already in the v2 tree.
-		DuplicateLeaf,
-		/// The requested app has no config set — not available for purchase.
-		AccessAppNotConfigured,
-	}
-
-	// Allow purchase_rwa as unsigned too
+		/// An ownership note leaf was inserted into the ownership tree of
+		/// `rwa_id` (by `purchase_rwa_v2`, `relist_v2` or `redeem_ownership_v2`).
+		/// Wallets sync from this stream.
+		OwnershipNoteInserted {
+			rwa_id: [u8; 32],
+			leaf: [u8; 32],
+			leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// An ownership note was resold via `relist_v2`. The seller's payment
+		/// leaf and the buyer's change leaf were inserted into the v2 tree.
+		RelistV2Completed {
+			rwa_id: [u8; 32],
+			quantity: u64,
+			tx_id: [u8; 16],
+			payment_leaf: [u8; 32],
+			payment_leaf_index: u32,
+			change_leaf: [u8; 32],
+			change_leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// An ownership note was spent for redemption on the RWA chain.
+		OwnershipRedeemedV2 {
+			rwa_id: [u8; 32],
+			quantity: u64,
+			tx_id: [u8; 16],
+		},
+		/// Sudo set the units of an RWA for sale on this lane (`None` = untracked).
+		RwaUnitsSet { rwa_id: [u8; 32], units: Option<u64> },
+		/// The RWA chain synced a per-unit price (`0` = de-listed).
+		RwaPriceSynced { rwa_id: [u8; 32], price: u64 },
+		/// Sudo set a per-unit USD price for an RWA (`0` = priced in planck).
+		RwaUsdPriceSet { rwa_id: [u8; 32], usd_cents: u64 },
+		/// A v2 purchase was placed in escrow until `deadline`.
+		EscrowOpened { tx_id: [u8; 16], deadline: BlockNumberFor<T> },
+		/// The RWA chain confirmed an escrowed purchase; the ownership note was inserted.
+		EscrowFulfilled { tx_id: [u8; 16], rwa_id: [u8; 32] },
+		/// An escrowed purchase timed out or was cancelled; the price was
+		/// returned to the buyer as the refund leaf committed at purchase time.
+		EscrowRefunded {
+			tx_id: [u8; 16],
+			refund_leaf: [u8; 32],
+			refund_leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// Sudo opened a sealed-bid auction.
+		AuctionCreated {
+			auction_id: u32,
+			rwa_id: [u8; 32],
+			quantity: u64,
+			reserve_price: u64,
+			bid_end: BlockNumberFor<T>,
+			reveal_end: BlockNumberFor<T>,
+		},
+		/// A sealed bid was placed; its lock note was inserted into the
+		/// auction's lock tree. Wallets sync lock paths from this stream.
+		AuctionBidPlaced {
+			auction_id: u32,
+			bid_index: u32,
+			lock_leaf: [u8; 32],
+			lock_leaf_index: u32,
+			new_lock_root: [u8; 32],
+		},
+		/// A sealed bid was opened; the lock value above the bid was returned
+		/// to the v2 tree as `change_leaf`.
+		AuctionBidRevealed {
+			auction_id: u32,
+			bid_index: u32,
+			bid: u64,
+			change_leaf: [u8; 32],
+			change_leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// An unrevealed bid's lock note was returned to the v2 tree.
+		AuctionBidReleased {
+			auction_id: u32,
+			bid_index: u32,
+			change_leaf: [u8; 32],
+			change_leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// A losing bid was refunded into the v2 tree by `settle_auction`.
+		AuctionRefundInserted {
+			auction_id: u32,
+			bid_index: u32,
+			refund_leaf: [u8; 32],
+			refund_leaf_index: u32,
+			new_root: [u8; 32],
+		},
+		/// An auction was settled; the winner's ownership note was inserted and
+		/// the result sent to the RWA chain.
+		AuctionSettled {
+			auction_id: u32,
+			rwa_id: [u8; 32],
+			quantity: u64,
+			price: u64,
+		},
+		/// An auction closed with no revealed bid at or above the reserve.
+		AuctionNoSale { auction_id: u32 },
+		/// Paymaster: a fee credit was prepaid.
+		FeeDeposited { leaf: [u8; 32], leaf_index: u32, new_root: [u8; 32] },
+		/// Paymaster: the block author's share of a consumed base fee.
+		FeePaid { author: <T as frame_system::Config>::AccountId, amount: BalanceOf<T> },
+		/// Paymaster: the author payout failed; the share stays with the Paymaster.
+		FeePayoutFailed { author: <T as frame_system::Config>::AccountId, amount: BalanceOf<T> },
+		/// Paymaster: the burned share of a consumed base fee.
+		FeeBurned { amount: BalanceOf<T> },
+		/// Sudo opened a credential registry run by `issuer`.
+		CredentialRegistryCreated { registry_id: u32, issuer: <T as frame_system::Config>::AccountId },
+		/// A credential leaf was issued. Holders sync Merkle paths from this stream.
+		CredentialIssued { registry_id: u32, leaf: [u8; 32], leaf_index: u32, new_root: [u8; 32] },
+		/// The RWA chain synced an asset's buyer eligibility (`None` = open to all).
+		RwaEligibilitySynced { rwa_id: [u8; 32], requirement: Option<EligibilityRequirement> },
+		/// Sudo retired (or restored) a proof version of an AIR.
+		ProofVersionRetired { air: u8, version: u16, retired: bool },
+		/// Sudo replaced the STARK acceptance policy.
+		StarkSecuritySet { security: StarkSecurity },
+		/// The v2 tree of epoch `epoch - 1` filled with `final_root`; leaves
+		/// from here on go into a fresh tree under `epoch`, starting at index 0.
+		TreeEpochV2Started { epoch: u32, final_root: [u8; 32] },
+		/// Sudo set how many blocks a v2 root stays acceptable.
+		RootRetentionSet { blocks: BlockNumberFor<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		ProofVerificationFailed,
+		NullifierAlreadyUsed,
+		MlDsaFailed,
+		/// The spend_tag was not registered at deposit time — deposit may not have occurred.
+		SpendTagNotFound,
+		/// This spend_tag has already been used in a previous purchase or withdrawal.
+		SpendTagAlreadyUsed,
+		/// The requested RWA has no price set — not available for purchase.
+		RwaPriceNotSet,
+		/// The note's deposited value is less than the RWA price.
+		InsufficientFunds,
+		/// purchase_rwa: note_value > price but change_commitment / change_spend_tag are missing.
+		MissingChangeOutput,
+		/// An ownership spend or ownership leaf proof failed to verify.
+		InvalidOwnershipProof,
+		/// This ownership note has already been relisted or redeemed.
+		OwnershipAlreadyUsed,
+		/// Phase 9: legacy v1 deposits are disabled — use deposit_v2.
+		LegacyDisabled,
+		/// Phase 9: the v2 Merkle tree is full (2^20 leaves) and no further
+		/// tree epoch can start.
+		TreeFullV2,
+		/// Phase 9: the anchored merkle_root is neither current, nor recorded
+		/// within `RootRetention` blocks in its tree epoch, nor that epoch's
+		/// final root.
+		RootNotRecent,
+		/// Phase 9: this leaf is already in the v2 tree.
+		DuplicateLeaf,
+		/// The requested app has no config set — not available for purchase.
+		AccessAppNotConfigured,
+		/// The requested access plan does not exist or is not for sale.
+		AccessPlanUnavailable,
+		/// More than `MAX_ACCESS_PLANS` plans.
+		TooManyAccessPlans,
+		/// A plan's duration must be non-zero.
+		InvalidAccessPlan,
+		/// `min_bits` must be between 1 and 128.
+		InvalidStarkSecurity,
+		/// The ownership tree is full (2^20 leaves).
+		OwnershipTreeFull,
+		/// The anchored ownership_root is neither current nor recent.
+		OwnershipRootNotRecent,
+		/// This ownership leaf is already in the ownership tree.
+		DuplicateOwnershipLeaf,
+		/// rwa_id is not canonical (asset_id LE u32 followed by zero bytes).
+		NonCanonicalRwaId,
+		/// Quantity is zero, or not 1 on the access lane.
+		InvalidQuantity,
+		/// Per-unit price × quantity overflows u64.
+		PriceOverflow,
+		/// The RWA is priced in USD but the spend names no oracle round.
+		RateRoundRequired,
+		/// The named oracle round is unknown, superseded too long ago, or stale.
+		RateUnavailable,
+		/// Fewer units are for sale on this lane than requested.
+		InsufficientUnits,
+		/// Caller is not the RWA chain sovereign account.
+		NotRwaChainSovereign,
+		/// A purchase with this tx_id is already in escrow.
+		DuplicateEscrow,
+		/// No escrowed purchase with this tx_id.
+		EscrowNotFound,
+		/// The escrowed purchase was already fulfilled or refunded.
+		EscrowNotPending,
+		/// The escrow deadline has not been reached yet.
+		EscrowNotExpired,
+		/// No auction with this id.
+		AuctionNotFound,
+		/// Bidding or reveal period is zero.
+		InvalidAuctionPeriod,
+		/// The auction's bidding window has closed.
+		BiddingClosed,
+		/// The auction is not in its reveal window.
+		NotInRevealWindow,
+		/// The reveal window has not ended yet.
+		AuctionNotClosed,
+		/// The auction has already been settled.
+		AuctionAlreadySettled,
+		/// The auction already holds `MAX_AUCTION_BIDS` bids.
+		TooManyBids,
+		/// No bid with this index in the auction.
+		BidNotFound,
+		/// The bid has already been revealed or released.
+		BidNotSealed,
+		/// bid and salt do not open the sealed bid commitment.
+		BidCommitmentMismatch,
+		/// The revealed bid is below the auction's reserve price.
+		BidBelowReserve,
+		/// The auth key does not match the bid's lock_pkd.
+		LockKeyMismatch,
+		/// This lock note has already been revealed or released.
+		LockAlreadySpent,
+		/// The auction's lock tree is full (2^20 leaves).
+		AuctionLockTreeFull,
+		/// This lock leaf is already in the auction's lock tree.
+		DuplicateLockLeaf,
+		/// Paymaster: a base fee is charged but no `FeeTicket` was supplied.
+		FeeRequired,
+		/// Paymaster: the fee root is neither the current nor a recent fee credit root.
+		FeeRootNotRecent,
+		/// Paymaster: this fee credit has already been consumed.
+		FeeNullifierAlreadyUsed,
+		/// Paymaster: the fee proof does not show an unspent credit of
+		/// `BaseFee` for this spend.
+		FeeProofInvalid,
+		/// Paymaster: this fee credit leaf is already in the tree.
+		DuplicateFeeCredit,
+		/// No credential registry with this id.
+		CredentialRegistryNotFound,
+		/// Caller is not the registry's credential issuer.
+		NotCredentialIssuer,
+		/// The credential tree is full (2^20 leaves).
+		CredentialTreeFull,
+		/// This credential leaf was already issued in the registry.
+		DuplicateCredential,
+		/// The RWA is restricted to eligible buyers but no credential was presented.
+		CredentialRequired,
+		/// The credential comes from a registry the RWA does not accept.
+		CredentialRegistryMismatch,
+		/// The credential's class lacks flags the RWA requires.
+		CredentialClassInsufficient,
+		/// Credential root is neither the registry's current nor a recent root.
+		CredentialRootNotRecent,
+		/// This credential was already used to buy this RWA.
+		CredentialAlreadyUsed,
+		/// Credential presentation failed to verify.
+		InvalidCredentialProof,
+		/// Auctions do not check buyer eligibility; restricted RWAs cannot be auctioned.
+		EligibilityRestricted,
+		/// Root retention must be between 1 block and `MaxRootRetention`.
+		InvalidRootRetention,
+		/// Paymaster: `BaseFee` does not fit the 64-bit committed amount.
+		FeeAmountOverflow,
+		/// Paymaster: the fee credit tree is full (2^20 leaves).
+		FeeTreeFull,
+	}
+
+	// Allow purchase_rwa as unsigned too
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
@@ -588,53 +1571,144 @@ pub type RecentRoots<T: Config> =
 							.build()
 					} else { InvalidTransaction::Call.into() }
 				}
-				Call::relist_private { inputs: relist, .. } => {
-					if OwnershipCommitments::<T>::get(relist.old_tx_id).is_some()
-						&& !OwnershipUsed::<T>::get(relist.old_tx_id)
-						&& SpendTagCommitments::<T>::contains_key(relist.new_buyer_spend_tag)
-						&& !SpendTagUsed::<T>::get(relist.new_buyer_spend_tag)
-					{
-						ValidTransaction::with_tag_prefix("NullaRelist")
-							.and_provides(relist.new_tx_id)
-							.and_provides(relist.new_buyer_spend_tag)
+				Call::purchase_rwa_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = SpendPublicV2::decode(&mut &public_inputs[..]) {
+						if NullifierUsed::<T>::get(inputs.nullifier) {
+							return InvalidTransaction::Stale.into();
+						}
+						if !Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root) {
+							return InvalidTransaction::Stale.into();
+						}
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let mut tx = ValidTransaction::with_tag_prefix("NullaPurchaseV2")
+							.and_provides(inputs.tx_id)
+							.and_provides(inputs.nullifier)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
-					} else { InvalidTransaction::Stale.into() }
+							.propagate(true);
+						if let Some(cred) = &inputs.credential {
+							if CredentialTagUsed::<T>::get(cred.tag) {
+								return InvalidTransaction::Stale.into();
+							}
+							tx = tx.and_provides(cred.tag);
+						}
+						Self::with_fee_tag(tx, fee_tag).build()
+					} else { InvalidTransaction::Call.into() }
 				}
-				Call::purchase_rwa_v2 { public_inputs, .. } => {
-					if let Ok(inputs) = SpendPublicV2::decode(&mut &public_inputs[..]) {
+				Call::withdraw_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = WithdrawPublicV2::decode(&mut &public_inputs[..]) {
 						if NullifierUsed::<T>::get(inputs.nullifier) {
 							return InvalidTransaction::Stale.into();
 						}
-						if !Self::v2_root_anchored(&inputs.merkle_root) {
+						if !Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root) {
 							return InvalidTransaction::Stale.into();
 						}
-						ValidTransaction::with_tag_prefix("NullaPurchaseV2")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("NullaWithdrawV2")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.nullifier)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
-				Call::withdraw_v2 { public_inputs, .. } => {
-					if let Ok(inputs) = WithdrawPublicV2::decode(&mut &public_inputs[..]) {
+				Call::relist_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = RelistPublicV2::decode(&mut &public_inputs[..]) {
+						if OwnershipNullifierUsed::<T>::get(inputs.ownership_nullifier)
+							|| NullifierUsed::<T>::get(inputs.nullifier)
+						{
+							return InvalidTransaction::Stale.into();
+						}
+						if !Self::ownership_root_anchored(&inputs.rwa_id, &inputs.ownership_root)
+							|| !Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root)
+						{
+							return InvalidTransaction::Stale.into();
+						}
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let mut tx = ValidTransaction::with_tag_prefix("NullaRelistV2")
+							.and_provides(inputs.tx_id)
+							.and_provides(inputs.ownership_nullifier)
+							.and_provides(inputs.nullifier)
+							.priority(100)
+							.longevity(64)
+							.propagate(true);
+						if let Some(cred) = &inputs.credential {
+							if CredentialTagUsed::<T>::get(cred.tag) {
+								return InvalidTransaction::Stale.into();
+							}
+							tx = tx.and_provides(cred.tag);
+						}
+						Self::with_fee_tag(tx, fee_tag).build()
+					} else { InvalidTransaction::Call.into() }
+				}
+				Call::redeem_ownership_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = RedeemPublicV2::decode(&mut &public_inputs[..]) {
+						if OwnershipNullifierUsed::<T>::get(inputs.ownership_nullifier) {
+							return InvalidTransaction::Stale.into();
+						}
+						if !Self::ownership_root_anchored(&inputs.rwa_id, &inputs.ownership_root) {
+							return InvalidTransaction::Stale.into();
+						}
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("NullaRedeemV2")
+							.and_provides(inputs.tx_id)
+							.and_provides(inputs.ownership_nullifier)
+							.priority(100)
+							.longevity(64)
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
+					} else { InvalidTransaction::Call.into() }
+				}
+				// ── Sealed-bid auctions ─────────────────────────────────────────────
+				Call::place_bid_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = AuctionBidPublicV2::decode(&mut &public_inputs[..]) {
 						if NullifierUsed::<T>::get(inputs.nullifier) {
 							return InvalidTransaction::Stale.into();
 						}
-						if !Self::v2_root_anchored(&inputs.merkle_root) {
+						if !Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root) {
 							return InvalidTransaction::Stale.into();
 						}
-						ValidTransaction::with_tag_prefix("NullaWithdrawV2")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("NullaAuctionBidV2")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.nullifier)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
+					} else { InvalidTransaction::Call.into() }
+				}
+				Call::reveal_bid_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = AuctionRevealPublicV2::decode(&mut &public_inputs[..]) {
+						if AuctionLockNullifierUsed::<T>::get(inputs.lock_nullifier) {
+							return InvalidTransaction::Stale.into();
+						}
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("NullaAuctionRevealV2")
+							.and_provides(inputs.tx_id)
+							.and_provides(inputs.lock_nullifier)
+							.and_provides((inputs.auction_id, inputs.bid_index))
+							.priority(100)
+							.longevity(64)
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
+					} else { InvalidTransaction::Call.into() }
+				}
+				Call::release_bid_v2 { public_inputs, fee, .. } => {
+					if let Ok(inputs) = AuctionReleasePublicV2::decode(&mut &public_inputs[..]) {
+						if AuctionLockNullifierUsed::<T>::get(inputs.lock_nullifier) {
+							return InvalidTransaction::Stale.into();
+						}
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("NullaAuctionReleaseV2")
+							.and_provides(inputs.tx_id)
+							.and_provides(inputs.lock_nullifier)
+							.and_provides((inputs.auction_id, inputs.bid_index))
+							.priority(100)
+							.longevity(64)
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
 				// ── Access-key lanes ────────────────────────────────────────────────
@@ -659,21 +1733,22 @@ pub type RecentRoots<T: Config> =
 							.build()
 					} else { InvalidTransaction::Call.into() }
 				}
-				Call::purchase_access_v2 { public_inputs, .. } => {
+				Call::purchase_access_v2 { public_inputs, fee, .. } => {
 					if let Ok(inputs) = SpendPublicV2::decode(&mut &public_inputs[..]) {
 						if NullifierUsed::<T>::get(inputs.nullifier) {
 							return InvalidTransaction::Stale.into();
 						}
-						if !Self::v2_root_anchored(&inputs.merkle_root) {
+						if !Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root) {
 							return InvalidTransaction::Stale.into();
 						}
-						ValidTransaction::with_tag_prefix("DistAccessPurchaseV2")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("DistAccessPurchaseV2")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.nullifier)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
 				_ => InvalidTransaction::Call.into(),
@@ -747,17 +1822,6 @@ pub type RecentRoots<T: Config> =
 			RecentRoots::<T>::put(&window);
 		}
 
-		/// Compute ownership commitment:
-		/// BLAKE3("nulla_rwa_ownership_v1" || rwa_id || blinding)
-		/// Must match exactly what the wallet and verifier compute.
-		fn compute_ownership_commitment(rwa_id: [u8; 32], blinding: [u8; 32]) -> [u8; 32] {
-			let mut h = blake3::Hasher::new();
-			h.update(b"nulla_rwa_ownership_v1");
-			h.update(&rwa_id);
-			h.update(&blinding);
-			*h.finalize().as_bytes()
-		}
-
 		/// Phase 9: pkd = BLAKE3("nulla_pk_digest_v2" || ml_dsa_pk).
 		fn compute_pk_digest(pk: &[u8]) -> [u8; 32] {
 			let mut h = blake3::Hasher::new();
@@ -766,136 +1830,1053 @@ pub type RecentRoots<T: Config> =
 			*h.finalize().as_bytes()
 		}
 
-		/// Phase 9: check root anchoring against CurrentRootV2 / RecentRootsV2.
-		fn v2_root_anchored(root: &[u8; 32]) -> bool {
-			if *root == CurrentRootV2::<T>::get() { return true; }
-			RecentRootsV2::<T>::get().iter().any(|r| r == root)
+		/// Phase 9: check root anchoring within a tree epoch. The current epoch
+		/// accepts CurrentRootV2, a closed epoch its final root, and either one
+		/// any root recorded in `RootHistoryV2` within `RootRetention` blocks.
+		pub fn v2_root_anchored(epoch: u32, root: &[u8; 32]) -> bool {
+			let current = TreeEpochV2::<T>::get();
+			if epoch > current { return false; }
+			if epoch == current && *root == CurrentRootV2::<T>::get() { return true; }
+			if epoch < current && FinalRootsV2::<T>::get(epoch).as_ref() == Some(root) {
+				return true;
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			RootRecordedAtV2::<T>::get(epoch, root)
+				.is_some_and(|at| now.saturating_sub(at) <= RootRetention::<T>::get())
+		}
+
+		/// Record `prev`, the v2 root current at the end of the previous block,
+		/// on the first tree change of a block. Later changes in the same block
+		/// record nothing, so the history holds at most one root per block.
+		fn record_root_v2(epoch: u32, prev: [u8; 32]) {
+			let at = frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+			if RootHistoryV2::<T>::contains_key(at) { return; }
+			RootHistoryV2::<T>::insert(at, (epoch, prev));
+			RootRecordedAtV2::<T>::insert(epoch, prev, at);
+		}
+
+		/// Record roots of the pre-v3 window, oldest first, newest at
+		/// `now - 2`, one block apart. `now - 1` stays free for the root
+		/// current at the upgrade, recorded by the block's first tree change.
+		/// Roots that would fall outside `RootRetention` are dropped.
+		fn import_roots_v2(roots: Vec<(u32, [u8; 32])>) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let floor = now.saturating_sub(RootRetention::<T>::get());
+			let mut at = now.saturating_sub(One::one());
+			for (epoch, root) in roots.into_iter().rev() {
+				if at <= floor { break; }
+				at = at.saturating_sub(One::one());
+				RootHistoryV2::<T>::insert(at, (epoch, root));
+				RootRecordedAtV2::<T>::insert(epoch, root, at);
+			}
+		}
+
+		/// Drop the v2 root recorded at block `at`, unless it was recorded
+		/// again later.
+		fn prune_root_v2(at: BlockNumberFor<T>) {
+			if let Some((epoch, root)) = RootHistoryV2::<T>::take(at) {
+				if RootRecordedAtV2::<T>::get(epoch, root) == Some(at) {
+					RootRecordedAtV2::<T>::remove(epoch, root);
+				}
+			}
+		}
+
+		/// Oldest (tree epoch, root) a v2 spend may still anchor to, other than
+		/// the final roots of closed epochs, which never expire. The current
+		/// root when no older root is retained.
+		pub fn oldest_acceptable_root_v2() -> (u32, [u8; 32]) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(RootRetention::<T>::get());
+			while at < now {
+				if let Some(entry) = RootHistoryV2::<T>::get(at) {
+					return entry;
+				}
+				at = at.saturating_add(One::one());
+			}
+			(TreeEpochV2::<T>::get(), CurrentRootV2::<T>::get())
+		}
+
+		/// v2 roots recorded within `RootRetention` blocks, oldest first, as
+		/// (block, tree epoch, root). The current root is not among them.
+		pub fn recent_roots_v2() -> Vec<(BlockNumberFor<T>, u32, [u8; 32])> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(RootRetention::<T>::get());
+			let mut roots = Vec::new();
+			while at < now {
+				if let Some((epoch, root)) = RootHistoryV2::<T>::get(at) {
+					roots.push((at, epoch, root));
+				}
+				at = at.saturating_add(One::one());
+			}
+			roots
+		}
+
+		/// (tree epoch, index) of a v2 leaf.
+		pub fn leaf_position_v2(leaf: &[u8; 32]) -> Option<(u32, u32)> {
+			LeafIndexV2::<T>::get(leaf).map(|index| (LeafEpochV2::<T>::get(leaf), index))
+		}
+
+		/// Prices, supply and eligibility of `rwa_id` on this lane.
+		pub fn rwa_listing(rwa_id: &[u8; 32]) -> super::RwaListing {
+			super::RwaListing {
+				price: RwaPrices::<T>::get(rwa_id),
+				usd_cents: RwaUsdPrices::<T>::get(rwa_id),
+				units: RwaUnits::<T>::get(rwa_id),
+				eligibility: RwaEligibility::<T>::get(rwa_id),
+			}
+		}
+
+		/// Dispatch `call` as an unsigned extrinsic on the current state and
+		/// roll every change back. Returns the exact error the call would
+		/// fail with; calls that are not unsigned fail with `BadOrigin`.
+		pub fn dry_run(call: Call<T>) -> DispatchResult {
+			frame_support::storage::with_transaction(|| {
+				let result = call.dispatch_bypass_filter(frame_system::RawOrigin::None.into());
+				sp_runtime::TransactionOutcome::Rollback(result.map(|_| ()).map_err(|e| e.error))
+			})
+		}
+
+		/// Close the full v2 tree: record its final root and start an empty
+		/// frontier under the next epoch.
+		fn v2_roll_epoch() -> Result<(), Error<T>> {
+			let epoch = TreeEpochV2::<T>::get();
+			let next = epoch.checked_add(1).ok_or(Error::<T>::TreeFullV2)?;
+			let final_root = CurrentRootV2::<T>::get();
+			FinalRootsV2::<T>::insert(epoch, final_root);
+			TreeEpochV2::<T>::put(next);
+			FrontierNodesV2::<T>::kill();
+			LeafCountV2::<T>::kill();
+			CurrentRootV2::<T>::kill();
+			Self::deposit_event(Event::TreeEpochV2Started { epoch: next, final_root });
+			Ok(())
 		}
 
-		/// Phase 9: insert a leaf into the v2 incremental tree.
-		/// Updates frontier, leaf count, current root, recent-roots window,
-		/// and the leaf index map. Returns (leaf_index, new_root).
+		/// Phase 9: insert a leaf into the v2 incremental tree, rolling over to
+		/// a new tree epoch when the current one is full.
+		/// Updates frontier, leaf count, current root, root history, and the
+		/// leaf index map. Returns (leaf_index, new_root); the index
+		/// is within the epoch in `TreeEpochV2` after the call.
 		fn v2_insert(leaf: [u8; 32]) -> Result<(u32, [u8; 32]), Error<T>> {
 			ensure!(!LeafIndexV2::<T>::contains_key(&leaf), Error::<T>::DuplicateLeaf);
-			let nodes = FrontierNodesV2::<T>::get();
-			let count = LeafCountV2::<T>::get();
-			let (new_nodes, new_count, new_root) =
-				T::ProofVerifier::v2_insert_leaf(nodes, count, leaf)
-					.ok_or(Error::<T>::TreeFullV2)?;
+			let mut nodes = FrontierNodesV2::<T>::get();
+			let mut count = LeafCountV2::<T>::get();
+			let mut inserted = T::ProofVerifier::v2_insert_leaf(nodes, count, leaf);
+			if inserted.is_none() && count > 0 {
+				Self::v2_roll_epoch()?;
+				nodes = FrontierNodesV2::<T>::get();
+				count = 0;
+				inserted = T::ProofVerifier::v2_insert_leaf(nodes, count, leaf);
+			}
+			let (new_nodes, new_count, new_root) = inserted.ok_or(Error::<T>::TreeFullV2)?;
+			let epoch = TreeEpochV2::<T>::get();
 			let idx = count;
 			FrontierNodesV2::<T>::put(new_nodes);
 			LeafCountV2::<T>::put(new_count);
 			LeafIndexV2::<T>::insert(&leaf, idx);
+			if epoch > 0 {
+				LeafEpochV2::<T>::insert(&leaf, epoch);
+			}
 			let prev = CurrentRootV2::<T>::get();
 			CurrentRootV2::<T>::put(new_root);
 			if count > 0 {
-				let mut window = RecentRootsV2::<T>::get();
+				Self::record_root_v2(epoch, prev);
+			}
+			Ok((idx, new_root))
+		}
+
+		/// rwa_id must be canonical: asset_id = LE u32 of `rwa_id[..4]`, the rest
+		/// zero. Mirrors `proofhub_verifier::v2::rwa_asset_id`.
+		fn ensure_canonical_rwa_id(rwa_id: &[u8; 32]) -> Result<(), Error<T>> {
+			ensure!(rwa_id[4..].iter().all(|b| *b == 0), Error::<T>::NonCanonicalRwaId);
+			Ok(())
+		}
+
+		/// Price of `quantity` units of `rwa_id`: the per-unit price × quantity.
+		/// The per-unit price is `RwaUsdPrices[rwa_id]` converted at oracle round
+		/// `rate_round` when the RWA is priced in USD, `RwaPrices[rwa_id]` otherwise.
+		/// Also checks the lane's remaining supply when `RwaUnits` is set.
+		fn rwa_total_price(rwa_id: &[u8; 32], quantity: u64, rate_round: Option<u32>) -> Result<u64, Error<T>> {
+			ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+			let usd_cents = RwaUsdPrices::<T>::get(rwa_id);
+			let unit_price = if usd_cents > 0 {
+				let round = rate_round.ok_or(Error::<T>::RateRoundRequired)?;
+				let rate = T::FiatRate::planck_per_cent(round).ok_or(Error::<T>::RateUnavailable)?;
+				usd_cents.checked_mul(rate).ok_or(Error::<T>::PriceOverflow)?
+			} else {
+				RwaPrices::<T>::get(rwa_id)
+			};
+			ensure!(unit_price > 0, Error::<T>::RwaPriceNotSet);
+			if let Some(units) = RwaUnits::<T>::get(rwa_id) {
+				ensure!(quantity <= units, Error::<T>::InsufficientUnits);
+			}
+			unit_price.checked_mul(quantity).ok_or(Error::<T>::PriceOverflow)
+		}
+
+		/// Draw `quantity` down from the lane's supply (no-op when untracked).
+		/// Call only after `rwa_total_price` has passed.
+		fn take_rwa_units(rwa_id: &[u8; 32], quantity: u64) {
+			RwaUnits::<T>::mutate(rwa_id, |units| {
+				if let Some(u) = units.as_mut() { *u = u.saturating_sub(quantity); }
+			});
+		}
+
+		/// Check root anchoring against the current / recent roots of `rwa_id`'s ownership tree.
+		fn ownership_root_anchored(rwa_id: &[u8; 32], root: &[u8; 32]) -> bool {
+			if *root == CurrentOwnershipRoot::<T>::get(rwa_id) { return true; }
+			RecentOwnershipRoots::<T>::get(rwa_id).iter().any(|r| r == root)
+		}
+
+		/// Insert an ownership note leaf into the ownership tree of `rwa_id` and
+		/// emit `OwnershipNoteInserted`. Same frontier logic as `v2_insert`.
+		fn ownership_insert(rwa_id: [u8; 32], leaf: [u8; 32]) -> Result<(u32, [u8; 32]), Error<T>> {
+			ensure!(
+				!OwnershipLeafIndex::<T>::contains_key(&rwa_id, &leaf),
+				Error::<T>::DuplicateOwnershipLeaf
+			);
+			let nodes = OwnershipFrontierNodes::<T>::get(rwa_id);
+			let count = OwnershipLeafCount::<T>::get(rwa_id);
+			let (new_nodes, new_count, new_root) =
+				T::ProofVerifier::v2_insert_leaf(nodes, count, leaf)
+					.ok_or(Error::<T>::OwnershipTreeFull)?;
+			OwnershipFrontierNodes::<T>::insert(rwa_id, new_nodes);
+			OwnershipLeafCount::<T>::insert(rwa_id, new_count);
+			OwnershipLeafIndex::<T>::insert(&rwa_id, &leaf, count);
+			let prev = CurrentOwnershipRoot::<T>::get(rwa_id);
+			CurrentOwnershipRoot::<T>::insert(rwa_id, new_root);
+			if count > 0 {
+				let mut window = RecentOwnershipRoots::<T>::get(rwa_id);
 				if window.len() >= 64 {
 					let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
 					for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
 					window = shifted;
 				}
 				let _ = window.try_push(prev);
-				RecentRootsV2::<T>::put(&window);
+				RecentOwnershipRoots::<T>::insert(rwa_id, &window);
 			}
-			Ok((idx, new_root))
+			Self::deposit_event(Event::OwnershipNoteInserted { rwa_id, leaf, leaf_index: count, new_root });
+			Ok((count, new_root))
 		}
-	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		// Pays::No: the deposit itself IS the "payment" (1 NULLA → pool). Length fees
-		// on a 24 KB STARK-proof extrinsic would otherwise require the depositor to hold
-		// extra balance above the deposit amount, creating an unnecessary friction point
-		// for a privacy protocol.  Pool validation still checks nonce & signature.
-		#[pallet::weight((Weight::zero(), Pays::No))]
-		pub fn deposit_public(
-			origin: OriginFor<T>,
-			commitment: [u8; 32],
-			// Phase 8: wire format: [1:u8][range_len:u32LE][range_bytes][v:u64LE][commit_len:u32LE][commit_bytes]
-			range_proof: BoundedVec<u8, ConstU32<65536>>,
-			// Phase 8: Poseidon rp_commitment = Poseidon(v, blinding); blinding is PRIVATE.
-			rp_commitment: [u8; 32],
-			// Phase 6: spend_tag = BLAKE3("nulla_spend_tag_v1" || deposit_pk_bytes)
-			// Registered on-chain to enable unlinkable purchase later.
-			spend_tag: [u8; 32],
-			hints_blob: BoundedVec<u8, ConstU32<4096>>,
-		) -> DispatchResult {
-			let _ = ensure_signed(origin)?;
-			// Phase 9 (spec 127): legacy v1 deposits are DISABLED.
-			// v1 notes remain spendable via purchase_rwa / withdraw_private as an
-			// exit ramp; new deposits must use deposit_v2 (zk-membership pool).
-			let _ = (commitment, range_proof, rp_commitment, spend_tag, hints_blob);
-			Err(Error::<T>::LegacyDisabled.into())
+		/// Spent-set slot of an ownership nullifier: the low 64 bits of nf_lo.
+		fn spent_key(nullifier: &[u8; 32]) -> u64 {
+			u64::from_le_bytes(nullifier[..8].try_into().expect("8 bytes"))
 		}
 
-		#[pallet::weight(Weight::zero())]
-		pub fn purchase_rwa(
-			origin: OriginFor<T>,
-			proof: Vec<u8>,
-			public_inputs: Vec<u8>,
-		) -> DispatchResult {
-			ensure_none(origin)?;
-
-			let inputs = RwaPurchaseInputs::decode(&mut &public_inputs[..])
-				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
-
-			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
-			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
-			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
-
-			let price = RwaPrices::<T>::get(inputs.rwa_id);
-			ensure!(price > 0, Error::<T>::RwaPriceNotSet);
+		/// Record a spent ownership nullifier in `OwnershipSpentNodes`. A slot
+		/// already holding another nullifier (a 64-bit key collision) is left
+		/// as is; both notes then read as spent to presentations.
+		fn ownership_spent_insert(nullifier: &[u8; 32]) {
+			let key = Self::spent_key(nullifier);
+			if OwnershipSpentNodes::<T>::contains_key(0, key) {
+				return;
+			}
+			let (_, siblings) = Self::ownership_spent_path(key);
+			let path = T::ProofVerifier::v2_spent_path(key, nullifier, &siblings);
+			for (level, node) in path.into_iter().enumerate() {
+				let index = key.checked_shr(level as u32).unwrap_or(0);
+				OwnershipSpentNodes::<T>::insert(level as u8, index, node);
+			}
+		}
 
-			// Phase 8.1: resolve change_rp_commitment.
-			// When a change note is present the client must have previously registered its
-			// rp_commitment via deposit_public. When there is no change note (v == price)
-			// pass [0u8;32] — the circuit treats it as the zero-change case.
-			let change_rp_commitment: [u8; 32] = if let Some(t_change) = inputs.change_spend_tag {
-				SpendTagRpCommitments::<T>::get(t_change)
-					.ok_or(Error::<T>::SpendTagNotFound)?
-			} else {
-				[0u8; 32]
-			};
+		/// Current spent-set root and the siblings of slot `key`, leaf level
+		/// first: the spent-set witness of an ownership presentation.
+		pub fn ownership_spent_path(key: u64) -> ([u8; 32], [[u8; 32]; 64]) {
+			let zero = T::ProofVerifier::v2_spent_zero_subtrees();
+			let siblings = core::array::from_fn(|level| {
+				OwnershipSpentNodes::<T>::get(level as u8, (key >> level) ^ 1).unwrap_or(zero[level])
+			});
+			(OwnershipSpentNodes::<T>::get(64, 0).unwrap_or(zero[64]), siblings)
+		}
 
-			// Phase 8 + 8.1: verify PurchaseAir STARK.
-			// Proves: Poseidon(iv,v,blinding)=rp_commitment AND v>=price
-			//     AND Poseidon(iv,v-price,c_blinding)=change_rp_commitment
-			let stored_rp = SpendTagRpCommitments::<T>::get(inputs.spend_tag)
-				.ok_or(Error::<T>::SpendTagNotFound)?;
+		/// Check the buyer eligibility of a purchase of `rwa_id`.
+		///
+		/// Open RWAs accept any buyer (an attached credential is ignored).
+		/// Restricted RWAs need a credential from the required registry whose
+		/// class holds every required flag, anchored to a recent credential
+		/// root, with an unused tag, and proven for this spend (`binding` = the
+		/// spend nullifier). Returns the tag to mark used.
+		fn check_eligibility(
+			rwa_id: &[u8; 32],
+			credential: &Option<CredentialPublicV2>,
+			proof: &[u8],
+			binding: &[u8; 32],
+		) -> Result<Option<[u8; 32]>, Error<T>> {
+			let Some(req) = RwaEligibility::<T>::get(rwa_id) else { return Ok(None) };
+			let cred = credential.as_ref().ok_or(Error::<T>::CredentialRequired)?;
+			ensure!(cred.registry_id == req.registry_id, Error::<T>::CredentialRegistryMismatch);
 			ensure!(
-				T::ProofVerifier::verify_purchase_proof(&inputs.purchase_proof, &stored_rp, &change_rp_commitment, price),
-				Error::<T>::ProofVerificationFailed
+				cred.class & req.required_class == req.required_class,
+				Error::<T>::CredentialClassInsufficient
 			);
+			ensure!(
+				Self::credential_root_anchored(cred.registry_id, &cred.root),
+				Error::<T>::CredentialRootNotRecent
+			);
+			ensure!(!CredentialTagUsed::<T>::get(cred.tag), Error::<T>::CredentialAlreadyUsed);
+			ensure!(
+				T::ProofVerifier::verify_credential(
+					proof,
+					&cred.root,
+					rwa_id,
+					cred.class,
+					&cred.tag,
+					binding,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidCredentialProof
+			);
+			Ok(Some(cred.tag))
+		}
 
-			ensure!(T::ProofVerifier::verify_purchase(&proof, &public_inputs), Error::<T>::MlDsaFailed);
-
-			NullifierUsed::<T>::insert(inputs.nullifier, true);
-			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
-
-			// Store ownership commitment so relist_private can verify ownership later
-			OwnershipCommitments::<T>::insert(inputs.tx_id, inputs.ownership_commitment);
+		/// Check root anchoring against the current / recent roots of a credential tree.
+		fn credential_root_anchored(registry_id: u32, root: &[u8; 32]) -> bool {
+			if CredentialLeafCount::<T>::get(registry_id) == 0 { return false; }
+			if *root == CurrentCredentialRoot::<T>::get(registry_id) { return true; }
+			RecentCredentialRoots::<T>::get(registry_id).iter().any(|r| r == root)
+		}
 
-			// Insert change note if client created one (v > price case).
+		/// Insert a credential leaf into the tree of `registry_id`.
+		/// Same frontier logic as `v2_insert`.
+		fn credential_insert(registry_id: u32, leaf: [u8; 32]) -> Result<(u32, [u8; 32]), Error<T>> {
+			ensure!(
+				!CredentialLeafIndex::<T>::contains_key(registry_id, &leaf),
+				Error::<T>::DuplicateCredential
+			);
+			let nodes = CredentialFrontierNodes::<T>::get(registry_id);
+			let count = CredentialLeafCount::<T>::get(registry_id);
+			let (new_nodes, new_count, new_root) =
+				T::ProofVerifier::v2_insert_leaf(nodes, count, leaf)
+					.ok_or(Error::<T>::CredentialTreeFull)?;
+			CredentialFrontierNodes::<T>::insert(registry_id, new_nodes);
+			CredentialLeafCount::<T>::insert(registry_id, new_count);
+			CredentialLeafIndex::<T>::insert(registry_id, &leaf, count);
+			let prev = CurrentCredentialRoot::<T>::get(registry_id);
+			CurrentCredentialRoot::<T>::insert(registry_id, new_root);
+			if count > 0 {
+				let mut window = RecentCredentialRoots::<T>::get(registry_id);
+				if window.len() >= 64 {
+					let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
+					for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
+					window = shifted;
+				}
+				let _ = window.try_push(prev);
+				RecentCredentialRoots::<T>::insert(registry_id, &window);
+			}
+			Ok((count, new_root))
+		}
+
+		/// Insert a lock note into the lock tree of `auction_id`.
+		/// Same frontier logic as `v2_insert`, without a recent-roots window.
+		fn auction_lock_insert(auction_id: u32, leaf: [u8; 32]) -> Result<(u32, [u8; 32]), Error<T>> {
+			ensure!(
+				!AuctionLockLeafIndex::<T>::contains_key(auction_id, &leaf),
+				Error::<T>::DuplicateLockLeaf
+			);
+			let nodes = AuctionLockFrontierNodes::<T>::get(auction_id);
+			let count = AuctionLockLeafCount::<T>::get(auction_id);
+			let (new_nodes, new_count, new_root) =
+				T::ProofVerifier::v2_insert_leaf(nodes, count, leaf)
+					.ok_or(Error::<T>::AuctionLockTreeFull)?;
+			AuctionLockFrontierNodes::<T>::insert(auction_id, new_nodes);
+			AuctionLockLeafCount::<T>::insert(auction_id, new_count);
+			AuctionLockLeafIndex::<T>::insert(auction_id, &leaf, count);
+			AuctionLockRoot::<T>::insert(auction_id, new_root);
+			Ok((count, new_root))
+		}
+
+		/// Refund a pending escrow: insert the buyer's refund leaf into the v2
+		/// tree and return the units to `RwaUnits`.
+		fn refund_rwa_escrow(tx_id: [u8; 16], mut escrow: RwaEscrow<BlockNumberFor<T>>) -> DispatchResult {
+			let refund_leaf = escrow.refund_leaf;
+			// A leaf already in the tree opens the same note; nothing to insert.
+			let (refund_leaf_index, new_root) = match LeafIndexV2::<T>::get(&refund_leaf) {
+				Some(index) => (index, CurrentRootV2::<T>::get()),
+				None => Self::v2_insert(refund_leaf)?,
+			};
+			RwaUnits::<T>::mutate(escrow.rwa_id, |units| {
+				if let Some(u) = units.as_mut() { *u = u.saturating_add(escrow.quantity); }
+			});
+			escrow.status = EscrowStatus::Refunded;
+			RwaEscrows::<T>::insert(tx_id, escrow);
+			Self::deposit_event(Event::EscrowRefunded { tx_id, refund_leaf, refund_leaf_index, new_root });
+			Ok(())
+		}
+
+		/// Sealed bid commitment,
+		/// BLAKE3("nulla_auction_bid_v1" ‖ auction_id LE ‖ bid LE ‖ salt).
+		pub fn auction_bid_commitment(auction_id: u32, bid: u64, salt: &[u8; 32]) -> [u8; 32] {
+			let mut h = blake3::Hasher::new();
+			h.update(b"nulla_auction_bid_v1");
+			h.update(&auction_id.to_le_bytes());
+			h.update(&bid.to_le_bytes());
+			h.update(salt);
+			*h.finalize().as_bytes()
+		}
+
+		/// Paymaster: check `fee` against `BaseFee` for a spend whose context
+		/// is `ctx` (BLAKE2-256 of its public inputs). Returns the fee nullifier
+		/// to consume, or `None` when no base fee is charged.
+		fn check_fee(fee: &Option<FeeTicket>, ctx: &[u8; 32]) -> Result<Option<[u8; 32]>, Error<T>> {
+			let base_fee = T::BaseFee::get();
+			if base_fee.is_zero() { return Ok(None); }
+			let ticket = fee.as_ref().ok_or(Error::<T>::FeeRequired)?;
+			let nf = ticket.fee_nullifier;
+			ensure!(!FeeNullifierUsed::<T>::get(nf), Error::<T>::FeeNullifierAlreadyUsed);
+			ensure!(Self::fee_root_anchored(&ticket.fee_root), Error::<T>::FeeRootNotRecent);
+			let amount: u64 = base_fee.try_into().map_err(|_| Error::<T>::FeeAmountOverflow)?;
+			ensure!(
+				T::ProofVerifier::verify_fee_credit(
+					&ticket.proof,
+					&ticket.fee_root,
+					amount,
+					&nf,
+					ctx,
+					&Self::stark_security(),
+				),
+				Error::<T>::FeeProofInvalid
+			);
+			Ok(Some(nf))
+		}
+
+		/// Paymaster: check root anchoring against the current / recent fee credit roots.
+		fn fee_root_anchored(root: &[u8; 32]) -> bool {
+			if FeeLeafCount::<T>::get() == 0 { return false; }
+			if *root == CurrentFeeRoot::<T>::get() { return true; }
+			RecentFeeRoots::<T>::get().iter().any(|r| r == root)
+		}
+
+		/// Paymaster: insert a fee credit leaf. Same frontier logic as `v2_insert`.
+		fn fee_insert(leaf: [u8; 32]) -> Result<(u32, [u8; 32]), Error<T>> {
+			ensure!(!FeeLeafIndex::<T>::contains_key(leaf), Error::<T>::DuplicateFeeCredit);
+			let (new_nodes, new_count, new_root) =
+				T::ProofVerifier::v2_insert_leaf(FeeFrontierNodes::<T>::get(), FeeLeafCount::<T>::get(), leaf)
+					.ok_or(Error::<T>::FeeTreeFull)?;
+			let count = FeeLeafCount::<T>::get();
+			FeeFrontierNodes::<T>::put(new_nodes);
+			FeeLeafCount::<T>::put(new_count);
+			FeeLeafIndex::<T>::insert(leaf, count);
+			let prev = CurrentFeeRoot::<T>::get();
+			CurrentFeeRoot::<T>::put(new_root);
+			if count > 0 {
+				let mut window = RecentFeeRoots::<T>::get();
+				if window.len() >= 64 {
+					let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
+					for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
+					window = shifted;
+				}
+				let _ = window.try_push(prev);
+				RecentFeeRoots::<T>::put(&window);
+			}
+			Ok((count, new_root))
+		}
+
+		/// Paymaster: `check_fee` for the transaction pool.
+		fn validate_fee(
+			fee: &Option<FeeTicket>,
+			ctx: &[u8; 32],
+		) -> Result<Option<[u8; 32]>, TransactionValidityError> {
+			Self::check_fee(fee, ctx).map_err(|e| match e {
+				Error::<T>::FeeNullifierAlreadyUsed => InvalidTransaction::Stale.into(),
+				_ => InvalidTransaction::Payment.into(),
+			})
+		}
+
+		/// Paymaster: spends sharing a fee credit conflict in the pool.
+		fn with_fee_tag(tx: ValidTransactionBuilder, fee_tag: Option<[u8; 32]>) -> ValidTransactionBuilder {
+			match fee_tag {
+				Some(nf) => tx.and_provides(nf),
+				None => tx,
+			}
+		}
+
+		/// Paymaster: message signed by the v2 ML-DSA-44 auth — the SCALE
+		/// public inputs, followed by SCALE(fee ticket) when one is attached.
+		fn auth_message(public_inputs: &[u8], fee: &Option<FeeTicket>) -> Vec<u8> {
+			let mut msg = public_inputs.to_vec();
+			if let Some(ticket) = fee { ticket.encode_to(&mut msg); }
+			msg
+		}
+
+		/// Body of `purchase_rwa_v2`.
+		fn do_purchase_rwa_v2(
+			auth: &[u8],
+			public_inputs: &[u8],
+			spend_proof: &[u8],
+			ownership_proof: &[u8],
+			refund_proof: &[u8],
+			credential_proof: &[u8],
+			fee: &Option<FeeTicket>,
+		) -> DispatchResult {
+			let inputs = SpendPublicV2::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root), Error::<T>::RootNotRecent);
+			let fee_nullifier = Self::check_fee(fee, &blake2_256(public_inputs))?;
+
+			Self::ensure_canonical_rwa_id(&inputs.rwa_id)?;
+			let price = Self::rwa_total_price(&inputs.rwa_id, inputs.quantity, inputs.rate_round)?;
+			ensure!(!RwaEscrows::<T>::contains_key(inputs.tx_id), Error::<T>::DuplicateEscrow);
+			let credential_tag = Self::check_eligibility(
+				&inputs.rwa_id,
+				&inputs.credential,
+				credential_proof,
+				&inputs.nullifier,
+			)?;
+
+			// ML-DSA-44 auth binds every public input field.
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(
+					auth,
+					&Self::auth_message(public_inputs, fee),
+					false,
+				),
+				Error::<T>::MlDsaFailed
+			);
+			// pkd recomputed on-chain from the revealed pk; binds the signing key
+			// to the spent note inside the STARK.
+			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let pkd = Self::compute_pk_digest(&auth[..1312]);
+
+			Self::check_spend(spend_proof, SpendStatementV2 {
+				root: inputs.merkle_root,
+				nullifier: inputs.nullifier,
+				pkd,
+				price_or_amount: price,
+				change_leaf: inputs.change_leaf,
+				change_pkd: inputs.change_pkd,
+				purchase_mode: true,
+			})?;
+
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					ownership_proof,
+					inputs.quantity,
+					&inputs.ownership_commitment,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidOwnershipProof
+			);
+			// The refund note is private: only the buyer knows its opening,
+			// so a refund is unlinkable to this purchase.
+			ensure!(
+				inputs.refund_leaf != inputs.change_leaf && !LeafIndexV2::<T>::contains_key(&inputs.refund_leaf),
+				Error::<T>::DuplicateLeaf
+			);
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					refund_proof,
+					price,
+					&inputs.refund_leaf,
+					&Self::stark_security(),
+				),
+				Error::<T>::ProofVerificationFailed
+			);
+
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+			if let Some(tag) = credential_tag {
+				CredentialTagUsed::<T>::insert(tag, true);
+			}
+			Self::take_rwa_units(&inputs.rwa_id, inputs.quantity);
+
+			// Change leaf is always inserted — zero-value change keeps every
+			// purchase identical in shape (no v == price traffic distinction).
+			let (change_leaf_index, new_root) = Self::v2_insert(inputs.change_leaf)?;
+
+			// The ownership note is held back until the RWA chain confirms.
+			let deadline = frame_system::Pallet::<T>::block_number().saturating_add(T::EscrowPeriod::get());
+			RwaEscrows::<T>::insert(inputs.tx_id, RwaEscrow {
+				rwa_id: inputs.rwa_id,
+				quantity: inputs.quantity,
+				price,
+				nullifier: inputs.nullifier,
+				ownership_commitment: inputs.ownership_commitment,
+				refund_leaf: inputs.refund_leaf,
+				deadline,
+				status: EscrowStatus::Pending,
+			});
+
+			Self::deposit_event(Event::PurchaseV2Authorized {
+				rwa_id: inputs.rwa_id,
+				quantity: inputs.quantity,
+				tx_id: inputs.tx_id,
+				change_leaf: inputs.change_leaf,
+				change_leaf_index,
+				new_root,
+			});
+			Self::deposit_event(Event::EscrowOpened { tx_id: inputs.tx_id, deadline });
+
+			// XCM to the RWA chain. The spend_tag slot carries the nullifier
+			// (already public) as the purchase record key.
+			T::RwaDispatch::send(
+				inputs.rwa_id,
+				inputs.nullifier,
+				inputs.nullifier,
+				price,
+				inputs.tx_id,
+				inputs.ownership_commitment,
+				inputs.quantity,
+			);
+
+			Self::charge_fee(fee, fee_nullifier);
+
+			Ok(())
+		}
+
+		/// Body of `purchase_access_v2`.
+		fn do_purchase_access_v2(
+			auth: &[u8],
+			public_inputs: &[u8],
+			spend_proof: &[u8],
+			fee: &Option<FeeTicket>,
+		) -> DispatchResult {
+			let inputs = SpendPublicV2::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root), Error::<T>::RootNotRecent);
+			let fee_nullifier = Self::check_fee(fee, &blake2_256(public_inputs))?;
+
+			// rwa_id field carries app_id in this lane; one key per spend.
+			let app_id = inputs.rwa_id;
+			ensure!(inputs.quantity == 1, Error::<T>::InvalidQuantity);
+			let cfg = AccessKeyConfigs::<T>::get(app_id)
+				.ok_or(Error::<T>::AccessAppNotConfigured)?;
+			let (price, terms) = cfg.quote(inputs.access_plan).ok_or(Error::<T>::AccessPlanUnavailable)?;
+
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(
+					auth,
+					&Self::auth_message(public_inputs, fee),
+					false,
+				),
+				Error::<T>::MlDsaFailed
+			);
+			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let pkd = Self::compute_pk_digest(&auth[..1312]);
+
+			Self::check_spend(spend_proof, SpendStatementV2 {
+				root: inputs.merkle_root,
+				nullifier: inputs.nullifier,
+				pkd,
+				price_or_amount: price,
+				change_leaf: inputs.change_leaf,
+				change_pkd: inputs.change_pkd,
+				purchase_mode: true,
+			})?;
+
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+
+			// Change leaf always inserted (uniform transaction shape).
+			let (change_leaf_index, new_root) = Self::v2_insert(inputs.change_leaf)?;
+
+			// Transfer price from pool to app payment account.
+			let payment_dest: T::AccountId =
+				Decode::decode(&mut &cfg.payment_account[..])
+					.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+			let pool = T::PoolAccount::get();
+			let amount: BalanceOf<T> = (price as u128).unique_saturated_into();
+			T::Currency::transfer(&pool, &payment_dest, amount, ExistenceRequirement::AllowDeath)?;
+
+			// ownership_commitment field carries access_key_commitment
+			let access_key_commitment = inputs.ownership_commitment;
+			let tx_id = inputs.tx_id;
+
+			Self::deposit_event(Event::AccessPurchaseV2Authorized {
+				app_id,
+				tx_id,
+				change_leaf: inputs.change_leaf,
+				change_leaf_index,
+				new_root,
+			});
+
+			// XCM to AuthGate (para 2003)
+			T::AccessDispatch::send(app_id, inputs.nullifier, tx_id, access_key_commitment, terms);
+
+			Self::charge_fee(fee, fee_nullifier);
+
+			Ok(())
+		}
+
+		/// Body of `withdraw_v2`.
+		fn do_withdraw_v2(
+			auth: &[u8],
+			public_inputs: &[u8],
+			spend_proof: &[u8],
+			fee: &Option<FeeTicket>,
+		) -> DispatchResult {
+			let inputs = WithdrawPublicV2::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root), Error::<T>::RootNotRecent);
+			let fee_nullifier = Self::check_fee(fee, &blake2_256(public_inputs))?;
+
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(
+					auth,
+					&Self::auth_message(public_inputs, fee),
+					true,
+				),
+				Error::<T>::MlDsaFailed
+			);
+			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let pkd = Self::compute_pk_digest(&auth[..1312]);
+
+			let zero_change = T::ProofVerifier::v2_zero_change_leaf();
+			Self::check_spend(spend_proof, SpendStatementV2 {
+				root: inputs.merkle_root,
+				nullifier: inputs.nullifier,
+				pkd,
+				price_or_amount: inputs.amount,
+				change_leaf: zero_change,
+				change_pkd: [0u8; 32],
+				purchase_mode: false,
+			})?;
+
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+
+			let dest: T::AccountId = Decode::decode(&mut &inputs.destination[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+			let pool = T::PoolAccount::get();
+			let value: BalanceOf<T> = (inputs.amount as u128).unique_saturated_into();
+			T::Currency::transfer(&pool, &dest, value, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::WithdrawV2Completed { tx_id: inputs.tx_id });
+			Self::charge_fee(fee, fee_nullifier);
+			Ok(())
+		}
+
+		/// Verify a spend's SpendAir statement against its proof.
+		fn check_spend(proof: &[u8], statement: SpendStatementV2) -> Result<(), Error<T>> {
+			ensure!(
+				T::ProofVerifier::verify_spend_v2(
+					proof,
+					&statement.root,
+					&statement.nullifier,
+					&statement.pkd,
+					statement.price_or_amount,
+					&statement.change_leaf,
+					&statement.change_pkd,
+					statement.purchase_mode,
+					&Self::stark_security(),
+				),
+				Error::<T>::ProofVerificationFailed
+			);
+			Ok(())
+		}
+
+		/// Paymaster: consume the fee credit checked by `check_fee` and split
+		/// `BaseFee` between burn and the block author.
+		fn charge_fee(fee: &Option<FeeTicket>, fee_nullifier: Option<[u8; 32]>) {
+			let (Some(_), Some(nf)) = (fee, fee_nullifier) else { return };
+			FeeNullifierUsed::<T>::insert(nf, true);
+
+			let payer = T::FeePayer::get();
+			let amount = T::BaseFee::get();
+			let burn_amount = T::FeeBurn::get() * amount;
+			let author_amount = amount.saturating_sub(burn_amount);
+			// Dropping the withdrawn imbalance reduces total issuance.
+			if T::Currency::withdraw(&payer, burn_amount, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive).is_ok() {
+				Self::deposit_event(Event::FeeBurned { amount: burn_amount });
+			}
+			let digest = frame_system::Pallet::<T>::digest();
+			if let Some(author) = T::FindAuthor::find_author(digest.logs.iter().filter_map(|d| d.as_pre_runtime())) {
+				match T::Currency::transfer(&payer, &author, author_amount, ExistenceRequirement::KeepAlive) {
+					Ok(_) => Self::deposit_event(Event::FeePaid { author, amount: author_amount }),
+					Err(_) => Self::deposit_event(Event::FeePayoutFailed { author, amount: author_amount }),
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		// Pays::No: the deposit itself IS the "payment" (1 NULLA → pool). Length fees
+		// on a 24 KB STARK-proof extrinsic would otherwise require the depositor to hold
+		// extra balance above the deposit amount, creating an unnecessary friction point
+		// for a privacy protocol.  Pool validation still checks nonce & signature.
+		#[pallet::weight((Weight::zero(), Pays::No))]
+		pub fn deposit_public(
+			origin: OriginFor<T>,
+			commitment: [u8; 32],
+			// Phase 8: wire format: [1:u8][range_len:u32LE][range_bytes][v:u64LE][commit_len:u32LE][commit_bytes]
+			range_proof: BoundedVec<u8, ConstU32<65536>>,
+			// Phase 8: Poseidon rp_commitment = Poseidon(v, blinding); blinding is PRIVATE.
+			rp_commitment: [u8; 32],
+			// Phase 6: spend_tag = BLAKE3("nulla_spend_tag_v1" || deposit_pk_bytes)
+			// Registered on-chain to enable unlinkable purchase later.
+			spend_tag: [u8; 32],
+			hints_blob: BoundedVec<u8, ConstU32<4096>>,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			// Phase 9 (spec 127): legacy v1 deposits are DISABLED.
+			// v1 notes remain spendable via purchase_rwa / withdraw_private as an
+			// exit ramp; new deposits must use deposit_v2 (zk-membership pool).
+			let _ = (commitment, range_proof, rp_commitment, spend_tag, hints_blob);
+			Err(Error::<T>::LegacyDisabled.into())
+		}
+
+		#[pallet::weight(Weight::zero())]
+		pub fn purchase_rwa(
+			origin: OriginFor<T>,
+			proof: Vec<u8>,
+			public_inputs: Vec<u8>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let inputs = RwaPurchaseInputs::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
+			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
+
+			let price = RwaPrices::<T>::get(inputs.rwa_id);
+			ensure!(price > 0, Error::<T>::RwaPriceNotSet);
+
+			// Phase 8.1: resolve change_rp_commitment.
+			// When a change note is present the client must have previously registered its
+			// rp_commitment via deposit_public. When there is no change note (v == price)
+			// pass [0u8;32] — the circuit treats it as the zero-change case.
+			let change_rp_commitment: [u8; 32] = if let Some(t_change) = inputs.change_spend_tag {
+				SpendTagRpCommitments::<T>::get(t_change)
+					.ok_or(Error::<T>::SpendTagNotFound)?
+			} else {
+				[0u8; 32]
+			};
+
+			// Phase 8 + 8.1: verify PurchaseAir STARK.
+			// Proves: Poseidon(iv,v,blinding)=rp_commitment AND v>=price
+			//     AND Poseidon(iv,v-price,c_blinding)=change_rp_commitment
+			let stored_rp = SpendTagRpCommitments::<T>::get(inputs.spend_tag)
+				.ok_or(Error::<T>::SpendTagNotFound)?;
+			ensure!(
+				T::ProofVerifier::verify_purchase_proof(&inputs.purchase_proof, &stored_rp, &change_rp_commitment, price),
+				Error::<T>::ProofVerificationFailed
+			);
+
+			ensure!(T::ProofVerifier::verify_purchase(&proof, &public_inputs), Error::<T>::MlDsaFailed);
+
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
+
+			// Insert change note if client created one (v > price case).
 			// v123 fix: emit ChangeNoteCreated unconditionally when change fields are
 			// present. If do_insert_note returns None the commitment was already
 			// inserted during the mandatory change-note pre-deposit (deposit_public
 			// registers SpendTagRpCommitments). The note is already spendable in the
 			// tree — use CurrentRoot as the canonical root for the event.
 			if let (Some(c_change), Some(t_change)) = (inputs.change_commitment, inputs.change_spend_tag) {
-				// Record the change note's rp_commitment so it can be used as input_rp
-				// if this change note is later spent via another purchase_rwa call.
+				// Record the change note's rp_commitment so it can be used as input_rp
+				// if this change note is later spent via another purchase_rwa call.
+				SpendTagChangeRpCommitments::<T>::insert(t_change, change_rp_commitment);
+				let mut leaves_vec: alloc::vec::Vec<[u8; 32]> = Leaves::<T>::get().into_inner();
+				let new_root = match Self::do_insert_note(c_change, t_change, &mut leaves_vec) {
+					Some(root) => {
+						Self::commit_merkle(leaves_vec, root);
+						root
+					}
+					None => {
+						// Commitment already in tree from pre-deposit — note is spendable.
+						CurrentRoot::<T>::get()
+					}
+				};
+				Self::deposit_event(Event::ChangeNoteCreated {
+					change_commitment: c_change,
+					change_spend_tag: t_change,
+					new_merkle_root: new_root,
+				});
+			}
+
+			Self::deposit_event(Event::RwaPurchaseAuthorized {
+				rwa_id: inputs.rwa_id,
+				tx_id: inputs.tx_id,
+			});
+
+			T::RwaDispatch::send(
+				inputs.rwa_id,
+				// destination is NOT forwarded — covered by proof binding only.
+				// Buyer proves ownership on RWA chain via ownership_blinding.
+				inputs.nullifier,
+				inputs.spend_tag,
+				price,
+				inputs.tx_id,
+				inputs.ownership_commitment,
+				// v1 purchases are always for the whole asset.
+				1,
+			);
+
+			Ok(())
+		}
+
+		/// Withdraw a private note back to public balance.
+		///
+		/// The caller proves ownership of the spend_tag via ML-DSA-44 signature
+		/// with domain \"nulla_withdrawal_v1\".  The verifier also checks:
+		///   BLAKE3(\"nulla_spend_tag_v1\" || pk_from_proof) == inputs.spend_tag
+		///
+		/// On success the pool transfers the note value to `inputs.destination`
+		/// and marks the spend_tag as used.
+		#[pallet::weight(Weight::zero())]
+		pub fn withdraw_private(
+			origin: OriginFor<T>,
+			proof: Vec<u8>,
+			public_inputs: Vec<u8>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let inputs = WithdrawInputs::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
+			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
+
+			ensure!(T::ProofVerifier::verify_withdrawal(&proof, &public_inputs), Error::<T>::MlDsaFailed);
+
+			// Phase 7: verify commitment opening — proves note_value is the value inside the stored commitment.
+			let stored_commitment = SpendTagCommitments::<T>::get(inputs.spend_tag)
+				.ok_or(Error::<T>::SpendTagNotFound)?;
+			ensure!(
+				T::ProofVerifier::verify_commitment(inputs.note_value, inputs.note_blinding, stored_commitment),
+				Error::<T>::ProofVerificationFailed
+			);
+			let amount = inputs.note_value;
+			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
+
+			// Decode destination AccountId
+			ensure!(inputs.destination.len() == 32, Error::<T>::ProofVerificationFailed);
+			let mut dest_bytes = [0u8; 32];
+			dest_bytes.copy_from_slice(&inputs.destination[..32]);
+			let dest: T::AccountId = Decode::decode(&mut &dest_bytes[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			let pool = T::PoolAccount::get();
+			let value: BalanceOf<T> = (amount as u128).unique_saturated_into();
+			T::Currency::transfer(&pool, &dest, value, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::WithdrawCompleted {
+				spend_tag: inputs.spend_tag,
+				tx_id: inputs.tx_id,
+			});
+			Ok(())
+		}
+
+		/// Private peer-to-peer RWA ownership transfer — LEGACY (v1), disabled.
+		///
+		/// The v1 path looked ownership up by `old_tx_id`, publicly linking every
+		/// resale to the original purchase. Resales now go through `relist_v2`,
+		/// which spends an ownership note from the ownership tree in zero knowledge.
+		/// v1 ownership records remain redeemable on the RWA chain by tx_id.
+		#[pallet::weight(Weight::zero())]
+		pub fn relist_private(
+			origin: OriginFor<T>,
+			inputs: RelistInputs,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let _ = inputs;
+			Err(Error::<T>::LegacyDisabled.into())
+		}
+
+		/// Sudo: set the price per unit (in planck) for an RWA.
+		/// Setting price to 0 disables the RWA for purchase.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_rwa_price(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			price: u64,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			RwaPrices::<T>::insert(rwa_id, price);
+			Self::deposit_event(Event::RwaPriceSet { rwa_id, price });
+			Ok(())
+		}
+
+		/// Set or update the config for a Web2 access-key app (sudo only).
+		///
+		/// `app_id`: 32-byte identifier matching the one registered on AuthGate.
+		/// `price`: value in planck the note must cover. Zero de-lists the app.
+		/// `payment_account`: raw 32-byte AccountId on DistProofHub that receives
+		///                     the note value when a purchase succeeds.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_access_config(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			price: u64,
+			payment_account: [u8; 32],
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			AccessKeyConfigs::<T>::mutate(app_id, |cfg| match cfg {
+				Some(cfg) => {
+					cfg.price = price;
+					cfg.payment_account = payment_account;
+				}
+				None => *cfg = Some(super::AppConfig { price, payment_account, plans: BoundedVec::default() }),
+			});
+			Self::deposit_event(Event::AccessConfigSet { app_id, price });
+			Ok(())
+		}
+
+		/// Purchase access to a Web2 app privately — v1 ML-DSA-44 + STARK path.
+		///
+		/// Identical to `purchase_rwa` except:
+		/// - `app_id` replaces `rwa_id` in `public_inputs` (first 32 bytes of
+		///   `RwaPurchaseInputs::spend_tag` are unchanged; `rwa_id` field carries
+		///   `app_id` semantically)
+		/// - Uses `AccessKeyConfigs` for price lookup instead of `RwaPrices`
+		/// - `ownership_commitment` carries `access_key_commitment`
+		///   = BLAKE3("nulla_access_key_v1" || app_id || blinding)
+		/// - Transfers price from pool → app `payment_account` on this chain
+		/// - Sends XCM to AuthGate (para 2003) via `T::AccessDispatch`
+		///
+		/// All RWA state is completely unchanged.
+		/// UNSIGNED. Privacy preserved via ML-DSA-44 + STARK proof binding.
+		#[pallet::weight(Weight::zero())]
+		pub fn purchase_access(
+			origin: OriginFor<T>,
+			proof: Vec<u8>,
+			public_inputs: Vec<u8>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let inputs = RwaPurchaseInputs::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
+			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
+
+			// rwa_id field carries app_id in this lane
+			let app_id = inputs.rwa_id;
+			let cfg = AccessKeyConfigs::<T>::get(app_id)
+				.ok_or(Error::<T>::AccessAppNotConfigured)?;
+			ensure!(cfg.price > 0, Error::<T>::AccessAppNotConfigured);
+
+			let change_rp_commitment: [u8; 32] = if let Some(t_change) = inputs.change_spend_tag {
+				SpendTagRpCommitments::<T>::get(t_change)
+					.ok_or(Error::<T>::SpendTagNotFound)?
+			} else {
+				[0u8; 32]
+			};
+
+			let stored_rp = SpendTagRpCommitments::<T>::get(inputs.spend_tag)
+				.ok_or(Error::<T>::SpendTagNotFound)?;
+			ensure!(
+				T::ProofVerifier::verify_purchase_proof(&inputs.purchase_proof, &stored_rp, &change_rp_commitment, cfg.price),
+				Error::<T>::ProofVerificationFailed
+			);
+			ensure!(T::ProofVerifier::verify_purchase(&proof, &public_inputs), Error::<T>::MlDsaFailed);
+
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
+
+			// Handle change note if present.
+			if let (Some(c_change), Some(t_change)) = (inputs.change_commitment, inputs.change_spend_tag) {
 				SpendTagChangeRpCommitments::<T>::insert(t_change, change_rp_commitment);
 				let mut leaves_vec: alloc::vec::Vec<[u8; 32]> = Leaves::<T>::get().into_inner();
 				let new_root = match Self::do_insert_note(c_change, t_change, &mut leaves_vec) {
-					Some(root) => {
-						Self::commit_merkle(leaves_vec, root);
-						root
-					}
-					None => {
-						// Commitment already in tree from pre-deposit — note is spendable.
-						CurrentRoot::<T>::get()
-					}
+					Some(root) => { Self::commit_merkle(leaves_vec, root); root }
+					None => CurrentRoot::<T>::get(),
 				};
 				Self::deposit_event(Event::ChangeNoteCreated {
 					change_commitment: c_change,
@@ -904,561 +2885,1059 @@ pub type RecentRoots<T: Config> =
 				});
 			}
 
-			Self::deposit_event(Event::RwaPurchaseAuthorized {
-				rwa_id: inputs.rwa_id,
-				tx_id: inputs.tx_id,
-			});
+			// Transfer price from pool to app payment account on this chain.
+			let payment_dest: T::AccountId =
+				Decode::decode(&mut &cfg.payment_account[..])
+					.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+			let pool = T::PoolAccount::get();
+			let amount: BalanceOf<T> = (cfg.price as u128).unique_saturated_into();
+			T::Currency::transfer(&pool, &payment_dest, amount, ExistenceRequirement::AllowDeath)?;
+
+			// access_key_commitment lives in the ownership_commitment field
+			let access_key_commitment = inputs.ownership_commitment;
+			let tx_id = inputs.tx_id;
+
+			Self::deposit_event(Event::AccessPurchaseAuthorized { app_id, tx_id });
+
+			// XCM to AuthGate (para 2003): nullifier = spend_tag (public nullifier for this lane)
+			T::AccessDispatch::send(app_id, inputs.nullifier, tx_id, access_key_commitment, None);
+
+			Ok(())
+		}
+
+		/// Purchase access to a Web2 app privately — v2 zk-membership path.
+		///
+		/// Identical to `purchase_rwa_v2` except:
+		/// - Uses `AccessKeyConfigs` for price lookup instead of `RwaPrices`:
+		///   `access_plan` picks a subscription plan, `None` a plan-less key
+		/// - `rwa_id` field in `SpendPublicV2` carries `app_id`
+		/// - `ownership_commitment` carries `access_key_commitment`
+		/// - Transfers price from pool → app `payment_account` on this chain
+		/// - Sends XCM to AuthGate (para 2003) via `T::AccessDispatch`
+		///
+		/// UNSIGNED. TRUE unlinkability via zk-membership STARK.
+		#[pallet::weight(Weight::zero())]
+		pub fn purchase_access_v2(
+			origin: OriginFor<T>,
+			auth: Vec<u8>,
+			public_inputs: Vec<u8>,
+			spend_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Self::do_purchase_access_v2(&auth, &public_inputs, &spend_proof, &fee)
+		}
+		///
+		/// SIGNED — the depositor pays `amount` into the pool (the cash-in boundary
+		/// is inherently public, as in Zcash t→z). Reveals ONLY the note leaf:
+		/// no spend_tag, no rp_commitment, no pk digest.
+		///
+		/// `deposit_proof` is a DepositV2Air STARK proving
+		/// leaf = NoteHash(amount, b, pkd) for private (b, pkd) — so the hidden
+		/// note value always equals the paid amount.
+		#[pallet::weight((Weight::zero(), Pays::No))]
+		pub fn deposit_v2(
+			origin: OriginFor<T>,
+			leaf: [u8; 32],
+			amount: u64,
+			deposit_proof: BoundedVec<u8, ConstU32<65536>>,
+			hints_blob: BoundedVec<u8, ConstU32<4096>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(&deposit_proof, amount, &leaf, &Self::stark_security()),
+				Error::<T>::ProofVerificationFailed
+			);
+			let pool = T::PoolAccount::get();
+			let value: BalanceOf<T> = (amount as u128).unique_saturated_into();
+			T::Currency::transfer(&who, &pool, value, ExistenceRequirement::KeepAlive)?;
+			let (leaf_index, new_root) = Self::v2_insert(leaf)?;
+			Self::deposit_event(Event::DepositV2Accepted { leaf, leaf_index, new_root, hints_blob });
+			Ok(())
+		}
+
+		/// Phase 9: purchase an RWA via zk-membership proof — TRUE unlinkability.
+		///
+		/// UNSIGNED. Reveals only: a recent merkle root, the nullifier, the
+		/// ML-DSA-44 pk (fresh per note), and action fields. The spent leaf is
+		/// never named — the SpendAir STARK proves membership in zero knowledge.
+		///
+		/// `auth` = ml_dsa_pk (1312B) ‖ ml_dsa_sig (2420B) over
+		/// BLAKE3("nulla_spend_auth_v2" ‖ public_inputs).
+		///
+		/// The spend pays `RwaPrices[rwa_id] × quantity`. `ownership_proof` and
+		/// `refund_proof` are DepositV2Air proofs that `ownership_commitment`
+		/// holds `quantity` units and `refund_leaf` holds the price, each for
+		/// private (b, pkd).
+		///
+		/// The purchase is held in escrow (`RwaEscrows`) for `EscrowPeriod`
+		/// blocks. The ownership note is inserted into the ownership tree of
+		/// `rwa_id` when the RWA chain confirms fulfilment (`xcm_confirm_escrow`);
+		/// on cancellation or timeout `refund_leaf` is inserted into the v2 tree.
+		#[pallet::weight(Weight::zero())]
+		pub fn purchase_rwa_v2(
+			origin: OriginFor<T>,
+			auth: Vec<u8>,
+			public_inputs: Vec<u8>,
+			spend_proof: Vec<u8>,
+			ownership_proof: Vec<u8>,
+			refund_proof: Vec<u8>,
+			credential_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Self::do_purchase_rwa_v2(
+				&auth,
+				&public_inputs,
+				&spend_proof,
+				&ownership_proof,
+				&refund_proof,
+				&credential_proof,
+				&fee,
+			)
+		}
+
+		/// Phase 9: withdraw a v2 note back to public balance via zk-membership proof.
+		///
+		/// UNSIGNED. The STARK enforces v == amount exactly (no change output).
+		/// `auth` = ml_dsa_pk ‖ sig over BLAKE3("nulla_withdraw_auth_v2" ‖ public_inputs).
+		#[pallet::weight(Weight::zero())]
+		pub fn withdraw_v2(
+			origin: OriginFor<T>,
+			auth: Vec<u8>,
+			public_inputs: Vec<u8>,
+			spend_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Self::do_withdraw_v2(&auth, &public_inputs, &spend_proof, &fee)
+		}
 
-			T::RwaDispatch::send(
-				inputs.rwa_id,
-				// destination is NOT forwarded — covered by proof binding only.
-				// Buyer proves ownership on RWA chain via ownership_blinding.
-				inputs.nullifier,
-				inputs.spend_tag,
-				price,
-				inputs.tx_id,
-				inputs.ownership_commitment,
+		/// Paymaster: prepay one base fee as a fee credit.
+		///
+		/// SIGNED. Transfers `BaseFee` to the Paymaster account and appends
+		/// `leaf` = NoteHash(BaseFee, b, 0) to the fee credit tree.
+		/// `deposit_proof` is a DepositV2Air STARK that the leaf holds exactly
+		/// `BaseFee`. An unsigned v2 spend consumes the credit by presenting a
+		/// `FeeTicket` — a membership proof under a fee root with a nullifier
+		/// derived from `b` — so the spend reveals neither the leaf nor the
+		/// depositing account. The base fee is then split between burn and the
+		/// block author.
+		#[pallet::weight(Weight::zero())]
+		pub fn deposit_fee(
+			origin: OriginFor<T>,
+			leaf: [u8; 32],
+			deposit_proof: BoundedVec<u8, ConstU32<65536>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let base_fee = T::BaseFee::get();
+			let amount: u64 = base_fee.try_into().map_err(|_| Error::<T>::FeeAmountOverflow)?;
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(&deposit_proof, amount, &leaf, &Self::stark_security()),
+				Error::<T>::FeeProofInvalid
 			);
-
+			ensure!(!FeeLeafIndex::<T>::contains_key(leaf), Error::<T>::DuplicateFeeCredit);
+			let payer = T::FeePayer::get();
+			T::Currency::transfer(&who, &payer, base_fee, ExistenceRequirement::KeepAlive)?;
+			let (leaf_index, new_root) = Self::fee_insert(leaf)?;
+			Self::deposit_event(Event::FeeDeposited { leaf, leaf_index, new_root });
 			Ok(())
 		}
 
-		/// Withdraw a private note back to public balance.
+		/// Resell an RWA ownership note privately — v2 zk-membership path.
 		///
-		/// The caller proves ownership of the spend_tag via ML-DSA-44 signature
-		/// with domain \"nulla_withdrawal_v1\".  The verifier also checks:
-		///   BLAKE3(\"nulla_spend_tag_v1\" || pk_from_proof) == inputs.spend_tag
+		/// UNSIGNED. Atomic swap of `quantity` ownership units for a value note:
+		///   - seller: SpendAir (purchase mode, units − change == quantity) over
+		///     the ownership tree of `rwa_id`, revealing only `ownership_nullifier`;
+		///     the remaining units return in `ownership_change_leaf`;
+		///   - buyer: SpendAir (purchase mode, v − cv == price) over the v2 tree;
+		///   - `new_ownership_proof` / `payment_proof`: DepositV2Air proofs that
+		///     the buyer's new ownership leaf holds `quantity` units and the
+		///     seller's payment leaf holds `price`.
 		///
-		/// On success the pool transfers the note value to `inputs.destination`
-		/// and marks the spend_tag as used.
+		/// Both `seller_auth` and `buyer_auth` sign `public_inputs ‖ SCALE(fee)`
+		/// under the spend domain. Nothing is sent to the RWA chain — the asset
+		/// stays locked there until an ownership note is redeemed.
 		#[pallet::weight(Weight::zero())]
-		pub fn withdraw_private(
+		pub fn relist_v2(
 			origin: OriginFor<T>,
-			proof: Vec<u8>,
+			seller_auth: Vec<u8>,
+			buyer_auth: Vec<u8>,
 			public_inputs: Vec<u8>,
+			ownership_proof: Vec<u8>,
+			spend_proof: Vec<u8>,
+			new_ownership_proof: Vec<u8>,
+			payment_proof: Vec<u8>,
+			credential_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
-			let inputs = WithdrawInputs::decode(&mut &public_inputs[..])
+			let inputs = RelistPublicV2::decode(&mut &public_inputs[..])
 				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 
-			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
-			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
-
-			ensure!(T::ProofVerifier::verify_withdrawal(&proof, &public_inputs), Error::<T>::MlDsaFailed);
+			Self::ensure_canonical_rwa_id(&inputs.rwa_id)?;
+			ensure!(inputs.quantity > 0, Error::<T>::InvalidQuantity);
+			ensure!(
+				!OwnershipNullifierUsed::<T>::get(inputs.ownership_nullifier),
+				Error::<T>::OwnershipAlreadyUsed
+			);
+			ensure!(
+				Self::ownership_root_anchored(&inputs.rwa_id, &inputs.ownership_root),
+				Error::<T>::OwnershipRootNotRecent
+			);
+			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
+			ensure!(Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root), Error::<T>::RootNotRecent);
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
+			let credential_tag = Self::check_eligibility(
+				&inputs.rwa_id,
+				&inputs.credential,
+				&credential_proof,
+				&inputs.nullifier,
+			)?;
+
+			let message = Self::auth_message(&public_inputs, &fee);
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(&seller_auth, &message, false),
+				Error::<T>::MlDsaFailed
+			);
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(&buyer_auth, &message, false),
+				Error::<T>::MlDsaFailed
+			);
+			ensure!(seller_auth.len() >= 1312 && buyer_auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let seller_pkd = Self::compute_pk_digest(&seller_auth[..1312]);
+			let buyer_pkd = Self::compute_pk_digest(&buyer_auth[..1312]);
 
-			// Phase 7: verify commitment opening — proves note_value is the value inside the stored commitment.
-			let stored_commitment = SpendTagCommitments::<T>::get(inputs.spend_tag)
-				.ok_or(Error::<T>::SpendTagNotFound)?;
 			ensure!(
-				T::ProofVerifier::verify_commitment(inputs.note_value, inputs.note_blinding, stored_commitment),
+				T::ProofVerifier::verify_spend_v2(
+					&ownership_proof,
+					&inputs.ownership_root,
+					&inputs.ownership_nullifier,
+					&seller_pkd,
+					inputs.quantity,
+					&inputs.ownership_change_leaf,
+					&inputs.ownership_change_pkd,
+					true,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidOwnershipProof
+			);
+			ensure!(
+				T::ProofVerifier::verify_spend_v2(
+					&spend_proof,
+					&inputs.merkle_root,
+					&inputs.nullifier,
+					&buyer_pkd,
+					inputs.price,
+					&inputs.change_leaf,
+					&inputs.change_pkd,
+					true,
+					&Self::stark_security(),
+				),
+				Error::<T>::ProofVerificationFailed
+			);
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					&new_ownership_proof,
+					inputs.quantity,
+					&inputs.new_ownership_leaf,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidOwnershipProof
+			);
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					&payment_proof,
+					inputs.price,
+					&inputs.payment_leaf,
+					&Self::stark_security(),
+				),
 				Error::<T>::ProofVerificationFailed
 			);
-			let amount = inputs.note_value;
-			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
 
-			// Decode destination AccountId
-			ensure!(inputs.destination.len() == 32, Error::<T>::ProofVerificationFailed);
-			let mut dest_bytes = [0u8; 32];
-			dest_bytes.copy_from_slice(&inputs.destination[..32]);
-			let dest: T::AccountId = Decode::decode(&mut &dest_bytes[..])
-				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+			OwnershipNullifierUsed::<T>::insert(inputs.ownership_nullifier, true);
+			Self::ownership_spent_insert(&inputs.ownership_nullifier);
+			NullifierUsed::<T>::insert(inputs.nullifier, true);
+			if let Some(tag) = credential_tag {
+				CredentialTagUsed::<T>::insert(tag, true);
+			}
 
-			let pool = T::PoolAccount::get();
-			let value: BalanceOf<T> = (amount as u128).unique_saturated_into();
-			T::Currency::transfer(&pool, &dest, value, ExistenceRequirement::AllowDeath)?;
+			Self::ownership_insert(inputs.rwa_id, inputs.ownership_change_leaf)?;
+			Self::ownership_insert(inputs.rwa_id, inputs.new_ownership_leaf)?;
+			// The buyer's note value is split into the seller's payment leaf and
+			// the buyer's change leaf; the pool balance is unchanged.
+			let (payment_leaf_index, _) = Self::v2_insert(inputs.payment_leaf)?;
+			let (change_leaf_index, new_root) = Self::v2_insert(inputs.change_leaf)?;
 
-			Self::deposit_event(Event::WithdrawCompleted {
-				spend_tag: inputs.spend_tag,
+			Self::deposit_event(Event::RelistV2Completed {
+				rwa_id: inputs.rwa_id,
+				quantity: inputs.quantity,
 				tx_id: inputs.tx_id,
+				payment_leaf: inputs.payment_leaf,
+				payment_leaf_index,
+				change_leaf: inputs.change_leaf,
+				change_leaf_index,
+				new_root,
 			});
+
+			Self::charge_fee(&fee, fee_nullifier);
+
 			Ok(())
 		}
 
-		/// Private peer-to-peer RWA ownership transfer (resell without revealing identity).
-		///
-		/// The reseller proves ownership via BLAKE3 preimage of the stored
-		/// ownership_commitment.  No ML-DSA-44 signature required \u2014 knowledge of
-		/// `ownership_blinding` IS the proof of ownership.
+		/// Redeem an RWA ownership note for the physical asset — v2 path.
 		///
-		/// On success:
-		///   - New buyer's spend_tag consumed, payment note created for reseller
-		///   - Optional change note created if buyer note > price
-		///   - XCM sent to RWA chain to update the ownership record
-		///
-		/// Only the final redemption (redeem_rwa_ownership on RWA chain) is public.
+		/// UNSIGNED. SpendAir in purchase mode (units − change == quantity) over
+		/// the ownership tree of `rwa_id`; the remaining units are re-inserted as
+		/// `ownership_change_leaf`, so partial redemptions keep the rest shielded.
+		/// `auth` signs `public_inputs ‖ SCALE(fee)` under the withdraw domain.
+		/// On success an XCM records the redemption on the RWA chain keyed by
+		/// `ownership_nullifier`, naming only `claimant` and `quantity`.
 		#[pallet::weight(Weight::zero())]
-		pub fn relist_private(
+		pub fn redeem_ownership_v2(
 			origin: OriginFor<T>,
-			inputs: RelistInputs,
+			auth: Vec<u8>,
+			public_inputs: Vec<u8>,
+			ownership_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
-			// 1. Verify ownership via BLAKE3 preimage
-			let stored_oc = OwnershipCommitments::<T>::get(inputs.old_tx_id)
-				.ok_or(Error::<T>::OwnershipNotFound)?;
-			ensure!(!OwnershipUsed::<T>::get(inputs.old_tx_id), Error::<T>::OwnershipAlreadyUsed);
+			let inputs = RedeemPublicV2::decode(&mut &public_inputs[..])
+				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 
-			// BLAKE3("nulla_rwa_ownership_v1" || rwa_id || blinding) == stored_oc
+			Self::ensure_canonical_rwa_id(&inputs.rwa_id)?;
+			ensure!(inputs.quantity > 0, Error::<T>::InvalidQuantity);
 			ensure!(
-				Self::compute_ownership_commitment(inputs.rwa_id, inputs.ownership_blinding) == stored_oc,
-				Error::<T>::InvalidOwnershipProof
+				!OwnershipNullifierUsed::<T>::get(inputs.ownership_nullifier),
+				Error::<T>::OwnershipAlreadyUsed
 			);
-
-			// 2. Verify new buyer has sufficient funds via commitment opening
-			ensure!(SpendTagCommitments::<T>::contains_key(inputs.new_buyer_spend_tag), Error::<T>::SpendTagNotFound);
-			ensure!(!SpendTagUsed::<T>::get(inputs.new_buyer_spend_tag), Error::<T>::SpendTagAlreadyUsed);
-			// Phase 7: verify buyer commitment opening — proves buyer_value is inside stored commitment.
-			let buyer_stored_commitment = SpendTagCommitments::<T>::get(inputs.new_buyer_spend_tag)
-				.ok_or(Error::<T>::SpendTagNotFound)?;
 			ensure!(
-				T::ProofVerifier::verify_commitment(inputs.buyer_value, inputs.buyer_blinding, buyer_stored_commitment),
-				Error::<T>::ProofVerificationFailed
+				Self::ownership_root_anchored(&inputs.rwa_id, &inputs.ownership_root),
+				Error::<T>::OwnershipRootNotRecent
 			);
-			let buyer_note_value = inputs.buyer_value;
-			ensure!(buyer_note_value >= inputs.price, Error::<T>::BuyerInsufficientFunds);
-
-			let change_amount = buyer_note_value - inputs.price;
-			if change_amount > 0 {
-				ensure!(
-					inputs.change_commitment.is_some()
-						&& inputs.change_spend_tag.is_some()
-						&& inputs.change_value.is_some()
-						&& inputs.change_blinding.is_some(),
-					Error::<T>::MissingChangeOutput
-				);
-				// Phase 7: verify change commitment opening and conservation.
-				let c_v = inputs.change_value.unwrap();
-				let c_b = inputs.change_blinding.unwrap();
-				let c_c = inputs.change_commitment.unwrap();
-				ensure!(c_v == change_amount, Error::<T>::ProofVerificationFailed);
-				ensure!(
-					T::ProofVerifier::verify_commitment(c_v, c_b, c_c),
-					Error::<T>::ProofVerificationFailed
-				);
-			}
-			// Phase 7: verify payment commitment opening — proves payment_commitment = BLAKE3(domain||price||payment_blinding).
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
+
 			ensure!(
-				T::ProofVerifier::verify_commitment(inputs.price, inputs.payment_blinding, inputs.payment_commitment),
-				Error::<T>::ProofVerificationFailed
+				T::ProofVerifier::verify_spend_auth_v2(
+					&auth,
+					&Self::auth_message(&public_inputs, &fee),
+					true,
+				),
+				Error::<T>::MlDsaFailed
 			);
+			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let pkd = Self::compute_pk_digest(&auth[..1312]);
 
-			// 3. Mark ownership and buyer spend_tag as consumed
-			OwnershipUsed::<T>::insert(inputs.old_tx_id, true);
-			SpendTagUsed::<T>::insert(inputs.new_buyer_spend_tag, true);
-
-			// 4. Insert reseller payment note into Merkle tree
-			let mut leaves_vec: alloc::vec::Vec<[u8; 32]> = Leaves::<T>::get().into_inner();
-			let payment_root = Self::do_insert_note(
-				inputs.payment_commitment,
-				inputs.payment_spend_tag,
-				&mut leaves_vec,
-			).ok_or(Error::<T>::ProofVerificationFailed)?;
-			let final_root;
-
-			// 5. Insert change note if needed
-			if change_amount > 0 {
-				let c_change = inputs.change_commitment.unwrap();
-				let t_change = inputs.change_spend_tag.unwrap();
-				if let Some(change_root) = Self::do_insert_note(c_change, t_change, &mut leaves_vec) {
-					Self::commit_merkle(leaves_vec, change_root);
-					Self::deposit_event(Event::ChangeNoteCreated {
-						change_commitment: c_change,
-						change_spend_tag: t_change,
-						new_merkle_root: change_root,
-					});
-					final_root = change_root;
-				} else {
-					Self::commit_merkle(leaves_vec, payment_root);
-					final_root = payment_root;
-				}
-			} else {
-				Self::commit_merkle(leaves_vec, payment_root);
-				final_root = payment_root;
-			}
+			ensure!(
+				T::ProofVerifier::verify_spend_v2(
+					&ownership_proof,
+					&inputs.ownership_root,
+					&inputs.ownership_nullifier,
+					&pkd,
+					inputs.quantity,
+					&inputs.ownership_change_leaf,
+					&inputs.ownership_change_pkd,
+					true,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidOwnershipProof
+			);
 
-			// 6. Register new ownership commitment for the new buyer
-			OwnershipCommitments::<T>::insert(inputs.new_tx_id, inputs.new_ownership_commitment);
+			OwnershipNullifierUsed::<T>::insert(inputs.ownership_nullifier, true);
+			Self::ownership_spent_insert(&inputs.ownership_nullifier);
+			Self::ownership_insert(inputs.rwa_id, inputs.ownership_change_leaf)?;
 
-			Self::deposit_event(Event::PaymentNoteCreated {
-				payment_commitment: inputs.payment_commitment,
-				payment_spend_tag: inputs.payment_spend_tag,
-				new_merkle_root: final_root,
-			});
-			Self::deposit_event(Event::RelistInitiated {
-				old_tx_id: inputs.old_tx_id,
-				new_tx_id: inputs.new_tx_id,
+			Self::deposit_event(Event::OwnershipRedeemedV2 {
 				rwa_id: inputs.rwa_id,
+				quantity: inputs.quantity,
+				tx_id: inputs.tx_id,
 			});
 
-			// 7. XCM to RWA chain: transfer ownership record
-			T::RwaDispatch::send_transfer(
-				inputs.old_tx_id,
-				inputs.new_ownership_commitment,
-				inputs.new_tx_id,
+			T::RwaDispatch::send_redemption(
+				inputs.rwa_id,
+				inputs.ownership_nullifier,
+				inputs.claimant,
+				inputs.tx_id,
+				inputs.quantity,
 			);
 
+			Self::charge_fee(&fee, fee_nullifier);
+
 			Ok(())
 		}
 
-		/// Sudo: set the price (in planck) for an RWA.
-		/// Setting price to 0 disables the RWA for purchase.
+		/// Sudo: set the units of an RWA still for sale on this lane.
+		/// `None` stops tracking supply here; v2 purchases then rely on the
+		/// RWA chain alone.
 		#[pallet::weight(Weight::zero())]
-		pub fn set_rwa_price(
+		pub fn set_rwa_units(
 			origin: OriginFor<T>,
 			rwa_id: [u8; 32],
-			price: u64,
+			units: Option<u64>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			RwaPrices::<T>::insert(rwa_id, price);
-			Self::deposit_event(Event::RwaPriceSet { rwa_id, price });
+			RwaUnits::<T>::set(rwa_id, units);
+			Self::deposit_event(Event::RwaUnitsSet { rwa_id, units });
 			Ok(())
 		}
 
-		/// Set or update the config for a Web2 access-key app (sudo only).
+		/// Sudo: open a sealed-bid auction for `quantity` units of `rwa_id`.
 		///
-		/// `app_id`: 32-byte identifier matching the one registered on AuthGate.
-		/// `price`: value in planck the note must cover. Zero de-lists the app.
-		/// `payment_account`: raw 32-byte AccountId on DistProofHub that receives
-		///                     the note value when a purchase succeeds.
+		/// Bids are accepted for `bidding_period` blocks, then revealed for
+		/// `reveal_period` blocks; anyone may call `settle_auction` afterwards.
+		/// When the lane tracks `RwaUnits`, the auctioned units are taken out of
+		/// fixed-price supply and returned if the auction ends without a sale.
 		#[pallet::weight(Weight::zero())]
-		pub fn set_access_config(
+		pub fn create_auction(
 			origin: OriginFor<T>,
-			app_id: [u8; 32],
-			price: u64,
-			payment_account: [u8; 32],
+			rwa_id: [u8; 32],
+			quantity: u64,
+			reserve_price: u64,
+			bidding_period: BlockNumberFor<T>,
+			reveal_period: BlockNumberFor<T>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			let cfg = super::AppConfig { price, payment_account };
-			AccessKeyConfigs::<T>::insert(app_id, cfg);
-			Self::deposit_event(Event::AccessConfigSet { app_id, price });
+			Self::ensure_canonical_rwa_id(&rwa_id)?;
+			ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+			ensure!(!RwaEligibility::<T>::contains_key(rwa_id), Error::<T>::EligibilityRestricted);
+			ensure!(
+				!bidding_period.is_zero() && !reveal_period.is_zero(),
+				Error::<T>::InvalidAuctionPeriod
+			);
+			if let Some(units) = RwaUnits::<T>::get(rwa_id) {
+				ensure!(quantity <= units, Error::<T>::InsufficientUnits);
+			}
+			Self::take_rwa_units(&rwa_id, quantity);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let bid_end = now.saturating_add(bidding_period);
+			let reveal_end = bid_end.saturating_add(reveal_period);
+			let auction_id = NextAuctionId::<T>::get();
+			NextAuctionId::<T>::put(auction_id.saturating_add(1));
+			Auctions::<T>::insert(auction_id, Auction {
+				rwa_id,
+				quantity,
+				reserve_price,
+				bid_end,
+				reveal_end,
+				bid_count: 0,
+				best_bid: None,
+				status: AuctionStatus::Open,
+			});
+
+			Self::deposit_event(Event::AuctionCreated {
+				auction_id,
+				rwa_id,
+				quantity,
+				reserve_price,
+				bid_end,
+				reveal_end,
+			});
 			Ok(())
 		}
 
-		/// Purchase access to a Web2 app privately — v1 ML-DSA-44 + STARK path.
-		///
-		/// Identical to `purchase_rwa` except:
-		/// - `app_id` replaces `rwa_id` in `public_inputs` (first 32 bytes of
-		///   `RwaPurchaseInputs::spend_tag` are unchanged; `rwa_id` field carries
-		///   `app_id` semantically)
-		/// - Uses `AccessKeyConfigs` for price lookup instead of `RwaPrices`
-		/// - `ownership_commitment` carries `access_key_commitment`
-		///   = BLAKE3("nulla_access_key_v1" || app_id || blinding)
-		/// - Transfers price from pool → app `payment_account` on this chain
-		/// - Sends XCM to AuthGate (para 2003) via `T::AccessDispatch`
+		/// Place a sealed bid by locking a v2 note.
 		///
-		/// All RWA state is completely unchanged.
-		/// UNSIGNED. Privacy preserved via ML-DSA-44 + STARK proof binding.
+		/// UNSIGNED. SpendAir in purchase mode with `price_or_amount = 0` moves
+		/// the note's whole value into `lock_leaf`, which is inserted into the
+		/// auction's lock tree. Neither the bidder nor the bid is revealed —
+		/// only the bid commitment. `auth` signs `public_inputs ‖ SCALE(fee)`
+		/// under the spend domain. Refused while `RwaEligibility` restricts
+		/// the asset.
 		#[pallet::weight(Weight::zero())]
-		pub fn purchase_access(
+		pub fn place_bid_v2(
 			origin: OriginFor<T>,
-			proof: Vec<u8>,
+			auth: Vec<u8>,
 			public_inputs: Vec<u8>,
+			spend_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
-			let inputs = RwaPurchaseInputs::decode(&mut &public_inputs[..])
+			let inputs = AuctionBidPublicV2::decode(&mut &public_inputs[..])
 				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 
+			let mut auction = Auctions::<T>::get(inputs.auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				auction.status == AuctionStatus::Open && now <= auction.bid_end,
+				Error::<T>::BiddingClosed
+			);
+			ensure!(!RwaEligibility::<T>::contains_key(auction.rwa_id), Error::<T>::EligibilityRestricted);
+			ensure!(auction.bid_count < MAX_AUCTION_BIDS, Error::<T>::TooManyBids);
 			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
-			ensure!(SpendTagCommitments::<T>::contains_key(inputs.spend_tag), Error::<T>::SpendTagNotFound);
-			ensure!(!SpendTagUsed::<T>::get(inputs.spend_tag), Error::<T>::SpendTagAlreadyUsed);
-
-			// rwa_id field carries app_id in this lane
-			let app_id = inputs.rwa_id;
-			let cfg = AccessKeyConfigs::<T>::get(app_id)
-				.ok_or(Error::<T>::AccessAppNotConfigured)?;
-			ensure!(cfg.price > 0, Error::<T>::AccessAppNotConfigured);
+			ensure!(Self::v2_root_anchored(inputs.tree_epoch, &inputs.merkle_root), Error::<T>::RootNotRecent);
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
 
-			let change_rp_commitment: [u8; 32] = if let Some(t_change) = inputs.change_spend_tag {
-				SpendTagRpCommitments::<T>::get(t_change)
-					.ok_or(Error::<T>::SpendTagNotFound)?
-			} else {
-				[0u8; 32]
-			};
+			ensure!(
+				T::ProofVerifier::verify_spend_auth_v2(
+					&auth,
+					&Self::auth_message(&public_inputs, &fee),
+					false,
+				),
+				Error::<T>::MlDsaFailed
+			);
+			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
+			let pkd = Self::compute_pk_digest(&auth[..1312]);
 
-			let stored_rp = SpendTagRpCommitments::<T>::get(inputs.spend_tag)
-				.ok_or(Error::<T>::SpendTagNotFound)?;
 			ensure!(
-				T::ProofVerifier::verify_purchase_proof(&inputs.purchase_proof, &stored_rp, &change_rp_commitment, cfg.price),
+				T::ProofVerifier::verify_spend_v2(
+					&spend_proof,
+					&inputs.merkle_root,
+					&inputs.nullifier,
+					&pkd,
+					0,
+					&inputs.lock_leaf,
+					&inputs.lock_pkd,
+					true,
+					&Self::stark_security(),
+				),
 				Error::<T>::ProofVerificationFailed
 			);
-			ensure!(T::ProofVerifier::verify_purchase(&proof, &public_inputs), Error::<T>::MlDsaFailed);
 
 			NullifierUsed::<T>::insert(inputs.nullifier, true);
-			SpendTagUsed::<T>::insert(inputs.spend_tag, true);
-
-			// Handle change note if present.
-			if let (Some(c_change), Some(t_change)) = (inputs.change_commitment, inputs.change_spend_tag) {
-				SpendTagChangeRpCommitments::<T>::insert(t_change, change_rp_commitment);
-				let mut leaves_vec: alloc::vec::Vec<[u8; 32]> = Leaves::<T>::get().into_inner();
-				let new_root = match Self::do_insert_note(c_change, t_change, &mut leaves_vec) {
-					Some(root) => { Self::commit_merkle(leaves_vec, root); root }
-					None => CurrentRoot::<T>::get(),
-				};
-				Self::deposit_event(Event::ChangeNoteCreated {
-					change_commitment: c_change,
-					change_spend_tag: t_change,
-					new_merkle_root: new_root,
-				});
-			}
-
-			// Transfer price from pool to app payment account on this chain.
-			let payment_dest: T::AccountId =
-				Decode::decode(&mut &cfg.payment_account[..])
-					.map_err(|_| Error::<T>::ProofVerificationFailed)?;
-			let pool = T::PoolAccount::get();
-			let amount: BalanceOf<T> = (cfg.price as u128).unique_saturated_into();
-			T::Currency::transfer(&pool, &payment_dest, amount, ExistenceRequirement::AllowDeath)?;
-
-			// access_key_commitment lives in the ownership_commitment field
-			let access_key_commitment = inputs.ownership_commitment;
-			let tx_id = inputs.tx_id;
-
-			Self::deposit_event(Event::AccessPurchaseAuthorized { app_id, tx_id });
-
-			// XCM to AuthGate (para 2003): nullifier = spend_tag (public nullifier for this lane)
-			T::AccessDispatch::send(app_id, inputs.nullifier, tx_id, access_key_commitment);
+			let (lock_leaf_index, new_lock_root) =
+				Self::auction_lock_insert(inputs.auction_id, inputs.lock_leaf)?;
+
+			let bid_index = auction.bid_count;
+			AuctionBids::<T>::insert(inputs.auction_id, bid_index, AuctionBid {
+				bid_commitment: inputs.bid_commitment,
+				lock_pkd: inputs.lock_pkd,
+				state: BidState::Sealed,
+			});
+			auction.bid_count = bid_index.saturating_add(1);
+			Auctions::<T>::insert(inputs.auction_id, auction);
+
+			Self::deposit_event(Event::AuctionBidPlaced {
+				auction_id: inputs.auction_id,
+				bid_index,
+				lock_leaf: inputs.lock_leaf,
+				lock_leaf_index,
+				new_lock_root,
+			});
 
-			Ok(())
-		}
+			Self::charge_fee(&fee, fee_nullifier);
 
-		/// Purchase access to a Web2 app privately — v2 zk-membership path.
-		///
-		/// Identical to `purchase_rwa_v2` except:
-		/// - Uses `AccessKeyConfigs` for price lookup instead of `RwaPrices`
-		/// - `rwa_id` field in `SpendPublicV2` carries `app_id`
-		/// - `ownership_commitment` carries `access_key_commitment`
-		/// - Transfers price from pool → app `payment_account` on this chain
-		/// - Sends XCM to AuthGate (para 2003) via `T::AccessDispatch`
+			Ok(())
+		}
+
+		/// Reveal a sealed bid during the reveal window.
 		///
-		/// UNSIGNED. TRUE unlinkability via zk-membership STARK.
+		/// UNSIGNED. `bid` and `salt` must open the bid commitment. The lock
+		/// note is spent over the auction's lock tree in purchase mode
+		/// (lock value − change == bid), so the lock is shown to cover the bid
+		/// without revealing its value; the excess returns to the v2 tree as
+		/// `change_leaf`. DepositV2Air proofs show `refund_leaf` holds `bid` and
+		/// `ownership_leaf` holds the auctioned quantity — `settle_auction`
+		/// inserts one or the other. `auth` must hash to the bid's `lock_pkd`.
 		#[pallet::weight(Weight::zero())]
-		pub fn purchase_access_v2(
+		pub fn reveal_bid_v2(
 			origin: OriginFor<T>,
 			auth: Vec<u8>,
 			public_inputs: Vec<u8>,
-			spend_proof: Vec<u8>,
+			lock_proof: Vec<u8>,
+			refund_proof: Vec<u8>,
+			ownership_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
-			let inputs = SpendPublicV2::decode(&mut &public_inputs[..])
+			let inputs = AuctionRevealPublicV2::decode(&mut &public_inputs[..])
 				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 
-			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
-			ensure!(Self::v2_root_anchored(&inputs.merkle_root), Error::<T>::RootNotRecent);
-
-			// rwa_id field carries app_id in this lane
-			let app_id = inputs.rwa_id;
-			let cfg = AccessKeyConfigs::<T>::get(app_id)
-				.ok_or(Error::<T>::AccessAppNotConfigured)?;
-			ensure!(cfg.price > 0, Error::<T>::AccessAppNotConfigured);
+			let mut auction = Auctions::<T>::get(inputs.auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				auction.status == AuctionStatus::Open && now > auction.bid_end && now <= auction.reveal_end,
+				Error::<T>::NotInRevealWindow
+			);
+			let mut bid = AuctionBids::<T>::get(inputs.auction_id, inputs.bid_index)
+				.ok_or(Error::<T>::BidNotFound)?;
+			ensure!(bid.state == BidState::Sealed, Error::<T>::BidNotSealed);
+			ensure!(
+				Self::auction_bid_commitment(inputs.auction_id, inputs.bid, &inputs.salt) == bid.bid_commitment,
+				Error::<T>::BidCommitmentMismatch
+			);
+			ensure!(inputs.bid > 0 && inputs.bid >= auction.reserve_price, Error::<T>::BidBelowReserve);
+			ensure!(
+				!AuctionLockNullifierUsed::<T>::get(inputs.lock_nullifier),
+				Error::<T>::LockAlreadySpent
+			);
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
 
 			ensure!(
-				T::ProofVerifier::verify_spend_auth_v2(&auth, &public_inputs, false),
+				T::ProofVerifier::verify_spend_auth_v2(
+					&auth,
+					&Self::auth_message(&public_inputs, &fee),
+					false,
+				),
 				Error::<T>::MlDsaFailed
 			);
 			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
 			let pkd = Self::compute_pk_digest(&auth[..1312]);
+			ensure!(pkd == bid.lock_pkd, Error::<T>::LockKeyMismatch);
 
 			ensure!(
 				T::ProofVerifier::verify_spend_v2(
-					&spend_proof,
-					&inputs.merkle_root,
-					&inputs.nullifier,
+					&lock_proof,
+					&AuctionLockRoot::<T>::get(inputs.auction_id),
+					&inputs.lock_nullifier,
 					&pkd,
-					cfg.price,
+					inputs.bid,
 					&inputs.change_leaf,
 					&inputs.change_pkd,
 					true,
+					&Self::stark_security(),
 				),
 				Error::<T>::ProofVerificationFailed
 			);
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					&refund_proof,
+					inputs.bid,
+					&inputs.refund_leaf,
+					&Self::stark_security(),
+				),
+				Error::<T>::ProofVerificationFailed
+			);
+			ensure!(
+				T::ProofVerifier::verify_deposit_v2(
+					&ownership_proof,
+					auction.quantity,
+					&inputs.ownership_leaf,
+					&Self::stark_security(),
+				),
+				Error::<T>::InvalidOwnershipProof
+			);
 
-			NullifierUsed::<T>::insert(inputs.nullifier, true);
-
-			// Change leaf always inserted (uniform transaction shape).
-			let (_change_leaf_index, _new_root) = Self::v2_insert(inputs.change_leaf)?;
-
-			// Transfer price from pool to app payment account.
-			let payment_dest: T::AccountId =
-				Decode::decode(&mut &cfg.payment_account[..])
-					.map_err(|_| Error::<T>::ProofVerificationFailed)?;
-			let pool = T::PoolAccount::get();
-			let amount: BalanceOf<T> = (cfg.price as u128).unique_saturated_into();
-			T::Currency::transfer(&pool, &payment_dest, amount, ExistenceRequirement::AllowDeath)?;
+			AuctionLockNullifierUsed::<T>::insert(inputs.lock_nullifier, true);
+			let (change_leaf_index, new_root) = Self::v2_insert(inputs.change_leaf)?;
 
-			// ownership_commitment field carries access_key_commitment
-			let access_key_commitment = inputs.ownership_commitment;
-			let tx_id = inputs.tx_id;
+			bid.state = BidState::Revealed {
+				amount: inputs.bid,
+				refund_leaf: inputs.refund_leaf,
+				ownership_leaf: inputs.ownership_leaf,
+			};
+			AuctionBids::<T>::insert(inputs.auction_id, inputs.bid_index, bid);
+			if auction.best_bid.map_or(true, |(_, best)| inputs.bid > best) {
+				auction.best_bid = Some((inputs.bid_index, inputs.bid));
+				Auctions::<T>::insert(inputs.auction_id, auction);
+			}
 
-			Self::deposit_event(Event::AccessPurchaseV2Authorized { app_id, tx_id });
+			Self::deposit_event(Event::AuctionBidRevealed {
+				auction_id: inputs.auction_id,
+				bid_index: inputs.bid_index,
+				bid: inputs.bid,
+				change_leaf: inputs.change_leaf,
+				change_leaf_index,
+				new_root,
+			});
 
-			// XCM to AuthGate (para 2003)
-			T::AccessDispatch::send(app_id, inputs.nullifier, tx_id, access_key_commitment);
+			Self::charge_fee(&fee, fee_nullifier);
 
 			Ok(())
 		}
+
+		/// Settle an auction after its reveal window.
 		///
-		/// SIGNED — the depositor pays `amount` into the pool (the cash-in boundary
-		/// is inherently public, as in Zcash t→z). Reveals ONLY the note leaf:
-		/// no spend_tag, no rp_commitment, no pk digest.
+		/// SIGNED (anyone). The highest revealed bid wins: its ownership leaf is
+		/// inserted into the ownership tree of `rwa_id` and the result is sent
+		/// to the RWA chain; the winning bid stays in the pool as payment.
+		/// Every other revealed bid is refunded by inserting its refund leaf
+		/// into the v2 tree. Unrevealed bids are returned via `release_bid_v2`.
 		///
-		/// `deposit_proof` is a DepositV2Air STARK proving
-		/// leaf = NoteHash(amount, b, pkd) for private (b, pkd) — so the hidden
-		/// note value always equals the paid amount.
-		#[pallet::weight((Weight::zero(), Pays::No))]
-		pub fn deposit_v2(
-			origin: OriginFor<T>,
-			leaf: [u8; 32],
-			amount: u64,
-			deposit_proof: BoundedVec<u8, ConstU32<65536>>,
-			hints_blob: BoundedVec<u8, ConstU32<4096>>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(
-				T::ProofVerifier::verify_deposit_v2(&deposit_proof, amount, &leaf),
-				Error::<T>::ProofVerificationFailed
-			);
-			let pool = T::PoolAccount::get();
-			let value: BalanceOf<T> = (amount as u128).unique_saturated_into();
-			T::Currency::transfer(&who, &pool, value, ExistenceRequirement::KeepAlive)?;
-			let (leaf_index, new_root) = Self::v2_insert(leaf)?;
-			Self::deposit_event(Event::DepositV2Accepted { leaf, leaf_index, new_root, hints_blob });
+		/// Sealed bids carry no credential, so an asset restricted by
+		/// `RwaEligibility` since the auction opened is not sold: every
+		/// revealed bid is refunded and the auction ends without a sale.
+		#[pallet::weight(Weight::zero())]
+		pub fn settle_auction(origin: OriginFor<T>, auction_id: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut auction = Auctions::<T>::get(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.status == AuctionStatus::Open, Error::<T>::AuctionAlreadySettled);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > auction.reveal_end, Error::<T>::AuctionNotClosed);
+
+			// Leaves already in their tree are skipped rather than failing the
+			// whole settlement: the note they open is spendable there already,
+			// and a copied leaf only forfeits the copier's own bid.
+			let winner = if RwaEligibility::<T>::contains_key(auction.rwa_id) {
+				None
+			} else {
+				auction.best_bid.map(|(index, _)| index)
+			};
+			let mut winning_leaf = None;
+			for (bid_index, bid) in AuctionBids::<T>::iter_prefix(auction_id) {
+				let BidState::Revealed { refund_leaf, ownership_leaf, .. } = bid.state else { continue };
+				if Some(bid_index) == winner {
+					winning_leaf = Some(ownership_leaf);
+					continue;
+				}
+				if LeafIndexV2::<T>::contains_key(&refund_leaf) { continue; }
+				let (refund_leaf_index, new_root) = Self::v2_insert(refund_leaf)?;
+				Self::deposit_event(Event::AuctionRefundInserted {
+					auction_id,
+					bid_index,
+					refund_leaf,
+					refund_leaf_index,
+					new_root,
+				});
+			}
+
+			match (auction.best_bid, winning_leaf) {
+				(Some((_, price)), Some(ownership_leaf)) => {
+					if !OwnershipLeafIndex::<T>::contains_key(&auction.rwa_id, &ownership_leaf) {
+						Self::ownership_insert(auction.rwa_id, ownership_leaf)?;
+					}
+					auction.status = AuctionStatus::Settled;
+					Self::deposit_event(Event::AuctionSettled {
+						auction_id,
+						rwa_id: auction.rwa_id,
+						quantity: auction.quantity,
+						price,
+					});
+					T::RwaDispatch::send_auction_result(
+						auction.rwa_id,
+						auction_id,
+						price,
+						auction.quantity,
+						ownership_leaf,
+					);
+				}
+				_ => {
+					auction.status = AuctionStatus::NoSale;
+					RwaUnits::<T>::mutate(auction.rwa_id, |units| {
+						if let Some(u) = units.as_mut() { *u = u.saturating_add(auction.quantity); }
+					});
+					Self::deposit_event(Event::AuctionNoSale { auction_id });
+				}
+			}
+			Auctions::<T>::insert(auction_id, auction);
 			Ok(())
 		}
 
-		/// Phase 9: purchase an RWA via zk-membership proof — TRUE unlinkability.
-		///
-		/// UNSIGNED. Reveals only: a recent merkle root, the nullifier, the
-		/// ML-DSA-44 pk (fresh per note), and action fields. The spent leaf is
-		/// never named — the SpendAir STARK proves membership in zero knowledge.
+		/// Return the lock note of a bid that was never revealed.
 		///
-		/// `auth` = ml_dsa_pk (1312B) ‖ ml_dsa_sig (2420B) over
-		/// BLAKE3("nulla_spend_auth_v2" ‖ public_inputs).
+		/// UNSIGNED. Accepted once the reveal window has ended. SpendAir in
+		/// purchase mode with `price_or_amount = 0` over the auction's lock tree
+		/// returns the full lock value to the v2 tree as `change_leaf`.
+		/// `auth` must hash to the bid's `lock_pkd`.
 		#[pallet::weight(Weight::zero())]
-		pub fn purchase_rwa_v2(
+		pub fn release_bid_v2(
 			origin: OriginFor<T>,
 			auth: Vec<u8>,
 			public_inputs: Vec<u8>,
-			spend_proof: Vec<u8>,
+			lock_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
-			let inputs = SpendPublicV2::decode(&mut &public_inputs[..])
+			let inputs = AuctionReleasePublicV2::decode(&mut &public_inputs[..])
 				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 
-			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
-			ensure!(Self::v2_root_anchored(&inputs.merkle_root), Error::<T>::RootNotRecent);
-
-			let price = RwaPrices::<T>::get(inputs.rwa_id);
-			ensure!(price > 0, Error::<T>::RwaPriceNotSet);
+			let auction = Auctions::<T>::get(inputs.auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > auction.reveal_end, Error::<T>::AuctionNotClosed);
+			let mut bid = AuctionBids::<T>::get(inputs.auction_id, inputs.bid_index)
+				.ok_or(Error::<T>::BidNotFound)?;
+			ensure!(bid.state == BidState::Sealed, Error::<T>::BidNotSealed);
+			ensure!(
+				!AuctionLockNullifierUsed::<T>::get(inputs.lock_nullifier),
+				Error::<T>::LockAlreadySpent
+			);
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
 
-			// ML-DSA-44 auth binds every public input field.
 			ensure!(
-				T::ProofVerifier::verify_spend_auth_v2(&auth, &public_inputs, false),
+				T::ProofVerifier::verify_spend_auth_v2(
+					&auth,
+					&Self::auth_message(&public_inputs, &fee),
+					false,
+				),
 				Error::<T>::MlDsaFailed
 			);
-			// pkd recomputed on-chain from the revealed pk; binds the signing key
-			// to the spent note inside the STARK.
 			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
 			let pkd = Self::compute_pk_digest(&auth[..1312]);
+			ensure!(pkd == bid.lock_pkd, Error::<T>::LockKeyMismatch);
 
 			ensure!(
 				T::ProofVerifier::verify_spend_v2(
-					&spend_proof,
-					&inputs.merkle_root,
-					&inputs.nullifier,
+					&lock_proof,
+					&AuctionLockRoot::<T>::get(inputs.auction_id),
+					&inputs.lock_nullifier,
 					&pkd,
-					price,
+					0,
 					&inputs.change_leaf,
 					&inputs.change_pkd,
 					true,
+					&Self::stark_security(),
 				),
 				Error::<T>::ProofVerificationFailed
 			);
 
-			NullifierUsed::<T>::insert(inputs.nullifier, true);
-
-			// Change leaf is always inserted — zero-value change keeps every
-			// purchase identical in shape (no v == price traffic distinction).
+			AuctionLockNullifierUsed::<T>::insert(inputs.lock_nullifier, true);
 			let (change_leaf_index, new_root) = Self::v2_insert(inputs.change_leaf)?;
+			bid.state = BidState::Released;
+			AuctionBids::<T>::insert(inputs.auction_id, inputs.bid_index, bid);
 
-			// Ownership record for later redemption on the RWA chain.
-			OwnershipCommitments::<T>::insert(inputs.tx_id, inputs.ownership_commitment);
-
-			Self::deposit_event(Event::PurchaseV2Authorized {
-				rwa_id: inputs.rwa_id,
-				tx_id: inputs.tx_id,
+			Self::deposit_event(Event::AuctionBidReleased {
+				auction_id: inputs.auction_id,
+				bid_index: inputs.bid_index,
 				change_leaf: inputs.change_leaf,
 				change_leaf_index,
 				new_root,
 			});
 
-			// XCM to the RWA chain. The spend_tag slot carries the nullifier
-			// (already public) as the purchase record key.
-			T::RwaDispatch::send(
-				inputs.rwa_id,
-				inputs.nullifier,
-				inputs.nullifier,
-				price,
-				inputs.tx_id,
-				inputs.ownership_commitment,
+			Self::charge_fee(&fee, fee_nullifier);
+
+			Ok(())
+		}
+
+		/// Confirm fulfilment of an escrowed v2 purchase.
+		///
+		/// MUST only be called via XCM `Transact` by the RWA chain sovereign
+		/// account (`fulfil_proofhub_purchase` on the marketplace). Inserts the
+		/// buyer's ownership note into the ownership tree of `rwa_id`. Accepted
+		/// after the deadline as long as no refund has been taken.
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_confirm_escrow(origin: OriginFor<T>, tx_id: [u8; 16]) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(caller == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+
+			let mut escrow = RwaEscrows::<T>::get(tx_id).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(escrow.status == EscrowStatus::Pending, Error::<T>::EscrowNotPending);
+
+			// A leaf already in the tree opens the same note; nothing to insert.
+			if !OwnershipLeafIndex::<T>::contains_key(&escrow.rwa_id, &escrow.ownership_commitment) {
+				Self::ownership_insert(escrow.rwa_id, escrow.ownership_commitment)?;
+			}
+			escrow.status = EscrowStatus::Fulfilled;
+			let rwa_id = escrow.rwa_id;
+			RwaEscrows::<T>::insert(tx_id, escrow);
+
+			Self::deposit_event(Event::EscrowFulfilled { tx_id, rwa_id });
+			Ok(())
+		}
+
+		/// Cancel an escrowed v2 purchase and refund the buyer.
+		///
+		/// MUST only be called via XCM `Transact` by the RWA chain sovereign
+		/// account (`cancel_proofhub_purchase` on the marketplace).
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_cancel_escrow(origin: OriginFor<T>, tx_id: [u8; 16]) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(caller == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+
+			let escrow = RwaEscrows::<T>::get(tx_id).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(escrow.status == EscrowStatus::Pending, Error::<T>::EscrowNotPending);
+			Self::refund_rwa_escrow(tx_id, escrow)
+		}
+
+		/// Refund an escrowed v2 purchase the RWA chain never confirmed.
+		///
+		/// SIGNED (anyone — typically a relayer, so the buyer stays anonymous).
+		/// Accepted from the escrow deadline on. Inserts the `refund_leaf` the
+		/// buyer committed to at purchase; only the buyer can open it.
+		#[pallet::weight(Weight::zero())]
+		pub fn refund_escrow(origin: OriginFor<T>, tx_id: [u8; 16]) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let escrow = RwaEscrows::<T>::get(tx_id).ok_or(Error::<T>::EscrowNotFound)?;
+			ensure!(escrow.status == EscrowStatus::Pending, Error::<T>::EscrowNotPending);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= escrow.deadline,
+				Error::<T>::EscrowNotExpired
 			);
+			Self::refund_rwa_escrow(tx_id, escrow)
+		}
 
+		/// Sync an RWA's per-unit price from the RWA chain marketplace.
+		///
+		/// MUST only be called via XCM `Transact` by the RWA chain sovereign
+		/// account, which pushes listing creation, price changes, sell-outs and
+		/// cancellations. `price == 0` de-lists the RWA on this lane and clears
+		/// its USD price too; a re-listed RWA is priced in planck until sudo sets
+		/// a USD price again.
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_sync_rwa_price(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			price: u64,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(caller == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+			if price == 0 {
+				RwaPrices::<T>::remove(rwa_id);
+				RwaUsdPrices::<T>::remove(rwa_id);
+			} else {
+				RwaPrices::<T>::insert(rwa_id, price);
+			}
+			Self::deposit_event(Event::RwaPriceSynced { rwa_id, price });
 			Ok(())
 		}
 
-		/// Phase 9: withdraw a v2 note back to public balance via zk-membership proof.
+		/// Sudo: price an RWA per unit in USD cents instead of planck.
 		///
-		/// UNSIGNED. The STARK enforces v == amount exactly (no change output).
-		/// `auth` = ml_dsa_pk ‖ sig over BLAKE3("nulla_withdraw_auth_v2" ‖ public_inputs).
+		/// Spends then name an oracle round (`rate_round`) and pay
+		/// `usd_cents × planck_per_cent(round) × quantity`. Zero returns the RWA
+		/// to planck pricing via `RwaPrices`.
 		#[pallet::weight(Weight::zero())]
-		pub fn withdraw_v2(
+		pub fn set_rwa_usd_price(
 			origin: OriginFor<T>,
-			auth: Vec<u8>,
-			public_inputs: Vec<u8>,
-			spend_proof: Vec<u8>,
+			rwa_id: [u8; 32],
+			usd_cents: u64,
 		) -> DispatchResult {
-			ensure_none(origin)?;
+			ensure_root(origin)?;
+			if usd_cents == 0 {
+				RwaUsdPrices::<T>::remove(rwa_id);
+			} else {
+				RwaUsdPrices::<T>::insert(rwa_id, usd_cents);
+			}
+			Self::deposit_event(Event::RwaUsdPriceSet { rwa_id, usd_cents });
+			Ok(())
+		}
 
-			let inputs = WithdrawPublicV2::decode(&mut &public_inputs[..])
-				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
+		/// Sudo: open a credential registry whose tree `issuer` may append to.
+		#[pallet::weight(Weight::zero())]
+		pub fn create_credential_registry(
+			origin: OriginFor<T>,
+			issuer: <T as frame_system::Config>::AccountId,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let registry_id = NextCredentialRegistryId::<T>::get();
+			NextCredentialRegistryId::<T>::put(registry_id.saturating_add(1));
+			CredentialIssuers::<T>::insert(registry_id, issuer.clone());
+			Self::deposit_event(Event::CredentialRegistryCreated { registry_id, issuer });
+			Ok(())
+		}
 
-			ensure!(!NullifierUsed::<T>::get(inputs.nullifier), Error::<T>::NullifierAlreadyUsed);
-			ensure!(Self::v2_root_anchored(&inputs.merkle_root), Error::<T>::RootNotRecent);
+		/// Issue a credential to an approved buyer (registry issuer only).
+		///
+		/// `leaf` = NoteHash(class, b, 0), computed by the buyer from a secret
+		/// `b` and handed to the issuer after off-chain checks. The issuer
+		/// learns the leaf, never `b`, so it cannot link later presentations.
+		#[pallet::weight(Weight::zero())]
+		pub fn issue_credential(
+			origin: OriginFor<T>,
+			registry_id: u32,
+			leaf: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let issuer = CredentialIssuers::<T>::get(registry_id)
+				.ok_or(Error::<T>::CredentialRegistryNotFound)?;
+			ensure!(who == issuer, Error::<T>::NotCredentialIssuer);
+			let (leaf_index, new_root) = Self::credential_insert(registry_id, leaf)?;
+			Self::deposit_event(Event::CredentialIssued { registry_id, leaf, leaf_index, new_root });
+			Ok(())
+		}
 
-			ensure!(
-				T::ProofVerifier::verify_spend_auth_v2(&auth, &public_inputs, true),
-				Error::<T>::MlDsaFailed
-			);
-			ensure!(auth.len() >= 1312, Error::<T>::MlDsaFailed);
-			let pkd = Self::compute_pk_digest(&auth[..1312]);
+		/// XCM-only: the RWA chain syncs the buyer eligibility an asset's
+		/// issuer set in the registry. `None` opens the asset to all buyers.
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_sync_rwa_eligibility(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			requirement: Option<EligibilityRequirement>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+			match requirement {
+				Some(req) => RwaEligibility::<T>::insert(rwa_id, req),
+				None => RwaEligibility::<T>::remove(rwa_id),
+			}
+			Self::deposit_event(Event::RwaEligibilitySynced { rwa_id, requirement });
+			Ok(())
+		}
 
-			let zero_change = T::ProofVerifier::v2_zero_change_leaf();
-			ensure!(
-				T::ProofVerifier::verify_spend_v2(
-					&spend_proof,
-					&inputs.merkle_root,
-					&inputs.nullifier,
-					&pkd,
-					inputs.amount,
-					&zero_change,
-					&[0u8; 32],
-					false,
-				),
-				Error::<T>::ProofVerificationFailed
-			);
+		/// Replace the subscription plans of a configured access-key app (sudo only).
+		///
+		/// A purchase picks a plan by index in `SpendPublicV2::access_plan`; the plan's
+		/// duration, tier and use limit are relayed to AuthGate with the grant.
+		/// Price a plan at zero to withdraw it while keeping the indices stable.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_access_plans(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			plans: Vec<super::AccessPlan>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let plans: BoundedVec<super::AccessPlan, ConstU32<{ super::MAX_ACCESS_PLANS }>> =
+				plans.try_into().map_err(|_| Error::<T>::TooManyAccessPlans)?;
+			ensure!(plans.iter().all(|p| p.duration > 0), Error::<T>::InvalidAccessPlan);
+			let count = plans.len() as u32;
+			AccessKeyConfigs::<T>::try_mutate(app_id, |cfg| -> DispatchResult {
+				let cfg = cfg.as_mut().ok_or(Error::<T>::AccessAppNotConfigured)?;
+				cfg.plans = plans;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AccessPlansSet { app_id, plans: count });
+			Ok(())
+		}
 
-			NullifierUsed::<T>::insert(inputs.nullifier, true);
+		/// Retire or restore a proof version (sudo only).
+		///
+		/// `air` is the verifier's `envelope::AirId` discriminant. Proofs of a
+		/// retired version fail verification; restoring is allowed so a
+		/// retirement can be undone during a migration window.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_proof_version_retired(
+			origin: OriginFor<T>,
+			air: u8,
+			version: u16,
+			retired: bool,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			if retired {
+				RetiredProofVersions::<T>::insert((air, version), true);
+			} else {
+				RetiredProofVersions::<T>::remove((air, version));
+			}
+			Self::deposit_event(Event::ProofVersionRetired { air, version, retired });
+			Ok(())
+		}
 
-			let dest: T::AccountId = Decode::decode(&mut &inputs.destination[..])
-				.map_err(|_| Error::<T>::ProofVerificationFailed)?;
-			let pool = T::PoolAccount::get();
-			let value: BalanceOf<T> = (inputs.amount as u128).unique_saturated_into();
-			T::Currency::transfer(&pool, &dest, value, ExistenceRequirement::AllowDeath)?;
+		/// Replace the STARK acceptance policy (sudo only).
+		///
+		/// Takes effect for the next proof verified; proofs below the new
+		/// floor or with a disallowed blowup or folding factor are rejected.
+		/// Wallets pick a matching prover preset (100 or 128 bits) ahead of
+		/// a raise.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_stark_security(origin: OriginFor<T>, security: StarkSecurity) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!((1..=128).contains(&security.min_bits), Error::<T>::InvalidStarkSecurity);
+			StarkSecurityPolicy::<T>::put(security);
+			Self::deposit_event(Event::StarkSecuritySet { security });
+			Ok(())
+		}
 
-			Self::deposit_event(Event::WithdrawV2Completed { tx_id: inputs.tx_id });
+		/// Set how many blocks a v2 root stays acceptable after it stops
+		/// being current (sudo only).
+		///
+		/// A raise applies to roots still held; roots already pruned stay
+		/// expired. A cut expires the roots beyond the new window at once.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_root_retention(origin: OriginFor<T>, blocks: BlockNumberFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				!blocks.is_zero() && blocks <= T::MaxRootRetention::get(),
+				Error::<T>::InvalidRootRetention
+			);
+			let old = RootRetention::<T>::get();
+			RootRetention::<T>::put(blocks);
+			// `on_initialize` prunes one block per block at `now - retention - 1`,
+			// so the blocks a cut skips over are pruned here.
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(old);
+			while at < now.saturating_sub(blocks) {
+				Self::prune_root_v2(at);
+				at = at.saturating_add(One::one());
+			}
+			Self::deposit_event(Event::RootRetentionSet { blocks });
 			Ok(())
 		}
 	}
@@ -1466,6 +3945,9 @@ pub type RecentRoots<T: Config> =
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod tests;
+
 pub trait WeightInfo {}
 
 pub mod weights {
@@ -0,0 +1,1650 @@
+use crate as pallet_proofs;
+use crate::{
+	AccessKeyConfigs, AccessPlan, AccessTerms, AuctionBidPublicV2, AuctionBids, AuctionLockRoot,
+	AuctionReleasePublicV2, AuctionRevealPublicV2, AuctionStatus, Auctions, BidState, CredentialPublicV2,
+	EligibilityRequirement, Error, EscrowStatus, Event, FeeLeafIndex, FeeNullifierUsed, FeeTicket, FinalRootsV2,
+	LeafCountV2, LeafIndexV2, NullifierUsed, OwnershipLeafIndex, OwnershipSpentNodes, ProofVerify, RedeemPublicV2,
+	RootHistoryV2, RootRecordedAtV2, RwaEscrows, RwaListing, RwaUnits, SpendPublicV2, SpendStatementV2, StarkSecurity,
+	WithdrawPublicV2,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, FindAuthor, Hooks, StorageVersion, UnfilteredDispatchable},
+	BoundedVec,
+};
+use frame_system::EnsureRoot;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	traits::ValidateUnsigned,
+	BuildStorage, ConsensusEngineId, Perbill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Proofs: pallet_proofs,
+		PriceOracle: pallet_price_oracle,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u128;
+}
+
+/// Stand-in for the STARK / ML-DSA / Pedersen verifier. Every check is a
+/// hash of exactly the statement it covers, so a proof made for one
+/// statement fails against any other:
+/// - fee credit:  `proof == SCALE(root, value, nullifier, context)`;
+/// - spend auth:  `auth = [PK; 1312] ‖ blake2_256(withdraw ‖ message)`;
+/// - DepositV2Air: `proof == SCALE(amount, leaf)`;
+/// - SpendAir:     `proof == SCALE(statement)`;
+/// - credential:  `proof == SCALE(root, rwa_id, class, tag, binding)`.
+pub struct MockVerifier;
+
+impl ProofVerify for MockVerifier {
+	fn verify_commitment(_: u64, _: [u8; 32], _: [u8; 32]) -> bool { false }
+	fn verify_purchase(_: &[u8], _: &[u8]) -> bool { false }
+	fn verify_withdrawal(_: &[u8], _: &[u8]) -> bool { false }
+	fn verify_range_proof(_: &[u8], _: &[u8; 32]) -> bool { false }
+	fn verify_purchase_proof(_: &[u8], _: &[u8; 32], _: &[u8; 32], _: u64) -> bool { false }
+	fn verify_deposit_v2(proof: &[u8], amount: u64, leaf: &[u8; 32], _: &StarkSecurity) -> bool {
+		proof == deposit_proof(amount, leaf).as_slice()
+	}
+	fn verify_spend_v2(
+		proof: &[u8],
+		root: &[u8; 32],
+		nullifier: &[u8; 32],
+		pkd: &[u8; 32],
+		price_or_amount: u64,
+		change_leaf: &[u8; 32],
+		change_pkd: &[u8; 32],
+		purchase_mode: bool,
+		_: &StarkSecurity,
+	) -> bool {
+		proof == spend_proof(&SpendStatementV2 {
+			root: *root,
+			nullifier: *nullifier,
+			pkd: *pkd,
+			price_or_amount,
+			change_leaf: *change_leaf,
+			change_pkd: *change_pkd,
+			purchase_mode,
+		})
+		.as_slice()
+	}
+	fn verify_spend_auth_v2(auth: &[u8], public_inputs: &[u8], withdraw: bool) -> bool {
+		auth == spend_auth(public_inputs, withdraw).as_slice()
+	}
+	fn v2_insert_leaf(
+		mut nodes: [[u8; 32]; 20],
+		leaf_count: u32,
+		leaf: [u8; 32],
+	) -> Option<([[u8; 32]; 20], u32, [u8; 32])> {
+		if leaf_count == u32::MAX { return None; }
+		nodes[0] = blake2_256(&(nodes[0], leaf).encode());
+		let count = leaf_count + 1;
+		Some((nodes, count, blake2_256(&(nodes[0], count).encode())))
+	}
+	fn v2_zero_change_leaf() -> [u8; 32] { [0u8; 32] }
+	fn v2_note_leaf(value: u64, blinding: &[u8; 32], pkd: &[u8; 32]) -> [u8; 32] {
+		blake2_256(&(value, blinding, pkd).encode())
+	}
+	fn v2_spent_zero_subtrees() -> Vec<[u8; 32]> {
+		let mut z = vec![[0u8; 32]];
+		for level in 0..64 { z.push(blake2_256(&(z[level], z[level]).encode())); }
+		z
+	}
+	fn v2_spent_path(key: u64, leaf: &[u8; 32], siblings: &[[u8; 32]; 64]) -> Vec<[u8; 32]> {
+		let mut path = vec![*leaf];
+		for level in 0..64 {
+			let node = path[level];
+			path.push(if (key >> level) & 1 == 0 {
+				blake2_256(&(node, siblings[level]).encode())
+			} else {
+				blake2_256(&(siblings[level], node).encode())
+			});
+		}
+		path
+	}
+	fn verify_credential(
+		proof: &[u8],
+		root: &[u8; 32],
+		rwa_id: &[u8; 32],
+		class: u64,
+		tag: &[u8; 32],
+		binding: &[u8; 32],
+		_: &StarkSecurity,
+	) -> bool {
+		proof == credential_proof(root, rwa_id, class, tag, binding).as_slice()
+	}
+	fn verify_fee_credit(
+		proof: &[u8],
+		root: &[u8; 32],
+		value: u64,
+		nullifier: &[u8; 32],
+		context: &[u8; 32],
+		_: &StarkSecurity,
+	) -> bool {
+		proof == fee_proof(root, value, nullifier, context).as_slice()
+	}
+}
+
+pub struct MockAuthor;
+impl FindAuthor<u64> for MockAuthor {
+	fn find_author<'a, I>(_: I) -> Option<u64>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(AUTHOR)
+	}
+}
+
+impl pallet_price_oracle::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxReporters = ConstU32<4>;
+	type MinReports = ConstU32<1>;
+	type MaxReportAge = ConstU64<100>;
+	type MaxRoundAge = ConstU64<20>;
+	type RoundGracePeriod = ConstU64<3>;
+}
+
+/// Lane `FiatRate` over the mock's oracle, as the runtime wires it.
+pub struct OracleFiatRate;
+impl crate::FiatRate for OracleFiatRate {
+	fn planck_per_cent(round: u32) -> Option<u64> {
+		pallet_price_oracle::Pallet::<Test>::rate(round)
+	}
+}
+
+parameter_types! {
+	pub const Pool: u64 = POOL;
+	pub const Payer: u64 = PAYER;
+	pub const Sovereign: u64 = SOVEREIGN;
+	pub const Burn: Perbill = Perbill::from_percent(50);
+	pub static BaseFee: u128 = 100;
+	pub static AccessSent: Vec<([u8; 32], [u8; 32], [u8; 16], [u8; 32], Option<AccessTerms>)> = vec![];
+}
+
+/// Records the grants a purchase would relay to AuthGate.
+pub struct RecordingAccessDispatch;
+impl crate::AccessKeyDispatch for RecordingAccessDispatch {
+	fn send(
+		app_id: [u8; 32],
+		nullifier: [u8; 32],
+		tx_id: [u8; 16],
+		access_key_commitment: [u8; 32],
+		terms: Option<AccessTerms>,
+	) {
+		AccessSent::mutate(|sent| sent.push((app_id, nullifier, tx_id, access_key_commitment, terms)));
+	}
+}
+
+impl pallet_proofs::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ProofVerifier = MockVerifier;
+	type Currency = Balances;
+	type PoolAccount = Pool;
+	type RwaDispatch = crate::NoopRwaDispatch;
+	type AccessDispatch = RecordingAccessDispatch;
+	type BaseFee = BaseFee;
+	type FeeBurn = Burn;
+	type FeePayer = Payer;
+	type FindAuthor = MockAuthor;
+	type RwaChainSovereign = Sovereign;
+	type EscrowPeriod = ConstU64<10>;
+	type FiatRate = OracleFiatRate;
+	type DefaultRootRetention = ConstU64<5>;
+	type MaxRootRetention = ConstU64<100>;
+}
+
+const ALICE: u64 = 1;
+const MALLORY: u64 = 2;
+const POOL: u64 = 10;
+const PAYER: u64 = 11;
+const AUTHOR: u64 = 12;
+const SOVEREIGN: u64 = 13;
+const REPORTER: u64 = 14;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 10_000), (MALLORY, 10_000), (POOL, 10_000), (PAYER, 1)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_price_oracle::GenesisConfig::<Test> { reporters: vec![REPORTER] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn fee_proof(root: &[u8; 32], value: u64, nullifier: &[u8; 32], context: &[u8; 32]) -> Vec<u8> {
+	(root, value, nullifier, context).encode()
+}
+
+fn deposit_proof(amount: u64, leaf: &[u8; 32]) -> Vec<u8> {
+	(amount, leaf).encode()
+}
+
+fn spend_proof(s: &SpendStatementV2) -> Vec<u8> {
+	(s.root, s.nullifier, s.pkd, s.price_or_amount, s.change_leaf, s.change_pkd, s.purchase_mode).encode()
+}
+
+fn credential_proof(root: &[u8; 32], rwa_id: &[u8; 32], class: u64, tag: &[u8; 32], binding: &[u8; 32]) -> Vec<u8> {
+	(root, rwa_id, class, tag, binding).encode()
+}
+
+fn spend_auth(message: &[u8], withdraw: bool) -> Vec<u8> {
+	let mut auth = vec![PK; 1312];
+	auth.extend_from_slice(&blake2_256(&(withdraw, message).encode()));
+	auth
+}
+
+const PK: u8 = 0x5a;
+
+fn pkd() -> [u8; 32] {
+	let mut h = blake3::Hasher::new();
+	h.update(b"nulla_pk_digest_v2");
+	h.update(&[PK; 1312]);
+	*h.finalize().as_bytes()
+}
+
+/// Fee credit leaf opened by `b`, as a wallet deposits it at the current base fee.
+fn fee_leaf(b: u8) -> [u8; 32] {
+	let amount = u64::try_from(BaseFee::get()).unwrap_or(u64::MAX);
+	MockVerifier::v2_note_leaf(amount, &[b; 32], &[0u8; 32])
+}
+
+/// Stand-in for the fee-scoped nullifier of the credit opened by `b`.
+fn fee_nullifier(b: u8) -> [u8; 32] {
+	blake2_256(&(b"fee", b).encode())
+}
+
+/// Deposits the fee credit opened by `b` from `who`.
+fn deposit_fee(who: u64, b: u8) -> [u8; 32] {
+	let leaf = fee_leaf(b);
+	let amount = u64::try_from(BaseFee::get()).unwrap();
+	let proof = deposit_proof(amount, &leaf).try_into().unwrap();
+	assert_ok!(Proofs::deposit_fee(RuntimeOrigin::signed(who), leaf, proof));
+	leaf
+}
+
+/// A fee ticket spending the credit opened by `b` under the current fee
+/// root, proven for the spend with `public_inputs`.
+fn ticket(b: u8, public_inputs: &[u8]) -> FeeTicket {
+	let fee_root = Proofs::current_fee_root();
+	let fee_nullifier = fee_nullifier(b);
+	let amount = u64::try_from(BaseFee::get()).unwrap_or(u64::MAX);
+	FeeTicket {
+		fee_root,
+		fee_nullifier,
+		proof: fee_proof(&fee_root, amount, &fee_nullifier, &blake2_256(public_inputs)),
+	}
+}
+
+fn contains(haystack: &[u8], needle: &[u8; 32]) -> bool {
+	haystack.windows(32).any(|w| w == needle)
+}
+
+/// SCALE public inputs of a withdrawal of `amount` to `to`, anchored in the
+/// current v2 root.
+fn withdrawal(nullifier: u8, amount: u64, to: u64) -> Vec<u8> {
+	withdrawal_at(Proofs::tree_epoch_v2(), Proofs::current_root_v2(), nullifier, amount, to)
+}
+
+/// As `withdrawal`, anchored in `merkle_root` of tree epoch `tree_epoch`.
+fn withdrawal_at(tree_epoch: u32, merkle_root: [u8; 32], nullifier: u8, amount: u64, to: u64) -> Vec<u8> {
+	let mut destination = [0u8; 32];
+	destination[..8].copy_from_slice(&to.to_le_bytes());
+	WithdrawPublicV2 {
+		tree_epoch,
+		merkle_root,
+		nullifier: [nullifier; 32],
+		amount,
+		destination,
+		tx_id: [nullifier; 16],
+	}
+	.encode()
+}
+
+/// Submits `withdraw_v2` with a valid auth and spend proof for `public_inputs`.
+fn withdraw(public_inputs: Vec<u8>, fee: Option<FeeTicket>) -> frame_support::dispatch::DispatchResult {
+	withdraw_call(public_inputs, fee)
+		.dispatch_bypass_filter(RuntimeOrigin::none())
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+/// `withdraw_v2` with a valid auth and spend proof for `public_inputs`.
+fn withdraw_call(public_inputs: Vec<u8>, fee: Option<FeeTicket>) -> crate::Call<Test> {
+	let inputs = <WithdrawPublicV2 as codec::Decode>::decode(&mut &public_inputs[..]).unwrap();
+	let mut message = public_inputs.clone();
+	if let Some(t) = &fee { t.encode_to(&mut message); }
+	let proof = spend_proof(&SpendStatementV2 {
+		root: inputs.merkle_root,
+		nullifier: inputs.nullifier,
+		pkd: pkd(),
+		price_or_amount: inputs.amount,
+		change_leaf: [0u8; 32],
+		change_pkd: [0u8; 32],
+		purchase_mode: false,
+	});
+	crate::Call::<Test>::withdraw_v2 { auth: spend_auth(&message, true), public_inputs, spend_proof: proof, fee }
+}
+
+#[test]
+fn withdraw_consumes_fee_ticket() {
+	new_test_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		let w = withdrawal(1, 500, ALICE);
+		assert_ok!(withdraw(w.clone(), Some(ticket(7, &w))));
+
+		assert!(FeeNullifierUsed::<Test>::get(fee_nullifier(7)));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 100 + 500);
+		assert_eq!(Balances::free_balance(AUTHOR), 50);
+		System::assert_has_event(Event::FeeBurned { amount: 50 }.into());
+		System::assert_last_event(Event::FeePaid { author: AUTHOR, amount: 50 }.into());
+	});
+}
+
+#[test]
+fn fee_spend_does_not_reveal_deposited_leaf() {
+	new_test_ext().execute_with(|| {
+		let ours = deposit_fee(ALICE, 7);
+		let other = deposit_fee(MALLORY, 8);
+		System::assert_last_event(
+			Event::FeeDeposited { leaf: other, leaf_index: 1, new_root: Proofs::current_fee_root() }.into(),
+		);
+		System::reset_events();
+
+		let w = withdrawal(1, 500, ALICE);
+		let fee = ticket(7, &w);
+		// The ticket names only the shared root, which covers both credits.
+		assert_eq!(fee.fee_root, Proofs::current_fee_root());
+		let call = crate::Call::<Test>::withdraw_v2 {
+			auth: vec![],
+			public_inputs: w.clone(),
+			spend_proof: vec![],
+			fee: Some(fee.clone()),
+		};
+		assert!(!contains(&call.encode(), &ours));
+		assert_ok!(withdraw(w, Some(fee)));
+
+		for record in System::events() {
+			assert!(!contains(&record.event.encode(), &ours));
+		}
+		// Both credits stay in the tree; only the nullifier marks a spend.
+		assert_eq!(FeeLeafIndex::<Test>::get(ours), Some(0));
+		assert_eq!(FeeLeafIndex::<Test>::get(other), Some(1));
+		assert_ne!(fee_nullifier(7), ours);
+	});
+}
+
+#[test]
+fn copied_fee_ticket_fails_in_another_spend() {
+	new_test_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		let victim = withdrawal(1, 500, ALICE);
+		let stolen = ticket(7, &victim);
+
+		// Mallory signs her own withdrawal over the copied ticket.
+		let own = withdrawal(2, 500, MALLORY);
+		assert_noop!(withdraw(own.clone(), Some(stolen.clone())), Error::<Test>::FeeProofInvalid);
+		let call = crate::Call::<Test>::withdraw_v2 {
+			auth: vec![],
+			public_inputs: own,
+			spend_proof: vec![],
+			fee: Some(stolen.clone()),
+		};
+		assert_eq!(
+			Proofs::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// The credit is untouched and still pays for the victim's spend.
+		assert!(!FeeNullifierUsed::<Test>::get(fee_nullifier(7)));
+		assert_ok!(withdraw(victim, Some(stolen)));
+	});
+}
+
+#[test]
+fn fee_ticket_cannot_be_used_twice() {
+	new_test_ext().execute_with(|| {
+		let leaf = deposit_fee(ALICE, 7);
+		let first = withdrawal(1, 500, ALICE);
+		assert_ok!(withdraw(first.clone(), Some(ticket(7, &first))));
+
+		// A fresh proof for a second spend finds the credit consumed.
+		let second = withdrawal(2, 500, ALICE);
+		assert_noop!(
+			withdraw(second.clone(), Some(ticket(7, &second))),
+			Error::<Test>::FeeNullifierAlreadyUsed
+		);
+		let call = crate::Call::<Test>::withdraw_v2 {
+			auth: vec![],
+			public_inputs: second,
+			spend_proof: vec![],
+			fee: Some(ticket(7, &first)),
+		};
+		assert_eq!(
+			Proofs::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Stale.into())
+		);
+		// Nor can the spent leaf be deposited again.
+		let proof = deposit_proof(100, &leaf).try_into().unwrap();
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), leaf, proof),
+			Error::<Test>::DuplicateFeeCredit
+		);
+	});
+}
+
+#[test]
+fn fee_ticket_needs_a_recent_fee_root() {
+	new_test_ext().execute_with(|| {
+		let w = withdrawal(1, 500, ALICE);
+		// No credit deposited yet: even the empty root is not anchored.
+		assert_noop!(withdraw(w.clone(), Some(ticket(7, &w))), Error::<Test>::FeeRootNotRecent);
+
+		deposit_fee(ALICE, 7);
+		let mut fee = ticket(7, &w);
+		fee.fee_root = [0xEEu8; 32];
+		assert_noop!(withdraw(w.clone(), Some(fee)), Error::<Test>::FeeRootNotRecent);
+
+		// A ticket against an earlier root stays valid after later deposits.
+		let early = ticket(7, &w);
+		deposit_fee(MALLORY, 8);
+		assert_eq!(Proofs::recent_fee_roots().to_vec(), vec![early.fee_root]);
+		assert_ok!(withdraw(w, Some(early)));
+	});
+}
+
+#[test]
+fn fee_deposit_must_hold_the_base_fee() {
+	new_test_ext().execute_with(|| {
+		let leaf = fee_leaf(7);
+		let proof = deposit_proof(99, &leaf).try_into().unwrap();
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), leaf, proof),
+			Error::<Test>::FeeProofInvalid
+		);
+		assert_eq!(Proofs::fee_leaf_count(), 0);
+	});
+}
+
+#[test]
+fn base_fee_above_u64_is_rejected() {
+	new_test_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		BaseFee::set(u64::MAX as u128 + 1);
+		let w = withdrawal(1, 500, ALICE);
+		assert_noop!(withdraw(w.clone(), Some(ticket(7, &w))), Error::<Test>::FeeAmountOverflow);
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), [1u8; 32], Default::default()),
+			Error::<Test>::FeeAmountOverflow
+		);
+	});
+}
+
+#[test]
+fn spend_without_ticket_needs_zero_base_fee() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(withdraw(withdrawal(1, 500, ALICE), None), Error::<Test>::FeeRequired);
+		BaseFee::set(0);
+		assert_ok!(withdraw(withdrawal(1, 500, ALICE), None));
+		System::assert_last_event(Event::WithdrawV2Completed { tx_id: [1u8; 16] }.into());
+	});
+}
+
+const UNIT_PRICE: u64 = 300;
+
+fn rwa(asset: u32) -> [u8; 32] {
+	let mut id = [0u8; 32];
+	id[..4].copy_from_slice(&asset.to_le_bytes());
+	id
+}
+
+/// A `purchase_rwa_v2` of `quantity` units of asset 1 at `UNIT_PRICE`, with
+/// distinct notes derived from `n`.
+fn purchase_inputs(n: u8, quantity: u64) -> SpendPublicV2 {
+	SpendPublicV2 {
+		tree_epoch: Proofs::tree_epoch_v2(),
+		merkle_root: Proofs::current_root_v2(),
+		nullifier: [n; 32],
+		rwa_id: rwa(1),
+		quantity,
+		rate_round: None,
+		credential: None,
+		access_plan: None,
+		tx_id: [n; 16],
+		ownership_commitment: [n.wrapping_add(0x40); 32],
+		change_leaf: [n.wrapping_add(0x80); 32],
+		change_pkd: [n.wrapping_add(0xc0); 32],
+		refund_leaf: [n.wrapping_add(0x20); 32],
+	}
+}
+
+/// Submits `purchase_rwa_v2` with valid auth, ownership proof and a spend
+/// proof paying `price`, and the given refund proof.
+fn purchase_with(
+	inputs: &SpendPublicV2,
+	price: u64,
+	refund_proof: Vec<u8>,
+) -> frame_support::dispatch::DispatchResult {
+	submit_purchase(inputs, price, refund_proof, vec![])
+}
+
+/// As `purchase_with`, presenting `credential_proof` for `inputs.credential`.
+fn submit_purchase(
+	inputs: &SpendPublicV2,
+	price: u64,
+	refund_proof: Vec<u8>,
+	credential_proof: Vec<u8>,
+) -> frame_support::dispatch::DispatchResult {
+	let public_inputs = inputs.encode();
+	let proof = spend_proof(&SpendStatementV2 {
+		root: inputs.merkle_root,
+		nullifier: inputs.nullifier,
+		pkd: pkd(),
+		price_or_amount: price,
+		change_leaf: inputs.change_leaf,
+		change_pkd: inputs.change_pkd,
+		purchase_mode: true,
+	});
+	Proofs::purchase_rwa_v2(
+		RuntimeOrigin::none(),
+		spend_auth(&public_inputs, false),
+		public_inputs,
+		proof,
+		deposit_proof(inputs.quantity, &inputs.ownership_commitment),
+		refund_proof,
+		credential_proof,
+		None,
+	)
+}
+
+/// `purchase_with` paying `price`, refunded in full on cancellation.
+fn purchase_at(inputs: &SpendPublicV2, price: u64) -> frame_support::dispatch::DispatchResult {
+	purchase_with(inputs, price, deposit_proof(price, &inputs.refund_leaf))
+}
+
+fn purchase(inputs: &SpendPublicV2) -> frame_support::dispatch::DispatchResult {
+	purchase_at(inputs, UNIT_PRICE * inputs.quantity)
+}
+
+/// Escrow tests run without the fee lane and with asset 1 listed.
+fn escrow_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		BaseFee::set(0);
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), UNIT_PRICE));
+	});
+	ext
+}
+
+fn escrow_status(tx_id: [u8; 16]) -> EscrowStatus {
+	RwaEscrows::<Test>::get(tx_id).unwrap().status
+}
+
+#[test]
+fn purchase_needs_refund_proof_of_price() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 2);
+		// A refund note for less than the price paid is refused.
+		assert_noop!(
+			purchase_with(&p, UNIT_PRICE * 2, deposit_proof(UNIT_PRICE, &p.refund_leaf)),
+			Error::<Test>::ProofVerificationFailed
+		);
+		let mut reused = purchase_inputs(1, 2);
+		reused.refund_leaf = reused.change_leaf;
+		assert_noop!(purchase(&reused), Error::<Test>::DuplicateLeaf);
+
+		assert_ok!(purchase(&p));
+		let escrow = RwaEscrows::<Test>::get(p.tx_id).unwrap();
+		assert_eq!(escrow.refund_leaf, p.refund_leaf);
+		assert_eq!(escrow.price, 600);
+		assert_eq!(escrow.status, EscrowStatus::Pending);
+		System::assert_last_event(Event::EscrowOpened { tx_id: p.tx_id, deadline: 11 }.into());
+		// The refund leaf stays out of the tree while the escrow is pending.
+		assert!(!LeafIndexV2::<Test>::contains_key(p.refund_leaf));
+	});
+}
+
+#[test]
+fn confirmed_escrow_inserts_ownership_only() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 1);
+		assert_ok!(purchase(&p));
+		assert_noop!(
+			Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(ALICE), p.tx_id),
+			Error::<Test>::NotRwaChainSovereign
+		);
+		assert_ok!(Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id));
+		System::assert_last_event(Event::EscrowFulfilled { tx_id: p.tx_id, rwa_id: rwa(1) }.into());
+		assert_eq!(escrow_status(p.tx_id), EscrowStatus::Fulfilled);
+		assert!(OwnershipLeafIndex::<Test>::contains_key(rwa(1), p.ownership_commitment));
+		assert!(!LeafIndexV2::<Test>::contains_key(p.refund_leaf));
+
+		// No refund after confirmation, by timeout or cancellation.
+		System::set_block_number(20);
+		assert_noop!(
+			Proofs::refund_escrow(RuntimeOrigin::signed(MALLORY), p.tx_id),
+			Error::<Test>::EscrowNotPending
+		);
+		assert_noop!(
+			Proofs::xcm_cancel_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id),
+			Error::<Test>::EscrowNotPending
+		);
+	});
+}
+
+#[test]
+fn cancelled_escrow_inserts_buyer_refund_leaf() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 1);
+		assert_ok!(purchase(&p));
+		assert_ok!(Proofs::xcm_cancel_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id));
+		assert_eq!(escrow_status(p.tx_id), EscrowStatus::Refunded);
+		let index = LeafIndexV2::<Test>::get(p.refund_leaf).unwrap();
+		System::assert_last_event(
+			Event::EscrowRefunded {
+				tx_id: p.tx_id,
+				refund_leaf: p.refund_leaf,
+				refund_leaf_index: index,
+				new_root: Proofs::current_root_v2(),
+			}
+			.into(),
+		);
+		assert!(!OwnershipLeafIndex::<Test>::contains_key(rwa(1), p.ownership_commitment));
+		assert_noop!(
+			Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id),
+			Error::<Test>::EscrowNotPending
+		);
+	});
+}
+
+#[test]
+fn escrow_times_out_at_deadline() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 1);
+		assert_ok!(purchase(&p));
+		System::set_block_number(10);
+		assert_noop!(
+			Proofs::refund_escrow(RuntimeOrigin::signed(MALLORY), p.tx_id),
+			Error::<Test>::EscrowNotExpired
+		);
+		System::set_block_number(11);
+		assert_ok!(Proofs::refund_escrow(RuntimeOrigin::signed(MALLORY), p.tx_id));
+		assert_eq!(escrow_status(p.tx_id), EscrowStatus::Refunded);
+		assert!(LeafIndexV2::<Test>::contains_key(p.refund_leaf));
+		// A late confirmation cannot deliver the asset as well.
+		assert_noop!(
+			Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id),
+			Error::<Test>::EscrowNotPending
+		);
+	});
+}
+
+#[test]
+fn confirmation_after_deadline_wins_while_unrefunded() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 1);
+		assert_ok!(purchase(&p));
+		System::set_block_number(30);
+		assert_ok!(Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id));
+		assert_eq!(escrow_status(p.tx_id), EscrowStatus::Fulfilled);
+		assert_noop!(
+			Proofs::refund_escrow(RuntimeOrigin::signed(MALLORY), p.tx_id),
+			Error::<Test>::EscrowNotPending
+		);
+		assert!(!LeafIndexV2::<Test>::contains_key(p.refund_leaf));
+	});
+}
+
+/// Redeems `quantity` units of the ownership note spent by `nullifier`,
+/// anchored in the current ownership root of asset 1.
+fn redeem(nullifier: [u8; 32], quantity: u64) -> frame_support::dispatch::DispatchResult {
+	let inputs = RedeemPublicV2 {
+		rwa_id: rwa(1),
+		ownership_root: Proofs::current_ownership_root(rwa(1)),
+		ownership_nullifier: nullifier,
+		quantity,
+		ownership_change_leaf: [nullifier[0].wrapping_add(0x10); 32],
+		ownership_change_pkd: [0u8; 32],
+		claimant: [0xaa; 32],
+		tx_id: [nullifier[0]; 16],
+	};
+	let proof = spend_proof(&SpendStatementV2 {
+		root: inputs.ownership_root,
+		nullifier,
+		pkd: pkd(),
+		price_or_amount: quantity,
+		change_leaf: inputs.ownership_change_leaf,
+		change_pkd: inputs.ownership_change_pkd,
+		purchase_mode: true,
+	});
+	let public_inputs = inputs.encode();
+	Proofs::redeem_ownership_v2(RuntimeOrigin::none(), spend_auth(&public_inputs, true), public_inputs, proof, None)
+}
+
+#[test]
+fn spent_ownership_nullifiers_fill_the_spent_set() {
+	escrow_ext().execute_with(|| {
+		let p = purchase_inputs(1, 2);
+		assert_ok!(purchase(&p));
+		assert_ok!(Proofs::xcm_confirm_escrow(RuntimeOrigin::signed(SOVEREIGN), p.tx_id));
+
+		// Slot keys of two nullifiers that differ only in bit 3 of the key.
+		let first = [0x01u8; 32];
+		let mut second = first;
+		second[0] ^= 0x08;
+		let key = |nf: &[u8; 32]| u64::from_le_bytes(nf[..8].try_into().unwrap());
+		let zero = MockVerifier::v2_spent_zero_subtrees();
+		let (empty_root, siblings) = Proofs::ownership_spent_path(key(&second));
+		assert_eq!(empty_root, zero[64]);
+		assert_eq!(siblings[3], zero[3]);
+
+		assert_ok!(redeem(first, 1));
+		assert_eq!(OwnershipSpentNodes::<Test>::get(0, key(&first)), Some(first));
+		// The spent note's subtree is now a sibling of the other slot, and
+		// the root is that slot's path over the empty leaf.
+		let (root, siblings) = Proofs::ownership_spent_path(key(&second));
+		assert_ne!(root, empty_root);
+		assert_ne!(siblings[3], zero[3]);
+		assert_eq!(MockVerifier::v2_spent_path(key(&second), &[0u8; 32], &siblings)[64], root);
+		// A spent slot has no empty-leaf path to the root.
+		let (_, siblings) = Proofs::ownership_spent_path(key(&first));
+		assert_ne!(MockVerifier::v2_spent_path(key(&first), &[0u8; 32], &siblings)[64], root);
+		assert_noop!(redeem(first, 1), Error::<Test>::OwnershipAlreadyUsed);
+	});
+}
+
+/// Escrow setup with asset 1 restricted to class-1 credentials of registry
+/// 0, which has issued one credential.
+fn credential_ext() -> sp_io::TestExternalities {
+	let mut ext = escrow_ext();
+	ext.execute_with(|| {
+		assert_ok!(Proofs::create_credential_registry(RuntimeOrigin::root(), ALICE));
+		assert_ok!(Proofs::issue_credential(RuntimeOrigin::signed(ALICE), 0, [1u8; 32]));
+		assert_ok!(Proofs::xcm_sync_rwa_eligibility(
+			RuntimeOrigin::signed(SOVEREIGN),
+			rwa(1),
+			Some(EligibilityRequirement { registry_id: 0, required_class: 1 }),
+		));
+	});
+	ext
+}
+
+/// `purchase_inputs` presenting a class-`class` credential with tag
+/// `[tag; 32]` under the current root of registry 0.
+fn credential_purchase(n: u8, class: u64, tag: u8) -> SpendPublicV2 {
+	SpendPublicV2 {
+		credential: Some(CredentialPublicV2 {
+			registry_id: 0,
+			root: Proofs::current_credential_root(0),
+			class,
+			tag: [tag; 32],
+		}),
+		..purchase_inputs(n, 1)
+	}
+}
+
+/// Submits `inputs` with a credential proof bound to its own spend.
+fn purchase_credentialed(inputs: &SpendPublicV2) -> frame_support::dispatch::DispatchResult {
+	let cred = inputs.credential.as_ref().unwrap();
+	let proof = credential_proof(&cred.root, &inputs.rwa_id, cred.class, &cred.tag, &inputs.nullifier);
+	submit_purchase(inputs, UNIT_PRICE, deposit_proof(UNIT_PRICE, &inputs.refund_leaf), proof)
+}
+
+#[test]
+fn restricted_asset_needs_a_credential_proof() {
+	credential_ext().execute_with(|| {
+		assert_noop!(purchase(&purchase_inputs(1, 1)), Error::<Test>::CredentialRequired);
+		let p = credential_purchase(1, 1, 9);
+		let refund = deposit_proof(UNIT_PRICE, &p.refund_leaf);
+		assert_noop!(
+			submit_purchase(&p, UNIT_PRICE, refund.clone(), vec![]),
+			Error::<Test>::InvalidCredentialProof
+		);
+		// A presentation proven for another spend does not carry over.
+		let cred = p.credential.clone().unwrap();
+		let copied = credential_proof(&cred.root, &p.rwa_id, cred.class, &cred.tag, &[2u8; 32]);
+		assert_noop!(submit_purchase(&p, UNIT_PRICE, refund, copied), Error::<Test>::InvalidCredentialProof);
+
+		assert_noop!(
+			purchase_credentialed(&credential_purchase(1, 2, 9)),
+			Error::<Test>::CredentialClassInsufficient
+		);
+		let mut other_registry = credential_purchase(1, 1, 9);
+		other_registry.credential.as_mut().unwrap().registry_id = 1;
+		assert_noop!(purchase_credentialed(&other_registry), Error::<Test>::CredentialRegistryMismatch);
+	});
+}
+
+#[test]
+fn credential_root_must_be_recent() {
+	credential_ext().execute_with(|| {
+		let mut unknown = credential_purchase(1, 1, 9);
+		unknown.credential.as_mut().unwrap().root = [0xee; 32];
+		assert_noop!(purchase_credentialed(&unknown), Error::<Test>::CredentialRootNotRecent);
+
+		// The first root stays valid for 64 more issuances, then expires.
+		let first = credential_purchase(1, 1, 9);
+		for n in 2..=65u8 {
+			assert_ok!(Proofs::issue_credential(RuntimeOrigin::signed(ALICE), 0, [n; 32]));
+		}
+		assert!(Proofs::recent_credential_roots(0).contains(&first.credential.as_ref().unwrap().root));
+		assert_ok!(Proofs::issue_credential(RuntimeOrigin::signed(ALICE), 0, [66u8; 32]));
+		assert_noop!(purchase_credentialed(&first), Error::<Test>::CredentialRootNotRecent);
+		assert_ok!(purchase_credentialed(&credential_purchase(1, 1, 9)));
+	});
+}
+
+#[test]
+fn valid_credential_buys_once_per_asset() {
+	credential_ext().execute_with(|| {
+		// Extra flags beyond the required class are fine.
+		assert_ok!(purchase_credentialed(&credential_purchase(1, 3, 9)));
+		assert!(Proofs::credential_tag_used([9u8; 32]));
+		assert_eq!(escrow_status([1u8; 16]), EscrowStatus::Pending);
+		// The same credential cannot buy again, even with a fresh note.
+		assert_noop!(
+			purchase_credentialed(&credential_purchase(2, 3, 9)),
+			Error::<Test>::CredentialAlreadyUsed
+		);
+	});
+}
+
+/// Inserts leaf `[n; 32]` into the v2 tree with `deposit_v2`.
+fn insert_leaf(n: u8) {
+	let leaf = [n; 32];
+	assert_ok!(Proofs::deposit_v2(
+		RuntimeOrigin::signed(ALICE),
+		leaf,
+		1,
+		deposit_proof(1, &leaf).try_into().unwrap(),
+		Default::default(),
+	));
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+	Proofs::on_initialize(n);
+}
+
+/// One leaf per block from block 1 to `last`; returns the root current at
+/// the end of each block, indexed by block.
+fn roots_through(last: u64) -> Vec<[u8; 32]> {
+	let mut roots = vec![[0u8; 32]];
+	for n in 1..=last {
+		if n > 1 { run_to(n); }
+		insert_leaf(n as u8);
+		roots.push(Proofs::current_root_v2());
+	}
+	roots
+}
+
+#[test]
+fn history_keeps_one_root_per_block() {
+	new_test_ext().execute_with(|| {
+		insert_leaf(1);
+		let end_of_1 = Proofs::current_root_v2();
+		run_to(2);
+		insert_leaf(2);
+		let intermediate = Proofs::current_root_v2();
+		insert_leaf(3);
+		insert_leaf(4);
+
+		assert_eq!(RootHistoryV2::<Test>::get(1), Some((0, end_of_1)));
+		assert_eq!(RootHistoryV2::<Test>::get(2), None);
+		assert_eq!(RootRecordedAtV2::<Test>::get(0, end_of_1), Some(1));
+		assert_eq!(RootRecordedAtV2::<Test>::get(0, intermediate), None);
+		// A root that was current only within a block is never anchorable
+		// once it is replaced.
+		assert!(!Proofs::v2_root_anchored(0, &intermediate));
+		assert!(Proofs::v2_root_anchored(0, &end_of_1));
+		assert_eq!(Proofs::recent_roots_v2(), vec![(1, 0, end_of_1)]);
+	});
+}
+
+#[test]
+fn root_expires_after_retention_blocks() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(2);
+		// Recorded at block 1; retention is 5.
+		run_to(6);
+		assert!(Proofs::v2_root_anchored(0, &roots[1]));
+		run_to(7);
+		assert!(!Proofs::v2_root_anchored(0, &roots[1]));
+		assert_eq!(RootHistoryV2::<Test>::get(1), None);
+		assert_eq!(RootRecordedAtV2::<Test>::get(0, roots[1]), None);
+		// The current root never expires.
+		run_to(50);
+		assert!(Proofs::v2_root_anchored(0, &roots[2]));
+	});
+}
+
+#[test]
+fn retention_cut_prunes_skipped_blocks() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(6);
+		run_to(7);
+		assert_eq!(RootHistoryV2::<Test>::get(1), None);
+		assert_noop!(
+			Proofs::set_root_retention(RuntimeOrigin::root(), 0),
+			Error::<Test>::InvalidRootRetention
+		);
+		assert_noop!(
+			Proofs::set_root_retention(RuntimeOrigin::root(), 101),
+			Error::<Test>::InvalidRootRetention
+		);
+
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::RootRetentionSet { blocks: 2 }.into());
+		for at in 2..=4 {
+			assert_eq!(RootHistoryV2::<Test>::get(at), None);
+			assert_eq!(RootRecordedAtV2::<Test>::get(0, roots[at as usize]), None);
+		}
+		assert_eq!(RootHistoryV2::<Test>::get(5), Some((0, roots[5])));
+		assert!(Proofs::v2_root_anchored(0, &roots[5]));
+		assert!(!Proofs::v2_root_anchored(0, &roots[4]));
+
+		// `on_initialize` carries on from the new window.
+		run_to(8);
+		assert_eq!(RootHistoryV2::<Test>::get(5), None);
+		assert!(Proofs::v2_root_anchored(0, &roots[6]));
+	});
+}
+
+#[test]
+fn retention_raise_does_not_restore_pruned_roots() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(6);
+		run_to(7);
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 2));
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 10));
+		// Within ten blocks, but pruned by the cut.
+		assert!(!Proofs::v2_root_anchored(0, &roots[3]));
+		// Still held, so the raise extends it.
+		run_to(14);
+		assert!(Proofs::v2_root_anchored(0, &roots[5]));
+		run_to(16);
+		assert!(!Proofs::v2_root_anchored(0, &roots[5]));
+	});
+}
+
+#[test]
+fn oldest_acceptable_root_tracks_the_window() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Proofs::oldest_acceptable_root_v2(), (0, [0u8; 32]));
+		let roots = roots_through(5);
+		run_to(6);
+		assert_eq!(Proofs::oldest_acceptable_root_v2(), (0, roots[1]));
+		run_to(7);
+		assert_eq!(Proofs::oldest_acceptable_root_v2(), (0, roots[2]));
+		run_to(20);
+		assert_eq!(Proofs::oldest_acceptable_root_v2(), (0, roots[5]));
+	});
+}
+
+#[test]
+fn lane_state_queries_track_the_tree() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Proofs::leaf_position_v2(&[1u8; 32]), None);
+		let roots = roots_through(3);
+		assert_eq!(Proofs::leaf_position_v2(&[3u8; 32]), Some((0, 2)));
+		assert_eq!(Proofs::recent_roots_v2(), vec![(1, 0, roots[1]), (2, 0, roots[2])]);
+		assert_eq!(Proofs::oldest_acceptable_root_v2(), (0, roots[1]));
+		assert!(Proofs::v2_root_anchored(0, &roots[3]));
+		assert!(!Proofs::v2_root_anchored(1, &roots[3]));
+	});
+}
+
+#[test]
+fn dry_run_matches_dispatch_without_changing_state() {
+	new_test_ext().execute_with(|| {
+		BaseFee::set(0);
+		insert_leaf(1);
+		let valid = withdraw_call(withdrawal(1, 500, ALICE), None);
+		assert_ok!(Proofs::dry_run(valid.clone()));
+		assert!(!NullifierUsed::<Test>::get([1u8; 32]));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 1);
+
+		assert_ok!(withdraw(withdrawal(1, 500, ALICE), None));
+		assert_eq!(Proofs::dry_run(valid), Err(Error::<Test>::NullifierAlreadyUsed.into()));
+		let stale = withdrawal_at(0, [0xee; 32], 2, 500, ALICE);
+		assert_eq!(
+			Proofs::dry_run(withdraw_call(stale.clone(), None)),
+			Err(Error::<Test>::RootNotRecent.into())
+		);
+		assert_noop!(withdraw(stale, None), Error::<Test>::RootNotRecent);
+		// Signed calls are refused as they would be unsigned.
+		let deposit = crate::Call::<Test>::deposit_v2 {
+			leaf: [2u8; 32],
+			amount: 1,
+			deposit_proof: deposit_proof(1, &[2u8; 32]).try_into().unwrap(),
+			hints_blob: Default::default(),
+		};
+		assert_eq!(Proofs::dry_run(deposit), Err(sp_runtime::DispatchError::BadOrigin));
+	});
+}
+
+/// Marks the current v2 tree full, so the next insert rolls the epoch.
+fn fill_tree() {
+	LeafCountV2::<Test>::put(u32::MAX);
+}
+
+#[test]
+fn full_tree_rolls_into_a_new_epoch() {
+	new_test_ext().execute_with(|| {
+		insert_leaf(1);
+		let final_root = Proofs::current_root_v2();
+		fill_tree();
+		run_to(2);
+		insert_leaf(2);
+
+		assert_eq!(Proofs::tree_epoch_v2(), 1);
+		assert_eq!(FinalRootsV2::<Test>::get(0), Some(final_root));
+		System::assert_has_event(Event::TreeEpochV2Started { epoch: 1, final_root }.into());
+		assert_eq!(LeafCountV2::<Test>::get(), 1);
+		assert_eq!(Proofs::leaf_position_v2(&[1u8; 32]), Some((0, 0)));
+		assert_eq!(Proofs::leaf_position_v2(&[2u8; 32]), Some((1, 0)));
+		let root = Proofs::current_root_v2();
+		assert_ne!(root, final_root);
+		assert!(Proofs::v2_root_anchored(1, &root));
+		// Roots are anchored only under the epoch they belong to.
+		assert!(!Proofs::v2_root_anchored(0, &root));
+		assert!(!Proofs::v2_root_anchored(1, &final_root));
+	});
+}
+
+#[test]
+fn previous_epoch_final_root_stays_spendable() {
+	new_test_ext().execute_with(|| {
+		BaseFee::set(0);
+		let roots = roots_through(2);
+		fill_tree();
+		run_to(3);
+		insert_leaf(3);
+		run_to(50);
+
+		// Past retention, the closed epoch's intermediate roots are gone...
+		assert_noop!(
+			withdraw(withdrawal_at(0, roots[1], 1, 500, ALICE), None),
+			Error::<Test>::RootNotRecent
+		);
+		// ...and its final root is only valid under its own epoch.
+		assert_noop!(
+			withdraw(withdrawal_at(1, roots[2], 1, 500, ALICE), None),
+			Error::<Test>::RootNotRecent
+		);
+		assert_ok!(withdraw(withdrawal_at(0, roots[2], 1, 500, ALICE), None));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 3 + 500);
+	});
+}
+
+#[test]
+fn nullifiers_stay_spent_across_epochs() {
+	new_test_ext().execute_with(|| {
+		BaseFee::set(0);
+		insert_leaf(1);
+		let final_root = Proofs::current_root_v2();
+		assert_ok!(withdraw(withdrawal(1, 500, ALICE), None));
+		fill_tree();
+		run_to(2);
+		insert_leaf(2);
+
+		assert_noop!(withdraw(withdrawal(1, 500, ALICE), None), Error::<Test>::NullifierAlreadyUsed);
+		assert_noop!(
+			withdraw(withdrawal_at(0, final_root, 1, 500, ALICE), None),
+			Error::<Test>::NullifierAlreadyUsed
+		);
+		assert_ok!(withdraw(withdrawal(2, 500, ALICE), None));
+	});
+}
+
+#[test]
+fn upgrade_moves_recent_roots_into_history() {
+	new_test_ext().execute_with(|| {
+		let window: BoundedVec<[u8; 32], ConstU32<64>> =
+			(1..=8u8).map(|n| [n; 32]).collect::<Vec<_>>().try_into().unwrap();
+		frame_support::storage::migration::put_storage_value(b"Proofs", b"RecentRootsV2", &[], window);
+		StorageVersion::new(0).put::<Proofs>();
+		run_to(10);
+
+		Proofs::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Proofs>(), 3);
+		assert!(!frame_support::storage::migration::have_storage_value(b"Proofs", b"RecentRootsV2", &[]));
+		// Newest at `now - 2`, one block apart, back to the retention floor.
+		assert_eq!(RootHistoryV2::<Test>::get(8), Some((0, [8u8; 32])));
+		assert_eq!(RootHistoryV2::<Test>::get(5), Some((0, [5u8; 32])));
+		assert_eq!(RootHistoryV2::<Test>::get(4), None);
+		assert!(Proofs::v2_root_anchored(0, &[5u8; 32]));
+		assert!(!Proofs::v2_root_anchored(0, &[4u8; 32]));
+	});
+}
+
+#[test]
+fn upgrade_clears_v1_ownership_records() {
+	use frame_support::{storage::migration, Blake2_128Concat, StorageHasher};
+	new_test_ext().execute_with(|| {
+		let keys: Vec<Vec<u8>> = (1..=3u8).map(|n| Blake2_128Concat::hash(&[n; 16])).collect();
+		for key in &keys {
+			migration::put_storage_value(b"Proofs", b"OwnershipCommitments", key, [7u8; 32]);
+		}
+		migration::put_storage_value(b"Proofs", b"OwnershipUsed", &keys[0], true);
+		StorageVersion::new(0).put::<Proofs>();
+
+		Proofs::on_runtime_upgrade();
+		for key in &keys {
+			assert!(!migration::have_storage_value(b"Proofs", b"OwnershipCommitments", key));
+			assert!(!migration::have_storage_value(b"Proofs", b"OwnershipUsed", key));
+		}
+		assert_eq!(Proofs::ownership_leaf_count(rwa(1)), 0);
+	});
+}
+
+const RESERVE: u64 = 100;
+
+/// Auction 0: one unit of asset 1, bids until block 6, reveals until 11.
+fn auction_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		BaseFee::set(0);
+		assert_ok!(Proofs::set_rwa_units(RuntimeOrigin::root(), rwa(1), Some(5)));
+		assert_ok!(Proofs::create_auction(RuntimeOrigin::root(), rwa(1), 1, RESERVE, 5, 5));
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(4));
+	});
+	ext
+}
+
+fn salt(n: u8) -> [u8; 32] {
+	[n.wrapping_add(0x70); 32]
+}
+
+/// Bidder `n` locks a note and seals `bid`.
+fn place_bid(n: u8, bid: u64) -> frame_support::dispatch::DispatchResult {
+	let inputs = AuctionBidPublicV2 {
+		auction_id: 0,
+		tree_epoch: Proofs::tree_epoch_v2(),
+		merkle_root: Proofs::current_root_v2(),
+		nullifier: [n; 32],
+		lock_leaf: [n.wrapping_add(0x10); 32],
+		lock_pkd: pkd(),
+		bid_commitment: Proofs::auction_bid_commitment(0, bid, &salt(n)),
+		tx_id: [n; 16],
+	};
+	let proof = spend_proof(&SpendStatementV2 {
+		root: inputs.merkle_root,
+		nullifier: inputs.nullifier,
+		pkd: pkd(),
+		price_or_amount: 0,
+		change_leaf: inputs.lock_leaf,
+		change_pkd: inputs.lock_pkd,
+		purchase_mode: true,
+	});
+	let public_inputs = inputs.encode();
+	Proofs::place_bid_v2(
+		RuntimeOrigin::none(),
+		spend_auth(&public_inputs, false),
+		public_inputs,
+		proof,
+		None,
+	)
+}
+
+fn refund_leaf(n: u8) -> [u8; 32] {
+	[n.wrapping_add(0x50); 32]
+}
+
+fn ownership_leaf(n: u8) -> [u8; 32] {
+	[n.wrapping_add(0x60); 32]
+}
+
+/// Bidder `n` opens bid `bid_index` as `bid`.
+fn reveal_bid(n: u8, bid_index: u32, bid: u64) -> frame_support::dispatch::DispatchResult {
+	let inputs = AuctionRevealPublicV2 {
+		auction_id: 0,
+		bid_index,
+		bid,
+		salt: salt(n),
+		lock_nullifier: [n.wrapping_add(0x20); 32],
+		change_leaf: [n.wrapping_add(0x30); 32],
+		change_pkd: [n.wrapping_add(0x40); 32],
+		refund_leaf: refund_leaf(n),
+		ownership_leaf: ownership_leaf(n),
+		tx_id: [n; 16],
+	};
+	let lock_proof = spend_proof(&SpendStatementV2 {
+		root: AuctionLockRoot::<Test>::get(0),
+		nullifier: inputs.lock_nullifier,
+		pkd: pkd(),
+		price_or_amount: bid,
+		change_leaf: inputs.change_leaf,
+		change_pkd: inputs.change_pkd,
+		purchase_mode: true,
+	});
+	let public_inputs = inputs.encode();
+	Proofs::reveal_bid_v2(
+		RuntimeOrigin::none(),
+		spend_auth(&public_inputs, false),
+		public_inputs,
+		lock_proof,
+		deposit_proof(bid, &inputs.refund_leaf),
+		deposit_proof(1, &inputs.ownership_leaf),
+		None,
+	)
+}
+
+/// Bidder `n` takes back the lock of unrevealed bid `bid_index`.
+fn release_bid(n: u8, bid_index: u32) -> frame_support::dispatch::DispatchResult {
+	let inputs = AuctionReleasePublicV2 {
+		auction_id: 0,
+		bid_index,
+		lock_nullifier: [n.wrapping_add(0x20); 32],
+		change_leaf: [n.wrapping_add(0x30); 32],
+		change_pkd: [n.wrapping_add(0x40); 32],
+		tx_id: [n; 16],
+	};
+	let lock_proof = spend_proof(&SpendStatementV2 {
+		root: AuctionLockRoot::<Test>::get(0),
+		nullifier: inputs.lock_nullifier,
+		pkd: pkd(),
+		price_or_amount: 0,
+		change_leaf: inputs.change_leaf,
+		change_pkd: inputs.change_pkd,
+		purchase_mode: true,
+	});
+	let public_inputs = inputs.encode();
+	Proofs::release_bid_v2(
+		RuntimeOrigin::none(),
+		spend_auth(&public_inputs, false),
+		public_inputs,
+		lock_proof,
+		None,
+	)
+}
+
+fn restrict_asset_1() {
+	assert_ok!(Proofs::xcm_sync_rwa_eligibility(
+		RuntimeOrigin::signed(SOVEREIGN),
+		rwa(1),
+		Some(EligibilityRequirement { registry_id: 0, required_class: 1 }),
+	));
+}
+
+#[test]
+fn highest_revealed_bid_wins_and_losers_are_refunded() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, 150));
+		assert_ok!(place_bid(2, 200));
+		run_to(7);
+		assert_noop!(place_bid(3, 300), Error::<Test>::BiddingClosed);
+		assert_ok!(reveal_bid(1, 0, 150));
+		assert_ok!(reveal_bid(2, 1, 200));
+		assert_eq!(Auctions::<Test>::get(0).unwrap().best_bid, Some((1, 200)));
+		assert_noop!(
+			Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0),
+			Error::<Test>::AuctionNotClosed
+		);
+
+		run_to(12);
+		assert_ok!(Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0));
+		System::assert_last_event(
+			Event::AuctionSettled { auction_id: 0, rwa_id: rwa(1), quantity: 1, price: 200 }.into(),
+		);
+		assert_eq!(Auctions::<Test>::get(0).unwrap().status, AuctionStatus::Settled);
+		assert!(OwnershipLeafIndex::<Test>::contains_key(rwa(1), ownership_leaf(2)));
+		assert!(!LeafIndexV2::<Test>::contains_key(refund_leaf(2)));
+		// The losing bid gets its refund leaf, not the asset.
+		assert!(LeafIndexV2::<Test>::contains_key(refund_leaf(1)));
+		assert!(!OwnershipLeafIndex::<Test>::contains_key(rwa(1), ownership_leaf(1)));
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(4));
+		assert_noop!(
+			Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0),
+			Error::<Test>::AuctionAlreadySettled
+		);
+	});
+}
+
+#[test]
+fn unrevealed_bid_is_released_after_reveal_window() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, 150));
+		run_to(7);
+		assert_noop!(release_bid(1, 0), Error::<Test>::AuctionNotClosed);
+		run_to(12);
+		assert_noop!(reveal_bid(1, 0, 150), Error::<Test>::NotInRevealWindow);
+		assert_ok!(release_bid(1, 0));
+		assert_eq!(AuctionBids::<Test>::get(0, 0).unwrap().state, BidState::Released);
+		assert!(LeafIndexV2::<Test>::contains_key([0x31; 32]));
+		assert_noop!(release_bid(1, 0), Error::<Test>::BidNotSealed);
+
+		// No revealed bid: no sale, and the unit goes back on sale.
+		assert_ok!(Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0));
+		System::assert_last_event(Event::AuctionNoSale { auction_id: 0 }.into());
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(5));
+	});
+}
+
+#[test]
+fn bid_below_reserve_cannot_be_revealed() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, RESERVE - 1));
+		run_to(7);
+		assert_noop!(reveal_bid(1, 0, RESERVE - 1), Error::<Test>::BidBelowReserve);
+		// Nor can it be opened as a different amount.
+		assert_noop!(reveal_bid(1, 0, RESERVE), Error::<Test>::BidCommitmentMismatch);
+		run_to(12);
+		assert_ok!(release_bid(1, 0));
+		assert_ok!(Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0));
+		assert_eq!(Auctions::<Test>::get(0).unwrap().status, AuctionStatus::NoSale);
+	});
+}
+
+#[test]
+fn tie_goes_to_the_first_bid_revealed() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, 150));
+		assert_ok!(place_bid(2, 150));
+		run_to(7);
+		// Bid 1 is revealed first, though it was placed second.
+		assert_ok!(reveal_bid(2, 1, 150));
+		assert_ok!(reveal_bid(1, 0, 150));
+		assert_eq!(Auctions::<Test>::get(0).unwrap().best_bid, Some((1, 150)));
+		run_to(12);
+		assert_ok!(Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0));
+		assert!(OwnershipLeafIndex::<Test>::contains_key(rwa(1), ownership_leaf(2)));
+		assert!(LeafIndexV2::<Test>::contains_key(refund_leaf(1)));
+	});
+}
+
+#[test]
+fn restricted_asset_takes_no_bids() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, 150));
+		restrict_asset_1();
+		assert_noop!(place_bid(2, 200), Error::<Test>::EligibilityRestricted);
+		assert_noop!(
+			Proofs::create_auction(RuntimeOrigin::root(), rwa(1), 1, RESERVE, 5, 5),
+			Error::<Test>::EligibilityRestricted
+		);
+	});
+}
+
+#[test]
+fn restricted_asset_settles_without_sale() {
+	auction_ext().execute_with(|| {
+		assert_ok!(place_bid(1, 150));
+		assert_ok!(place_bid(2, 200));
+		run_to(7);
+		assert_ok!(reveal_bid(1, 0, 150));
+		assert_ok!(reveal_bid(2, 1, 200));
+		restrict_asset_1();
+		run_to(12);
+		assert_ok!(Proofs::settle_auction(RuntimeOrigin::signed(MALLORY), 0));
+		System::assert_last_event(Event::AuctionNoSale { auction_id: 0 }.into());
+		assert_eq!(Auctions::<Test>::get(0).unwrap().status, AuctionStatus::NoSale);
+		// Both bids, the best one included, are refunded.
+		assert!(LeafIndexV2::<Test>::contains_key(refund_leaf(1)));
+		assert!(LeafIndexV2::<Test>::contains_key(refund_leaf(2)));
+		assert!(!OwnershipLeafIndex::<Test>::contains_key(rwa(1), ownership_leaf(2)));
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(5));
+	});
+}
+
+const USD_CENTS: u64 = 250;
+
+/// Asset 1 priced at `USD_CENTS` per unit; oracle round 0 at 2 planck per cent.
+fn usd_ext() -> sp_io::TestExternalities {
+	let mut ext = escrow_ext();
+	ext.execute_with(|| {
+		assert_ok!(Proofs::set_rwa_usd_price(RuntimeOrigin::root(), rwa(1), USD_CENTS));
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 2));
+		assert_eq!(PriceOracle::current_round(), Some(0));
+	});
+	ext
+}
+
+fn usd_purchase(n: u8, quantity: u64, round: u32) -> SpendPublicV2 {
+	SpendPublicV2 { rate_round: Some(round), ..purchase_inputs(n, quantity) }
+}
+
+#[test]
+fn usd_priced_rwa_converts_at_the_named_round() {
+	usd_ext().execute_with(|| {
+		assert_noop!(purchase_at(&purchase_inputs(1, 3), 1_500), Error::<Test>::RateRoundRequired);
+		assert_noop!(purchase_at(&usd_purchase(1, 3, 1), 1_500), Error::<Test>::RateUnavailable);
+		// The planck price no longer applies once the RWA is priced in USD.
+		assert_noop!(
+			purchase_at(&usd_purchase(1, 3, 0), UNIT_PRICE * 3),
+			Error::<Test>::ProofVerificationFailed
+		);
+		assert_ok!(purchase_at(&usd_purchase(1, 3, 0), USD_CENTS * 2 * 3));
+		assert_eq!(RwaEscrows::<Test>::get([1u8; 16]).unwrap().price, 1_500);
+	});
+}
+
+#[test]
+fn replaced_round_is_refused_after_grace_period() {
+	usd_ext().execute_with(|| {
+		// NULLA falls: round 1 asks twice as many planck as round 0.
+		run_to(4);
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 4));
+		assert_eq!(PriceOracle::current_round(), Some(1));
+
+		// Spends built against round 0 still land during the grace period...
+		run_to(7);
+		assert_ok!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2));
+		// ...but a buyer cannot keep picking the cheaper round after it.
+		run_to(8);
+		assert_noop!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2), Error::<Test>::RateUnavailable);
+		assert_noop!(
+			purchase_at(&usd_purchase(2, 1, 1), USD_CENTS * 2),
+			Error::<Test>::ProofVerificationFailed
+		);
+		assert_ok!(purchase_at(&usd_purchase(2, 1, 1), USD_CENTS * 4));
+	});
+}
+
+#[test]
+fn unconfirmed_round_goes_stale() {
+	usd_ext().execute_with(|| {
+		run_to(21);
+		assert_ok!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2));
+		run_to(22);
+		assert_noop!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2), Error::<Test>::RateUnavailable);
+		// An unchanged report re-confirms the round rather than opening one.
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 2));
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		assert_ok!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2));
+	});
+}
+
+#[test]
+fn price_overflow_is_rejected() {
+	usd_ext().execute_with(|| {
+		// usd_cents × planck_per_cent overflows.
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), u64::MAX / 100));
+		assert_noop!(purchase_at(&usd_purchase(1, 1, 1), 0), Error::<Test>::PriceOverflow);
+		// unit price × quantity overflows.
+		assert_ok!(Proofs::set_rwa_usd_price(RuntimeOrigin::root(), rwa(1), 0));
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), u64::MAX / 2 + 1));
+		assert_noop!(purchase_at(&purchase_inputs(1, 2), 0), Error::<Test>::PriceOverflow);
+	});
+}
+
+#[test]
+fn zero_price_sync_delists_usd_priced_rwa() {
+	usd_ext().execute_with(|| {
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), 0));
+		assert_eq!(Proofs::rwa_usd_prices(rwa(1)), 0);
+		assert_noop!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2), Error::<Test>::RwaPriceNotSet);
+
+		// Re-listed by the RWA chain, it sells at the synced planck price.
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), UNIT_PRICE));
+		assert_ok!(purchase_at(&purchase_inputs(1, 1), UNIT_PRICE));
+	});
+}
+
+#[test]
+fn rwa_listing_reports_the_lane_terms() {
+	usd_ext().execute_with(|| {
+		let unlisted = RwaListing { price: 0, usd_cents: 0, units: None, eligibility: None };
+		assert_eq!(Proofs::rwa_listing(&rwa(2)), unlisted);
+		restrict_asset_1();
+		assert_eq!(
+			Proofs::rwa_listing(&rwa(1)),
+			RwaListing {
+				price: UNIT_PRICE,
+				usd_cents: USD_CENTS,
+				units: None,
+				eligibility: Some(EligibilityRequirement { registry_id: 0, required_class: 1 }),
+			}
+		);
+		assert_ok!(Proofs::set_rwa_units(RuntimeOrigin::root(), rwa(1), Some(5)));
+		assert_eq!(Proofs::rwa_listing(&rwa(1)).units, Some(5));
+	});
+}
+
+const APP: [u8; 32] = [0xa1; 32];
+const APP_PAYEE: u64 = 15;
+const KEY_PRICE: u64 = 50;
+const BASIC: AccessPlan = AccessPlan { price: 300, duration: 100, tier: 1, max_uses: 10 };
+const WITHDRAWN: AccessPlan = AccessPlan { price: 0, duration: 100, tier: 2, max_uses: 0 };
+
+fn payment_account(who: u64) -> [u8; 32] {
+	let mut account = [0u8; 32];
+	account[..8].copy_from_slice(&who.to_le_bytes());
+	account
+}
+
+/// `APP` sells plan-less keys at `KEY_PRICE` and the plans `[BASIC, WITHDRAWN]`,
+/// paid to `APP_PAYEE`; no fee lane.
+fn access_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		BaseFee::set(0);
+		assert_ok!(Proofs::set_access_config(RuntimeOrigin::root(), APP, KEY_PRICE, payment_account(APP_PAYEE)));
+		assert_ok!(Proofs::set_access_plans(RuntimeOrigin::root(), APP, vec![BASIC, WITHDRAWN]));
+	});
+	ext
+}
+
+/// A `purchase_access_v2` of one `APP` key under `plan`, with distinct notes
+/// derived from `n`.
+fn access_inputs(n: u8, plan: Option<u8>) -> SpendPublicV2 {
+	SpendPublicV2 { rwa_id: APP, access_plan: plan, ..purchase_inputs(n, 1) }
+}
+
+/// Submits `purchase_access_v2` with valid auth and a spend proof paying `price`.
+fn purchase_access(inputs: &SpendPublicV2, price: u64) -> frame_support::dispatch::DispatchResult {
+	let public_inputs = inputs.encode();
+	let proof = spend_proof(&SpendStatementV2 {
+		root: inputs.merkle_root,
+		nullifier: inputs.nullifier,
+		pkd: pkd(),
+		price_or_amount: price,
+		change_leaf: inputs.change_leaf,
+		change_pkd: inputs.change_pkd,
+		purchase_mode: true,
+	});
+	Proofs::purchase_access_v2(RuntimeOrigin::none(), spend_auth(&public_inputs, false), public_inputs, proof, None)
+}
+
+#[test]
+fn access_plans_need_a_configured_app() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Proofs::set_access_plans(RuntimeOrigin::signed(ALICE), APP, vec![BASIC]),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Proofs::set_access_plans(RuntimeOrigin::root(), APP, vec![BASIC]),
+			Error::<Test>::AccessAppNotConfigured
+		);
+		assert_ok!(Proofs::set_access_config(RuntimeOrigin::root(), APP, KEY_PRICE, payment_account(APP_PAYEE)));
+		assert_noop!(
+			Proofs::set_access_plans(RuntimeOrigin::root(), APP, vec![BASIC; 9]),
+			Error::<Test>::TooManyAccessPlans
+		);
+		assert_noop!(
+			Proofs::set_access_plans(RuntimeOrigin::root(), APP, vec![BASIC, AccessPlan { duration: 0, ..BASIC }]),
+			Error::<Test>::InvalidAccessPlan
+		);
+
+		assert_ok!(Proofs::set_access_plans(RuntimeOrigin::root(), APP, vec![BASIC, WITHDRAWN]));
+		System::assert_last_event(Event::AccessPlansSet { app_id: APP, plans: 2 }.into());
+		// Repricing plan-less keys keeps the plans.
+		assert_ok!(Proofs::set_access_config(RuntimeOrigin::root(), APP, 0, payment_account(APP_PAYEE)));
+		assert_eq!(AccessKeyConfigs::<Test>::get(APP).unwrap().plans.to_vec(), vec![BASIC, WITHDRAWN]);
+	});
+}
+
+#[test]
+fn plan_purchase_pays_the_plan_price_and_relays_its_terms() {
+	access_ext().execute_with(|| {
+		assert_noop!(purchase_access(&access_inputs(1, Some(0)), KEY_PRICE), Error::<Test>::ProofVerificationFailed);
+		assert_noop!(
+			purchase_access(&SpendPublicV2 { quantity: 2, ..access_inputs(1, Some(0)) }, BASIC.price),
+			Error::<Test>::InvalidQuantity
+		);
+		assert_ok!(purchase_access(&access_inputs(1, Some(0)), BASIC.price));
+		assert_eq!(Balances::free_balance(APP_PAYEE), BASIC.price as u128);
+		assert_eq!(
+			AccessSent::get(),
+			vec![(APP, [1; 32], [1; 16], [0x41; 32], Some(AccessTerms { duration: 100, tier: 1, max_uses: 10 }))]
+		);
+
+		assert_ok!(purchase_access(&access_inputs(2, None), KEY_PRICE));
+		assert_eq!(AccessSent::get()[1], (APP, [2; 32], [2; 16], [0x42; 32], None));
+		assert_eq!(Balances::free_balance(APP_PAYEE), (BASIC.price + KEY_PRICE) as u128);
+	});
+}
+
+#[test]
+fn withdrawn_or_missing_plan_is_not_sold() {
+	access_ext().execute_with(|| {
+		assert_noop!(purchase_access(&access_inputs(1, Some(1)), 0), Error::<Test>::AccessPlanUnavailable);
+		assert_noop!(purchase_access(&access_inputs(1, Some(2)), BASIC.price), Error::<Test>::AccessPlanUnavailable);
+		// A zero plan-less price stops plan-less sales but not plan sales.
+		assert_ok!(Proofs::set_access_config(RuntimeOrigin::root(), APP, 0, payment_account(APP_PAYEE)));
+		assert_noop!(purchase_access(&access_inputs(1, None), 0), Error::<Test>::AccessPlanUnavailable);
+		assert_ok!(purchase_access(&access_inputs(1, Some(0)), BASIC.price));
+		assert_eq!(AccessSent::get().len(), 1);
+	});
+}
+
+#[test]
+fn renewal_purchase_relays_a_fresh_grant() {
+	access_ext().execute_with(|| {
+		assert_ok!(purchase_access(&access_inputs(1, Some(0)), BASIC.price));
+		// The renewal is bought from an unrelated note; nothing in it names the
+		// first grant or its key. AuthGate ties them only when the key holder
+		// opens both commitments.
+		assert_ok!(purchase_access(&access_inputs(2, Some(0)), BASIC.price));
+		let sent = AccessSent::get();
+		let (first, renewal) = (&sent[0], &sent[1]);
+		assert_eq!((first.0, first.4), (renewal.0, renewal.4));
+		assert_ne!(first.1, renewal.1);
+		assert_ne!(first.2, renewal.2);
+		assert_ne!(first.3, renewal.3);
+	});
+}
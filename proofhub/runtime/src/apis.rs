@@ -47,9 +47,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
-	SLOT_DURATION, VERSION,
+	AccountId, Balance, Block, BlockNumber, ConsensusHook, Executive, InherentDataExt, Nonce,
+	ParachainSystem, Proofs, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System,
+	TransactionPayment, SLOT_DURATION, VERSION,
 };
 
 // we move some impls outside so we can easily use them with `docify`.
@@ -227,6 +227,52 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_proofs_runtime_api::PrivacyLaneApi<Block, BlockNumber, pallet_proofs::Call<Runtime>> for Runtime {
+		fn current_root() -> (u32, [u8; 32]) {
+			(pallet_proofs::TreeEpochV2::<Runtime>::get(), pallet_proofs::CurrentRootV2::<Runtime>::get())
+		}
+
+		fn frontier() -> (u32, [[u8; 32]; 20]) {
+			(pallet_proofs::LeafCountV2::<Runtime>::get(), pallet_proofs::FrontierNodesV2::<Runtime>::get())
+		}
+
+		fn recent_roots() -> Vec<(BlockNumber, u32, [u8; 32])> {
+			Proofs::recent_roots_v2()
+		}
+
+		fn oldest_acceptable_root() -> (u32, [u8; 32]) {
+			Proofs::oldest_acceptable_root_v2()
+		}
+
+		fn root_acceptable(tree_epoch: u32, root: [u8; 32]) -> bool {
+			Proofs::v2_root_anchored(tree_epoch, &root)
+		}
+
+		fn leaf_index(leaf: [u8; 32]) -> Option<(u32, u32)> {
+			Proofs::leaf_position_v2(&leaf)
+		}
+
+		fn nullifier_used(nullifier: [u8; 32]) -> bool {
+			pallet_proofs::NullifierUsed::<Runtime>::get(nullifier)
+		}
+
+		fn ownership_spent_path(key: u64) -> ([u8; 32], [[u8; 32]; 64]) {
+			Proofs::ownership_spent_path(key)
+		}
+
+		fn rwa_listing(rwa_id: [u8; 32]) -> pallet_proofs::RwaListing {
+			Proofs::rwa_listing(&rwa_id)
+		}
+
+		fn access_config(app_id: [u8; 32]) -> Option<pallet_proofs::AppConfig> {
+			pallet_proofs::AccessKeyConfigs::<Runtime>::get(app_id)
+		}
+
+		fn dry_run(call: pallet_proofs::Call<Runtime>) -> Result<(), sp_runtime::DispatchError> {
+			Proofs::dry_run(call)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -63,8 +63,8 @@ use super::{
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
 	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
 	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, DAYS, EXISTENTIAL_DEPOSIT, HOURS,
+	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, MINUTES, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
 };
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -357,10 +357,68 @@ impl pallet_proofs::Config for Runtime {
 	type PoolAccount = PrivacyPoolAccount;
 	type RwaDispatch = RwaXcmDispatch;
 	type AccessDispatch = AccessGateXcmDispatch;
+	type BaseFee = PrivateBaseFee;
+	type FeeBurn = PrivateFeeBurn;
+	type FeePayer = PaymasterFeePayer;
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type RwaChainSovereign = RwaChainSovereign;
+	type EscrowPeriod = RwaEscrowPeriod;
+	type FiatRate = OracleFiatRate;
+	type DefaultRootRetention = DefaultRootRetention;
+	type MaxRootRetention = MaxRootRetention;
+}
+
+/// Configure the NULLA/USD price feed.
+impl pallet_price_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxReporters = ConstU32<16>;
+	type MinReports = ConstU32<3>;
+	type MaxReportAge = PriceReportMaxAge;
+	type MaxRoundAge = PriceRoundMaxAge;
+	type RoundGracePeriod = PriceRoundGracePeriod;
+}
+
+parameter_types! {
+	/// Reports older than ten minutes are left out of the median.
+	pub const PriceReportMaxAge: BlockNumber = 10 * MINUTES;
+	/// A rate nobody re-confirmed for an hour is stale.
+	pub const PriceRoundMaxAge: BlockNumber = HOURS;
+	/// Spends built against a superseded round stay valid for ten blocks.
+	pub const PriceRoundGracePeriod: BlockNumber = 10;
+}
+
+/// Lane `FiatRate` backed by the oracle's rate rounds.
+pub struct OracleFiatRate;
+impl pallet_proofs::FiatRate for OracleFiatRate {
+	fn planck_per_cent(round: u32) -> Option<u64> {
+		pallet_price_oracle::Pallet::<Runtime>::rate(round)
+	}
 }
 
 // Runtime proof verifier wired to the local `verifier` crate.
 pub struct RuntimeProofVerifier;
+impl RuntimeProofVerifier {
+	/// Whether `proof` carries a registered version of `air` that governance
+	/// has not retired.
+	fn accepts(proof: &[u8], air: verifier::envelope::AirId) -> bool {
+		verifier::envelope::version_of(proof, air)
+			.is_some_and(|v| !pallet_proofs::RetiredProofVersions::<Runtime>::get((air as u8, v)))
+	}
+
+	fn security(security: &pallet_proofs::StarkSecurity) -> verifier::envelope::StarkSecurity {
+		use verifier::envelope::SecurityModel;
+		verifier::envelope::StarkSecurity {
+			model: match security.model {
+				pallet_proofs::SecurityModel::Conjectured => SecurityModel::Conjectured,
+				pallet_proofs::SecurityModel::Proven => SecurityModel::Proven,
+			},
+			min_bits: security.min_bits,
+			blowup_factors: security.blowup_factors,
+			fri_folding_factors: security.fri_folding_factors,
+		}
+	}
+}
 impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 	fn verify_commitment(value: u64, blinding: [u8; 32], commitment: [u8; 32]) -> bool {
 		verifier::verify_commitment(value, blinding, commitment)
@@ -380,8 +438,14 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 
 	// --- Phase 9 (v2 zk-membership) ---
 
-	fn verify_deposit_v2(proof: &[u8], amount: u64, leaf: &[u8; 32]) -> bool {
-		verifier::deposit_v2::verify_deposit_v2(proof, amount, leaf)
+	fn verify_deposit_v2(
+		proof: &[u8],
+		amount: u64,
+		leaf: &[u8; 32],
+		security: &pallet_proofs::StarkSecurity,
+	) -> bool {
+		Self::accepts(proof, verifier::envelope::AirId::Deposit)
+			&& verifier::deposit_v2::verify_deposit_v2_with(proof, amount, leaf, &Self::security(security))
 	}
 	fn verify_spend_v2(
 		proof: &[u8],
@@ -392,10 +456,13 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 		change_leaf: &[u8; 32],
 		change_pkd: &[u8; 32],
 		purchase_mode: bool,
+		security: &pallet_proofs::StarkSecurity,
 	) -> bool {
-		verifier::spend_v2::verify_spend_v2(
-			proof, root, nullifier, pkd, price_or_amount, change_leaf, change_pkd, purchase_mode,
-		)
+		Self::accepts(proof, verifier::envelope::AirId::Spend)
+			&& verifier::spend_v2::verify_spend_v2_with(
+				proof, root, nullifier, pkd, price_or_amount, change_leaf, change_pkd, purchase_mode,
+				&Self::security(security),
+			)
 	}
 	fn verify_spend_auth_v2(auth: &[u8], public_inputs: &[u8], withdraw: bool) -> bool {
 		verifier::verify_spend_auth_v2(auth, public_inputs, withdraw)
@@ -413,12 +480,62 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 	fn v2_zero_change_leaf() -> [u8; 32] {
 		verifier::spend_v2::zero_change_leaf()
 	}
+	fn v2_note_leaf(value: u64, blinding: &[u8; 32], pkd: &[u8; 32]) -> [u8; 32] {
+		verifier::v2::note_hash(value, blinding, pkd)
+	}
+	fn v2_spent_zero_subtrees() -> alloc::vec::Vec<[u8; 32]> {
+		verifier::v2::spent_zero_subtrees()
+	}
+	fn v2_spent_path(key: u64, leaf: &[u8; 32], siblings: &[[u8; 32]; 64]) -> alloc::vec::Vec<[u8; 32]> {
+		verifier::v2::spent_path(key, leaf, siblings)
+	}
+	fn verify_credential(
+		proof: &[u8],
+		root: &[u8; 32],
+		rwa_id: &[u8; 32],
+		class: u64,
+		tag: &[u8; 32],
+		binding: &[u8; 32],
+		security: &pallet_proofs::StarkSecurity,
+	) -> bool {
+		Self::accepts(proof, verifier::envelope::AirId::Credential)
+			&& verifier::credential_v2::verify_credential_proof_with(
+				proof, root, rwa_id, class, tag, binding, &Self::security(security),
+			)
+	}
+	fn verify_fee_credit(
+		proof: &[u8],
+		root: &[u8; 32],
+		value: u64,
+		nullifier: &[u8; 32],
+		context: &[u8; 32],
+		security: &pallet_proofs::StarkSecurity,
+	) -> bool {
+		Self::accepts(proof, verifier::envelope::AirId::Fee)
+			&& verifier::fee::verify_fee_proof_with(
+				proof, root, value, nullifier, context, &Self::security(security),
+			)
+	}
 }
 
 parameter_types! {
 	pub const PrivateBaseFee: Balance = MICRO_UNIT;
+	/// Half of every consumed base fee is burned, the rest goes to the block author.
+	pub const PrivateFeeBurn: Perbill = Perbill::from_percent(50);
+	/// A root stays acceptable for an hour after it stops being current,
+	/// until governance sets `RootRetention`.
+	pub const DefaultRootRetention: BlockNumber = HOURS;
+	pub const MaxRootRetention: BlockNumber = 7 * DAYS;
 	pub const PaymasterPalletId: PalletId = PalletId(*b"nll/pay0");
 	pub const PoolPalletId: PalletId = PalletId(*b"nll/pool");
+	/// A v2 RWA purchase the RWA chain has not confirmed after a week can be refunded.
+	pub const RwaEscrowPeriod: BlockNumber = 7 * DAYS;
+	/// Sovereign account of the RWA parachain (para 2001) on this chain.
+	/// Computed as: Sibling(2001).into_account_truncating().
+	pub RwaChainSovereign: AccountId = {
+		use polkadot_parachain_primitives::primitives::Sibling;
+		Sibling::from(2001u32).into_account_truncating()
+	};
 	// REMOVED: GenesisCommitments - faucet logic deprecated
 }
 
@@ -449,6 +566,7 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 		_note_value: u64,
 		tx_id: [u8; 16],
 		ownership_commitment: [u8; 32],
+		units: u64,
 	) {
 		use codec::Encode;
 		use xcm::latest::prelude::*;
@@ -458,17 +576,20 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 
 		// Encode call: [pallet_index=51][call_index=4][args SCALE]
 		// Matches RWA runtime: pallet_index(51) = RwaMarketplace, call_index(4) = xcm_record_purchase
-		// buyer/destination is intentionally NOT encoded — it must not travel in the
+		// buyer/destination is intentionally NOT disclosed — it must not travel in the
 		// XCM message or it would reveal the buyer's AccountId on the RWA chain.
-		// Ownership is claimed via ownership_commitment blinding at redemption time.
+		// The `buyer` argument slot is filled with zeros so the SCALE layout still
+		// matches; ownership is claimed via ownership_commitment at redemption time.
 		let mut call_data = alloc::vec::Vec::new();
 		call_data.push(51u8); // RwaMarketplace pallet index
 		call_data.push(4u8);  // xcm_record_purchase call index
 		asset_id.encode_to(&mut call_data);
+		[0u8; 32].encode_to(&mut call_data); // buyer (withheld)
 		spend_tag.encode_to(&mut call_data);
 		nullifier.encode_to(&mut call_data);
 		tx_id.encode_to(&mut call_data);
 		ownership_commitment.encode_to(&mut call_data);
+		units.encode_to(&mut call_data);
 
 		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2001)]));
 		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
@@ -494,25 +615,81 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 		}
 	}
 
-	/// Send XCM to RWA chain to transfer an ownership record from old_tx_id to new_tx_id.
-	/// Called by `relist_private` after verifying the reseller's ownership proof.
+	/// Send XCM to RWA chain to record a v2 ownership-note redemption.
+	/// Called by `redeem_ownership_v2` after the ownership note is spent.
 	///
-	/// Encodes: pallet_index=51, call_index=5 (xcm_transfer_ownership)
-	fn send_transfer(
-		old_tx_id: [u8; 16],
-		new_ownership_commitment: [u8; 32],
-		new_tx_id: [u8; 16],
+	/// Encodes: pallet_index=51, call_index=6 (xcm_record_redemption)
+	fn send_redemption(
+		rwa_id: [u8; 32],
+		ownership_nullifier: [u8; 32],
+		claimant: [u8; 32],
+		tx_id: [u8; 16],
+		units: u64,
 	) {
 		use codec::Encode;
 		use xcm::latest::prelude::*;
 
+		let asset_id = u32::from_le_bytes([rwa_id[0], rwa_id[1], rwa_id[2], rwa_id[3]]);
+
 		// Encode call: [pallet_index=51][call_index=6][args SCALE]
 		let mut call_data = alloc::vec::Vec::new();
 		call_data.push(51u8); // RwaMarketplace pallet index
-		call_data.push(6u8);  // xcm_transfer_ownership call index
-		old_tx_id.encode_to(&mut call_data);
-		new_ownership_commitment.encode_to(&mut call_data);
-		new_tx_id.encode_to(&mut call_data);
+		call_data.push(6u8);  // xcm_record_redemption call index
+		asset_id.encode_to(&mut call_data);
+		ownership_nullifier.encode_to(&mut call_data);
+		claimant.encode_to(&mut call_data);
+		tx_id.encode_to(&mut call_data);
+		units.encode_to(&mut call_data);
+
+		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2001)]));
+		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
+			Instruction::<()>::UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None },
+			Instruction::<()>::Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				fallback_max_weight: Some(Weight::from_parts(500_000_000, 64 * 1024)),
+				call: call_data.into(),
+			},
+		]);
+
+		let mut dest_opt = Some(dest);
+		let mut msg_opt = Some(xcm_msg);
+		match xcm_config::XcmRouter::validate(&mut dest_opt, &mut msg_opt) {
+			Ok((ticket, _)) => {
+				if let Err(e) = xcm_config::XcmRouter::deliver(ticket) {
+					log::debug!(target: "proofhub::xcm", "RWA redemption XCM deliver failed: {:?}", e);
+				}
+			}
+			Err(e) => {
+				log::debug!(target: "proofhub::xcm", "RWA redemption XCM validate failed: {:?}", e);
+			}
+		}
+	}
+
+	/// Send XCM to RWA chain to record a settled sealed-bid auction.
+	/// Called by `settle_auction` once the winning bid is known.
+	///
+	/// Encodes: pallet_index=51, call_index=7 (xcm_record_auction_result)
+	fn send_auction_result(
+		rwa_id: [u8; 32],
+		auction_id: u32,
+		price: u64,
+		units: u64,
+		ownership_commitment: [u8; 32],
+	) {
+		use codec::Encode;
+		use xcm::latest::prelude::*;
+
+		let asset_id = u32::from_le_bytes([rwa_id[0], rwa_id[1], rwa_id[2], rwa_id[3]]);
+
+		// Encode call: [pallet_index=51][call_index=7][args SCALE]
+		let mut call_data = alloc::vec::Vec::new();
+		call_data.push(51u8); // RwaMarketplace pallet index
+		call_data.push(7u8);  // xcm_record_auction_result call index
+		asset_id.encode_to(&mut call_data);
+		auction_id.encode_to(&mut call_data);
+		price.encode_to(&mut call_data);
+		units.encode_to(&mut call_data);
+		ownership_commitment.encode_to(&mut call_data);
 
 		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2001)]));
 		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
@@ -529,11 +706,11 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 		match xcm_config::XcmRouter::validate(&mut dest_opt, &mut msg_opt) {
 			Ok((ticket, _)) => {
 				if let Err(e) = xcm_config::XcmRouter::deliver(ticket) {
-					log::debug!(target: "proofhub::xcm", "RWA transfer XCM deliver failed: {:?}", e);
+					log::debug!(target: "proofhub::xcm", "RWA auction XCM deliver failed: {:?}", e);
 				}
 			}
 			Err(e) => {
-				log::debug!(target: "proofhub::xcm", "RWA transfer XCM validate failed: {:?}", e);
+				log::debug!(target: "proofhub::xcm", "RWA auction XCM validate failed: {:?}", e);
 			}
 		}
 	}
@@ -553,6 +730,7 @@ impl pallet_proofs::AccessKeyDispatch for AccessGateXcmDispatch {
 		nullifier: [u8; 32],
 		tx_id: [u8; 16],
 		access_key_commitment: [u8; 32],
+		terms: Option<pallet_proofs::AccessTerms>,
 	) {
 		use codec::Encode;
 		use xcm::latest::prelude::*;
@@ -566,6 +744,7 @@ impl pallet_proofs::AccessKeyDispatch for AccessGateXcmDispatch {
 		nullifier.encode_to(&mut call_data);
 		tx_id.encode_to(&mut call_data);
 		access_key_commitment.encode_to(&mut call_data);
+		terms.encode_to(&mut call_data);
 
 		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2003)]));
 		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
@@ -312,6 +312,10 @@ mod runtime {
 	// Proof Hub: proofs pallet
 	#[runtime::pallet_index(51)]
 	pub type Proofs = pallet_proofs;
+
+	// NULLA/USD price feed for USD-priced RWAs
+	#[runtime::pallet_index(52)]
+	pub type PriceOracle = pallet_price_oracle;
 }
 
 #[docify::export(register_validate_block)]
@@ -0,0 +1,45 @@
+//! Prove time for DepositV2Air and SpendAir at the default options.
+//!
+//! cargo bench --features prover              # single-threaded
+//! cargo bench --features concurrent          # parallel trace + prover
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use proofhub_verifier::prover::{
+    build_spend_trace, pk_digest, prove_deposit, prove_spend, zero_subtrees, SpendWitness, LEVELS,
+};
+
+fn spend_witness() -> SpendWitness {
+    let z = zero_subtrees();
+    let mut siblings = [[0u8; 32]; LEVELS];
+    siblings.copy_from_slice(&z[..LEVELS]);
+    SpendWitness {
+        v: 10_000,
+        b: [0x21; 32],
+        pkd: pk_digest(&[0xAB; 1312]),
+        index: 0,
+        siblings,
+        cv: 6_000,
+        cb: [0x37; 32],
+        cpkd: pk_digest(&[0xCD; 1312]),
+    }
+}
+
+fn deposit(c: &mut Criterion) {
+    let pkd = pk_digest(&[0xAB; 1312]);
+    let mut group = c.benchmark_group("deposit_v2");
+    group.sample_size(10);
+    group.bench_function("prove", |b| b.iter(|| prove_deposit(10_000, &[0x21; 32], &pkd)));
+    group.finish();
+}
+
+fn spend(c: &mut Criterion) {
+    let w = spend_witness();
+    let mut group = c.benchmark_group("spend_v2");
+    group.sample_size(10);
+    group.bench_function("trace", |b| b.iter(|| build_spend_trace(&w)));
+    group.bench_function("prove", |b| b.iter(|| prove_spend(&w, 4_000, 1)));
+    group.finish();
+}
+
+criterion_group!(benches, deposit, spend);
+criterion_main!(benches);
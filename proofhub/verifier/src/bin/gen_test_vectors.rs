@@ -0,0 +1,17 @@
+/// Print the hash test vectors of a domain as JSON — the contents of
+/// `test-vectors/<domain>.json`.
+/// Usage: gen_test_vectors <v2|v3>
+
+use proofhub_verifier::vectors::{json, Domain};
+
+fn main() {
+    let domain = match std::env::args().nth(1).as_deref() {
+        Some("v2") => Domain::V2,
+        Some("v3") => Domain::V3,
+        _ => {
+            eprintln!("Usage: gen_test_vectors <v2|v3>");
+            std::process::exit(1);
+        }
+    };
+    print!("{}", json(domain));
+}
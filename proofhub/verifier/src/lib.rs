@@ -158,8 +158,148 @@ const POSEIDON_RF:     usize = 8;
 const POSEIDON_RP:     usize = 56;
 const POSEIDON_ROUNDS: usize = POSEIDON_RF + POSEIDON_RP; // 64
 
+/// Round constants rc[round][col] = F128(LE16(BLAKE3("poseidon_f128_rc_v1"
+/// ‖ 0x01 ‖ round ‖ col))), reduced mod p. Precomputed so trace builders and
+/// periodic columns do not hash per round; `poseidon_rc_table_matches_blake3`
+/// checks the table against `poseidon_rc_derive`.
+const POSEIDON_RC: [[u128; POSEIDON_T]; POSEIDON_ROUNDS] = [
+    [0x8dc4876f917d5faafe5f1cadf46e88aa, 0xe05da077817d1b9558cfd03f731a5646,
+     0x96cacb49e40e076409717c0f2731948e, 0x984bda987cb7888d99549e0143902742],
+    [0x56813cdb75215481e1ca1d2ad34a055c, 0xc110c45e1e89e8727bca056076f4dd40,
+     0xd0067346eac30b8c4d096e2006ba0e56, 0x9af340caa1e9b4dec6d5bff9fee28c75],
+    [0xaf4e0caf996a22e416dd3f9eb2d0c147, 0x1797c0611f7b21ac68b6563d13f31825,
+     0x26cc9a8445ad6f0388ce1bf5309d09d1, 0x43b34e8a50cb5c242268bbc88e5b24e4],
+    [0xa2afbe1f0562b440bd04a37e0029ca94, 0xd97902ee821af665d6a7a833542e16ca,
+     0x5910f8fac618d4f895426995104ed9f6, 0x7a0af32656343b92ace2a667004ac944],
+    [0x108e2e1ccb85f0c407ed96dfd8485212, 0xaa6e57ed91f72a754ee2005e686dd754,
+     0x05275da9ebdbc6784b4a4c6527d4bdc6, 0x7222b7a89970cedbb58003cf3e56da88],
+    [0x2fac8b1d76452a5222d7b7667b5f48fb, 0xdcbd6729bc6d66121267ac9bceae43c1,
+     0x2614310317dc08d8f1e562fa3617a402, 0xd2c1a74e97492e9025f4919e34237f17],
+    [0x6dd5952d0297e011e739279ed99d0db8, 0xbfa50eb881e7a4b5f098ad0031eb7bde,
+     0x84fd9e95cfb04729a1c2fdb1c8794a77, 0x0ba6c15914cb4c956eae601bcc80611e],
+    [0x617252daeb74bae46188b87bdc358891, 0x5b74ff8737f3896e43ab733da877311e,
+     0xddec06807084302c8727a4fc6884597c, 0x85c82cd3d5a531197bc9f0a9d3d1d492],
+    [0xb277192167b0c0c173a2112f054f2cf2, 0x020d307abec5326d95a6eb84d55cf1ad,
+     0xbf847d5fc368c7073feda8dd5ab647cd, 0xf4ed2809f2735962d9dd288b00fb01ca],
+    [0x2bda3d32490c9e246fedb369ec6bb04e, 0x4637e1fef3d92077183ef86231a8055c,
+     0x32542196a7dd110e6b48664fb59296e1, 0x53c0a0c59e7bb8b71b5423fc63b7dad7],
+    [0xd59153c41b02a71c6f5a3165eae18a0a, 0xb3efe3d73096a3224b2461729d2209cf,
+     0x8e1b934663b275af4c0f5e559761298f, 0x37ae3bfa21355315b3018071ce79d8f6],
+    [0xbe5bb0dcd6217e436ca42ae915adbb43, 0x29120896e7be5897377144d93e0325d9,
+     0x304a43323e78d2bfe3cacf070a8df858, 0x2e26ed5e8c363b7e399e4fc69d890600],
+    [0x5a2cfdbf2d1b4410dc6f84d2ad6b7734, 0xff754de317a2d92aaef5cae023e3d544,
+     0xa617fb837a39a9d1f7655f1fdf07ac95, 0x898451697d327b5cc2f46368d94f1853],
+    [0xe8267bd86f58837c294bc10095c0db8e, 0xdc8bb6c57f1ff530b00c0947f21d1c2c,
+     0x0931b9b6f197d556fbae20267d093c99, 0x74eb25cd3d9cb7821deb384cc2a9a079],
+    [0xe9d8a7b159ef1efe8733328739b9e48e, 0x65c36bd43e7f4f7fe0e39f5eb9a300cb,
+     0x39e40c1afe3efbbae77e44315316f785, 0x7768e3e5d9c41d393425cfeef1207ed1],
+    [0x5136805b04588296050ec2a4ac01ee89, 0x74b3708c8951f7f49f521faeb2a39f0e,
+     0x1c2aa388ab1053bb6f1b946c4759d8ba, 0xeffe79fb1ed311073f9176bde0ab340f],
+    [0x3ff41033e26ffa02d906625fa6995680, 0xc2ef22fbdec98ca89731619632629bae,
+     0x81e4aae90c50d8ed25f36342d545d447, 0x519e7ca6ac783c22be9b0863a2451b12],
+    [0x79d2924b0bfdb56feda31d896ae64b09, 0x8109dc4dd693cc3ddc7ab58abb2e8a33,
+     0x9c7b65a6daf83e2d1f75b91937458d86, 0x1f5dfc4aa5992e9cdcf607db5030ae15],
+    [0x22283e817ed80cefb0d54acdd3f027e1, 0x583968bd976244afca908749d55caae8,
+     0x6224c7f5e3e5a0e4bc4da46675bb6c2f, 0x97a6577253d730186ca2e9e71426ef04],
+    [0xc76738bb9c8ec2459daa2f07d0db984c, 0x933c6ce10525546f4b65d7cc2c86efc2,
+     0xbd0081ccd745b8fc3736e0b9e960389f, 0x893a628a1e85cf8d40d5f04db74c9759],
+    [0xed24df2606870f7b5ca0884f0a2a2088, 0x13d5cae48e54b6ffdd0467c9fbd18324,
+     0x50194c6e74198aaa0ff261a267e79d3f, 0xf4c5fbf558bc16c9c952db508e13cf9c],
+    [0xa717e9ae84874cee5d034d7f376a204b, 0x48db09fe1827fa06f1f77aa0fbe04dbc,
+     0xdda9c8c96db8313687c50e9400a74fda, 0xa5267eb5c5c34d903da32afa6e80464d],
+    [0x6111520670a6acf7304774f502d36fc4, 0xf22d57b4dfc68daa9b0bdf33f0ab280c,
+     0xc3e3e789c8114eca9914dd9c1acf99c8, 0x8d61b6edc9725caa3d9cda4fbb7c9465],
+    [0xa3b14b0eff124cb099902f58cdbcb6bd, 0x09c7218e86c6cd1f7e01b90e7455cb07,
+     0x2ee0e52b4f978fb30a17f20c9deffd81, 0xadca57f461c53796fb07b9db92bc9d38],
+    [0x858371d8854a6e95cc6acf31dc484f11, 0x54ae3a62fe66a6d18f36faebf0619308,
+     0xd666b594ab7e88078ea516fd315ab1e7, 0xf3ae9c18d188c4b7cc25e9d77fa699a1],
+    [0xaad4345ef83c98f56a53147dbbeca66a, 0xdf7f1d8a530f395e00d560e4bbd8c7cd,
+     0xfe1ec136bc2b3854f36c053729eec1e8, 0xa4018365743a39028bedcc6a2ab3d28b],
+    [0xbd657f3e2ffe60c35bafa8be0efa7e83, 0x6fd9f0456373829d3ca7c30fb26480fa,
+     0x259f058d138af46f8fcbef47865a6c82, 0x559405333790515f991f87b240d9172d],
+    [0x56def430b9f6ec17e53526b245fe2b17, 0x357171c8a7fbe4d5f92392fc37642789,
+     0xf8dca7068b58c867d8f9f829b2a6c8a6, 0x320b9b4ae1bda8f5e6ae6ae4c46cfdd6],
+    [0x8ffd0e43a2a6e299da4cc26a5764542f, 0x550a88a3eda9570a9ce0cb3ca6b936e6,
+     0xef5aeba30a1f92b391dca24afaa7dc4f, 0xb875c45b824d803af0e59fbd5cd212e5],
+    [0xa469391381713269d10574631e8d1834, 0xccd85e53d27a82d2fcdd027255e3e68e,
+     0xe4f338848f74469c34f2fbd0d38ba787, 0xf5bc08d7ee1e72211f9bbc8f8382f6f1],
+    [0x32751e12acd0d29eed6dbee5515dea81, 0xb9ef4b6b1af4a128b0227db9a0ca893f,
+     0xde829b048b5f3dd125a9fb8ed23745ca, 0x9e8527fb088297add861b503ef65b1cc],
+    [0xb1a3421580fd030c342801c12a07ac6d, 0x4ec05a1b8b71ce0dab6bf690c7673239,
+     0x9ca85e1451a352029295f0291e1630c8, 0x8af88a7418e131fed1b5a4e7976a3d3d],
+    [0x59f6ceea1b654b2f4b11e66575fb8ff2, 0x9d2597bff29828d122342102bd50464c,
+     0x9798435b382e2189bffaecc1fb42ae1f, 0x0d0466789203256aebba995b8f7dd515],
+    [0x50a8a6b66f3e4d606c47749f5acfb43a, 0x467f2d9f55ae67d1431ae0739123c837,
+     0x648b9e6bfde0c0dc565e1d65f49b0a75, 0x39833055fde6a0b801da7efcf30726fe],
+    [0xe871e3e6b87d61ddaa8ef4ea363283af, 0x4e17e10dc40ac8aabfd974d222a0a69a,
+     0x35fe81bbee33825ad0407efbce064fb2, 0xb35f663f9d9755a7a071730c3a5d3404],
+    [0x0912047e00db729546f62f11d7cd8854, 0x6266a6d724adcf532df821c291fed37c,
+     0xd9664ec8b6fbf544630565a6dea600e5, 0x320aa907b3d9bb3a912a2adc00cb555b],
+    [0x591ae2ad6cfe803f01208bd2b58b7562, 0xfaae2e0b54db75d71c932dc716ec18d5,
+     0xdfe5c5c44bc34e5d217ebe3afe684736, 0xa6ce4614bc9d547d1d90bd3cd8bf034e],
+    [0xa740d44f8486a19875eaca1dc594a939, 0x0018bdcc9aef4a7f82c4d0461f554a44,
+     0x1574ae3a3bab54b356bf28980b1222a7, 0x15330bca44626f79f1a2298ffb0fa879],
+    [0xcc7953d45414a0a6668b973bc9ce08f9, 0xa584101f4ca18ff646ea2099f7360c5b,
+     0x2d73a28f678eef91af31b333eaad24c4, 0xfea91dc2fc4303de87735798b94fd3cd],
+    [0xd7aa24b05fbe248b2f14e8f4ffa16c88, 0x27754794e7961a7f1d54ffe24ac837f4,
+     0xbed03bdfbbec1520d1faf2d46332943f, 0x0a51c1061e90aea21a57f31c2c362de6],
+    [0x60995adf847701a43fc8351767bb8283, 0xbdadc992176499494e61ab921d1c4863,
+     0x68f8a93ffe93556e4d90b03abcad13ed, 0xc57f3c1b38d943e8cff27b41f01591ff],
+    [0x7b04490a2c815f0f777dc1e6c6953d43, 0x8c5b3de6a226db9b0e71601aae33283c,
+     0x65dea115d36ce366eff171d1ede30dc6, 0x687d3e1f98501ff34704d9f1de33a842],
+    [0x8b62930d205522a96f0c360c0bda50b1, 0x5a2726a90a5317f1ff49c452ee17a8f0,
+     0x747baef602a824e0fdfc6de4652fb1e4, 0xb33c7d92879eca6b4f7af79646c04b0f],
+    [0x8e2966857c71c91ef51e10fbecee7aa1, 0x520dbc32aa307ddc0564d28dc1161dac,
+     0x22df6a2413abf0d2cc5a973955b2142e, 0x47d7bcbad71e9c973474de7b023bea97],
+    [0x25e326d99cf802a5331584e80c085500, 0x927f1031ebf98d81fff4197e7bb404cd,
+     0x75aaa5aba9c733b05d064c14d11be1d6, 0x56294a0853b608e524199733a71fcb6a],
+    [0xa731ae5eadb1e64adf866e87d30f9d22, 0x3cd62f8b774ed62074013a67d8478da5,
+     0x2b8b9778d6c0543dfbe45cceaeba1b54, 0xc09db49afc77aeeb2e36ce328ff286f3],
+    [0x23d2e11aac27f11ca49038027fe8864f, 0x67f1f76ce59d016d802a7af9645ccc81,
+     0x7bdcbbb1fed9529e1a048a21ccca5146, 0x811c8e58b4ac790f8b984f245e995e10],
+    [0xce2838bf722d4f3d870b00e76c962aae, 0xb8f325021b8737e064252fb58e52a7be,
+     0x741369efa21ff8ffccbdb60f2d7245c3, 0x5e8ceb712dd9bb408dabd0a914a40565],
+    [0xe54afb1941f7f4c84ec2a06a83418acd, 0xaa87f782aa77eb74343568ce110707ec,
+     0x3e3c62a4e096d47be294f3c31565dcf9, 0xfe735c4df1af07a02adbf25344f7ba40],
+    [0xb8a81a0b4f67f2ef3417e301b2055e87, 0x8ebd73eddf64073c19db47b0bb1a473a,
+     0x2dc970472da9352bcb866447cffdb3cb, 0xaaa1c3e55176de1ac9c0b81342d0b2d8],
+    [0x86b048d1aedef5f7b28699244a2cf5da, 0x4987b6e99c8e88b43fce76d25d2bacbb,
+     0xeda8be45453aa5f362402523cf6d34c8, 0x6e26ecb4911b56f10da0b09417edf7fb],
+    [0x4712a1a87a6e91b376a7349c09a6e655, 0x21c2458db583d4ba0b2796226e835e72,
+     0x7f1a50866aa4f822b734e3e81dcdb4b3, 0x7a435e96957adf85ac553591b210e992],
+    [0xb585711ede2b629e97adb198aa294f8f, 0x777294e4d19944fc6a2d397194e933bb,
+     0xffc514272ebac5f5f71863f8a60c027f, 0x3e6e879e859ee8bebceb338a6c4c3b1c],
+    [0x0bfe2e77d202642a3286a1c7e0c42a64, 0x34c28324704629f40397754e772526bc,
+     0xa9a0f9cbc6e5472595a90271ac52072d, 0xbb348fd1843365c1d13dbe12853946f7],
+    [0x3d75305a07c134d86b3b65e01762cfb6, 0x4b58394f669de5f85a50b3112c7796c0,
+     0xc51f91189742c41740d68753abd1ff59, 0x3e92738cb822de330a17dec2f48c8f41],
+    [0x5aa74d7b6d23b9d24b636d6d49698bbb, 0x0f37d5bd2e78e37a78b41fdaa249f36d,
+     0x7326491545c42f600390a300f54720bf, 0x8deb2b16d783cef346838a896d2703a3],
+    [0x08e6555674d077b3e807743a7aa13965, 0x3190d8542b42108cac20920a929ffe26,
+     0x4b8040d6cfaead90deeee96d5622cb17, 0x93a5166028531f391d56d2e81504f7fd],
+    [0xe4234db74ff1f16b3fdefc2d31746433, 0x42d9a6b4b73aa27c97a851f7c811a944,
+     0xd212582ac5b0003875833cf958f2416e, 0xaa8249574a1037bf35e4592d5f1e1032],
+    [0x3f3fd2bcf0700e880c6bcb1ea1af1172, 0x3fcc18bf2bd4956242374e5519f725b0,
+     0x8667a8f93ee398e0e2d9aff9dc1a78e6, 0xbcdf4cd3864bb472a12f9efd649c1101],
+    [0xc323884523241587fcb1d3ed519b24b5, 0xbaf25d113c5fd4be8e100a27adb59aab,
+     0x137d47ed9924ebfb217cf55347e1b05c, 0x06c27bdb39a883d8b7cfce4170e10d56],
+    [0xaeca9d6caa42e71cf2d58fad58c199c8, 0x75a3e6537745328ffb7a6c370b64d7c6,
+     0x8ba5570f062e198e626d6e20b04ab001, 0x28ac2757d35a372cd844fde27c489eea],
+    [0x2ef5f2a4a78f52bacab8933cf1c6b4dd, 0xbe69ac4a9188bd175e5afc59fd24d1cf,
+     0x6f7f4e601ae191d4e88673935b42f0e5, 0x7ec16b6ce34468d8deca3e3afa8c221b],
+    [0xefc654cbb4e57ddce324e3b6f64ea195, 0x6edfc2260427f1e68ef01d7d9a0eeade,
+     0x267e8c7a6e6efd53ae15043b0e5fd222, 0x911be926991137d89ab0459c02f1c5d2],
+    [0x58277fbd810c0c59972e818d4cd5366a, 0xcd480e9b695097d7fdc1e0713a0a8b37,
+     0x6fedf1f717d6170cbf3d827be2c036af, 0x71aef260f66b6b333cd15721129f85d8],
+];
+
 #[inline]
 fn poseidon_rc(round: usize, col: usize) -> BaseElement {
+    BaseElement::new(POSEIDON_RC[round][col])
+}
+
+#[cfg(test)]
+fn poseidon_rc_derive(round: usize, col: usize) -> BaseElement {
     let mut buf = [0u8; 22];
     buf[..19].copy_from_slice(b"poseidon_f128_rc_v1");
     buf[19] = 0x01;
@@ -199,7 +339,7 @@ fn poseidon_periodic() -> Vec<Vec<BaseElement>> {
 /// Evaluate one Poseidon round.
 fn poseidon_eval_round_base(s: &[BaseElement; 4], round: usize, out: &mut [BaseElement; 4]) {
     let is_full = if is_full_round(round) { BaseElement::ONE } else { BaseElement::ZERO };
-    let rc = [poseidon_rc(round, 0), poseidon_rc(round, 1), poseidon_rc(round, 2), poseidon_rc(round, 3)];
+    let rc = POSEIDON_RC[round].map(BaseElement::new);
     let a = [s[0] + rc[0], s[1] + rc[1], s[2] + rc[2], s[3] + rc[3]];
     let ac = [a[0].square()*a[0], a[1].square()*a[1], a[2].square()*a[2], a[3].square()*a[3]];
     let b0 = ac[0];
@@ -590,6 +730,258 @@ pub fn verify_purchase_proof(proof: &[u8], rp_commitment: &[u8; 32], change_rp_c
     verify_stark_purchase(proof, rp_commitment, change_rp_commitment, price)
 }
 
+// ===================================================================
+//  Proof envelope — versioned v2 STARK proofs
+//
+//  envelope = MAGIC (4) ‖ SCALE(ProofHeader) ‖ winterfell proof bytes
+//
+//  The header names the AIR, its version (trace layout, Poseidon
+//  parameters, transcript domain) and the STARK options the proof was
+//  generated with. Verifiers look (air, version, options) up in
+//  REGISTRY, dispatch on the version and require the proof's own
+//  options to equal the header's, so a proof cannot claim parameters
+//  it was not made with.
+//
+//  Proofs without the magic predate the envelope and are read as
+//  version 1. A raw winterfell proof starts with its trace width, so
+//  none of the AIRs below can produce one that begins with MAGIC.
+//
+//  Either way the proof must then meet the caller's StarkSecurity:
+//  a conjectured or proven security floor plus the blowup and FRI
+//  folding factors it allows. The runtime passes the lane's policy in
+//  through `ProofVerify` and keeps a retired-version set on top of
+//  REGISTRY, letting governance drop a version once wallets have moved
+//  off it.
+// ===================================================================
+pub mod envelope {
+    use super::*;
+    use winter_verifier::{AcceptableOptions, BatchingMethod, Proof};
+
+    /// Envelope prefix.
+    pub const MAGIC: [u8; 4] = *b"NZKP";
+
+    /// Version of proofs without an envelope.
+    pub const LEGACY_VERSION: u16 = 1;
+
+    /// Version the provers in this crate emit.
+    pub const CURRENT_VERSION: u16 = 1;
+
+    /// The AIR a proof is for. The discriminant is the on-chain key of
+    /// the runtime's retired-version set.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum AirId {
+        #[codec(index = 0)]
+        Deposit = 0,
+        #[codec(index = 1)]
+        Spend = 1,
+        #[codec(index = 2)]
+        Ownership = 2,
+        #[codec(index = 3)]
+        Credential = 3,
+        #[codec(index = 4)]
+        Fee = 4,
+    }
+
+    /// STARK parameters carried in the header. Batching is Linear / Horner
+    /// for every registered version.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct StarkOptions {
+        pub num_queries: u8,
+        pub blowup_factor: u8,
+        pub grinding_factor: u8,
+        pub fri_folding_factor: u8,
+        pub fri_remainder_max_degree: u8,
+        /// Extension degree of the composition field: 1, 2 or 3.
+        pub field_extension: u8,
+    }
+
+    impl StarkOptions {
+        pub fn proof_options(&self) -> ProofOptions {
+            let field_extension = match self.field_extension {
+                2 => FieldExtension::Quadratic,
+                3 => FieldExtension::Cubic,
+                _ => FieldExtension::None,
+            };
+            ProofOptions::new(
+                self.num_queries as usize,
+                self.blowup_factor as usize,
+                self.grinding_factor as u32,
+                field_extension,
+                self.fri_folding_factor as usize,
+                self.fri_remainder_max_degree as usize,
+                BatchingMethod::Linear,
+                BatchingMethod::Horner,
+            )
+        }
+    }
+
+    /// Options of every pre-envelope proof and the provers' default:
+    /// 28 queries at blowup 8, no grinding, base field. About 83
+    /// conjectured bits.
+    pub const V1_OPTIONS: StarkOptions = StarkOptions {
+        num_queries: 28,
+        blowup_factor: 8,
+        grinding_factor: 0,
+        fri_folding_factor: 8,
+        fri_remainder_max_degree: 127,
+        field_extension: 1,
+    };
+
+    /// 100 conjectured bits: 26 queries at blowup 16. The f128 base field
+    /// still leaves headroom at the spend trace's LDE size.
+    pub const SECURE_100: StarkOptions = StarkOptions {
+        num_queries: 26,
+        blowup_factor: 16,
+        grinding_factor: 0,
+        fri_folding_factor: 8,
+        fri_remainder_max_degree: 127,
+        field_extension: 1,
+    };
+
+    /// 128 conjectured bits, capped by BLAKE3-256 collision resistance:
+    /// 30 queries at blowup 16 plus 16 grinding bits, over the quadratic
+    /// extension so the field bound is not the limit.
+    pub const SECURE_128: StarkOptions = StarkOptions {
+        num_queries: 30,
+        blowup_factor: 16,
+        grinding_factor: 16,
+        fri_folding_factor: 8,
+        fri_remainder_max_degree: 127,
+        field_extension: 2,
+    };
+
+    /// Every option set a version-1 proof may be made with.
+    pub const V1_PRESETS: &[StarkOptions] = &[V1_OPTIONS, SECURE_100, SECURE_128];
+
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct ProofHeader {
+        pub air: AirId,
+        pub version: u16,
+        pub options: StarkOptions,
+    }
+
+    /// A supported (air, version) and the options its proofs may use.
+    pub struct Registered {
+        pub air: AirId,
+        pub version: u16,
+        pub options: &'static [StarkOptions],
+    }
+
+    /// Supported versions. A version is added here together with its
+    /// dispatch arm in the AIR's verify function.
+    pub const REGISTRY: &[Registered] = &[
+        Registered { air: AirId::Deposit, version: 1, options: V1_PRESETS },
+        Registered { air: AirId::Spend, version: 1, options: V1_PRESETS },
+        Registered { air: AirId::Ownership, version: 1, options: V1_PRESETS },
+        Registered { air: AirId::Credential, version: 1, options: V1_PRESETS },
+        Registered { air: AirId::Fee, version: 1, options: V1_PRESETS },
+    ];
+
+    fn is_registered(header: &ProofHeader) -> bool {
+        REGISTRY.iter().any(|r| {
+            r.air == header.air && r.version == header.version && r.options.contains(&header.options)
+        })
+    }
+
+    /// Which security bound `StarkSecurity::min_bits` applies to.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum SecurityModel {
+        #[codec(index = 0)]
+        Conjectured,
+        #[codec(index = 1)]
+        Proven,
+    }
+
+    /// What a verifier accepts, independent of the proof's version.
+    ///
+    /// `blowup_factors` and `fri_folding_factors` are bit sets: bit k
+    /// allows a factor of 2^k, and 0 allows any factor.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct StarkSecurity {
+        pub model: SecurityModel,
+        pub min_bits: u32,
+        pub blowup_factors: u8,
+        pub fri_folding_factors: u8,
+    }
+
+    impl StarkSecurity {
+        /// The floor every verifier applied before it was configurable.
+        pub const DEFAULT: StarkSecurity = StarkSecurity {
+            model: SecurityModel::Conjectured,
+            min_bits: 80,
+            blowup_factors: 0,
+            fri_folding_factors: 0,
+        };
+
+        pub fn acceptable(&self) -> AcceptableOptions {
+            match self.model {
+                SecurityModel::Conjectured => AcceptableOptions::MinConjecturedSecurity(self.min_bits),
+                SecurityModel::Proven => AcceptableOptions::MinProvenSecurity(self.min_bits),
+            }
+        }
+
+        /// Whether `options` uses an allowed blowup and folding factor.
+        pub fn allows(&self, options: &ProofOptions) -> bool {
+            let allowed = |set: u8, factor: usize| {
+                let k = factor.trailing_zeros();
+                set == 0 || (factor.is_power_of_two() && k < 8 && (set >> k) & 1 == 1)
+            };
+            allowed(self.blowup_factors, options.blowup_factor())
+                && allowed(self.fri_folding_factors, options.to_fri_options().folding_factor())
+        }
+    }
+
+    impl Default for StarkSecurity {
+        fn default() -> Self {
+            Self::DEFAULT
+        }
+    }
+
+    /// Prefix `proof` with an envelope header.
+    pub fn seal(air: AirId, version: u16, options: StarkOptions, proof: &[u8]) -> Vec<u8> {
+        let header = ProofHeader { air, version, options };
+        let mut out = Vec::with_capacity(MAGIC.len() + header.encoded_size() + proof.len());
+        out.extend_from_slice(&MAGIC);
+        header.encode_to(&mut out);
+        out.extend_from_slice(proof);
+        out
+    }
+
+    /// Split a proof for `air` into (version, header options, winterfell
+    /// bytes). Header options are None for a raw proof. None when the
+    /// header is malformed, names another AIR or is not in REGISTRY.
+    pub fn open(proof: &[u8], air: AirId) -> Option<(u16, Option<StarkOptions>, &[u8])> {
+        let mut rest = match proof.strip_prefix(&MAGIC[..]) {
+            Some(rest) => rest,
+            None => return Some((LEGACY_VERSION, None, proof)),
+        };
+        let header = ProofHeader::decode(&mut rest).ok()?;
+        if header.air != air || !is_registered(&header) { return None; }
+        Some((header.version, Some(header.options), rest))
+    }
+
+    /// Version of a proof for `air`, or None if it would be rejected by
+    /// `open`. Lets the runtime check its retired set before verifying.
+    pub fn version_of(proof: &[u8], air: AirId) -> Option<u16> {
+        open(proof, air).map(|(version, _, _)| version)
+    }
+
+    /// Open and parse a proof for `air` and check its parameters against
+    /// its header and `security`. Returns the version to dispatch on, the
+    /// proof and the options to verify it under.
+    pub(crate) fn open_stark(
+        proof_bytes: &[u8],
+        air: AirId,
+        security: &StarkSecurity,
+    ) -> Option<(u16, Proof, AcceptableOptions)> {
+        let (version, options, body) = open(proof_bytes, air)?;
+        let proof = Proof::from_bytes(body).ok()?;
+        if options.is_some_and(|o| *proof.options() != o.proof_options()) { return None; }
+        if !security.allows(proof.options()) { return None; }
+        Some((version, proof, security.acceptable()))
+    }
+}
+
 // ===================================================================
 //  Phase 9 — v2 zk-membership primitives (ZK_MEMBERSHIP_SPEC_V2.md)
 //
@@ -610,7 +1002,7 @@ pub mod v2 {
     pub const WITHDRAW_AUTH_DOMAIN: &[u8] = b"nulla_withdraw_auth_v2";
 
     #[inline]
-    fn iv_from_domain(domain: &[u8]) -> BaseElement {
+    pub(crate) fn iv_from_domain(domain: &[u8]) -> BaseElement {
         let d = blake3::hash(domain);
         BaseElement::new(u128::from_le_bytes(d.as_bytes()[..16].try_into().unwrap()))
     }
@@ -678,6 +1070,23 @@ pub mod v2 {
         pack(state[1], state[2])
     }
 
+    /// RWA asset id of a canonical `rwa_id`: `rwa_id[..4]` as LE u32, bytes
+    /// 4..32 zero — the layout the RWA chain decodes in `xcm_record_purchase`.
+    pub fn rwa_asset_id(rwa_id: &[u8; 32]) -> Option<u32> {
+        if rwa_id[4..].iter().any(|b| *b != 0) { return None; }
+        Some(u32::from_le_bytes(rwa_id[..4].try_into().unwrap()))
+    }
+
+    /// Ownership note leaf: NoteHash(units, b, pkd).
+    ///
+    /// Each rwa_id has its own ownership tree, so the asset is bound by the
+    /// tree root and the note value is the unit count. Spending with SpendAir
+    /// in purchase mode splits a holding: the 64-bit range check on the change
+    /// value rules out taking more units than the note holds.
+    pub fn ownership_leaf(units: u64, b: &[u8; 32], pkd: &[u8; 32]) -> [u8; 32] {
+        note_hash(units, b, pkd)
+    }
+
     /// Zero-subtree constants: Z[0] = [0;32], Z[i+1] = hash2(Z[i], Z[i]).
     /// Returns Z[0..=MERKLE_DEPTH] (21 entries). Root of empty tree = Z[20].
     pub fn zero_subtrees() -> Vec<[u8; 32]> {
@@ -784,6 +1193,533 @@ pub mod v2 {
         }
         node == *root
     }
+
+    /// Depth of the ownership spent set: a sparse Merkle tree with one slot
+    /// per `spent_key`, holding the nullifier of a spent ownership note or
+    /// Z[0] when empty.
+    pub const SPENT_DEPTH: usize = 64;
+
+    /// Spent-set slot of an ownership nullifier: the low 64 bits of nf_lo.
+    pub fn spent_key(nullifier: &[u8; 32]) -> u64 {
+        u64::from_le_bytes(nullifier[..8].try_into().unwrap())
+    }
+
+    /// Zero subtrees of the spent set, Z[0..=SPENT_DEPTH] (65 entries).
+    /// Root of the empty set = Z[64].
+    pub fn spent_zero_subtrees() -> Vec<[u8; 32]> {
+        let mut z = Vec::with_capacity(SPENT_DEPTH + 1);
+        z.push([0u8; 32]);
+        for i in 0..SPENT_DEPTH {
+            let prev = z[i];
+            z.push(merkle_hash2(&prev, &prev));
+        }
+        z
+    }
+
+    /// Nodes on the path of spent-set slot `key` holding `leaf`, from the
+    /// leaf (entry 0) to the root (entry SPENT_DEPTH). Bit d of `key` set
+    /// means the level-d node is a right child, as in `verify_merkle_path`.
+    pub fn spent_path(key: u64, leaf: &[u8; 32], siblings: &[[u8; 32]; SPENT_DEPTH]) -> Vec<[u8; 32]> {
+        let mut path = Vec::with_capacity(SPENT_DEPTH + 1);
+        let mut node = *leaf;
+        path.push(node);
+        for d in 0..SPENT_DEPTH {
+            node = if (key >> d) & 1 == 0 {
+                merkle_hash2(&node, &siblings[d])
+            } else {
+                merkle_hash2(&siblings[d], &node)
+            };
+            path.push(node);
+        }
+        path
+    }
+}
+
+// ===================================================================
+//  Poseidon2 permutation (v3 domain)
+//
+//  Poseidon2 (Grassi, Khovratovich, Schofnegger — ePrint 2023/323) over
+//  f128, p = 2^128 − 45·2^40 + 1, with t = 4 and α = 3 (gcd(3, p−1) = 1).
+//
+//  Rounds: R_F = 8, R_P = 84. The reference round-number script
+//  (calc_round_numbers.py, M = 128) gives 6 + 78; the numbers here add
+//  its margin of two full rounds and 7.5% of the partial rounds.
+//
+//  Round constants: the reference Grain LFSR, seeded with field = 1,
+//  sbox = 0, n = 128, t = 4, R_F = 8, R_P = 84 and 30 ones, clocked 160
+//  times, then self-shrinking; 128-bit big-endian samples ≥ p are
+//  rejected. 92 × 4 constants in round order; partial rounds use column
+//  0 only. `poseidon2_constants_from_grain` regenerates the table.
+//
+//  External matrix: M4 from the paper, [5 7 1 3; 4 6 1 1; 1 3 5 7; 1 1 4 6].
+//  Internal matrix: ones off the diagonal, diagonal (2, 3, 4, 5) — the
+//  first choice in 2..=5 for which every M_I^k, k = 1..8, has an
+//  irreducible degree-4 minimal polynomial (paper §5.3).
+//
+//  Layout: M_E; 4 full rounds; 84 partial rounds; 4 full rounds.
+// ===================================================================
+pub mod poseidon2 {
+    use super::*;
+
+    pub const T: usize = 4;
+    pub const RF: usize = 8;
+    pub const RP: usize = 84;
+    pub const ROUNDS: usize = RF + RP; // 92
+
+    /// External matrix M_E = M4.
+    pub const M4: [[u128; T]; T] = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+
+    /// Internal matrix minus the all-ones matrix: out_i = d_i · x_i + Σx.
+    pub const INTERNAL_DIAG_M_1: [u128; T] = [1, 2, 3, 4];
+
+    /// Round constants (Grain LFSR, see the section header).
+    pub const ROUND_CONSTANTS: [[u128; T]; ROUNDS] = [
+        [0x221b2ea2977dc93e4627d7acd622ae2d, 0x6e3f839d4c090054db19400d4e13578b,
+         0xe2b99add0ae61c893f493d1728918767, 0xe0a9ee0c58a7ed0cb671dd90fd398362],
+        [0x247e6d735ec48e3462b500fac3162c7a, 0x53ad10d438239c95749a8bc3d9ed3b64,
+         0xdfe54d6b8d535c2bcd15c9337516af98, 0xce1aa71b433f7c15db8e86bc457040ed],
+        [0xf1b46a9975107812571baa60dc29a3ba, 0x7227d0cea5cf8a85a25e889a219cecdc,
+         0x7a08801caba78fd1d3419aada21cbc9e, 0xda1669de3845e00a2be454bf9ae89b82],
+        [0x4809f452e7b993d99786fb592a28aa7d, 0x940ccf215008f69731b08d723fad663d,
+         0xef9f990fe0eba25cfbc8ae895a969d0f, 0x000ab0c5ab194a3026ba932689319782],
+        [0x0a9a0c9e900fe62932efacc939fcc8b6, 0x31572ed69ae0540d45a1acbe622227e1,
+         0x66ad001c7090a7d54e069f2a5fffd156, 0xe5db0d92e95e669c4cee809beddd23ed],
+        [0xad81d4a0d993e24e8da608c4c8bcc230, 0xee96ea10e8ef396e0cb8455588995dfc,
+         0x80ee2ad71bdad0086149f6d6b7de50ec, 0x441758334abad98538282ce513de0df6],
+        [0xcdc14eaeef61f31bb31e444937e9670b, 0x7e9936d6a5b6210188709e9507563f66,
+         0x97299063ddb11f3eb66b1a83edb6c6c3, 0x6be6c7f5ad571f2be4c5c4ec692bb57b],
+        [0xf5b5595762a9b7f124c04c20fc70e50a, 0x32e17c811401057a4e9a1eedd9934953,
+         0xbb80c6178252ffbb5aded7f539dd2034, 0x35bbaa624e2ddb607deb28741095e84a],
+        [0x042ce89eb6f4abf71d0f69895673fcc3, 0x4b801a5c63417ac15bc4e463d0788dfa,
+         0x7f463234d968b5b1d1e2c18a4e1868b2, 0xd030fe0ad713be7af6ceebccd32cf317],
+        [0x4290b2e51686133e8734cc6812f0a836, 0x8920a19d50c571664d55f02371feeabe,
+         0x6c43bcd43e3de2dee72e094b512df614, 0x371381b72d979b473227b0a3cbf792cd],
+        [0xb6e0d7b4d7228a94b017972e9617eb72, 0x5f5bd6a1b87d7ccfeef1d9ac92750ff1,
+         0x999ff31b4041c02d08e8547f4cf4a3c9, 0x65bddb4c925999ecb4d85d286501c7c9],
+        [0x83fe865648f0dbaae7866cb55cbdf271, 0x42d9694a6676f9c56eabfd4bf64c01f6,
+         0x1f239d80a68454edfadd9631caef68f1, 0xe56a153edbffeabf53b637733a4073f3],
+        [0x7ff6e1c1566ed8af366e583dcbbe9eaa, 0x2da3e7a8886ce7936e13e579b1a9d9f2,
+         0x54f2d607bacc145b3997a2cb160ed005, 0x3f4ee0662324e1f53a22511741cb36bf],
+        [0x4b798eeda491fa5ce13b11f9e621a74a, 0x76046640ede7edd4a7fc2b8f735b319a,
+         0x3a409fb4696f949d5a2785059fee4c5f, 0x00a981e70f91789697cd7c349e9728a7],
+        [0x5766b66bb602d1da8b490d49026884b9, 0x899f9161ad6ac5df8d2bacb4d42066c0,
+         0xcb3ab55627df1d0d7c34d577798654ef, 0x399d6f63761d9aca7df177f982ce2ef9],
+        [0xda21bed8c70c7d07e38ca6f062cc844b, 0xac266fdc328628d4148a0405c1ffc9ae,
+         0x75ff5fe68d89533121d71789f8f285cd, 0xa67fc836fe3930bca7e2049458577e0e],
+        [0x47afac4d49fb96d3ef97425fd391d661, 0x56b24a7905befb70231724e9690099da,
+         0x997ae28f4b76f98dad56a1641e3a90b5, 0xd85f9cef029da9581005a81a40cb57ef],
+        [0x061e3d348d0daf1cd24855b0191d73f1, 0xb6e21533177d6954e771c28add6a74c8,
+         0x8647ac9359e52a262ec5b9966ed93ec9, 0x120fa5e13df4bf10deccacbc7c0ab80d],
+        [0xe5447301d50baa4c7167648e30082c4c, 0xc6fd973090c3c9336151d320e3b2b00e,
+         0x3f55c3fb6400043e62aaa4ed8d8dc013, 0x43cdf29adef0042d68df9da680e05ed5],
+        [0x95c0cb4a8718362de630fc2be02ce300, 0xa49af2d4223aa8309d9b921e7fcbb36e,
+         0x6d8e19c3562469704fee0f16e9f46e93, 0x0f8ba12638ef6350ec3fcafc03244329],
+        [0x8f46d95344ef5a0b53df1d9d4efdea03, 0x75a1c8d556a23c2a67acc50a9e66e3db,
+         0xfa26d16be4217c24bf320cd4821df57b, 0x109036064a1d375f70f0500fc1d07687],
+        [0x2a719eb16ffc6d6d6ef8ead44dc161cc, 0x47499e5c7299796fc567af8684898184,
+         0xe20c1f904dc4be265a5008fb47c75013, 0x43d68e5511a615f7824a0163b37385ce],
+        [0x2d88b80af20ed6a1e29fe3e439e06e44, 0x67daa95159bc84976848497480da95f0,
+         0x96a62d0e910baf6a7208ac3617758cf2, 0xf898a594ba3515cc12b28976040c7821],
+        [0x08bf77200938a76375474c787c139de1, 0x1197ce606f6ef746405f379cea1f3cfb,
+         0x8c927d9f6388add85c98af5dbb0b15fc, 0x890cec20999c2150527cc2f91320d768],
+        [0xc225beb50ce20461d4782c8171d1efbd, 0xa1a29a349f7bb8c6d30cfb2ef9bd8625,
+         0xa7a8aed6011c148ef3d8fe333eb0193d, 0x1ba5ed478964a13ad49301f12bb1c0c2],
+        [0x5d886cf63e1089fddd7fcec56ef50b0f, 0x50020b18f96976a7b8f3f4bbe7e73648,
+         0x1d035a692a116769816ee6fd0feaf611, 0xa9b13d7360bdd9bdebecdd5b73892f7f],
+        [0x168abfe2ac65996f5bfe1e9b61b1a1f3, 0xfd45313b0e96dd861f58c43f2efa83e4,
+         0x315627f00dec13a4eba8b9de69c9a45c, 0xdf6078de5a51c187cfb79b2093ef4387],
+        [0x12f7176fe75386090b984247a81a8c25, 0x5a568acf886c7cf0141df8c5fea133e4,
+         0x00a91159745fab2914932c1405beb316, 0xb5d318683f1df2617585ffd8bb8ba45d],
+        [0xb4505442df382e0667819e0c5923ec75, 0xf9d5d3d6e60f17054654f8cb62b2b5fe,
+         0x71f15715ffd60bc37f8048386dcf044b, 0xdd71c479cd290af7f2a06bbbbe9c0fa0],
+        [0x2212169da11a6513d12c967c627b415c, 0x098651a230fbc0133e1afe89ff852c0b,
+         0x31a23406707877ba6d12d6b6f661599e, 0x644a82910c4ff0eee1e906920d5a012e],
+        [0x70f57be86a5006899dcdf616d417dd06, 0x99a90f3d0110fcbd345269126dcc7ab5,
+         0x0de4d58220c621d8ef82faa18b5c72ae, 0x7aec768d60c12d04a7467d66d9406987],
+        [0x5c44099daf085c928fdaed33bd86db47, 0x2d148aac06b4aeb353ac75031231e49b,
+         0x6e0d19f1ae671a18e6b559adee21312b, 0xd50876e9214e78953be9d54065a96b83],
+        [0x94e6f407cafde697f0c66ca5c62b236d, 0x32daac166984de4f84f496e1d1c77bd7,
+         0x095d802b0d6a82cabb61a4c6df5d0eae, 0x1f1301adc16c93e897907123238db046],
+        [0xe3112bb5dc63aa4eec4d5e0cc2f3e050, 0x91901641ba560ef907fdf04d3a348034,
+         0x36349a84467c5845927c51bfcb3f3095, 0xeaf80e49bdc82d64a76a6c9a6047ca5d],
+        [0xeeb37e1f466bced4854554e04008b3f4, 0x1e051d23888bcc3958965fe865e80973,
+         0x2b57495772ca9d08df03af58f538085d, 0x4c32067e953b37a338cf8154af11f47e],
+        [0xc7e4dfc6352cc79626111a0287772184, 0xdec0af9735832a27fb5f3abdb519fc3e,
+         0xb9dc3496093dac73d15ad13be03124cb, 0xa378352beee1be583ede1d2a01711b75],
+        [0x7cf14ee1e826d45f5ea452fcc4f94d20, 0x8b6b5d78e2fe94f21a97a257e73528d6,
+         0x09052deb922dabd0060ed5e68d9fb88e, 0x878708d67410348c66012af6ba800e20],
+        [0xcff542075f50f5613249b7a623ba0080, 0x28e1f80765e37f74bdb8d13c011c8ac6,
+         0x573828d8c7445c09f51971e3f64ee747, 0x87479e08b906f9b8a2291ccf94faf617],
+        [0x3e5be21c34c6eb077492aa489818f689, 0x468b418ae9797e61ba765aeb734babc6,
+         0x84bd6004a1ba9bfecddaa356cc3204d7, 0x1327854247e5cc433565ef8d823897d3],
+        [0x260d58ef3d26929fa5d075eb99a95823, 0x1a2d6fa372c027a21d96dc5a78cbbeef,
+         0xad4ff63c8fb303320b17948a991fdd03, 0x382cd9d90bd876bf5c1c39521f4f781a],
+        [0xfba9484918d05b8915cf2611c31b3391, 0x9a860a3392ddb6373d6a0f9428fc3d75,
+         0x0678f1ca14d97e70651511099f0fcc03, 0xa8128b1e6fbc4b5be1b6bb6d27a4cb82],
+        [0xa4028842c68b8553e66414941e503d49, 0x9b85f44197843ae297fa74ea90ca31d4,
+         0xd2864771ef3b69361ac9c2248f9e55f6, 0xb7e04a87c0422afdcadbbf20f173cc5b],
+        [0x4ee88e317daa06f200eb81e7036ecc3e, 0x98313b810f868f6e63c309703a523592,
+         0x654410e649678155c6cac75475eafbca, 0x9ec29b95aee64e2b3ade1aab26bb7aea],
+        [0x7d15ddb84bff83b29cf9953539668058, 0x2a28c46a500ca2507fe447ab20e721f3,
+         0xf7f7def9c27089db1eba5a41adae2bce, 0x4237f02bbb86612752eb4b404572029c],
+        [0x2caadcd1e59b618600d611c0f42cde3f, 0x8680969ccd600f4b114fa864439d4392,
+         0x8bd6fd05a1cdf960c13d13241fef466d, 0xce302992da9a8542ebd1f7c7cdb460bd],
+        [0x30913a39a34f0936833048bf6c988975, 0xabf1950d0fd4333f6c0655991c62ac48,
+         0x293d2880cfff3637491f2298f79f621b, 0xbd5ddfe2b888ebde7f5f9e3f20c58bca],
+        [0x25be2893501b8a92a01c2ce5d3ac24e4, 0x1cf5d1f49121894ad263d220b29775b5,
+         0x83d5b5fdad857e4d895c2630e0eb0b52, 0xa3ec479acb282c63c3bae23829619546],
+        [0xa0dea0e79ec6c3cef181209a15d2864b, 0xe6ce14d04cff3800b0319dc88502bed2,
+         0x083a3b9057773db7d6691cd6796cce23, 0x278a417bc0357274511de54131cfd040],
+        [0xe249a24267957dc994abaa1f562c6123, 0x8995f419a25e14358ad301806e5ef367,
+         0xbdefb22f2992ac1b160289bfa721b85a, 0x274f3d7cd14a170177f2ff059f8142a5],
+        [0x9dca9fa923601f7556700a7f8cf0271f, 0x63892bcfbf4587f979d5309b1cd35b21,
+         0x7a3b557232c4a014478ed24e95c78bda, 0x5c0f1e90daae88cc03c1e767d73c0997],
+        [0xe1ebc0560f2aa723bd3bae7f917ec767, 0x4829a2c24aabdd298227de4bcac2e3d2,
+         0x0d05bf4bbc9c7ab7077b8f78c8eb2d3e, 0x9f16250c3c5b643c07954326f2f382a8],
+        [0xa9c59905a94d972cff782d06559d5aa2, 0xd5c8656a447da22844f3b45dc6d18f01,
+         0x4619ea70253856242e65fc387cbd241c, 0xd92d3bec45481095547fcaed6a92d8e0],
+        [0x194353734ff77987614a216324737fc4, 0x789d2ebe55d4d31cd24f1459be2af570,
+         0xaab71d2583956b4171b19861cbf3533a, 0x21aee473f96f3f6ffe89838356b5e2d9],
+        [0x02103c633115e0964c3c4571c551dfd7, 0x92ca8b01c6e185bee29a86c23891beab,
+         0xa726f03bb914344998561ef119304a0f, 0x4717becaa3525220a6efc2adb7aa9a9f],
+        [0xec930ef90e38c918902863ece4c09381, 0x4a88f117d8da2126bfd8a29f3f84d15b,
+         0x9b14b61d2ff5611a690bc38a94303941, 0x63641e2dc41439b21b3a9f98049d9e9e],
+        [0x1db88756ee61702fc5c91b2776602cb2, 0x0f372b85246d048afa6d38ae0f223cd3,
+         0xcb6a1d84b9eb41b668594738629ab291, 0xd24700ad2c734366b249346d45cc853b],
+        [0xbf0ad498d7e20335b7e4ae939110ac9e, 0x8d3b132bb6302d72d6879d5853075e70,
+         0x818b90a12a183ea125163d9d5b354e39, 0x185cd20bccc21042ed5a75bd1172f6c8],
+        [0x1df26ee87568ff6b57fe24d04ad9937f, 0xdb0a81c13e52af30c22e07617223e23a,
+         0x63a02d4d94e310a15698073b5165a883, 0x7df589b393fb2349e902a59406349664],
+        [0x23c446d57c62306f97529620b4e1895e, 0x6bf815e4569c708367d26aaedf642500,
+         0x82c37c1cc6ba3d07bd406f6a556159b1, 0x261547969bf2ac534e76901ec0fd7872],
+        [0x8f57553d4442e4a69312b53cd7b05adb, 0x223479f5153ca47dd36a54de9c1cd0c8,
+         0x7ab47aca580cf1db693c924bc95e9a61, 0x316874a992833ab80ab563c9bf907ad6],
+        [0xcd9259da0d3e29a808f961645ecbb15e, 0x141ac75765c2d82282192dd2cc47c6a9,
+         0x946c21cecfa04eb263f6bcda0db479e1, 0x6fdf7b2188395849c1e0da425935558e],
+        [0x2d888d5ec4457570f74f3f51993f72b6, 0x38a2c7c6a04a4a07dbca888bb50070f7,
+         0xbddbe684162d13d88717295039dc38a5, 0x1ea6eb872d47126f32029ea42bb630d6],
+        [0x2a438555c9e707110c1d29de18ce8f82, 0xa16ee4c7ddad7e4139734921549cd21e,
+         0x56b506037e95bc5160016a12912b157d, 0x6c539975fb5b6f9dec8d5744f0f5ddb6],
+        [0x6fbbe043c4238c8863566bc736978294, 0x224db7528cc52af70f384f8f2ede87fd,
+         0x9df94d01a90b85c0f7d9b50079d545ec, 0x48117182ced4f2a5e685cb3c8008039d],
+        [0x3682d9cdab9faf35d23b12ac9e5a4528, 0x250da8779ac6b59cab07430e4d0c4e58,
+         0xd979606ed9a94dcb38adfd3294066ae9, 0x9e0e8a8e0a761682a6bca0bfad167b8c],
+        [0x58e39a80863a9478ced1bbedb2e0108c, 0xc0937c44b21ce1f6a2d39bfd31c4c9dd,
+         0x7ddcad15b405801e04a4ae65a7b75e96, 0x177963e07e474e91f6d6406645216d23],
+        [0x0130e4604ca3a1f18050ab6b30b24a14, 0x64435d3f028649eccb0565c04a5577a0,
+         0x1205d17119177c9b3296fdcf821df41f, 0x61f0a26333955a611926d960657cd8fd],
+        [0xf94a522c799b73110785197364936732, 0xc42e81bd426f5b6be53ce4f6d32d204e,
+         0x7ec9621e2295701e3d799774a86145e4, 0xb6300c08e67d72fe245464e467d404af],
+        [0xf2651129dbf2859b55c644854c658f9c, 0x9df1d99d273cf6a6c964380017dde6e8,
+         0xd5a161e3879d7a7f4145cee4137b859d, 0x152811e31634881385d3c3de1ef43afc],
+        [0x8ef4634a0af22d75fd5ed1c0aec8640c, 0xde50b0d632dbdcfc2590f7fc6f47a7a7,
+         0x762f513a0b5d5ef8a137c3cad81a0f09, 0x665d739f4c1fe210af2a57592bcff390],
+        [0x482b658d311711bc22e9a8e9b9ffec4b, 0xae61c8d33dcdfb7157241b31f6686ef8,
+         0x3bf70ab644d00d94cef7f0911e6e4873, 0xc0819aa85c7d56923912b40f69c6bdf7],
+        [0x31e0e7a65cda16452af1c4fbb4b0d821, 0x1b814dab3c40106d9d8efb8447dcdb8e,
+         0x5336c08bbc0a0dda6fa6b5a8efb88274, 0x5a055ce0cf858c41e405d912550ee6b1],
+        [0x05acfbb40e8c4198f3d77913f169b032, 0x69822f5db0a980de2557fcf032b61b8a,
+         0xbd90946e94b25faced5cec41740532fe, 0x04d71c4b49233e03cdc4fabded6a2cd7],
+        [0xb2f5d2425da6a04e136fd7dc26252ed5, 0xf9ab83d66d8d7c38d7cc4ef00086c939,
+         0xdf1e17af5b7ac5ca422f2a7b1ad5729e, 0x43e9411cf1788fd14025f05f4e26d9c7],
+        [0x5118a448864beb3ee09053445e31f008, 0xc5eef1de255f929adee38af797005c43,
+         0x2542c294565adccfdd11ac3b3551c735, 0x06d93d5ca254aeb86736d4438267af61],
+        [0x9b910e2ad403d3425ae6d4eac914dd86, 0x78756c27a5b257d7ae64fd45a2a22dc3,
+         0x9f95859d01e2dc240c8ca6a97e348bb4, 0xb7eeacb4b3b82706bf5a777a97d7adf8],
+        [0xaafa8a28ed791ffec65daa117815e7d8, 0x95d207289a044daf61809ce6be144e4f,
+         0x61c5fd343197eb64907513138c292586, 0x5c0c2bf255900d9ec96a08bc71b09409],
+        [0xa6a572798641494d603909d42b81df87, 0xbce44bdf136e1fb6b2f3221ea32b25b5,
+         0x61627a06330546154ae681b45a22cdd2, 0xe95947eae30f271f360b900a86b71469],
+        [0xcec003cbfa0a471389d1fd548c03409e, 0x5f765b85bf24d398af2cc9329780b11f,
+         0x22bd736519a83b13fada8b186adf6a27, 0x622cbe866ffc0472e2e692a444f39121],
+        [0x0564ca21e3418da144ef2331e20adfb2, 0x82348f35d2b501f84304ec86448ce99f,
+         0x7a2e1d2c91ceeaa0d108f37980b942b1, 0xf7f28893611c22975e998b3ea66a2ca4],
+        [0x8dbc666481dd71cf4c6a48a7e8707b3d, 0xeb21aa17c0569a5bc914aec2b034a7c1,
+         0xcfc3170af658b58eb0aeca10efb10a8d, 0x7265fafd41456ab10d66c82e6a5c3e5e],
+        [0xb2aad5266a0089326f64932c4a9bc3a2, 0x7dec6176edb8a019f1f960c2c4ac582f,
+         0xccf01259f6ff06a2c8335b30b2da00ad, 0xa02116fd90917699048fd3fa0ab33373],
+        [0x35dc29ccb7dfa2085a37a41657d0d638, 0xc4f905834fc95137da132625193ebcc9,
+         0xdd24395c99eaf18ea57050ec8adf9943, 0x84addecb1a40ae8cc89019d19338bf19],
+        [0x6db1f167db8d25838eb7f1b7f7bcd0ef, 0x61d5623339aaf31a348a7e403d33e1a4,
+         0xeae4b4339b62c0362c256ac2c8410060, 0xff3dde4b798decfeaed56dffff9d3f60],
+        [0x625cd7d08fed9cc54866ebd2f4d53e0d, 0x80e2611391df997b4a04c4781f43ef3e,
+         0xc1fa4e7728f2c1d321f6731d6c58aff0, 0x100cd8131a61ead78ca8a88ddc646b00],
+        [0x98a0fa582127ed7470c0c62865a607c4, 0x40291cd9e719b1233e65b4ede55b4a7c,
+         0x738e57fde43ccc6f78a688ac486c4179, 0x8aae8dbb571967fb86109eb3c8c971d2],
+        [0xf394227d92706fe149cd549442580ee1, 0x10336e9a766965590fde021af2c71a05,
+         0x74a4be6f22f629b614860ce1f8e167e0, 0x084ca6715796457b8f7d7f80864944eb],
+        [0xcc5bd20b21550c51beb95217aa38994b, 0xc187a814ab44b1c0a23218fcbe161fb9,
+         0xead4b0c4b3b68cd3a20ae714f2805102, 0xaf4c27c1e89c417f48dcb21cbc391c5d],
+        [0xf1ff4ceb4e9da979252683c98247d10c, 0x9b4128b393985bcd200dad96fcf483bf,
+         0x535e12c4af2dd92be6901f8fba3a3b27, 0x25691bfd7c53f088733539404368ca03],
+        [0xfc7864210d1abc68d86260a001791ec2, 0xfd146938e92ae27bf47b01c6560c8996,
+         0xc78e08e55b183f0061973a7cefa4c413, 0xbbfb52e6f7855a77c35f94686f00c72b],
+        [0x48a1e83f21ede80690d6e1a2a5a96049, 0xe647eec3e298743c8bfd30fe96270673,
+         0xc31c5a4c4caeef60c9533a57c73a235b, 0xfbe72f7b65745b6c89b174cf7104799c],
+        [0xf129181f4e23c8b6d63105a9be46f80a, 0x5a28728100694a0ac7e3e41b0a1007df,
+         0x7ddff3379cf171185c53e5b642082c3e, 0x166571424ab53e00b06e38057fd65743],
+    ];
+
+    #[inline]
+    fn sbox(x: BaseElement) -> BaseElement {
+        x.square() * x
+    }
+
+    fn external_layer(state: &mut [BaseElement; T]) {
+        let x = *state;
+        for (out, row) in state.iter_mut().zip(M4.iter()) {
+            *out = row.iter().zip(x.iter()).fold(BaseElement::ZERO, |acc, (m, v)| acc + BaseElement::new(*m) * *v);
+        }
+    }
+
+    fn internal_layer(state: &mut [BaseElement; T]) {
+        let sum = state.iter().fold(BaseElement::ZERO, |acc, v| acc + *v);
+        for (x, d) in state.iter_mut().zip(INTERNAL_DIAG_M_1.iter()) {
+            *x = *x * BaseElement::new(*d) + sum;
+        }
+    }
+
+    fn full_round(state: &mut [BaseElement; T], rc: &[u128; T]) {
+        for (x, c) in state.iter_mut().zip(rc.iter()) {
+            *x = sbox(*x + BaseElement::new(*c));
+        }
+        external_layer(state);
+    }
+
+    fn partial_round(state: &mut [BaseElement; T], rc: u128) {
+        state[0] = sbox(state[0] + BaseElement::new(rc));
+        internal_layer(state);
+    }
+
+    /// Poseidon2 permutation (t=4, α=3, 8 + 84 rounds).
+    pub fn permute(state: &mut [BaseElement; T]) {
+        external_layer(state);
+        for rc in &ROUND_CONSTANTS[..RF / 2] {
+            full_round(state, rc);
+        }
+        for rc in &ROUND_CONSTANTS[RF / 2..RF / 2 + RP] {
+            partial_round(state, rc[0]);
+        }
+        for rc in &ROUND_CONSTANTS[RF / 2 + RP..] {
+            full_round(state, rc);
+        }
+    }
+}
+
+// ===================================================================
+//  v3 domain — Poseidon2 notes, nullifiers and Merkle nodes
+//
+//  The v2 sponge layouts (spec §4–5) over the Poseidon2 permutation,
+//  with their own IVs. pk_digest stays BLAKE3 and is shared with v2.
+//  The AIRs still hash with the v2 Poseidon; moving a circuit to v3
+//  means a new version of its AIR in `envelope::REGISTRY`.
+// ===================================================================
+pub mod v3 {
+    use super::*;
+    use super::poseidon2::permute;
+    use super::v2::{iv_from_domain, pack, unpack};
+    use alloc::vec::Vec;
+
+    pub use super::v2::{pk_digest, MERKLE_DEPTH};
+
+    /// NOTE_IV_V3 = F128(LE16(BLAKE3("nulla_note_iv_v3")))
+    pub fn note_iv() -> BaseElement { iv_from_domain(b"nulla_note_iv_v3") }
+    /// NULLIFIER_IV_V3 = F128(LE16(BLAKE3("nulla_nullifier_iv_v3")))
+    pub fn nullifier_iv() -> BaseElement { iv_from_domain(b"nulla_nullifier_iv_v3") }
+    /// MERKLE_IV_V3 = F128(LE16(BLAKE3("nulla_merkle_iv_v3")))
+    pub fn merkle_iv() -> BaseElement { iv_from_domain(b"nulla_merkle_iv_v3") }
+
+    /// Note leaf: two-permutation sponge over (v, b, pkd).
+    pub fn note_hash(v: u64, b: &[u8; 32], pkd: &[u8; 32]) -> [u8; 32] {
+        let (b_lo, b_hi) = unpack(b);
+        let (pkd_lo, pkd_hi) = unpack(pkd);
+        let mut state = [note_iv(), BaseElement::new(v as u128), b_lo, b_hi];
+        permute(&mut state);
+        state[1] += pkd_lo;
+        state[2] += pkd_hi;
+        permute(&mut state);
+        pack(state[1], state[2])
+    }
+
+    /// Nullifier: one permutation over the blinding.
+    pub fn nullifier_hash(b: &[u8; 32]) -> [u8; 32] {
+        let (b_lo, b_hi) = unpack(b);
+        let mut state = [nullifier_iv(), b_lo, b_hi, BaseElement::ZERO];
+        permute(&mut state);
+        pack(state[1], state[2])
+    }
+
+    /// Merkle node hash: two-permutation sponge over (L, R).
+    pub fn merkle_hash2(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let (l_lo, l_hi) = unpack(left);
+        let (r_lo, r_hi) = unpack(right);
+        let mut state = [merkle_iv(), l_lo, l_hi, r_lo];
+        permute(&mut state);
+        state[1] += r_hi;
+        permute(&mut state);
+        pack(state[1], state[2])
+    }
+
+    /// Zero-subtree constants: Z[0] = [0;32], Z[i+1] = hash2(Z[i], Z[i]).
+    pub fn zero_subtrees() -> Vec<[u8; 32]> {
+        let mut z = Vec::with_capacity(MERKLE_DEPTH + 1);
+        z.push([0u8; 32]);
+        for i in 0..MERKLE_DEPTH {
+            let prev = z[i];
+            z.push(merkle_hash2(&prev, &prev));
+        }
+        z
+    }
+}
+
+// ===================================================================
+//  Hash test vectors
+//
+//  `json` renders test-vectors/{v2,v3}.json: raw permutations, pk_digest,
+//  note_hash, nullifier_hash, merkle_hash2 and the zero subtrees.
+//  `gen_test_vectors` prints them; `vectors_tests` checks the committed
+//  files, which were produced by an independent implementation.
+// ===================================================================
+pub mod vectors {
+    use super::*;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use core::fmt::Write;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Domain {
+        V2,
+        V3,
+    }
+
+    struct Hashes {
+        domain: &'static str,
+        permutation: &'static str,
+        permute: fn(&mut [BaseElement; 4]),
+        note_hash: fn(u64, &[u8; 32], &[u8; 32]) -> [u8; 32],
+        nullifier_hash: fn(&[u8; 32]) -> [u8; 32],
+        merkle_hash2: fn(&[u8; 32], &[u8; 32]) -> [u8; 32],
+        zero_subtrees: fn() -> Vec<[u8; 32]>,
+    }
+
+    fn hashes(domain: Domain) -> Hashes {
+        match domain {
+            Domain::V2 => Hashes {
+                domain: "v2",
+                permutation: "poseidon-f128-t4-a3-rf8-rp56",
+                permute: poseidon_perm,
+                note_hash: v2::note_hash,
+                nullifier_hash: v2::nullifier_hash,
+                merkle_hash2: v2::merkle_hash2,
+                zero_subtrees: v2::zero_subtrees,
+            },
+            Domain::V3 => Hashes {
+                domain: "v3",
+                permutation: "poseidon2-f128-t4-a3-rf8-rp84",
+                permute: poseidon2::permute,
+                note_hash: v3::note_hash,
+                nullifier_hash: v3::nullifier_hash,
+                merkle_hash2: v3::merkle_hash2,
+                zero_subtrees: v3::zero_subtrees,
+            },
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        let mut s = String::with_capacity(bytes.len() * 2);
+        for b in bytes {
+            let _ = write!(s, "{b:02x}");
+        }
+        s
+    }
+
+    fn elements(state: &[BaseElement; 4]) -> String {
+        let items: Vec<String> = state.iter().map(|e| format!("\"{}\"", e.as_int())).collect();
+        format!("[{}]", items.join(", "))
+    }
+
+    fn list(out: &mut String, key: &str, items: &[String], last: bool) {
+        let _ = writeln!(out, "  \"{key}\": [");
+        let _ = writeln!(out, "    {}", items.join(",\n    "));
+        let _ = writeln!(out, "  ]{}", if last { "" } else { "," });
+    }
+
+    /// The test-vector file of `domain`.
+    pub fn json(domain: Domain) -> String {
+        let h = hashes(domain);
+        let mut out = String::new();
+        let _ = writeln!(out, "{{");
+        let _ = writeln!(out, "  \"domain\": \"{}\",", h.domain);
+        let _ = writeln!(out, "  \"permutation\": \"{}\",", h.permutation);
+
+        let p_1 = BaseElement::ZERO - BaseElement::ONE;
+        let inputs = [
+            [BaseElement::ZERO; 4],
+            [BaseElement::new(0), BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)],
+            [p_1; 4],
+        ];
+        let permute: Vec<String> = inputs
+            .iter()
+            .map(|input| {
+                let mut output = *input;
+                (h.permute)(&mut output);
+                format!("{{\"input\": {}, \"output\": {}}}", elements(input), elements(&output))
+            })
+            .collect();
+        list(&mut out, "permute", &permute, false);
+
+        let pks: [Vec<u8>; 2] = [alloc::vec![0xAA; 1312], (0..1312).map(|i| (i % 251) as u8).collect()];
+        let pk_digests: Vec<String> = pks
+            .iter()
+            .map(|pk| format!("{{\"pk\": \"{}\", \"pkd\": \"{}\"}}", hex(pk), hex(&v2::pk_digest(pk))))
+            .collect();
+        list(&mut out, "pk_digest", &pk_digests, false);
+
+        let notes = [
+            (0u64, [0u8; 32], [0u8; 32]),
+            (1_000_000_000_000, [0x01; 32], v2::pk_digest(&pks[0])),
+            (u64::MAX, [0xff; 32], [0xff; 32]),
+        ];
+        let note_hashes: Vec<String> = notes
+            .iter()
+            .map(|(v, b, pkd)| {
+                format!(
+                    "{{\"value\": {v}, \"blinding\": \"{}\", \"pkd\": \"{}\", \"leaf\": \"{}\"}}",
+                    hex(b),
+                    hex(pkd),
+                    hex(&(h.note_hash)(*v, b, pkd)),
+                )
+            })
+            .collect();
+        list(&mut out, "note_hash", &note_hashes, false);
+
+        let nullifiers: Vec<String> = [[0u8; 32], [0x01; 32], [0xff; 32]]
+            .iter()
+            .map(|b| format!("{{\"blinding\": \"{}\", \"nullifier\": \"{}\"}}", hex(b), hex(&(h.nullifier_hash)(b))))
+            .collect();
+        list(&mut out, "nullifier_hash", &nullifiers, false);
+
+        let l1 = (h.note_hash)(notes[1].0, &notes[1].1, &notes[1].2);
+        let l2 = (h.note_hash)(notes[2].0, &notes[2].1, &notes[2].2);
+        let nodes: Vec<String> = [([0u8; 32], [0u8; 32]), (l1, l2), (l2, l1)]
+            .iter()
+            .map(|(l, r)| {
+                format!(
+                    "{{\"left\": \"{}\", \"right\": \"{}\", \"node\": \"{}\"}}",
+                    hex(l),
+                    hex(r),
+                    hex(&(h.merkle_hash2)(l, r)),
+                )
+            })
+            .collect();
+        list(&mut out, "merkle_hash2", &nodes, false);
+
+        let zero: Vec<String> = (h.zero_subtrees)().iter().map(|z| format!("\"{}\"", hex(z))).collect();
+        list(&mut out, "zero_subtrees", &zero, true);
+        let _ = writeln!(out, "}}");
+        out
+    }
 }
 
 // ===================================================================
@@ -939,6 +1875,21 @@ pub mod spend_v2 {
         note_iv: BaseElement,
         merkle_iv: BaseElement,
         nullifier_iv: BaseElement,
+        /// Fourth element of the nullifier perm's initial state. Zero for
+        /// on-chain spends; set by `credential_v2` and `fee` to a scope element so
+        /// the nullifier slot carries an unlinkable tag instead.
+        nf_tweak: BaseElement,
+    }
+
+    impl SpendAir {
+        /// SpendAir with the row-0 nullifier state `[NULLIFIER_IV, b_lo, b_hi, nf_tweak]`.
+        pub(crate) fn with_nullifier_tweak(
+            ti: TraceInfo, pi: SpendPI, opts: ProofOptions, nf_tweak: BaseElement,
+        ) -> Self {
+            let mut air = <Self as Air>::new(ti, pi, opts);
+            air.nf_tweak = nf_tweak;
+            air
+        }
     }
 
     impl Air for SpendAir {
@@ -987,6 +1938,7 @@ pub mod spend_v2 {
                 note_iv: v2::note_iv(),
                 merkle_iv: v2::merkle_iv(),
                 nullifier_iv: v2::nullifier_iv(),
+                nf_tweak: BaseElement::ZERO,
             }
         }
 
@@ -1015,6 +1967,7 @@ pub mod spend_v2 {
             let note_iv = E::from(self.note_iv);
             let merkle_iv = E::from(self.merkle_iv);
             let nullifier_iv = E::from(self.nullifier_iv);
+            let nf_tweak = E::from(self.nf_tweak);
             let (pkd_lo_b, pkd_hi_b) = v2::unpack(&self.pi.pkd);
             let (cpkd_lo_b, cpkd_hi_b) = v2::unpack(&self.pi.change_pkd);
             let pkd_lo = E::from(pkd_lo_b);
@@ -1090,7 +2043,7 @@ pub mod spend_v2 {
             r[18] = m_row0 * (c[0] - nullifier_iv);
             r[19] = m_row0 * (c[1] - c[7]);
             r[20] = m_row0 * (c[2] - c[8]);
-            r[21] = m_row0 * c[3];
+            r[21] = m_row0 * (c[3] - nf_tweak);
         }
 
         fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
@@ -1124,9 +2077,28 @@ pub mod spend_v2 {
         change_pkd: &[u8; 32],
         purchase_mode: bool,
     ) -> bool {
-        let proof = match winter_verifier::Proof::from_bytes(proof_bytes) {
-            Ok(p) => p,
-            Err(_) => return false,
+        verify_spend_v2_with(
+            proof_bytes, root, nullifier, pkd, price_or_amount, change_leaf, change_pkd, purchase_mode,
+            &envelope::StarkSecurity::DEFAULT,
+        )
+    }
+
+    /// `verify_spend_v2` under a caller-chosen security policy.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_spend_v2_with(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        nullifier: &[u8; 32],
+        pkd: &[u8; 32],
+        price_or_amount: u64,
+        change_leaf: &[u8; 32],
+        change_pkd: &[u8; 32],
+        purchase_mode: bool,
+        security: &envelope::StarkSecurity,
+    ) -> bool {
+        let (version, proof, acceptable) = match envelope::open_stark(proof_bytes, envelope::AirId::Spend, security) {
+            Some(opened) => opened,
+            None => return false,
         };
         let pi = SpendPI {
             root: *root,
@@ -1137,10 +2109,12 @@ pub mod spend_v2 {
             change_pkd: *change_pkd,
             mode: if purchase_mode { 1 } else { 0 },
         };
-        let acceptable = winter_verifier::AcceptableOptions::MinConjecturedSecurity(80);
-        winter_verifier::verify::<SpendAir, StarkHash, StarkCoin, StarkVC>(
-            proof, pi, &acceptable,
-        ).is_ok()
+        match version {
+            1 => winter_verifier::verify::<SpendAir, StarkHash, StarkCoin, StarkVC>(
+                proof, pi, &acceptable,
+            ).is_ok(),
+            _ => false,
+        }
     }
 
     /// Canonical zero-change leaf used in withdraw mode:
@@ -1153,14 +2127,14 @@ pub mod spend_v2 {
     #[cfg(feature = "prover")]
     pub mod prover_impl {
         use super::*;
-        use super::super::poseidon_eval_round_base;
+        use super::super::{poseidon_eval_round_base, poseidon_perm};
         use winter_verifier::math::{fields::f128::BaseElement, FieldElement};
         use winterfell::{
             crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
             matrix::ColMatrix,
-            AuxRandElements, BatchingMethod, CompositionPoly, CompositionPolyTrace,
+            AuxRandElements, CompositionPoly, CompositionPolyTrace,
             DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
-            FieldExtension, PartitionOptions, ProofOptions, Prover, StarkDomain,
+            PartitionOptions, ProofOptions, Prover, StarkDomain,
             TraceInfo, TracePolyTable, TraceTable,
         };
         type HashFn = Blake3_256<BaseElement>;
@@ -1173,26 +2147,112 @@ pub mod spend_v2 {
             pub cv: u64, pub cb: [u8; 32], pub cpkd: [u8; 32],
         }
 
-        fn run_perm_rows(cols: &mut [alloc::vec::Vec<BaseElement>], start_row: usize) {
-            for k in 0..64 {
-                let s = [cols[0][start_row+k], cols[1][start_row+k],
-                         cols[2][start_row+k], cols[3][start_row+k]];
-                let mut out = [BaseElement::ZERO; 4];
-                poseidon_eval_round_base(&s, k, &mut out);
-                cols[0][start_row+k+1] = out[0];
-                cols[1][start_row+k+1] = out[1];
-                cols[2][start_row+k+1] = out[2];
-                cols[3][start_row+k+1] = out[3];
+        /// Input and output state of each active block, and the r_hi carry
+        /// of each Merkle level.
+        struct BlockChain {
+            input: [[BaseElement; 4]; ACTIVE_BLOCKS],
+            output: [[BaseElement; 4]; ACTIVE_BLOCKS],
+            r_hi: [BaseElement; LEVELS],
+        }
+
+        /// The 45 perms chain through their inputs, so they are hashed in
+        /// order first; the trace blocks are then independent.
+        fn block_chain(w: &SpendWitness, nf_tweak: BaseElement) -> BlockChain {
+            let z = BaseElement::ZERO;
+            let (b_lo, b_hi)       = v2::unpack(&w.b);
+            let (pkd_lo, pkd_hi)   = v2::unpack(&w.pkd);
+            let (cb_lo, cb_hi)     = v2::unpack(&w.cb);
+            let (cpkd_lo, cpkd_hi) = v2::unpack(&w.cpkd);
+            let mut input = [[z; 4]; ACTIVE_BLOCKS];
+            let mut output = [[z; 4]; ACTIVE_BLOCKS];
+            let mut r_hi = [z; LEVELS];
+            let mut run = |k: usize, s: [BaseElement; 4]| {
+                input[k] = s;
+                let mut out = s;
+                poseidon_perm(&mut out);
+                output[k] = out;
+                out
+            };
+            // b0: nullifier perm; b1, b2: note perms.
+            run(0, [v2::nullifier_iv(), b_lo, b_hi, nf_tweak]);
+            let s = run(1, [v2::note_iv(), BaseElement::new(w.v as u128), b_lo, b_hi]);
+            let mut cur = run(2, [s[0], s[1] + pkd_lo, s[2] + pkd_hi, s[3]]);
+            // b3+2d, b4+2d: Merkle level d.
+            for d in 0..LEVELS {
+                let (s_lo, s_hi) = v2::unpack(&w.siblings[d]);
+                let (l_lo, l_hi, r_lo, hi) = if (w.index >> d) & 1 == 1 {
+                    (s_lo, s_hi, cur[1], cur[2])
+                } else {
+                    (cur[1], cur[2], s_lo, s_hi)
+                };
+                r_hi[d] = hi;
+                let s = run(3 + 2 * d, [v2::merkle_iv(), l_lo, l_hi, r_lo]);
+                cur = run(4 + 2 * d, [s[0], s[1] + hi, s[2], s[3]]);
             }
+            // b43, b44: change note perms.
+            let s = run(STEP_LCHG / BLOCK, [v2::note_iv(), BaseElement::new(w.cv as u128), cb_lo, cb_hi]);
+            run(STEP_ACHG / BLOCK, [s[0], s[1] + cpkd_lo, s[2] + cpkd_hi, s[3]]);
+            BlockChain { input, output, r_hi }
         }
 
-        fn freeze_state_rows(cols: &mut [alloc::vec::Vec<BaseElement>], from_row: usize, to_row: usize) {
-            for r in from_row..=to_row {
-                for c in 0..4 { cols[c][r] = cols[c][r-1]; }
+        /// State columns 0–3 of block `k`: step 0 holds the previous block's
+        /// output (block 0: its own input), step 1 the input, steps 2–65 the
+        /// round outputs and steps 66–127 the frozen output. Padding blocks
+        /// hold the last output throughout.
+        fn fill_state_block(k: usize, mut state: [&mut [BaseElement]; 4], chain: &BlockChain) {
+            let mut put = |row: usize, s: &[BaseElement; 4]| {
+                for (col, v) in state.iter_mut().zip(s.iter()) { col[row] = *v; }
+            };
+            if k >= ACTIVE_BLOCKS {
+                let last = chain.output[ACTIVE_BLOCKS - 1];
+                for row in 0..BLOCK { put(row, &last); }
+                return;
+            }
+            let input = chain.input[k];
+            put(0, if k == 0 { &input } else { &chain.output[k - 1] });
+            put(1, &input);
+            let mut s = input;
+            let mut out = s;
+            for r in 0..64 {
+                poseidon_eval_round_base(&s, r, &mut out);
+                s = out;
+                put(r + 2, &s);
+            }
+            for row in 66..BLOCK { put(row, &s); }
+        }
+
+        /// Fill the state columns block by block, across threads under the
+        /// `concurrent` feature.
+        fn fill_state_blocks(cols: &mut [alloc::vec::Vec<BaseElement>], chain: &BlockChain) {
+            let [c0, c1, c2, c3, ..] = cols else { unreachable!("trace has {TRACE_WIDTH} columns") };
+            #[cfg(not(feature = "concurrent"))]
+            c0.chunks_mut(BLOCK)
+                .zip(c1.chunks_mut(BLOCK))
+                .zip(c2.chunks_mut(BLOCK))
+                .zip(c3.chunks_mut(BLOCK))
+                .enumerate()
+                .for_each(|(k, (((s0, s1), s2), s3))| fill_state_block(k, [s0, s1, s2, s3], chain));
+            #[cfg(feature = "concurrent")]
+            {
+                use rayon::prelude::*;
+                c0.par_chunks_mut(BLOCK)
+                    .zip(c1.par_chunks_mut(BLOCK))
+                    .zip(c2.par_chunks_mut(BLOCK))
+                    .zip(c3.par_chunks_mut(BLOCK))
+                    .enumerate()
+                    .for_each(|(k, (((s0, s1), s2), s3))| fill_state_block(k, [s0, s1, s2, s3], chain));
             }
         }
 
         pub fn build_spend_trace(w: &SpendWitness) -> TraceTable<BaseElement> {
+            build_spend_trace_with_tweak(w, BaseElement::ZERO)
+        }
+
+        /// `build_spend_trace` with the nullifier perm seeded by `nf_tweak`
+        /// (see `SpendAir::with_nullifier_tweak`).
+        pub(crate) fn build_spend_trace_with_tweak(
+            w: &SpendWitness, nf_tweak: BaseElement,
+        ) -> TraceTable<BaseElement> {
             let n = TRACE_LEN;
             let z = BaseElement::ZERO;
             let one = BaseElement::ONE;
@@ -1200,9 +2260,7 @@ pub mod spend_v2 {
                 alloc::vec![alloc::vec![z; n]; TRACE_WIDTH];
 
             let (b_lo, b_hi)       = v2::unpack(&w.b);
-            let (pkd_lo, pkd_hi)   = v2::unpack(&w.pkd);
             let (cb_lo, cb_hi)     = v2::unpack(&w.cb);
-            let (cpkd_lo, cpkd_hi) = v2::unpack(&w.cpkd);
             let vf  = BaseElement::new(w.v  as u128);
             let cvf = BaseElement::new(w.cv as u128);
 
@@ -1253,71 +2311,15 @@ pub mod spend_v2 {
             }
 
             // --- State simulation ---
-            // Block 0: nullifier perm.
-            cols[0][0] = v2::nullifier_iv();
-            cols[1][0] = b_lo;
-            cols[2][0] = b_hi;
-            cols[3][0] = z;
-            freeze_state_rows(&mut cols, 1, 1);
-            run_perm_rows(&mut cols, 1);
-            freeze_state_rows(&mut cols, 66, 128);
-            // Block 1: note perm 1.
-            cols[0][129] = v2::note_iv();
-            cols[1][129] = vf;
-            cols[2][129] = b_lo;
-            cols[3][129] = b_hi;
-            run_perm_rows(&mut cols, 129);
-            freeze_state_rows(&mut cols, 194, 256);
-            // Block 2: note perm 2 (absorb pkd).
-            cols[0][257] = cols[0][256];
-            cols[1][257] = cols[1][256] + pkd_lo;
-            cols[2][257] = cols[2][256] + pkd_hi;
-            cols[3][257] = cols[3][256];
-            run_perm_rows(&mut cols, 257);
-            freeze_state_rows(&mut cols, 322, 384);
-            // Merkle levels.
+            let chain = block_chain(w, nf_tweak);
+            // r_hi carry (col 11): from each level's link to the end of its
+            // absorb block, and to the end of the trace after the last level.
             for d in 0..LEVELS {
                 let base = STEP_MRK_BASE + 256 * d;
-                let cur1 = cols[1][base];
-                let cur2 = cols[2][base];
-                let bit  = (w.index >> d) & 1;
-                let (s_lo, s_hi) = v2::unpack(&w.siblings[d]);
-                let (l_lo, l_hi, r_lo, r_hi) = if bit == 1 {
-                    (s_lo, s_hi, cur1, cur2)
-                } else {
-                    (cur1, cur2, s_lo, s_hi)
-                };
-                cols[0][base + 1] = v2::merkle_iv();
-                cols[1][base + 1] = l_lo;
-                cols[2][base + 1] = l_hi;
-                cols[3][base + 1] = r_lo;
-                let rhi_end = core::cmp::min(base + 256, n - 1);
-                for r in (base + 1)..=rhi_end { cols[11][r] = r_hi; }
-                run_perm_rows(&mut cols, base + 1);
-                freeze_state_rows(&mut cols, base + 66, base + 128);
-                cols[0][base + 129] = cols[0][base + 128];
-                cols[1][base + 129] = cols[1][base + 128] + r_hi;
-                cols[2][base + 129] = cols[2][base + 128];
-                cols[3][base + 129] = cols[3][base + 128];
-                run_perm_rows(&mut cols, base + 129);
-                freeze_state_rows(&mut cols, base + 194, base + 256);
-                if d == LEVELS - 1 {
-                    for r in (base + 257)..n { cols[11][r] = r_hi; }
-                }
+                let end = if d == LEVELS - 1 { n - 1 } else { base + 256 };
+                for r in (base + 1)..=end { cols[11][r] = chain.r_hi[d]; }
             }
-            // Change note (link at STEP_LCHG).
-            cols[0][STEP_LCHG + 1] = v2::note_iv();
-            cols[1][STEP_LCHG + 1] = cvf;
-            cols[2][STEP_LCHG + 1] = cb_lo;
-            cols[3][STEP_LCHG + 1] = cb_hi;
-            run_perm_rows(&mut cols, STEP_LCHG + 1);
-            freeze_state_rows(&mut cols, STEP_LCHG + 66, STEP_ACHG);
-            cols[0][STEP_ACHG + 1] = cols[0][STEP_ACHG];
-            cols[1][STEP_ACHG + 1] = cols[1][STEP_ACHG] + cpkd_lo;
-            cols[2][STEP_ACHG + 1] = cols[2][STEP_ACHG] + cpkd_hi;
-            cols[3][STEP_ACHG + 1] = cols[3][STEP_ACHG];
-            run_perm_rows(&mut cols, STEP_ACHG + 1);
-            freeze_state_rows(&mut cols, ROW_CLEAF + 1, n - 1);
+            fill_state_blocks(&mut cols, &chain);
 
             TraceTable::init(cols)
         }
@@ -1362,6 +2364,16 @@ pub mod spend_v2 {
 
         /// Generate the STARK proof bytes. `mode` = 1 for purchase, 0 for withdraw.
         pub fn prove_spend(w: &SpendWitness, price_or_amount: u64, mode: u64) -> alloc::vec::Vec<u8> {
+            prove_spend_with(w, price_or_amount, mode, envelope::V1_OPTIONS)
+        }
+
+        /// `prove_spend` with one of the `envelope::V1_PRESETS` options.
+        pub fn prove_spend_with(
+            w: &SpendWitness,
+            price_or_amount: u64,
+            mode: u64,
+            options: envelope::StarkOptions,
+        ) -> alloc::vec::Vec<u8> {
             let note_leaf = v2::note_hash(w.v, &w.b, &w.pkd);
             let root = {
                 let mut node = note_leaf;
@@ -1386,10 +2398,10 @@ pub mod spend_v2 {
             let trace = build_spend_trace(w);
             let prover = SpendProverInner {
                 pi,
-                options: ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 127,
-                    BatchingMethod::Linear, BatchingMethod::Horner),
+                options: options.proof_options(),
             };
-            prover.prove(trace).expect("spend STARK").to_bytes()
+            let proof = prover.prove(trace).expect("spend STARK").to_bytes();
+            envelope::seal(envelope::AirId::Spend, envelope::CURRENT_VERSION, options, &proof)
         }
     }
 }
@@ -1516,15 +2528,27 @@ pub mod deposit_v2 {
 
     /// Verify a DepositV2Air STARK proof.
     pub fn verify_deposit_v2(proof_bytes: &[u8], amount: u64, leaf: &[u8; 32]) -> bool {
-        let proof = match winter_verifier::Proof::from_bytes(proof_bytes) {
-            Ok(p) => p,
-            Err(_) => return false,
+        verify_deposit_v2_with(proof_bytes, amount, leaf, &envelope::StarkSecurity::DEFAULT)
+    }
+
+    /// `verify_deposit_v2` under a caller-chosen security policy.
+    pub fn verify_deposit_v2_with(
+        proof_bytes: &[u8],
+        amount: u64,
+        leaf: &[u8; 32],
+        security: &envelope::StarkSecurity,
+    ) -> bool {
+        let (version, proof, acceptable) = match envelope::open_stark(proof_bytes, envelope::AirId::Deposit, security) {
+            Some(opened) => opened,
+            None => return false,
         };
         let pi = DepositPI { amount, leaf: *leaf };
-        let acceptable = winter_verifier::AcceptableOptions::MinConjecturedSecurity(80);
-        winter_verifier::verify::<DepositV2Air, StarkHash, StarkCoin, StarkVC>(
-            proof, pi, &acceptable,
-        ).is_ok()
+        match version {
+            1 => winter_verifier::verify::<DepositV2Air, StarkHash, StarkCoin, StarkVC>(
+                proof, pi, &acceptable,
+            ).is_ok(),
+            _ => false,
+        }
     }
 
     #[cfg(feature = "prover")]
@@ -1534,9 +2558,9 @@ pub mod deposit_v2 {
         use winterfell::{
             crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
             matrix::ColMatrix,
-            AuxRandElements, BatchingMethod, CompositionPoly, CompositionPolyTrace,
+            AuxRandElements, CompositionPoly, CompositionPolyTrace,
             DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
-            FieldExtension, PartitionOptions, ProofOptions, Prover, StarkDomain,
+            PartitionOptions, ProofOptions, Prover, StarkDomain,
             TraceInfo, TracePolyTable, TraceTable,
         };
         type HashFn = Blake3_256<BaseElement>;
@@ -1630,28 +2654,1018 @@ pub mod deposit_v2 {
 
         /// Returns `(leaf, proof_bytes)`. The leaf is what you pass to `deposit_v2`.
         pub fn prove_deposit(v: u64, b: &[u8; 32], pkd: &[u8; 32]) -> ([u8; 32], alloc::vec::Vec<u8>) {
+            prove_deposit_with(v, b, pkd, envelope::V1_OPTIONS)
+        }
+
+        /// `prove_deposit` with one of the `envelope::V1_PRESETS` options.
+        pub fn prove_deposit_with(
+            v: u64,
+            b: &[u8; 32],
+            pkd: &[u8; 32],
+            options: envelope::StarkOptions,
+        ) -> ([u8; 32], alloc::vec::Vec<u8>) {
             let leaf = v2::note_hash(v, b, pkd);
             let prover = DepositProverInner {
                 pi: DepositPI { amount: v, leaf },
-                options: ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 127,
-                    BatchingMethod::Linear, BatchingMethod::Horner),
+                options: options.proof_options(),
             };
             let trace = build_deposit_trace(v, b, pkd);
             let proof = prover.prove(trace).expect("deposit STARK").to_bytes();
+            let proof = envelope::seal(envelope::AirId::Deposit, envelope::CURRENT_VERSION, options, &proof);
             (leaf, proof)
         }
     }
 }
 
 // ===================================================================
-//  Phase 9 — v2 spend authorization (ML-DSA-44)
+//  STARK 6: OwnershipAir — challenge-bound ownership presentation
 //
-//  auth = pk (1312 bytes) ‖ sig (2420 bytes)
-//  message = BLAKE3(domain ‖ public_inputs)
-//  domain:  "nulla_spend_auth_v2" (purchase) /
-//           "nulla_withdraw_auth_v2" (withdraw)
+//  Off-chain proof that the holder knows (b, pkd) for an unspent
+//  ownership leaf NoteHash(units, b, pkd) under an on-chain ownership
+//  root of `rwa_id`, without naming the leaf or consuming the note.
+//  pkd stays private, so presentations of one note (or of two notes of
+//  one key) cannot be linked through it.
 //
-//  The pk digest binds the signing key to the note inside the STARK:
+//  The nullifier perm is seeded by a challenge element instead of 0:
+//
+//    tweak = F128(LE16(BLAKE3("nulla_ownership_challenge_v1" ‖ rwa_id ‖ challenge)))
+//    tag   = Poseidon([NULLIFIER_IV, b_lo, b_hi, tweak])[1..3]
+//
+//  The tag is unlinkable to the on-chain nullifier (tweak ≠ 0) and
+//  stable per (note, rwa_id, challenge), so a verifier reusing one
+//  challenge sees the same note presented twice.
+//
+//  Unspent: the trace also derives the note's real nullifier nf and
+//  proves an empty slot at key = nf_lo mod 2^64 in the lane's spent
+//  set (`v2::spent_path`), a depth-64 sparse Merkle tree with a leaf
+//  set at the key of every spent ownership nullifier. The 64 path bits
+//  form the key, and nf_lo = key + 2^64·hi with hi range-checked to 64
+//  bits. Public inputs: root, rwa_id, units, challenge, tag, spent_root.
+//
+//  Layout: 256 blocks of 128 rows; blocks 0–171 are active.
+//    b0         tag perm             b1, b2     note perms (pkd from cols 12, 13)
+//    b3–b42     ownership path, 20 levels (link, absorb)
+//    b43–b170   spent-set path, 64 levels from the empty leaf
+//    b171       nullifier perm (tweak 0), checked against key and hi
+//  Columns:
+//    0–3 state   4 bit (hi bits rows 0–63, then one path bit per level)
+//    5 acc   6 pow (hi range check)   7 b_lo   8 b_hi   9 s_lo   10 s_hi
+//    11 r_hi carry   12 pkd_lo   13 pkd_hi   14 key acc   15 key pow   16 hi
+// ===================================================================
+pub mod ownership_v2 {
+    use super::*;
+    use super::spend_v2::{poseidon_periodic_128, BLOCK};
+    use super::v2;
+    use alloc::vec::Vec;
+
+    pub const CHALLENGE_DOMAIN: &[u8] = b"nulla_ownership_challenge_v1";
+
+    pub const TRACE_LEN: usize = 32768;
+    pub const TRACE_WIDTH: usize = 17;
+    pub const LEVELS: usize = v2::MERKLE_DEPTH; // 20
+    pub const SPENT_LEVELS: usize = v2::SPENT_DEPTH; // 64
+
+    pub const STEP_LNOTE: usize = 128;
+    pub const STEP_ANOTE: usize = 256;
+    pub const STEP_MRK_BASE: usize = 384; // ownership level d at 384 + 256d
+    pub const STEP_SPENT_BASE: usize = STEP_MRK_BASE + 256 * LEVELS; // 5504, spent level j at +256j
+    pub const STEP_LNF: usize = STEP_SPENT_BASE + 256 * SPENT_LEVELS; // 21888
+    pub const ROW_TAG: usize = 65;
+    pub const ROW_ROOT: usize = STEP_SPENT_BASE - BLOCK + 65; // 5441
+    pub const ROW_SPENT_ROOT: usize = STEP_LNF - BLOCK + 65; // 21825
+    pub const ROW_NF: usize = STEP_LNF + 65; // 21953
+    pub const ACTIVE_BLOCKS: usize = STEP_LNF / BLOCK + 1; // 172
+
+    /// Nullifier-perm tweak binding a presentation to `rwa_id` and `challenge`.
+    pub fn challenge_element(rwa_id: &[u8; 32], challenge: &[u8; 32]) -> BaseElement {
+        let mut h = blake3::Hasher::new();
+        h.update(CHALLENGE_DOMAIN);
+        h.update(rwa_id);
+        h.update(challenge);
+        BaseElement::new(u128::from_le_bytes(h.finalize().as_bytes()[..16].try_into().unwrap()))
+    }
+
+    /// Presentation tag: the nullifier perm over the blinding, seeded by
+    /// `challenge_element(rwa_id, challenge)`.
+    pub fn presentation_tag(b: &[u8; 32], rwa_id: &[u8; 32], challenge: &[u8; 32]) -> [u8; 32] {
+        let (b_lo, b_hi) = v2::unpack(b);
+        let mut state = [v2::nullifier_iv(), b_lo, b_hi, challenge_element(rwa_id, challenge)];
+        poseidon_perm(&mut state);
+        v2::pack(state[1], state[2])
+    }
+
+    #[derive(Clone)]
+    pub struct OwnershipPI {
+        pub root: [u8; 32],
+        /// Canonical rwa_id — `verify_ownership_proof` rejects any other.
+        pub rwa_id: [u8; 32],
+        /// Units held by the presented note.
+        pub units: u64,
+        pub challenge: [u8; 32],
+        pub tag: [u8; 32],
+        /// Root of the ownership spent set the note is absent from.
+        pub spent_root: [u8; 32],
+    }
+
+    impl ToElements<BaseElement> for OwnershipPI {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            let (r_lo, r_hi) = v2::unpack(&self.root);
+            let (a_lo, a_hi) = v2::unpack(&self.rwa_id);
+            let (c_lo, c_hi) = v2::unpack(&self.challenge);
+            let (t_lo, t_hi) = v2::unpack(&self.tag);
+            let (s_lo, s_hi) = v2::unpack(&self.spent_root);
+            alloc::vec![r_lo, r_hi, a_lo, a_hi, BaseElement::new(self.units as u128),
+                c_lo, c_hi, t_lo, t_hi, s_lo, s_hi]
+        }
+    }
+
+    /// Full-length (32768) schedule masks.
+    /// Order: [m_rnd, m_frz, m_lnote, m_anote, m_lmrk, m_amrk, m_lleaf, m_lnf,
+    ///         m_key, m_rstep, m_rst, m_row0, m_auxfrz, m_bitfrz, m_rhifrz, m_nfchk]
+    pub fn ownership_masks() -> Vec<Vec<BaseElement>> {
+        let z = BaseElement::ZERO;
+        let o = BaseElement::ONE;
+        let n = TRACE_LEN;
+        let mut m_rnd    = alloc::vec![z; n];
+        let mut m_frz    = alloc::vec![z; n];
+        let mut m_lnote  = alloc::vec![z; n];
+        let mut m_anote  = alloc::vec![z; n];
+        let mut m_lmrk   = alloc::vec![z; n];
+        let mut m_amrk   = alloc::vec![z; n];
+        let mut m_lleaf  = alloc::vec![z; n];
+        let mut m_lnf    = alloc::vec![z; n];
+        let mut m_key    = alloc::vec![z; n];
+        let mut m_rstep  = alloc::vec![z; n];
+        let mut m_rst    = alloc::vec![z; n];
+        let mut m_row0   = alloc::vec![z; n];
+        let mut m_auxfrz = alloc::vec![o; n];
+        let mut m_bitfrz = alloc::vec![z; n];
+        let mut m_rhifrz = alloc::vec![o; n];
+        let mut m_nfchk  = alloc::vec![z; n];
+        for b in 0..ACTIVE_BLOCKS {
+            for s in 1..=64 { m_rnd[b * BLOCK + s] = o; }
+            for s in 65..BLOCK { m_frz[b * BLOCK + s] = o; }
+        }
+        m_frz[0] = o; // tag block boundary: hold initial state
+        m_lnote[STEP_LNOTE] = o;
+        m_anote[STEP_ANOTE] = o;
+        for d in 0..LEVELS {
+            let link = STEP_MRK_BASE + 256 * d;
+            m_lmrk[link] = o;
+            m_amrk[link + BLOCK] = o;
+            m_auxfrz[link] = z;
+            m_rhifrz[link] = z;
+        }
+        for j in 0..SPENT_LEVELS {
+            let link = STEP_SPENT_BASE + 256 * j;
+            // Level 0 hashes up from the empty leaf rather than the state.
+            if j == 0 { m_lleaf[link] = o; } else { m_lmrk[link] = o; }
+            m_amrk[link + BLOCK] = o;
+            m_key[link] = o;
+            m_auxfrz[link] = z;
+            m_rhifrz[link] = z;
+        }
+        m_lnf[STEP_LNF] = o;
+        for s in 0..63 { m_rstep[s] = o; }
+        m_rst[63] = o;
+        m_row0[0] = o;
+        for s in STEP_MRK_BASE..STEP_LNF {
+            if (s - STEP_MRK_BASE) % 256 != 0 { m_bitfrz[s] = o; }
+        }
+        m_nfchk[ROW_NF] = o;
+        alloc::vec![
+            m_rnd, m_frz, m_lnote, m_anote, m_lmrk, m_amrk, m_lleaf, m_lnf,
+            m_key, m_rstep, m_rst, m_row0, m_auxfrz, m_bitfrz, m_rhifrz, m_nfchk,
+        ]
+    }
+
+    pub struct OwnershipAir {
+        ctx: AirContext<BaseElement>,
+        pi: OwnershipPI,
+        note_iv: BaseElement,
+        merkle_iv: BaseElement,
+        nullifier_iv: BaseElement,
+        tweak: BaseElement,
+    }
+
+    impl Air for OwnershipAir {
+        type BaseField = BaseElement;
+        type PublicInputs = OwnershipPI;
+
+        fn new(ti: TraceInfo, pi: OwnershipPI, opts: ProofOptions) -> Self {
+            let d = alloc::vec![
+                // r0–r3: Poseidon round (cube w/ period-128 rc) × full mask
+                TransitionConstraintDegree::with_cycles(3, alloc::vec![BLOCK, TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(3, alloc::vec![BLOCK, TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(3, alloc::vec![BLOCK, TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(3, alloc::vec![BLOCK, TRACE_LEN]),
+                // r4: bit boolean
+                TransitionConstraintDegree::new(2),
+                // r5: acc step
+                TransitionConstraintDegree::with_cycles(2, alloc::vec![TRACE_LEN]),
+                // r6: pow step
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                // r7: hi range sum check at step 63
+                TransitionConstraintDegree::with_cycles(2, alloc::vec![TRACE_LEN]),
+                // r8–r12: global carries (b_lo, b_hi, pkd_lo, pkd_hi, hi)
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+                // r13–r14: aux freeze
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                // r15: bit freeze in the path regions
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                // r16: r_hi carry set/freeze
+                TransitionConstraintDegree::with_cycles(2, alloc::vec![TRACE_LEN]),
+                // r17–r18: key accumulator and pow, stepped at spent-set links
+                TransitionConstraintDegree::with_cycles(2, alloc::vec![TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                // r19–r22: row-0 tag perm init
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+                // r23: nf_lo == key + 2^64·hi
+                TransitionConstraintDegree::with_cycles(1, alloc::vec![TRACE_LEN]),
+            ];
+            let tweak = challenge_element(&pi.rwa_id, &pi.challenge);
+            OwnershipAir {
+                ctx: AirContext::new(ti, d, 10, opts),
+                pi,
+                note_iv: v2::note_iv(),
+                merkle_iv: v2::merkle_iv(),
+                nullifier_iv: v2::nullifier_iv(),
+                tweak,
+            }
+        }
+
+        fn context(&self) -> &AirContext<BaseElement> { &self.ctx }
+
+        fn get_periodic_column_values(&self) -> Vec<Vec<BaseElement>> {
+            let mut res = poseidon_periodic_128();
+            res.extend(ownership_masks());
+            res
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self, f: &EvaluationFrame<E>, p: &[E], r: &mut [E]) {
+            // p: [rc0,rc1,rc2,rc3,isf,
+            //     m_rnd,m_frz,m_lnote,m_anote,m_lmrk,m_amrk,m_lleaf,m_lnf,
+            //     m_key,m_rstep,m_rst,m_row0,m_auxfrz,m_bitfrz,m_rhifrz,m_nfchk]
+            let one = E::ONE;
+            let c = f.current();
+            let n = f.next();
+            let (rc0, rc1, rc2, rc3, isf) = (p[0], p[1], p[2], p[3], p[4]);
+            let (m_rnd, m_frz, m_lnote, m_anote) = (p[5], p[6], p[7], p[8]);
+            let (m_lmrk, m_amrk, m_lleaf, m_lnf) = (p[9], p[10], p[11], p[12]);
+            let (m_key, m_rstep, m_rst, m_row0) = (p[13], p[14], p[15], p[16]);
+            let (m_auxfrz, m_bitfrz, m_rhifrz, m_nfchk) = (p[17], p[18], p[19], p[20]);
+
+            let note_iv = E::from(self.note_iv);
+            let merkle_iv = E::from(self.merkle_iv);
+            let nullifier_iv = E::from(self.nullifier_iv);
+            let tweak = E::from(self.tweak);
+            let units = E::from(BaseElement::new(self.pi.units as u128));
+            let two_64 = E::from(BaseElement::new(1u128 << 64));
+
+            // --- Poseidon round on current state ---
+            let a = [c[0] + rc0, c[1] + rc1, c[2] + rc2, c[3] + rc3];
+            let ac = [
+                a[0].square() * a[0], a[1].square() * a[1],
+                a[2].square() * a[2], a[3].square() * a[3],
+            ];
+            let b0 = ac[0];
+            let b1 = isf * ac[1] + (one - isf) * a[1];
+            let b2 = isf * ac[2] + (one - isf) * a[2];
+            let b3 = isf * ac[3] + (one - isf) * a[3];
+            let bsum = b0 + b1 + b2 + b3;
+            let exp = [b0 + bsum, b1 + bsum, b2 + bsum, b3 + bsum];
+
+            // --- merkle link mux (bit/sibling read from NEXT row: frozen per level) ---
+            let bit = n[4];
+            let l_lo = bit * n[9] + (one - bit) * c[1];
+            let l_hi = bit * n[10] + (one - bit) * c[2];
+            let r_lo = bit * c[1] + (one - bit) * n[9];
+            let r_hi = bit * c[2] + (one - bit) * n[10];
+            // The same mux with the empty leaf (0, 0) as the current node.
+            let e_rhi = (one - bit) * n[10];
+
+            // r0–r3: state column transitions (masks are disjoint per step)
+            let lnote = [note_iv, units, c[7], c[8]];
+            let lmrk = [merkle_iv, l_lo, l_hi, r_lo];
+            let lleaf = [merkle_iv, bit * n[9], bit * n[10], (one - bit) * n[9]];
+            let lnf = [nullifier_iv, c[7], c[8], E::ZERO];
+            let abs1 = m_anote * c[12] + m_amrk * c[11];
+            let abs2 = m_anote * c[13];
+            let m_abs = m_anote + m_amrk;
+            for i in 0..4 {
+                let absorb_i = match i { 1 => abs1, 2 => abs2, _ => E::ZERO };
+                r[i] = m_rnd * (n[i] - exp[i])
+                    + m_frz * (n[i] - c[i])
+                    + m_lnote * (n[i] - lnote[i])
+                    + m_lmrk * (n[i] - lmrk[i])
+                    + m_lleaf * (n[i] - lleaf[i])
+                    + m_lnf * (n[i] - lnf[i])
+                    + m_abs * (n[i] - c[i]) - absorb_i;
+            }
+
+            // r4: bit is boolean everywhere
+            r[4] = c[4] * (c[4] - one);
+            // r5: range accumulator step
+            r[5] = m_rstep * (n[5] - c[5] - c[4] * c[6]);
+            // r6: pow doubling
+            r[6] = m_rstep * (n[6] - c[6].double());
+            // r7: range sum check — hi at step 63
+            r[7] = m_rst * (c[5] + c[4] * c[6] - c[16]);
+            // r8–r12: global carries frozen
+            r[8] = n[7] - c[7];
+            r[9] = n[8] - c[8];
+            r[10] = n[12] - c[12];
+            r[11] = n[13] - c[13];
+            r[12] = n[16] - c[16];
+            // r13–r14: aux frozen except at link steps
+            r[13] = m_auxfrz * (n[9] - c[9]);
+            r[14] = m_auxfrz * (n[10] - c[10]);
+            // r15: path bit frozen within each level
+            r[15] = m_bitfrz * (n[4] - c[4]);
+            // r16: r_hi carry set at links, frozen otherwise
+            r[16] = m_lmrk * (n[11] - r_hi) + m_lleaf * (n[11] - e_rhi) + m_rhifrz * (n[11] - c[11]);
+            // r17–r18: key gains bit·2^j at spent-set level j, frozen otherwise
+            r[17] = n[14] - c[14] - m_key * n[4] * c[15];
+            r[18] = n[15] - c[15] - m_key * c[15];
+            // r19–r22: tag perm initial state at row 0
+            r[19] = m_row0 * (c[0] - nullifier_iv);
+            r[20] = m_row0 * (c[1] - c[7]);
+            r[21] = m_row0 * (c[2] - c[8]);
+            r[22] = m_row0 * (c[3] - tweak);
+            // r23: the spent-set key is the low 64 bits of nf_lo
+            r[23] = m_nfchk * (c[1] - c[14] - two_64 * c[16]);
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+            let (tag_lo, tag_hi) = v2::unpack(&self.pi.tag);
+            let (root_lo, root_hi) = v2::unpack(&self.pi.root);
+            let (sr_lo, sr_hi) = v2::unpack(&self.pi.spent_root);
+            alloc::vec![
+                Assertion::single(5, 0, BaseElement::ZERO),
+                Assertion::single(6, 0, BaseElement::ONE),
+                Assertion::single(14, 0, BaseElement::ZERO),
+                Assertion::single(15, 0, BaseElement::ONE),
+                Assertion::single(1, ROW_TAG, tag_lo),
+                Assertion::single(2, ROW_TAG, tag_hi),
+                Assertion::single(1, ROW_ROOT, root_lo),
+                Assertion::single(2, ROW_ROOT, root_hi),
+                Assertion::single(1, ROW_SPENT_ROOT, sr_lo),
+                Assertion::single(2, ROW_SPENT_ROOT, sr_hi),
+            ]
+        }
+    }
+
+    /// Verify an ownership presentation.
+    ///
+    /// `root` must be checked by the caller against the chain's
+    /// `CurrentOwnershipRoot` / `RecentOwnershipRoots` for `rwa_id`, and
+    /// `spent_root` against `CurrentOwnershipSpentRoot`; `challenge` is
+    /// chosen by the verifier. Returns false for a non-canonical `rwa_id`.
+    pub fn verify_ownership_proof(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        rwa_id: &[u8; 32],
+        units: u64,
+        challenge: &[u8; 32],
+        tag: &[u8; 32],
+        spent_root: &[u8; 32],
+    ) -> bool {
+        verify_ownership_proof_with(
+            proof_bytes, root, rwa_id, units, challenge, tag, spent_root, &envelope::StarkSecurity::DEFAULT,
+        )
+    }
+
+    /// `verify_ownership_proof` under a caller-chosen security policy.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_ownership_proof_with(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        rwa_id: &[u8; 32],
+        units: u64,
+        challenge: &[u8; 32],
+        tag: &[u8; 32],
+        spent_root: &[u8; 32],
+        security: &envelope::StarkSecurity,
+    ) -> bool {
+        if v2::rwa_asset_id(rwa_id).is_none() { return false; }
+        let (version, proof, acceptable) = match envelope::open_stark(proof_bytes, envelope::AirId::Ownership, security) {
+            Some(opened) => opened,
+            None => return false,
+        };
+        let pi = OwnershipPI {
+            root: *root,
+            rwa_id: *rwa_id,
+            units,
+            challenge: *challenge,
+            tag: *tag,
+            spent_root: *spent_root,
+        };
+        match version {
+            1 => winter_verifier::verify::<OwnershipAir, StarkHash, StarkCoin, StarkVC>(
+                proof, pi, &acceptable,
+            ).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Proof-generation infrastructure — only compiled when feature = "prover".
+    #[cfg(feature = "prover")]
+    pub mod prover_impl {
+        use super::*;
+        use super::super::{poseidon_eval_round_base, poseidon_perm};
+        use winter_verifier::math::{fields::f128::BaseElement, FieldElement};
+        use winterfell::{
+            crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
+            matrix::ColMatrix,
+            AuxRandElements, CompositionPoly, CompositionPolyTrace,
+            DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
+            PartitionOptions, ProofOptions, Prover, StarkDomain,
+            TraceInfo, TracePolyTable, TraceTable,
+        };
+        type HashFn = Blake3_256<BaseElement>;
+        type VC = MerkleTree<HashFn>;
+        type RandCoin = DefaultRandomCoin<HashFn>;
+
+        /// Private witness: the ownership note opening, its Merkle path, and
+        /// the spent-set siblings of its nullifier's slot (`v2::spent_key`).
+        pub struct OwnershipWitness {
+            pub units: u64, pub b: [u8; 32], pub pkd: [u8; 32], pub index: u32,
+            pub siblings: [[u8; 32]; LEVELS],
+            pub spent_siblings: [[u8; 32]; SPENT_LEVELS],
+        }
+
+        /// Public side of a presentation, handed to the verifier with the proof.
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        pub struct OwnershipPresentation {
+            pub root: [u8; 32],
+            pub rwa_id: [u8; 32],
+            pub units: u64,
+            pub challenge: [u8; 32],
+            pub tag: [u8; 32],
+            pub spent_root: [u8; 32],
+            pub proof: alloc::vec::Vec<u8>,
+        }
+
+        /// Node of a path level: the link and absorb perm inputs and the
+        /// r_hi carry, given the current node and the level's sibling.
+        fn level_inputs(
+            cur: (BaseElement, BaseElement), sibling: &[u8; 32], right: bool,
+        ) -> ([BaseElement; 4], BaseElement) {
+            let (s_lo, s_hi) = v2::unpack(sibling);
+            let (l_lo, l_hi, r_lo, r_hi) = if right {
+                (s_lo, s_hi, cur.0, cur.1)
+            } else {
+                (cur.0, cur.1, s_lo, s_hi)
+            };
+            ([v2::merkle_iv(), l_lo, l_hi, r_lo], r_hi)
+        }
+
+        /// Input and output state of each active block, and the r_hi carry
+        /// of each ownership and spent-set level.
+        struct BlockChain {
+            input: alloc::vec::Vec<[BaseElement; 4]>,
+            output: alloc::vec::Vec<[BaseElement; 4]>,
+            r_hi: alloc::vec::Vec<BaseElement>,
+        }
+
+        fn block_chain(w: &OwnershipWitness, key: u64, tweak: BaseElement) -> BlockChain {
+            let z = BaseElement::ZERO;
+            let (b_lo, b_hi) = v2::unpack(&w.b);
+            let (pkd_lo, pkd_hi) = v2::unpack(&w.pkd);
+            let mut input = alloc::vec::Vec::with_capacity(ACTIVE_BLOCKS);
+            let mut output = alloc::vec::Vec::with_capacity(ACTIVE_BLOCKS);
+            let mut r_hi = alloc::vec::Vec::with_capacity(LEVELS + SPENT_LEVELS);
+            let mut run = |s: [BaseElement; 4]| {
+                input.push(s);
+                let mut out = s;
+                poseidon_perm(&mut out);
+                output.push(out);
+                out
+            };
+            // b0: tag perm; b1, b2: note perms.
+            run([v2::nullifier_iv(), b_lo, b_hi, tweak]);
+            let s = run([v2::note_iv(), BaseElement::new(w.units as u128), b_lo, b_hi]);
+            let mut cur = run([s[0], s[1] + pkd_lo, s[2] + pkd_hi, s[3]]);
+            // b3+2d, b4+2d: ownership level d.
+            for d in 0..LEVELS {
+                let (link, hi) = level_inputs((cur[1], cur[2]), &w.siblings[d], (w.index >> d) & 1 == 1);
+                r_hi.push(hi);
+                let s = run(link);
+                cur = run([s[0], s[1] + hi, s[2], s[3]]);
+            }
+            // b43+2j, b44+2j: spent-set level j, from the empty leaf.
+            let mut node = (z, z);
+            for j in 0..SPENT_LEVELS {
+                let (link, hi) = level_inputs(node, &w.spent_siblings[j], (key >> j) & 1 == 1);
+                r_hi.push(hi);
+                let s = run(link);
+                let out = run([s[0], s[1] + hi, s[2], s[3]]);
+                node = (out[1], out[2]);
+            }
+            // b171: nullifier perm.
+            run([v2::nullifier_iv(), b_lo, b_hi, z]);
+            BlockChain { input, output, r_hi }
+        }
+
+        /// State columns 0–3 of block `k`, as in SpendAir: step 0 holds the
+        /// previous block's output (block 0: its own input), step 1 the
+        /// input, steps 2–65 the round outputs and steps 66–127 the frozen
+        /// output. Padding blocks hold the last output throughout.
+        fn fill_state_block(k: usize, mut state: [&mut [BaseElement]; 4], chain: &BlockChain) {
+            let mut put = |row: usize, s: &[BaseElement; 4]| {
+                for (col, v) in state.iter_mut().zip(s.iter()) { col[row] = *v; }
+            };
+            if k >= ACTIVE_BLOCKS {
+                let last = chain.output[ACTIVE_BLOCKS - 1];
+                for row in 0..BLOCK { put(row, &last); }
+                return;
+            }
+            let input = chain.input[k];
+            put(0, if k == 0 { &input } else { &chain.output[k - 1] });
+            put(1, &input);
+            let mut s = input;
+            let mut out = s;
+            for r in 0..64 {
+                poseidon_eval_round_base(&s, r, &mut out);
+                s = out;
+                put(r + 2, &s);
+            }
+            for row in 66..BLOCK { put(row, &s); }
+        }
+
+        /// Fill the state columns block by block, across threads under the
+        /// `concurrent` feature.
+        fn fill_state_blocks(cols: &mut [alloc::vec::Vec<BaseElement>], chain: &BlockChain) {
+            let [c0, c1, c2, c3, ..] = cols else { unreachable!("trace has {TRACE_WIDTH} columns") };
+            #[cfg(not(feature = "concurrent"))]
+            c0.chunks_mut(BLOCK)
+                .zip(c1.chunks_mut(BLOCK))
+                .zip(c2.chunks_mut(BLOCK))
+                .zip(c3.chunks_mut(BLOCK))
+                .enumerate()
+                .for_each(|(k, (((s0, s1), s2), s3))| fill_state_block(k, [s0, s1, s2, s3], chain));
+            #[cfg(feature = "concurrent")]
+            {
+                use rayon::prelude::*;
+                c0.par_chunks_mut(BLOCK)
+                    .zip(c1.par_chunks_mut(BLOCK))
+                    .zip(c2.par_chunks_mut(BLOCK))
+                    .zip(c3.par_chunks_mut(BLOCK))
+                    .enumerate()
+                    .for_each(|(k, (((s0, s1), s2), s3))| fill_state_block(k, [s0, s1, s2, s3], chain));
+            }
+        }
+
+        pub(crate) fn build_ownership_trace(w: &OwnershipWitness, tweak: BaseElement) -> TraceTable<BaseElement> {
+            let n = TRACE_LEN;
+            let z = BaseElement::ZERO;
+            let one = BaseElement::ONE;
+            let mut cols: alloc::vec::Vec<alloc::vec::Vec<BaseElement>> =
+                alloc::vec![alloc::vec![z; n]; TRACE_WIDTH];
+
+            let (b_lo, b_hi) = v2::unpack(&w.b);
+            let (pkd_lo, pkd_hi) = v2::unpack(&w.pkd);
+            let nf = v2::nullifier_hash(&w.b);
+            let key = v2::spent_key(&nf);
+            let hi = u64::from_le_bytes(nf[8..16].try_into().unwrap());
+            let hif = BaseElement::new(hi as u128);
+
+            // Global carries (cols 7, 8, 12, 13, 16).
+            for r in 0..n {
+                cols[7][r]  = b_lo;
+                cols[8][r]  = b_hi;
+                cols[12][r] = pkd_lo;
+                cols[13][r] = pkd_hi;
+                cols[16][r] = hif;
+            }
+            // Bit column col[4]: hi bits rows 0-63, then one bit per path level.
+            for i in 0..64 {
+                cols[4][i] = BaseElement::new(((hi >> i) & 1) as u128);
+            }
+            let bits = (0..LEVELS).map(|d| (w.index >> d) & 1 == 1)
+                .chain((0..SPENT_LEVELS).map(|j| (key >> j) & 1 == 1));
+            let siblings = w.siblings.iter().chain(w.spent_siblings.iter());
+            for (l, (bit, sibling)) in bits.zip(siblings).enumerate() {
+                let link = STEP_MRK_BASE + 256 * l;
+                let (s_lo, s_hi) = v2::unpack(sibling);
+                let end = if l == LEVELS + SPENT_LEVELS - 1 { n - 1 } else { link + 256 };
+                for r in (link + 1)..=end {
+                    // The bit is free after the last level; keep it in place.
+                    cols[4][r]  = if bit { one } else { z };
+                    cols[9][r]  = s_lo;
+                    cols[10][r] = s_hi;
+                }
+            }
+            // acc / pow (cols 5, 6).
+            cols[5][0] = z;
+            cols[6][0] = one;
+            for s in 0..63 {
+                cols[5][s + 1] = cols[5][s] + cols[4][s] * cols[6][s];
+                cols[6][s + 1] = cols[6][s].double();
+            }
+            for r in 64..n {
+                cols[5][r] = cols[5][63];
+                cols[6][r] = cols[6][63];
+            }
+            // key acc / pow (cols 14, 15): step at each spent-set link.
+            let mut acc = z;
+            let mut pow = one;
+            for r in 0..n {
+                cols[14][r] = acc;
+                cols[15][r] = pow;
+                if (STEP_SPENT_BASE..STEP_LNF).contains(&r) && (r - STEP_SPENT_BASE) % 256 == 0 {
+                    let j = (r - STEP_SPENT_BASE) / 256;
+                    if (key >> j) & 1 == 1 { acc += pow; }
+                    pow = pow.double();
+                }
+            }
+
+            // --- State simulation ---
+            let chain = block_chain(w, key, tweak);
+            // r_hi carry (col 11): from each level's link to the next link,
+            // and to the end of the trace after the last level.
+            for (l, hi) in chain.r_hi.iter().enumerate() {
+                let link = STEP_MRK_BASE + 256 * l;
+                let end = if l == LEVELS + SPENT_LEVELS - 1 { n - 1 } else { link + 256 };
+                for r in (link + 1)..=end { cols[11][r] = *hi; }
+            }
+            fill_state_blocks(&mut cols, &chain);
+
+            TraceTable::init(cols)
+        }
+
+        struct OwnershipProverInner {
+            pi: OwnershipPI,
+            options: ProofOptions,
+        }
+
+        impl Prover for OwnershipProverInner {
+            type BaseField = BaseElement;
+            type Air = OwnershipAir;
+            type Trace = TraceTable<BaseElement>;
+            type HashFn = HashFn;
+            type VC = VC;
+            type RandomCoin = RandCoin;
+            type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultTraceLde<E, Self::HashFn, Self::VC>;
+            type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+            type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintEvaluator<'a, Self::Air, E>;
+            fn get_pub_inputs(&self, _: &Self::Trace) -> OwnershipPI { self.pi.clone() }
+            fn options(&self) -> &ProofOptions { &self.options }
+            fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, ti: &TraceInfo, mt: &ColMatrix<Self::BaseField>,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::TraceLde<E>, TracePolyTable<E>) { DefaultTraceLde::new(ti, mt, d, po) }
+            fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, cpt: CompositionPolyTrace<E>, nc: usize,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+                DefaultConstraintCommitment::new(cpt, nc, d, po)
+            }
+            fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+                &self, air: &'a Self::Air, are: Option<AuxRandElements<E>>,
+                cc: winterfell::ConstraintCompositionCoefficients<E>,
+            ) -> Self::ConstraintEvaluator<'a, E> {
+                DefaultConstraintEvaluator::new(air, are, cc)
+            }
+        }
+
+        /// Prove ownership of an unspent `rwa_id` note, bound to `challenge`.
+        /// Returns None for a non-canonical `rwa_id`.
+        pub fn prove_ownership(
+            w: &OwnershipWitness,
+            rwa_id: &[u8; 32],
+            challenge: &[u8; 32],
+        ) -> Option<OwnershipPresentation> {
+            prove_ownership_with(w, rwa_id, challenge, envelope::V1_OPTIONS)
+        }
+
+        /// `prove_ownership` with one of the `envelope::V1_PRESETS` options.
+        pub fn prove_ownership_with(
+            w: &OwnershipWitness,
+            rwa_id: &[u8; 32],
+            challenge: &[u8; 32],
+            options: envelope::StarkOptions,
+        ) -> Option<OwnershipPresentation> {
+            v2::rwa_asset_id(rwa_id)?;
+            let leaf = v2::ownership_leaf(w.units, &w.b, &w.pkd);
+            let root = crate::prover::root_from_path(&leaf, w.index, &w.siblings);
+            let key = v2::spent_key(&v2::nullifier_hash(&w.b));
+            let spent_root = v2::spent_path(key, &[0u8; 32], &w.spent_siblings)[SPENT_LEVELS];
+            let tag = presentation_tag(&w.b, rwa_id, challenge);
+            let trace = build_ownership_trace(w, challenge_element(rwa_id, challenge));
+            let pi = OwnershipPI { root, rwa_id: *rwa_id, units: w.units, challenge: *challenge, tag, spent_root };
+            let prover = OwnershipProverInner {
+                pi,
+                options: options.proof_options(),
+            };
+            let proof = prover.prove(trace).expect("ownership STARK").to_bytes();
+            let proof = envelope::seal(envelope::AirId::Ownership, envelope::CURRENT_VERSION, options, &proof);
+            Some(OwnershipPresentation {
+                root, rwa_id: *rwa_id, units: w.units, challenge: *challenge, tag, spent_root, proof,
+            })
+        }
+    }
+}
+
+// ===================================================================
+//  Buyer eligibility credentials
+//
+//  A credential is a leaf NoteHash(class, b, 0) in an issuer's
+//  credential tree on ProofHub. `class` is a bit set of eligibility
+//  flags chosen by the issuer; `b` is the holder's secret. A purchase
+//  of a gated asset presents the credential alongside the spend,
+//  reusing SpendAir in withdraw mode (v == class, zero change) with
+//  the nullifier perm seeded by the asset:
+//
+//    tweak = F128(LE16(BLAKE3("nulla_credential_scope_v1" ‖ rwa_id)))
+//    tag   = Poseidon([NULLIFIER_IV, b_lo, b_hi, tweak])[1..3]
+//
+//  The tag is stable per (credential, rwa_id), so one credential buys
+//  an asset once: sharing it does not let several buyers through. It
+//  is unlinkable across assets and to the credential leaf.
+//
+//  `binding` (the spend nullifier) is in the transcript but not
+//  constrained, tying the presentation to one spend so it cannot be
+//  lifted into another buyer's purchase. Public inputs: root, rwa_id,
+//  class, tag, binding.
+// ===================================================================
+pub mod credential_v2 {
+    use super::*;
+    use super::spend_v2::{SpendAir, SpendPI};
+    use super::v2;
+    use alloc::vec::Vec;
+
+    pub const SCOPE_DOMAIN: &[u8] = b"nulla_credential_scope_v1";
+
+    /// Nullifier-perm tweak scoping a credential tag to `rwa_id`.
+    pub fn scope_element(rwa_id: &[u8; 32]) -> BaseElement {
+        let mut h = blake3::Hasher::new();
+        h.update(SCOPE_DOMAIN);
+        h.update(rwa_id);
+        BaseElement::new(u128::from_le_bytes(h.finalize().as_bytes()[..16].try_into().unwrap()))
+    }
+
+    /// Credential leaf: NoteHash(class, b, 0). Credentials carry no key
+    /// digest, so presentations reveal nothing about the holder's notes.
+    pub fn credential_leaf(class: u64, b: &[u8; 32]) -> [u8; 32] {
+        v2::note_hash(class, b, &[0u8; 32])
+    }
+
+    /// Credential tag: the nullifier perm over the secret, seeded by
+    /// `scope_element(rwa_id)`.
+    pub fn credential_tag(b: &[u8; 32], rwa_id: &[u8; 32]) -> [u8; 32] {
+        let (b_lo, b_hi) = v2::unpack(b);
+        let mut state = [v2::nullifier_iv(), b_lo, b_hi, scope_element(rwa_id)];
+        poseidon_perm(&mut state);
+        v2::pack(state[1], state[2])
+    }
+
+    #[derive(Clone)]
+    pub struct CredentialPI {
+        pub root: [u8; 32],
+        /// Canonical rwa_id — `verify_credential_proof` rejects any other.
+        pub rwa_id: [u8; 32],
+        /// Eligibility flags of the presented credential.
+        pub class: u64,
+        pub tag: [u8; 32],
+        /// Spend the presentation is tied to; transcript-only.
+        pub binding: [u8; 32],
+    }
+
+    impl CredentialPI {
+        /// The SpendAir statement being proven: withdraw mode over the
+        /// credential tree with `price_or_amount = class` and a zero pkd.
+        pub(crate) fn spend_pi(&self) -> SpendPI {
+            SpendPI {
+                root: self.root,
+                nullifier: self.tag,
+                pkd: [0u8; 32],
+                price_or_amount: self.class,
+                change_leaf: super::spend_v2::zero_change_leaf(),
+                change_pkd: [0u8; 32],
+                mode: 0,
+            }
+        }
+    }
+
+    impl ToElements<BaseElement> for CredentialPI {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            let (r_lo, r_hi) = v2::unpack(&self.root);
+            let (a_lo, a_hi) = v2::unpack(&self.rwa_id);
+            let (t_lo, t_hi) = v2::unpack(&self.tag);
+            let (x_lo, x_hi) = v2::unpack(&self.binding);
+            alloc::vec![r_lo, r_hi, a_lo, a_hi, BaseElement::new(self.class as u128),
+                t_lo, t_hi, x_lo, x_hi]
+        }
+    }
+
+    /// SpendAir constraints under the CredentialPI transcript.
+    pub struct CredentialAir {
+        inner: SpendAir,
+    }
+
+    impl Air for CredentialAir {
+        type BaseField = BaseElement;
+        type PublicInputs = CredentialPI;
+
+        fn new(ti: TraceInfo, pi: CredentialPI, opts: ProofOptions) -> Self {
+            let tweak = scope_element(&pi.rwa_id);
+            CredentialAir { inner: SpendAir::with_nullifier_tweak(ti, pi.spend_pi(), opts, tweak) }
+        }
+
+        fn context(&self) -> &AirContext<BaseElement> { self.inner.context() }
+
+        fn get_periodic_column_values(&self) -> Vec<Vec<BaseElement>> {
+            self.inner.get_periodic_column_values()
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self, f: &EvaluationFrame<E>, p: &[E], r: &mut [E]) {
+            self.inner.evaluate_transition(f, p, r)
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+            self.inner.get_assertions()
+        }
+    }
+
+    /// Verify a credential presentation.
+    ///
+    /// `root` must be checked by the caller against the issuer's current /
+    /// recent credential roots, and `tag` against the spent-tag set.
+    /// Returns false for a non-canonical `rwa_id`.
+    pub fn verify_credential_proof(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        rwa_id: &[u8; 32],
+        class: u64,
+        tag: &[u8; 32],
+        binding: &[u8; 32],
+    ) -> bool {
+        verify_credential_proof_with(
+            proof_bytes, root, rwa_id, class, tag, binding, &envelope::StarkSecurity::DEFAULT,
+        )
+    }
+
+    /// `verify_credential_proof` under a caller-chosen security policy.
+    pub fn verify_credential_proof_with(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        rwa_id: &[u8; 32],
+        class: u64,
+        tag: &[u8; 32],
+        binding: &[u8; 32],
+        security: &envelope::StarkSecurity,
+    ) -> bool {
+        if v2::rwa_asset_id(rwa_id).is_none() { return false; }
+        let (version, proof, acceptable) = match envelope::open_stark(proof_bytes, envelope::AirId::Credential, security) {
+            Some(opened) => opened,
+            None => return false,
+        };
+        let pi = CredentialPI { root: *root, rwa_id: *rwa_id, class, tag: *tag, binding: *binding };
+        match version {
+            1 => winter_verifier::verify::<CredentialAir, StarkHash, StarkCoin, StarkVC>(
+                proof, pi, &acceptable,
+            ).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Proof-generation infrastructure — only compiled when feature = "prover".
+    #[cfg(feature = "prover")]
+    pub mod prover_impl {
+        use super::*;
+        use super::super::spend_v2::{LEVELS, prover_impl::{SpendWitness, build_spend_trace_with_tweak}};
+        use winter_verifier::math::{fields::f128::BaseElement, FieldElement};
+        use winterfell::{
+            crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
+            matrix::ColMatrix,
+            AuxRandElements, CompositionPoly, CompositionPolyTrace,
+            DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
+            PartitionOptions, ProofOptions, Prover, StarkDomain,
+            TraceInfo, TracePolyTable, TraceTable,
+        };
+        type HashFn = Blake3_256<BaseElement>;
+        type VC = MerkleTree<HashFn>;
+        type RandCoin = DefaultRandomCoin<HashFn>;
+
+        /// Private witness: the credential opening and its Merkle path.
+        pub struct CredentialWitness {
+            pub class: u64, pub b: [u8; 32], pub index: u32,
+            pub siblings: [[u8; 32]; LEVELS],
+        }
+
+        /// Public side of a presentation, placed in the spend's public inputs.
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        pub struct CredentialPresentation {
+            pub root: [u8; 32],
+            pub rwa_id: [u8; 32],
+            pub class: u64,
+            pub tag: [u8; 32],
+            pub binding: [u8; 32],
+            pub proof: alloc::vec::Vec<u8>,
+        }
+
+        struct CredentialProverInner {
+            pi: CredentialPI,
+            options: ProofOptions,
+        }
+
+        impl Prover for CredentialProverInner {
+            type BaseField = BaseElement;
+            type Air = CredentialAir;
+            type Trace = TraceTable<BaseElement>;
+            type HashFn = HashFn;
+            type VC = VC;
+            type RandomCoin = RandCoin;
+            type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultTraceLde<E, Self::HashFn, Self::VC>;
+            type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+            type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintEvaluator<'a, Self::Air, E>;
+            fn get_pub_inputs(&self, _: &Self::Trace) -> CredentialPI { self.pi.clone() }
+            fn options(&self) -> &ProofOptions { &self.options }
+            fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, ti: &TraceInfo, mt: &ColMatrix<Self::BaseField>,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::TraceLde<E>, TracePolyTable<E>) { DefaultTraceLde::new(ti, mt, d, po) }
+            fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, cpt: CompositionPolyTrace<E>, nc: usize,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+                DefaultConstraintCommitment::new(cpt, nc, d, po)
+            }
+            fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+                &self, air: &'a Self::Air, are: Option<AuxRandElements<E>>,
+                cc: winterfell::ConstraintCompositionCoefficients<E>,
+            ) -> Self::ConstraintEvaluator<'a, E> {
+                DefaultConstraintEvaluator::new(air, are, cc)
+            }
+        }
+
+        /// Present a credential for a purchase of `rwa_id`, tied to the spend
+        /// nullifier `binding`. Returns None for a non-canonical `rwa_id`.
+        pub fn prove_credential(
+            w: &CredentialWitness,
+            rwa_id: &[u8; 32],
+            binding: &[u8; 32],
+        ) -> Option<CredentialPresentation> {
+            prove_credential_with(w, rwa_id, binding, envelope::V1_OPTIONS)
+        }
+
+        /// `prove_credential` with one of the `envelope::V1_PRESETS` options.
+        pub fn prove_credential_with(
+            w: &CredentialWitness,
+            rwa_id: &[u8; 32],
+            binding: &[u8; 32],
+            options: envelope::StarkOptions,
+        ) -> Option<CredentialPresentation> {
+            v2::rwa_asset_id(rwa_id)?;
+            let leaf = credential_leaf(w.class, &w.b);
+            let root = crate::prover::root_from_path(&leaf, w.index, &w.siblings);
+            let tag = credential_tag(&w.b, rwa_id);
+            let spend = SpendWitness {
+                v: w.class, b: w.b, pkd: [0u8; 32], index: w.index,
+                siblings: w.siblings,
+                cv: 0, cb: [0u8; 32], cpkd: [0u8; 32],
+            };
+            let trace = build_spend_trace_with_tweak(&spend, scope_element(rwa_id));
+            let pi = CredentialPI { root, rwa_id: *rwa_id, class: w.class, tag, binding: *binding };
+            let prover = CredentialProverInner {
+                pi,
+                options: options.proof_options(),
+            };
+            let proof = prover.prove(trace).expect("credential STARK").to_bytes();
+            let proof = envelope::seal(envelope::AirId::Credential, envelope::CURRENT_VERSION, options, &proof);
+            Some(CredentialPresentation {
+                root, rwa_id: *rwa_id, class: w.class, tag, binding: *binding, proof,
+            })
+        }
+    }
+}
+
+// ===================================================================
+//  Phase 9 — v2 spend authorization (ML-DSA-44)
+//
+//  auth = pk (1312 bytes) ‖ sig (2420 bytes)
+//  message = BLAKE3(domain ‖ public_inputs)
+//  domain:  "nulla_spend_auth_v2" (purchase) /
+//           "nulla_withdraw_auth_v2" (withdraw)
+//
+//  The pk digest binds the signing key to the note inside the STARK:
 //  pkd = BLAKE3("nulla_pk_digest_v2" ‖ pk) is a SpendAir public input.
 // ===================================================================
 pub fn verify_spend_auth_v2(auth: &[u8], public_inputs: &[u8], withdraw: bool) -> bool {
@@ -1673,18 +3687,280 @@ pub fn verify_spend_auth_v2(auth: &[u8], public_inputs: &[u8], withdraw: bool) -
     }
 }
 
+// ===================================================================
+//  Paymaster fee credits
+//
+//  A fee credit is a leaf NoteHash(value, b, 0) in the lane's fee-credit
+//  tree, appended by the signed `deposit_fee` that pays `value`. A spend
+//  presents it like a credential (SpendAir in withdraw mode, zero pkd,
+//  zero change) with the nullifier perm seeded by a fixed fee scope:
+//
+//    tweak     = F128(LE16(BLAKE3("nulla_fee_scope_v1")))
+//    nullifier = Poseidon([NULLIFIER_IV, b_lo, b_hi, tweak])[1..3]
+//
+//  The spend reveals the fee root and this nullifier, never the leaf, so
+//  a consumed credit is hidden among every credit under that root and
+//  cannot be traced to the account that deposited it. `context` (the
+//  BLAKE2-256 of the spend's public inputs) is in the transcript but not
+//  constrained: a proof lifted into another spend does not verify.
+//  Public inputs: root, value, nullifier, context.
+// ===================================================================
+pub mod fee {
+    use super::*;
+    use super::spend_v2::{SpendAir, SpendPI};
+    use super::v2;
+    use alloc::vec::Vec;
+
+    pub const SCOPE_DOMAIN: &[u8] = b"nulla_fee_scope_v1";
+
+    /// Nullifier-perm tweak of fee credits; no spend note or credential
+    /// uses it.
+    pub fn scope_element() -> BaseElement {
+        let h = blake3::hash(SCOPE_DOMAIN);
+        BaseElement::new(u128::from_le_bytes(h.as_bytes()[..16].try_into().unwrap()))
+    }
+
+    /// Fee credit leaf: NoteHash(value, b, 0).
+    pub fn credit_leaf(value: u64, b: &[u8; 32]) -> [u8; 32] {
+        v2::note_hash(value, b, &[0u8; 32])
+    }
+
+    /// Nullifier consumed with the credit opened by `b`.
+    pub fn nullifier(b: &[u8; 32]) -> [u8; 32] {
+        let (b_lo, b_hi) = v2::unpack(b);
+        let mut state = [v2::nullifier_iv(), b_lo, b_hi, scope_element()];
+        poseidon_perm(&mut state);
+        v2::pack(state[1], state[2])
+    }
+
+    /// What a fee proof is bound to: BLAKE2-256 of the spend's SCALE
+    /// public inputs.
+    pub fn context(public_inputs: &[u8]) -> [u8; 32] {
+        blake2_256(public_inputs)
+    }
+
+    #[derive(Clone)]
+    pub struct FeePI {
+        pub root: [u8; 32],
+        /// The lane's base fee; the credit must hold exactly this.
+        pub value: u64,
+        pub nullifier: [u8; 32],
+        /// Spend the credit pays for; transcript-only.
+        pub context: [u8; 32],
+    }
+
+    impl FeePI {
+        /// The SpendAir statement being proven: withdraw mode over the
+        /// fee-credit tree with `price_or_amount = value` and a zero pkd.
+        pub(crate) fn spend_pi(&self) -> SpendPI {
+            SpendPI {
+                root: self.root,
+                nullifier: self.nullifier,
+                pkd: [0u8; 32],
+                price_or_amount: self.value,
+                change_leaf: super::spend_v2::zero_change_leaf(),
+                change_pkd: [0u8; 32],
+                mode: 0,
+            }
+        }
+    }
+
+    impl ToElements<BaseElement> for FeePI {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            let (r_lo, r_hi) = v2::unpack(&self.root);
+            let (n_lo, n_hi) = v2::unpack(&self.nullifier);
+            let (c_lo, c_hi) = v2::unpack(&self.context);
+            alloc::vec![r_lo, r_hi, BaseElement::new(self.value as u128), n_lo, n_hi, c_lo, c_hi]
+        }
+    }
+
+    /// SpendAir constraints under the FeePI transcript.
+    pub struct FeeAir {
+        inner: SpendAir,
+    }
+
+    impl Air for FeeAir {
+        type BaseField = BaseElement;
+        type PublicInputs = FeePI;
+
+        fn new(ti: TraceInfo, pi: FeePI, opts: ProofOptions) -> Self {
+            FeeAir { inner: SpendAir::with_nullifier_tweak(ti, pi.spend_pi(), opts, scope_element()) }
+        }
+
+        fn context(&self) -> &AirContext<BaseElement> { self.inner.context() }
+
+        fn get_periodic_column_values(&self) -> Vec<Vec<BaseElement>> {
+            self.inner.get_periodic_column_values()
+        }
+
+        fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+            &self, f: &EvaluationFrame<E>, p: &[E], r: &mut [E]) {
+            self.inner.evaluate_transition(f, p, r)
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+            self.inner.get_assertions()
+        }
+    }
+
+    /// Verify a fee credit spend.
+    ///
+    /// `root` must be checked by the caller against the current / recent
+    /// fee-credit roots, and `nullifier` against the spent set.
+    pub fn verify_fee_proof(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        value: u64,
+        nullifier: &[u8; 32],
+        context: &[u8; 32],
+    ) -> bool {
+        verify_fee_proof_with(proof_bytes, root, value, nullifier, context, &envelope::StarkSecurity::DEFAULT)
+    }
+
+    /// `verify_fee_proof` under a caller-chosen security policy.
+    pub fn verify_fee_proof_with(
+        proof_bytes: &[u8],
+        root: &[u8; 32],
+        value: u64,
+        nullifier: &[u8; 32],
+        context: &[u8; 32],
+        security: &envelope::StarkSecurity,
+    ) -> bool {
+        let (version, proof, acceptable) = match envelope::open_stark(proof_bytes, envelope::AirId::Fee, security) {
+            Some(opened) => opened,
+            None => return false,
+        };
+        let pi = FeePI { root: *root, value, nullifier: *nullifier, context: *context };
+        match version {
+            1 => winter_verifier::verify::<FeeAir, StarkHash, StarkCoin, StarkVC>(
+                proof, pi, &acceptable,
+            ).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Proof-generation infrastructure — only compiled when feature = "prover".
+    #[cfg(feature = "prover")]
+    pub mod prover_impl {
+        use super::*;
+        use super::super::spend_v2::{LEVELS, prover_impl::{SpendWitness, build_spend_trace_with_tweak}};
+        use winter_verifier::math::{fields::f128::BaseElement, FieldElement};
+        use winterfell::{
+            crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree},
+            matrix::ColMatrix,
+            AuxRandElements, CompositionPoly, CompositionPolyTrace,
+            DefaultConstraintCommitment, DefaultConstraintEvaluator, DefaultTraceLde,
+            PartitionOptions, ProofOptions, Prover, StarkDomain,
+            TraceInfo, TracePolyTable, TraceTable,
+        };
+        type HashFn = Blake3_256<BaseElement>;
+        type VC = MerkleTree<HashFn>;
+        type RandCoin = DefaultRandomCoin<HashFn>;
+
+        /// Private witness: the credit opening and its Merkle path.
+        pub struct FeeWitness {
+            pub value: u64, pub b: [u8; 32], pub index: u32,
+            pub siblings: [[u8; 32]; LEVELS],
+        }
+
+        /// Public side of a fee credit spend: the lane's `FeeTicket`.
+        #[derive(Clone, PartialEq, Eq, Debug)]
+        pub struct FeeSpend {
+            pub root: [u8; 32],
+            pub nullifier: [u8; 32],
+            pub proof: alloc::vec::Vec<u8>,
+        }
+
+        struct FeeProverInner {
+            pi: FeePI,
+            options: ProofOptions,
+        }
+
+        impl Prover for FeeProverInner {
+            type BaseField = BaseElement;
+            type Air = FeeAir;
+            type Trace = TraceTable<BaseElement>;
+            type HashFn = HashFn;
+            type VC = VC;
+            type RandomCoin = RandCoin;
+            type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultTraceLde<E, Self::HashFn, Self::VC>;
+            type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+            type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+                DefaultConstraintEvaluator<'a, Self::Air, E>;
+            fn get_pub_inputs(&self, _: &Self::Trace) -> FeePI { self.pi.clone() }
+            fn options(&self) -> &ProofOptions { &self.options }
+            fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, ti: &TraceInfo, mt: &ColMatrix<Self::BaseField>,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::TraceLde<E>, TracePolyTable<E>) { DefaultTraceLde::new(ti, mt, d, po) }
+            fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+                &self, cpt: CompositionPolyTrace<E>, nc: usize,
+                d: &StarkDomain<Self::BaseField>, po: PartitionOptions,
+            ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+                DefaultConstraintCommitment::new(cpt, nc, d, po)
+            }
+            fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+                &self, air: &'a Self::Air, are: Option<AuxRandElements<E>>,
+                cc: winterfell::ConstraintCompositionCoefficients<E>,
+            ) -> Self::ConstraintEvaluator<'a, E> {
+                DefaultConstraintEvaluator::new(air, are, cc)
+            }
+        }
+
+        /// Spend a fee credit for the spend with SCALE `public_inputs`.
+        pub fn prove_fee(w: &FeeWitness, public_inputs: &[u8]) -> FeeSpend {
+            prove_fee_with(w, public_inputs, envelope::V1_OPTIONS)
+        }
+
+        /// `prove_fee` with one of the `envelope::V1_PRESETS` options.
+        pub fn prove_fee_with(w: &FeeWitness, public_inputs: &[u8], options: envelope::StarkOptions) -> FeeSpend {
+            let leaf = credit_leaf(w.value, &w.b);
+            let root = crate::prover::root_from_path(&leaf, w.index, &w.siblings);
+            let nullifier = nullifier(&w.b);
+            let spend = SpendWitness {
+                v: w.value, b: w.b, pkd: [0u8; 32], index: w.index,
+                siblings: w.siblings,
+                cv: 0, cb: [0u8; 32], cpkd: [0u8; 32],
+            };
+            let trace = build_spend_trace_with_tweak(&spend, scope_element());
+            let pi = FeePI { root, value: w.value, nullifier, context: context(public_inputs) };
+            let prover = FeeProverInner {
+                pi,
+                options: options.proof_options(),
+            };
+            let proof = prover.prove(trace).expect("fee STARK").to_bytes();
+            let proof = envelope::seal(envelope::AirId::Fee, envelope::CURRENT_VERSION, options, &proof);
+            FeeSpend { root, nullifier, proof }
+        }
+    }
+}
+
 /// Proof generation (wallet / test binary side).
 /// Enabled by the `prover` feature — not compiled into the runtime.
 #[cfg(feature = "prover")]
 pub mod prover {
     /// Re-export deposit proof builder.
-    pub use super::deposit_v2::prover_impl::{build_deposit_trace, prove_deposit};
+    pub use super::deposit_v2::prover_impl::{build_deposit_trace, prove_deposit, prove_deposit_with};
     /// Re-export spend proof builder and witness type.
-    pub use super::spend_v2::prover_impl::{SpendWitness, build_spend_trace, prove_spend};
+    pub use super::spend_v2::prover_impl::{SpendWitness, build_spend_trace, prove_spend, prove_spend_with};
+    /// Re-export ownership presentation prover (off-chain proof of RWA ownership).
+    pub use super::ownership_v2::prover_impl::{
+        OwnershipWitness, OwnershipPresentation, prove_ownership, prove_ownership_with,
+    };
+    /// Re-export credential presentation prover (buyer eligibility).
+    pub use super::credential_v2::prover_impl::{
+        CredentialWitness, CredentialPresentation, prove_credential, prove_credential_with,
+    };
+    /// Re-export the paymaster fee credit prover.
+    pub use super::fee::prover_impl::{FeeWitness, FeeSpend, prove_fee, prove_fee_with};
+    /// Prover option presets (≈83, 100 and 128 conjectured bits).
+    pub use super::envelope::{StarkOptions, SECURE_100, SECURE_128, V1_OPTIONS};
     /// Re-export v2 crypto helpers needed by wallet code.
     pub use super::v2::{
         MERKLE_DEPTH as LEVELS, note_hash, nullifier_hash, pk_digest, reference_root,
-        zero_subtrees, verify_merkle_path, merkle_hash2,
+        zero_subtrees, verify_merkle_path, merkle_hash2, ownership_leaf, rwa_asset_id,
         SPEND_AUTH_DOMAIN, WITHDRAW_AUTH_DOMAIN,
     };
 
@@ -1738,6 +4014,15 @@ mod v2_tests {
     use alloc::vec::Vec;
     use std::println;
 
+    #[test]
+    fn poseidon_rc_table_matches_blake3() {
+        for round in 0..super::POSEIDON_ROUNDS {
+            for col in 0..super::POSEIDON_T {
+                assert_eq!(super::poseidon_rc(round, col), super::poseidon_rc_derive(round, col));
+            }
+        }
+    }
+
     #[test]
     fn vectors_v2_note() {
         let b = [0x01u8; 32];
@@ -1789,6 +4074,21 @@ mod v2_tests {
         assert_eq!(root, reference_root(&[leaf]));
     }
 
+    #[test]
+    fn ownership_leaf_carries_units() {
+        let b = [0x02u8; 32];
+        let pkd = pk_digest(&[0xBBu8; 1312]);
+        assert_eq!(ownership_leaf(250, &b, &pkd), note_hash(250, &b, &pkd));
+        assert_ne!(ownership_leaf(250, &b, &pkd), ownership_leaf(251, &b, &pkd));
+        let mut rwa_id = [0u8; 32];
+        rwa_id[..4].copy_from_slice(&7u32.to_le_bytes());
+        assert_eq!(rwa_asset_id(&rwa_id), Some(7));
+        // Non-canonical ids (high bytes set) are rejected.
+        let mut bad = rwa_id;
+        bad[31] = 1;
+        assert_eq!(rwa_asset_id(&bad), None);
+    }
+
     #[test]
     fn frontier_matches_reference_many() {
         // Cross-check incremental frontier against the reference tree
@@ -2355,3 +4655,340 @@ mod spend_v2_tests {
         assert!(!super::verify_spend_auth_v2(&auth, &bad, false));
     }
 }
+
+// ===================================================================
+//  OwnershipAir — presentation roundtrip (uses the `prover` feature).
+// ===================================================================
+#[cfg(all(test, feature = "prover"))]
+mod ownership_v2_tests {
+    use super::ownership_v2::*;
+    use super::prover::{OwnershipWitness, prove_ownership};
+    use super::v2;
+
+    #[test]
+    fn ownership_presentation_roundtrip() {
+        let mut rwa_id = [0u8; 32];
+        rwa_id[..4].copy_from_slice(&42u32.to_le_bytes());
+        let b = [0x05u8; 32];
+        let pkd = v2::pk_digest(&[0xCCu8; 1312]);
+
+        // Ownership tree with an unrelated note before ours (index 1).
+        let units = 125u64;
+        let other = v2::ownership_leaf(40, &[0x06u8; 32], &pkd);
+        let ours = v2::ownership_leaf(units, &b, &pkd);
+        let z = v2::zero_subtrees();
+        let mut siblings = [[0u8; 32]; v2::MERKLE_DEPTH];
+        siblings[0] = other;
+        for d in 1..v2::MERKLE_DEPTH { siblings[d] = z[d]; }
+        let root = v2::reference_root(&[other, ours]);
+
+        // Spent set holding the other note's nullifier: our slot's only
+        // non-empty sibling is the subtree where the two keys diverge.
+        let zs = v2::spent_zero_subtrees();
+        let empty: [[u8; 32]; v2::SPENT_DEPTH] = core::array::from_fn(|d| zs[d]);
+        let spent_nf = v2::nullifier_hash(&[0x06u8; 32]);
+        let spent = v2::spent_path(v2::spent_key(&spent_nf), &spent_nf, &empty);
+        let nf = v2::nullifier_hash(&b);
+        let key = v2::spent_key(&nf);
+        let split = 63 - (key ^ v2::spent_key(&spent_nf)).leading_zeros() as usize;
+        let mut spent_siblings = empty;
+        spent_siblings[split] = spent[split];
+        let spent_root = spent[v2::SPENT_DEPTH];
+
+        let challenge = [0x77u8; 32];
+        let w = OwnershipWitness { units, b, pkd, index: 1, siblings, spent_siblings };
+        let p = prove_ownership(&w, &rwa_id, &challenge).expect("canonical rwa_id");
+        assert_eq!(p.root, root);
+        assert_eq!(p.spent_root, spent_root);
+        assert!(verify_ownership_proof(&p.proof, &root, &rwa_id, units, &challenge, &p.tag, &spent_root));
+
+        // The tag is not the on-chain nullifier and depends on the challenge.
+        assert_ne!(p.tag, nf);
+        assert_ne!(p.tag, presentation_tag(&b, &rwa_id, &[0x78u8; 32]));
+
+        // Another challenge, asset, unit count or root is rejected.
+        assert!(!verify_ownership_proof(&p.proof, &root, &rwa_id, units, &[0x78u8; 32], &p.tag, &spent_root));
+        let mut other_asset = rwa_id;
+        other_asset[0] = 43;
+        assert!(!verify_ownership_proof(&p.proof, &root, &other_asset, units, &challenge, &p.tag, &spent_root));
+        assert!(!verify_ownership_proof(&p.proof, &root, &rwa_id, units + 1, &challenge, &p.tag, &spent_root));
+        let mut bad_root = root;
+        bad_root[0] ^= 1;
+        assert!(!verify_ownership_proof(&p.proof, &bad_root, &rwa_id, units, &challenge, &p.tag, &spent_root));
+
+        // Once the note is spent, the spent root moves and the proof no longer verifies.
+        let after = v2::spent_path(key, &nf, &spent_siblings)[v2::SPENT_DEPTH];
+        assert!(!verify_ownership_proof(&p.proof, &root, &rwa_id, units, &challenge, &p.tag, &after));
+    }
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod credential_v2_tests {
+    use super::credential_v2::*;
+    use super::prover::{CredentialWitness, prove_credential};
+    use super::v2;
+
+    #[test]
+    fn credential_presentation_roundtrip() {
+        let mut rwa_id = [0u8; 32];
+        rwa_id[..4].copy_from_slice(&7u32.to_le_bytes());
+        let b = [0x09u8; 32];
+        let class = 0b101u64;
+
+        // Credential tree with another holder's credential first (index 1).
+        let other = credential_leaf(0b001, &[0x0Au8; 32]);
+        let ours = credential_leaf(class, &b);
+        let z = v2::zero_subtrees();
+        let mut siblings = [[0u8; 32]; v2::MERKLE_DEPTH];
+        siblings[0] = other;
+        for d in 1..v2::MERKLE_DEPTH { siblings[d] = z[d]; }
+        let root = v2::reference_root(&[other, ours]);
+
+        let binding = [0x33u8; 32];
+        let w = CredentialWitness { class, b, index: 1, siblings };
+        let p = prove_credential(&w, &rwa_id, &binding).expect("canonical rwa_id");
+        assert_eq!(p.root, root);
+        assert!(verify_credential_proof(&p.proof, &root, &rwa_id, class, &p.tag, &binding));
+
+        // One tag per (credential, asset), unlinkable to the spend nullifier.
+        assert_eq!(p.tag, credential_tag(&b, &rwa_id));
+        assert_ne!(p.tag, v2::nullifier_hash(&b));
+        let mut other_asset = rwa_id;
+        other_asset[0] = 8;
+        assert_ne!(p.tag, credential_tag(&b, &other_asset));
+
+        // Another spend binding, asset, class or root is rejected.
+        assert!(!verify_credential_proof(&p.proof, &root, &rwa_id, class, &p.tag, &[0x34u8; 32]));
+        assert!(!verify_credential_proof(&p.proof, &root, &other_asset, class, &p.tag, &binding));
+        assert!(!verify_credential_proof(&p.proof, &root, &rwa_id, 0b111, &p.tag, &binding));
+        let mut bad_root = root;
+        bad_root[0] ^= 1;
+        assert!(!verify_credential_proof(&p.proof, &bad_root, &rwa_id, class, &p.tag, &binding));
+    }
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod fee_tests {
+    use super::fee::*;
+    use super::prover::{FeeWitness, prove_fee};
+    use super::v2;
+
+    #[test]
+    fn fee_credit_roundtrip() {
+        let b = [0x21u8; 32];
+        let value = 100u64;
+
+        // Another depositor's credit first; ours at index 1.
+        let other = credit_leaf(value, &[0x22u8; 32]);
+        let ours = credit_leaf(value, &b);
+        let z = v2::zero_subtrees();
+        let mut siblings = [[0u8; 32]; v2::MERKLE_DEPTH];
+        siblings[0] = other;
+        for d in 1..v2::MERKLE_DEPTH { siblings[d] = z[d]; }
+        let root = v2::reference_root(&[other, ours]);
+
+        let inputs = b"spend public inputs";
+        let ctx = context(inputs);
+        let p = prove_fee(&FeeWitness { value, b, index: 1, siblings }, inputs);
+        assert_eq!(p.root, root);
+        assert_eq!(p.nullifier, nullifier(&b));
+        assert!(verify_fee_proof(&p.proof, &root, value, &p.nullifier, &ctx));
+
+        // The nullifier is neither the leaf nor the note nullifier of `b`.
+        assert_ne!(p.nullifier, ours);
+        assert_ne!(p.nullifier, v2::nullifier_hash(&b));
+
+        // Another spend, value, nullifier or root is rejected.
+        assert!(!verify_fee_proof(&p.proof, &root, value, &p.nullifier, &context(b"other spend")));
+        assert!(!verify_fee_proof(&p.proof, &root, value + 1, &p.nullifier, &ctx));
+        assert!(!verify_fee_proof(&p.proof, &root, value, &nullifier(&[0x22u8; 32]), &ctx));
+        let mut bad_root = root;
+        bad_root[0] ^= 1;
+        assert!(!verify_fee_proof(&p.proof, &bad_root, value, &p.nullifier, &ctx));
+    }
+}
+
+// ===================================================================
+//  Proof envelope — version dispatch (uses the `prover` feature).
+// ===================================================================
+#[cfg(all(test, feature = "prover"))]
+mod envelope_tests {
+    use super::deposit_v2::verify_deposit_v2;
+    use super::envelope::*;
+    use super::prover::prove_deposit;
+    use super::v2;
+    use parity_scale_codec::Encode;
+
+    #[test]
+    fn envelope_version_dispatch() {
+        let pkd = v2::pk_digest(&[0xABu8; 1312]);
+        let (leaf, sealed) = prove_deposit(500, &[0x09u8; 32], &pkd);
+        let v1 = ProofHeader { air: AirId::Deposit, version: 1, options: V1_OPTIONS };
+        assert!(sealed.starts_with(&MAGIC));
+        assert_eq!(version_of(&sealed, AirId::Deposit), Some(CURRENT_VERSION));
+        assert!(verify_deposit_v2(&sealed, 500, &leaf));
+
+        // The bare winterfell proof is still read as version 1.
+        let raw = &sealed[MAGIC.len() + v1.encoded_size()..];
+        assert_eq!(version_of(raw, AirId::Deposit), Some(LEGACY_VERSION));
+        assert!(verify_deposit_v2(raw, 500, &leaf));
+
+        // Another AIR, an unregistered version or options not matching
+        // the proof are rejected.
+        assert_eq!(version_of(&sealed, AirId::Spend), None);
+        let v2_header = seal(AirId::Deposit, 2, V1_OPTIONS, raw);
+        assert_eq!(version_of(&v2_header, AirId::Deposit), None);
+        assert!(!verify_deposit_v2(&v2_header, 500, &leaf));
+        let weaker = StarkOptions { num_queries: 20, ..V1_OPTIONS };
+        assert!(!verify_deposit_v2(&seal(AirId::Deposit, 1, weaker, raw), 500, &leaf));
+        assert!(!verify_deposit_v2(&sealed[..MAGIC.len() + 2], 500, &leaf));
+    }
+}
+
+// ===================================================================
+//  STARK security presets — policy checks and size / cost trade-off
+//  (uses the `prover` feature).
+// ===================================================================
+#[cfg(all(test, feature = "prover"))]
+mod security_tests {
+    use super::deposit_v2::verify_deposit_v2_with;
+    use super::envelope::*;
+    use super::prover::{prove_deposit_with, prove_spend_with, SpendWitness};
+    use super::spend_v2::verify_spend_v2_with;
+    use super::v2;
+    use parity_scale_codec::Encode;
+    use std::time::Instant;
+
+    const PRESETS: [(&str, StarkOptions); 3] =
+        [("v1", V1_OPTIONS), ("secure-100", SECURE_100), ("secure-128", SECURE_128)];
+
+    #[test]
+    fn security_policy_floor_and_factors() {
+        let pkd = v2::pk_digest(&[0xABu8; 1312]);
+        let b = [0x0bu8; 32];
+        let (leaf, v1) = prove_deposit_with(500, &b, &pkd, V1_OPTIONS);
+        let (_, s100) = prove_deposit_with(500, &b, &pkd, SECURE_100);
+        let default = StarkSecurity::DEFAULT;
+        assert!(verify_deposit_v2_with(&v1, 500, &leaf, &default));
+        assert!(verify_deposit_v2_with(&s100, 500, &leaf, &default));
+
+        // A 100-bit floor rejects the v1 options.
+        let floor_100 = StarkSecurity { min_bits: 100, ..default };
+        assert!(!verify_deposit_v2_with(&v1, 500, &leaf, &floor_100));
+        assert!(verify_deposit_v2_with(&s100, 500, &leaf, &floor_100));
+
+        // Only blowup 16 allowed.
+        let blowup_16 = StarkSecurity { blowup_factors: 1 << 4, ..default };
+        assert!(!verify_deposit_v2_with(&v1, 500, &leaf, &blowup_16));
+        assert!(verify_deposit_v2_with(&s100, 500, &leaf, &blowup_16));
+        let folding_4 = StarkSecurity { fri_folding_factors: 1 << 2, ..default };
+        assert!(!verify_deposit_v2_with(&s100, 500, &leaf, &folding_4));
+
+        // A header naming weaker options than the proof carries is rejected.
+        let header = ProofHeader { air: AirId::Deposit, version: 1, options: SECURE_100 };
+        let raw = &s100[MAGIC.len() + header.encoded_size()..];
+        let relabelled = seal(AirId::Deposit, 1, V1_OPTIONS, raw);
+        assert!(!verify_deposit_v2_with(&relabelled, 500, &leaf, &default));
+    }
+
+    #[test]
+    fn security_presets_bench() {
+        let pkd = v2::pk_digest(&[0xABu8; 1312]);
+        let b = [0x21u8; 32];
+        let (v, price) = (10_000u64, 4_000u64);
+        let z = v2::zero_subtrees();
+        let mut siblings = [[0u8; 32]; v2::MERKLE_DEPTH];
+        siblings.copy_from_slice(&z[..v2::MERKLE_DEPTH]);
+        let root = v2::reference_root(&[v2::note_hash(v, &b, &pkd)]);
+        let (cb, cpkd) = ([0x37u8; 32], v2::pk_digest(&[0xCDu8; 1312]));
+        let change_leaf = v2::note_hash(v - price, &cb, &cpkd);
+        let w = SpendWitness { v, b, pkd, index: 0, siblings, cv: v - price, cb, cpkd };
+
+        for (name, options) in PRESETS {
+            let security = StarkSecurity { min_bits: 1, ..StarkSecurity::DEFAULT };
+            let t0 = Instant::now();
+            let (leaf, deposit) = prove_deposit_with(v, &b, &pkd, options);
+            let t_prove = t0.elapsed();
+            let t1 = Instant::now();
+            assert!(verify_deposit_v2_with(&deposit, v, &leaf, &security));
+            let t_verify = t1.elapsed();
+            std::println!(
+                "{:10} deposit proof={:6}B  prove={:?}  verify={:?}",
+                name, deposit.len(), t_prove, t_verify
+            );
+
+            let t0 = Instant::now();
+            let spend = prove_spend_with(&w, price, 1, options);
+            let t_prove = t0.elapsed();
+            let t1 = Instant::now();
+            let nullifier = v2::nullifier_hash(&b);
+            assert!(verify_spend_v2_with(
+                &spend, &root, &nullifier, &pkd, price, &change_leaf, &cpkd, true, &security,
+            ));
+            let t_verify = t1.elapsed();
+            std::println!(
+                "{:10} spend   proof={:6}B  prove={:?}  verify={:?}",
+                name, spend.len(), t_prove, t_verify
+            );
+        }
+    }
+}
+
+// ===================================================================
+//  Hash test vectors and Poseidon2 constants
+// ===================================================================
+#[cfg(test)]
+mod vectors_tests {
+    use super::poseidon2::{ROUND_CONSTANTS, RF, RP, T};
+    use super::vectors::{json, Domain};
+    use super::{BaseElement, StarkField};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn vectors_files_current() {
+        assert_eq!(json(Domain::V2), include_str!("../test-vectors/v2.json"));
+        assert_eq!(json(Domain::V3), include_str!("../test-vectors/v3.json"));
+    }
+
+    #[test]
+    fn poseidon2_constants_from_grain() {
+        fn bits(out: &mut Vec<u8>, v: usize, width: usize) {
+            out.extend((0..width).rev().map(|i| ((v >> i) & 1) as u8));
+        }
+        let mut s = Vec::with_capacity(80);
+        bits(&mut s, 1, 2); // field: prime
+        bits(&mut s, 0, 4); // sbox: x^α
+        bits(&mut s, 128, 12);
+        bits(&mut s, T, 12);
+        bits(&mut s, RF, 10);
+        bits(&mut s, RP, 10);
+        bits(&mut s, (1 << 30) - 1, 30);
+
+        let mut step = || {
+            let b = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+            s.remove(0);
+            s.push(b);
+            b
+        };
+        for _ in 0..160 {
+            step();
+        }
+        let mut bit = || loop {
+            let (a, b) = (step(), step());
+            if a == 1 {
+                return b;
+            }
+        };
+        for round in ROUND_CONSTANTS.iter() {
+            for rc in round.iter() {
+                let x = loop {
+                    let x = (0..128).fold(0u128, |x, _| (x << 1) | bit() as u128);
+                    if x < BaseElement::MODULUS {
+                        break x;
+                    }
+                };
+                assert_eq!(x, *rc);
+            }
+        }
+    }
+}
@@ -7,6 +7,27 @@
 //! 2. `redeem_asset`          — note-holder proves spend via nullifier.
 //! 3. `xcm_record_purchase`   — called via XCM by the ProofHub sovereign account
 //!    after a `purchaseRwa` extrinsic is accepted on the ProofHub parachain.
+//! 4. `xcm_record_redemption` — called via XCM by the ProofHub sovereign account
+//!    after a v2 ownership note is spent by `redeem_ownership_v2`.
+//! 5. `xcm_record_auction_result` — called via XCM by the ProofHub sovereign
+//!    account when a sealed-bid auction is settled by `settle_auction`.
+//!
+//! ProofHub v2 purchases are held in escrow on ProofHub until the admin
+//! resolves them here: `fulfil_proofhub_purchase` and `cancel_proofhub_purchase`
+//! send an XCM back to ProofHub that releases the buyer's ownership note or
+//! refunds the price.
+//!
+//! Listing prices are pushed to every lane in `PriceSyncLanes` over XCM on
+//! listing creation, price changes, sell-out and cancellation, so a lane's
+//! `RwaPrices` follows the listing here.
+//!
+//! Listing calls and escrow resolution accept `AdminOrigin` or the admin
+//! account of the asset's issuer in `pallet-rwa-registry`; lane management
+//! stays with `AdminOrigin`.
+//!
+//! Assets may be split into `total_units` shares in `pallet-rwa-registry`.
+//! Listings, purchases and redemptions all carry a unit count; an asset is only
+//! released once every one of its units has been redeemed.
 
 pub use pallet::*;
 
@@ -18,10 +39,57 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
     use pallet_rwa_registry::pallet as registry;
+    use registry::IssuerRole;
     use codec::DecodeWithMemTracking;
 
     pub const MAX_CONTACT_LEN: u32 = 256;
 
+    /// Maximum number of lanes that receive price updates.
+    pub const MAX_PRICE_SYNC_LANES: u32 = 8;
+
+    /// Trait implemented by the runtime to send an XCM `Transact` back to the
+    /// ProofHub parachain (para 2000) resolving an escrowed v2 purchase.
+    ///
+    /// Delivery failures are logged and swallowed; an unresolved escrow is
+    /// refunded by ProofHub once its deadline passes.
+    pub trait ProofHubEscrowDispatch {
+        /// Fulfilment confirmed — ProofHub inserts the buyer's ownership note.
+        fn confirm(tx_id: [u8; 16]);
+        /// Purchase cancelled — ProofHub refunds the buyer.
+        fn cancel(tx_id: [u8; 16]);
+    }
+
+    /// No-op implementation used when XCM is not wired (e.g. tests).
+    impl ProofHubEscrowDispatch for () {
+        fn confirm(_: [u8; 16]) {}
+        fn cancel(_: [u8; 16]) {}
+    }
+
+    /// Trait implemented by the runtime to send an XCM `Transact` carrying
+    /// `xcm_sync_rwa_price(rwa_id, price)` to a lane parachain.
+    ///
+    /// Delivery failures are logged and swallowed — a listing change on this
+    /// chain must not fail because a lane is unreachable.
+    pub trait LanePriceDispatch {
+        fn send(lane: &PriceSyncLane, rwa_id: [u8; 32], price: u64);
+    }
+
+    /// No-op implementation used when XCM is not wired (e.g. tests).
+    impl LanePriceDispatch for () {
+        fn send(_: &PriceSyncLane, _: [u8; 32], _: u64) {}
+    }
+
+    /// A lane parachain that receives price updates, and where its
+    /// `xcm_sync_rwa_price` call lives.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct PriceSyncLane {
+        pub para_id:      u32,
+        /// Pallet index of the lane's proofs pallet.
+        pub pallet_index: u8,
+        /// Call index of `xcm_sync_rwa_price` in that pallet.
+        pub call_index:   u8,
+    }
+
     // ── Sub-types ────────────────────────────────────────────────────────────
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
@@ -38,19 +106,31 @@ pub mod pallet {
         Redeemed,
     }
 
+    /// How the admin resolved a ProofHub purchase held in escrow on ProofHub.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub enum PurchaseResolution {
+        Fulfilled,
+        Cancelled,
+    }
+
     #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
     pub struct Listing<AccountId> {
         /// Company account that created the listing.
         pub seller: AccountId,
         /// On-chain asset identifier (from pallet-rwa-registry).
         pub asset_id: u32,
-        /// Asking price denominated in ProofHub commitment value (informational only).
+        /// Asking price PER UNIT in ProofHub commitment value (informational only).
         /// Payment is proven via ZK on ProofHub — no direct transfer on this chain.
         pub price_hint: u128,
+        /// Units offered by this listing.
+        pub units: u64,
+        /// Units sold through `buy_privately` so far.
+        pub units_sold: u64,
         /// Current lifecycle state.
         pub status: ListingStatus,
         /// Blake2-256 hash of the ProofHub output commitment used as payment.
-        /// Set by the buyer when calling buy_privately.
+        /// Set by the buyer when calling buy_privately (the most recent buyer
+        /// for fractional listings; every sale is also in `AssetSoldPrivately`).
         /// `None` until `buy_privately` is called.
         pub entry_commitment_hash: Option<[u8; 32]>,
     }
@@ -64,6 +144,8 @@ pub mod pallet {
         /// Optional contact info supplied by the redeemer (encrypted off-chain).
         pub contact: Option<BoundedVec<u8, ConstU32<{ MAX_CONTACT_LEN }>>>,
         pub redeemer: AccountId,
+        /// Units redeemed by this claim.
+        pub units: u64,
     }
 
     // ── Config ───────────────────────────────────────────────────────────────
@@ -85,6 +167,14 @@ pub mod pallet {
         /// Only this account is allowed to call `xcm_record_purchase`.
         #[pallet::constant]
         type ProofHubSovereign: Get<Self::AccountId>;
+
+        /// XCM dispatch back to ProofHub for escrowed purchases.
+        /// Use `()` when XCM is not needed.
+        type EscrowDispatch: ProofHubEscrowDispatch;
+
+        /// XCM dispatch of listing prices to the lanes in `PriceSyncLanes`.
+        /// Use `()` when XCM is not needed.
+        type PriceDispatch: LanePriceDispatch;
     }
 
     /// A purchase initiated from the ProofHub parachain via XCM.
@@ -102,6 +192,38 @@ pub mod pallet {
         /// Private ownership note: BLAKE3("nulla_rwa_ownership_v1" || rwa_id || blinding).
         /// The buyer reveals `blinding` to `redeem_rwa_ownership` to prove ownership.
         pub ownership_commitment: [u8; 32],
+        /// Units bought (`1` for indivisible assets).
+        pub units:                u64,
+    }
+
+    /// A v2 ownership-note redemption proven on ProofHub.
+    ///
+    /// ProofHub resales happen inside its ownership tree and never reach this
+    /// chain, so the record names no purchase tx_id or previous owner.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct ProofHubRedemptionRecord {
+        pub asset_id:  u32,
+        /// 32-byte AccountId of the claimant on this chain.
+        pub claimant:  [u8; 32],
+        /// ProofHub redemption tx_id (fresh per redemption, not the purchase tx_id).
+        pub tx_id:     [u8; 16],
+        /// Units redeemed; the holder's remaining units stay shielded on ProofHub.
+        pub units:     u64,
+    }
+
+    /// A sealed-bid auction settled on ProofHub.
+    ///
+    /// The winner holds an ownership note in ProofHub's ownership tree and
+    /// redeems it like any v2 purchase; neither bidders nor losing bids reach
+    /// this chain.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct ProofHubAuctionRecord {
+        pub asset_id:             u32,
+        /// Winning bid for all `units`, in ProofHub note value.
+        pub price:                u64,
+        pub units:                u64,
+        /// Winner's ownership note leaf in ProofHub's ownership tree.
+        pub ownership_commitment: [u8; 32],
     }
 
     // ── Storage ──────────────────────────────────────────────────────────────
@@ -129,10 +251,37 @@ pub mod pallet {
     pub type ProofHubPurchases<T: Config> =
         StorageMap<_, Identity, [u8; 16], ProofHubPurchaseRecord>;
 
-    /// Guards against double-redemption of ownership notes, keyed by tx_id.
+    /// Units already redeemed from each ProofHub purchase, keyed by tx_id.
+    /// Guards against redeeming more units than were bought.
+    #[pallet::storage]
+    pub type OwnershipUnitsRedeemed<T: Config> =
+        StorageMap<_, Identity, [u8; 16], u64, ValueQuery>;
+
+    /// Units of each asset redeemed through `redeem_asset`; the registry lock is
+    /// released once this reaches the asset's `total_units`.
     #[pallet::storage]
-    pub type OwnershipRedeemed<T: Config> =
-        StorageMap<_, Identity, [u8; 16], bool, ValueQuery>;
+    pub type AssetUnitsRedeemed<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, u64, ValueQuery>;
+
+    /// v2 redemptions received from ProofHub via XCM, keyed by ownership nullifier.
+    #[pallet::storage]
+    pub type ProofHubRedemptions<T: Config> =
+        StorageMap<_, Identity, [u8; 32], ProofHubRedemptionRecord>;
+
+    /// Admin resolution of ProofHub purchases, keyed by tx_id.
+    #[pallet::storage]
+    pub type ProofHubPurchaseResolutions<T: Config> =
+        StorageMap<_, Identity, [u8; 16], PurchaseResolution>;
+
+    /// Lanes that receive listing price updates. Managed by the admin.
+    #[pallet::storage]
+    pub type PriceSyncLanes<T: Config> =
+        StorageValue<_, BoundedVec<PriceSyncLane, ConstU32<{ MAX_PRICE_SYNC_LANES }>>, ValueQuery>;
+
+    /// Auction results received from ProofHub via XCM, keyed by ProofHub auction_id.
+    #[pallet::storage]
+    pub type ProofHubAuctionResults<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, ProofHubAuctionRecord>;
 
     // ── Events ───────────────────────────────────────────────────────────────
     #[pallet::event]
@@ -143,20 +292,32 @@ pub mod pallet {
             asset_id: u32,
             seller: T::AccountId,
             price_hint: u128,
+            units: u64,
         },
         /// Listing cancelled (before any sale).
         ListingCancelled { asset_id: u32 },
+        /// Per-unit price of an active listing changed.
+        PriceHintUpdated { asset_id: u32, price_hint: u128 },
+        /// A listing price was pushed to every lane in `PriceSyncLanes`
+        /// (`price == 0` de-lists the asset there).
+        PriceSynced { asset_id: u32, price: u64, lanes: u32 },
+        /// A lane was added to `PriceSyncLanes`.
+        PriceSyncLaneAdded { lane: PriceSyncLane },
+        /// A lane was removed from `PriceSyncLanes`.
+        PriceSyncLaneRemoved { para_id: u32 },
         /// Private sale recorded.  Entry commitment hash stored on-chain.
         AssetSoldPrivately {
             asset_id: u32,
             buyer: T::AccountId,
             entry_commitment_hash: [u8; 32],
+            units: u64,
         },
         /// Redemption claim submitted on-chain.
         RedemptionSubmitted {
             asset_id: u32,
             nullifier_hash: [u8; 32],
             redeemer: T::AccountId,
+            units: u64,
         },
         /// Asset fully redeemed; company should physically release it.
         AssetRedeemed { asset_id: u32, nullifier_hash: [u8; 32] },
@@ -169,12 +330,37 @@ pub mod pallet {
             spend_tag:            [u8; 32],
             nullifier:            [u8; 32],
             ownership_commitment: [u8; 32],
+            units:                u64,
         },
         /// Ownership note redeemed — company should release the physical asset.
         OwnershipRedeemed {
             tx_id:    [u8; 16],
             asset_id: u32,
             redeemer: T::AccountId,
+            units:    u64,
+        },
+        /// v2 ownership note redeemed on ProofHub — company should release the
+        /// physical asset to `claimant`.
+        ProofHubRedemptionReceived {
+            tx_id:               [u8; 16],
+            asset_id:            u32,
+            claimant:            [u8; 32],
+            ownership_nullifier: [u8; 32],
+            units:               u64,
+        },
+        /// Admin confirmed a ProofHub purchase; ProofHub was told to release
+        /// the buyer's ownership note.
+        ProofHubPurchaseFulfilled { tx_id: [u8; 16] },
+        /// Admin cancelled a ProofHub purchase; ProofHub was told to refund the buyer.
+        ProofHubPurchaseCancelled { tx_id: [u8; 16] },
+        /// Sealed-bid auction settled on ProofHub — `units` of the asset were
+        /// sold for `price` to an undisclosed ownership-note holder.
+        ProofHubAuctionSettled {
+            auction_id:           u32,
+            asset_id:             u32,
+            price:                u64,
+            units:                u64,
+            ownership_commitment: [u8; 32],
         },
     }
 
@@ -198,16 +384,37 @@ pub mod pallet {
         OwnershipAlreadyRedeemed,
         /// The supplied blinding does not match the stored ownership commitment.
         InvalidOwnershipProof,
+        /// A redemption with this ownership nullifier was already recorded.
+        RedemptionAlreadyRecorded,
+        /// Unit counts must be non-zero.
+        ZeroUnits,
+        /// More units requested than are listed, bought or still unredeemed.
+        InsufficientUnits,
+        /// A result for this ProofHub auction was already recorded.
+        AuctionAlreadyRecorded,
+        /// This ProofHub purchase was already fulfilled or cancelled.
+        PurchaseAlreadyResolved,
+        /// This ProofHub purchase was cancelled and cannot be redeemed.
+        PurchaseCancelled,
+        /// This lane is already in `PriceSyncLanes`.
+        PriceSyncLaneExists,
+        /// This lane is not in `PriceSyncLanes`.
+        PriceSyncLaneNotFound,
+        /// `PriceSyncLanes` is full.
+        TooManyPriceSyncLanes,
+        /// Lane prices are u64; this `price_hint` does not fit.
+        PriceHintTooLarge,
     }
 
     // ── Calls ────────────────────────────────────────────────────────────────
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Create a new listing for a locked asset (Admin only).
+        /// Create a new listing for a locked asset (Admin or the issuer admin).
         ///
         /// `seller` is the company account that receives off-chain settlement.
-        /// `price_hint` is informational — the actual payment is proven via ZK
-        /// on ProofHub and is not enforced by this chain.
+        /// `price_hint` is the per-unit price and is informational — the actual
+        /// payment is proven via ZK on ProofHub and is not enforced by this chain.
+        /// `units` may not exceed the asset's unsold units in the registry.
         #[pallet::weight(10_000)]
         #[pallet::call_index(0)]
         pub fn list_asset(
@@ -215,84 +422,112 @@ pub mod pallet {
             asset_id: u32,
             seller: T::AccountId,
             price_hint: u128,
+            units: u64,
         ) -> DispatchResult {
-            T::AdminOrigin::ensure_origin(origin)?;
+            registry::Pallet::<T>::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
             ensure!(
                 registry::Pallet::<T>::is_available_for_sale(asset_id),
                 Error::<T>::AssetNotAvailableForSale
             );
+            ensure!(units > 0, Error::<T>::ZeroUnits);
+            ensure!(
+                units <= registry::Pallet::<T>::units_available(asset_id),
+                Error::<T>::InsufficientUnits
+            );
             let listing = Listing {
                 seller: seller.clone(),
                 asset_id,
                 price_hint,
+                units,
+                units_sold: 0,
                 status: ListingStatus::Active,
                 entry_commitment_hash: None,
             };
             Listings::<T>::insert(asset_id, listing);
-            Self::deposit_event(Event::AssetListed { asset_id, seller, price_hint });
-            Ok(())
+            Self::deposit_event(Event::AssetListed { asset_id, seller, price_hint, units });
+            Self::sync_price(asset_id, price_hint)
         }
 
-        /// Cancel an active listing (Admin only).
+        /// Cancel an active listing (Admin or the issuer admin).
         ///
         /// Also releases the registry lock so the asset can be re-listed or
         /// disposed of — without this the asset would stay permanently locked.
+        /// If some units of a fractional listing were already sold the lock is
+        /// kept: those holders still need to redeem against it.
         #[pallet::weight(5_000)]
         #[pallet::call_index(1)]
         pub fn cancel_listing(origin: OriginFor<T>, asset_id: u32) -> DispatchResult {
-            T::AdminOrigin::ensure_origin(origin)?;
-            Listings::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
+            registry::Pallet::<T>::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            let units_sold = Listings::<T>::try_mutate(asset_id, |maybe| -> Result<u64, DispatchError> {
                 let listing = maybe.as_mut().ok_or(Error::<T>::ListingNotFound)?;
                 ensure!(listing.status == ListingStatus::Active, Error::<T>::NotActive);
                 listing.status = ListingStatus::Cancelled;
-                Ok(())
+                Ok(listing.units_sold)
             })?;
             // Release the registry lock so the asset is no longer permanently stuck.
-            registry::Pallet::<T>::internal_release(asset_id)?;
+            if units_sold == 0 {
+                registry::Pallet::<T>::internal_release(asset_id)?;
+            }
             Self::deposit_event(Event::ListingCancelled { asset_id });
-            Ok(())
+            Self::sync_price(asset_id, 0)
         }
 
         /// Private purchase via ZK proof.
         ///
         /// The buyer has already spent an existing ProofHub note on the ProofHub
         /// parachain via `submit_proof`, producing an output commitment C of value
-        /// >= listing.price_hint.  The buyer passes `entry_commitment_hash =
+        /// >= listing.price_hint × units.  The buyer passes `entry_commitment_hash =
         /// blake2_256(C)` here.  No token transfer occurs on this chain — the
         /// payment is proven entirely on ProofHub.
         ///
         /// The seller verifies off-chain that:
         ///   1. A `ProofAccepted` event on ProofHub contains C as an output.
-        ///   2. The committed value >= listing.price_hint.
+        ///   2. The committed value >= listing.price_hint × units.
         ///   3. The seller can redeem C on ProofHub using the blinding provided
         ///      off-chain by the protocol.
+        ///
+        /// The listing stays `Active` until all of its units are sold.
         #[pallet::weight(15_000)]
         #[pallet::call_index(2)]
         pub fn buy_privately(
             origin: OriginFor<T>,
             asset_id: u32,
+            units: u64,
             entry_commitment_hash: [u8; 32],
         ) -> DispatchResult {
             let buyer = ensure_signed(origin)?;
-            Listings::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
+            ensure!(units > 0, Error::<T>::ZeroUnits);
+            let sold_out = Listings::<T>::try_mutate(asset_id, |maybe| -> Result<bool, DispatchError> {
                 let listing = maybe.as_mut().ok_or(Error::<T>::ListingNotFound)?;
                 ensure!(listing.status == ListingStatus::Active, Error::<T>::NotActive);
                 ensure!(
                     registry::Pallet::<T>::is_available_for_sale(asset_id),
                     Error::<T>::AssetNotAvailableForSale
                 );
+                let sold = listing
+                    .units_sold
+                    .checked_add(units)
+                    .filter(|s| *s <= listing.units)
+                    .ok_or(Error::<T>::InsufficientUnits)?;
                 // No token transfer — payment is proven via ZK on ProofHub.
-                listing.status = ListingStatus::SoldPrivately;
+                listing.units_sold = sold;
+                if sold == listing.units {
+                    listing.status = ListingStatus::SoldPrivately;
+                }
                 listing.entry_commitment_hash = Some(entry_commitment_hash);
-                // Mark registry as sold (asset stays locked until redemption).
-                registry::Pallet::<T>::internal_mark_sold(asset_id)?;
-                Ok(())
+                // Record the sale in the registry (asset stays locked until redemption).
+                registry::Pallet::<T>::internal_sell_units(asset_id, units)?;
+                Ok(sold == listing.units)
             })?;
             Self::deposit_event(Event::AssetSoldPrivately {
                 asset_id,
                 buyer,
                 entry_commitment_hash,
+                units,
             });
+            if sold_out {
+                Self::sync_price(asset_id, 0)?;
+            }
             Ok(())
         }
 
@@ -303,27 +538,43 @@ pub mod pallet {
         /// `NullifierUsed` storage).  The company verifies this off-chain before
         /// physically handing over the asset.  The `AssetRedeemed` event is the
         /// on-chain anchor for that handover.
+        ///
+        /// `units` redeems part of a fractional holding; the registry lock is
+        /// only released once every unit of the asset has been redeemed.
         #[pallet::weight(15_000)]
         #[pallet::call_index(3)]
         pub fn redeem_asset(
             origin: OriginFor<T>,
             asset_id: u32,
             nullifier_hash: [u8; 32],
+            units: u64,
             contact: Option<BoundedVec<u8, ConstU32<{ MAX_CONTACT_LEN }>>>,
         ) -> DispatchResult {
             let redeemer = ensure_signed(origin)?;
+            ensure!(units > 0, Error::<T>::ZeroUnits);
             // Guard against double-redemption on this chain.
             ensure!(
                 !NullifierUsed::<T>::get(nullifier_hash),
                 Error::<T>::NullifierAlreadyUsed
             );
-            // Asset must have been sold privately first.
+            // At least some units must have been sold privately first.
             let listing =
                 Listings::<T>::get(asset_id).ok_or(Error::<T>::ListingNotFound)?;
             ensure!(
-                listing.status == ListingStatus::SoldPrivately,
+                listing.units_sold > 0
+                    && matches!(
+                        listing.status,
+                        ListingStatus::Active | ListingStatus::SoldPrivately | ListingStatus::Cancelled
+                    ),
                 Error::<T>::AssetNotSoldPrivately
             );
+            let redeemed = AssetUnitsRedeemed::<T>::get(asset_id)
+                .checked_add(units)
+                .filter(|r| *r <= listing.units_sold)
+                .ok_or(Error::<T>::InsufficientUnits)?;
+            let total_units = registry::Assets::<T>::get(asset_id)
+                .map(|a| a.total_units)
+                .unwrap_or(listing.units);
 
             // Mark nullifier used.
             NullifierUsed::<T>::insert(nullifier_hash, true);
@@ -334,25 +585,30 @@ pub mod pallet {
                 nullifier_hash,
                 contact,
                 redeemer: redeemer.clone(),
+                units,
             };
             RedemptionClaims::<T>::insert(nullifier_hash, claim);
-
-            // Update listing status.
-            Listings::<T>::mutate(asset_id, |maybe| {
-                if let Some(l) = maybe.as_mut() {
-                    l.status = ListingStatus::Redeemed;
-                }
-            });
-
-            // Unlock in registry — company can now dispose of physical asset.
-            registry::Pallet::<T>::internal_release(asset_id)?;
+            AssetUnitsRedeemed::<T>::insert(asset_id, redeemed);
 
             Self::deposit_event(Event::RedemptionSubmitted {
                 asset_id,
                 nullifier_hash,
                 redeemer,
+                units,
             });
-            Self::deposit_event(Event::AssetRedeemed { asset_id, nullifier_hash });
+
+            if redeemed >= total_units {
+                // Update listing status.
+                Listings::<T>::mutate(asset_id, |maybe| {
+                    if let Some(l) = maybe.as_mut() {
+                        l.status = ListingStatus::Redeemed;
+                    }
+                });
+
+                // Unlock in registry — company can now dispose of physical asset.
+                registry::Pallet::<T>::internal_release(asset_id)?;
+                Self::deposit_event(Event::AssetRedeemed { asset_id, nullifier_hash });
+            }
             Ok(())
         }
 
@@ -366,6 +622,11 @@ pub mod pallet {
         /// against double-recording (same `tx_id`), and emits
         /// `ProofHubPurchaseReceived`.  The company SHOULD watch for this event and
         /// initiate the off-chain redemption flow for the buyer.
+        ///
+        /// `units` is the number of shares bought; unit supply for lane sales is
+        /// enforced on the lane that sold them. The units are counted against
+        /// the active listing and the registry, and a sell-out de-lists the
+        /// asset on every lane.
         #[pallet::weight(10_000)]
         #[pallet::call_index(4)]
         pub fn xcm_record_purchase(
@@ -377,9 +638,11 @@ pub mod pallet {
             nullifier: [u8; 32],
             tx_id: [u8; 16],
             ownership_commitment: [u8; 32],
+            units: u64,
         ) -> DispatchResult {
             let caller = ensure_signed(origin)?;
             ensure!(caller == T::ProofHubSovereign::get(), Error::<T>::NotProofHubSovereign);
+            ensure!(units > 0, Error::<T>::ZeroUnits);
             ensure!(
                 !ProofHubPurchases::<T>::contains_key(tx_id),
                 Error::<T>::PurchaseAlreadyRecorded
@@ -389,8 +652,9 @@ pub mod pallet {
             let mut rwa_id = [0u8; 32];
             rwa_id[..4].copy_from_slice(&asset_id.to_le_bytes());
 
-            let record = ProofHubPurchaseRecord { rwa_id, buyer, spend_tag, nullifier, ownership_commitment };
+            let record = ProofHubPurchaseRecord { rwa_id, buyer, spend_tag, nullifier, ownership_commitment, units };
             ProofHubPurchases::<T>::insert(tx_id, record);
+            Self::record_lane_sale(asset_id, units)?;
 
             Self::deposit_event(Event::ProofHubPurchaseReceived {
                 tx_id,
@@ -399,6 +663,7 @@ pub mod pallet {
                 spend_tag,
                 nullifier,
                 ownership_commitment,
+                units,
             });
             Ok(())
         }
@@ -415,38 +680,270 @@ pub mod pallet {
         /// This is a PUBLIC transaction: the redeemer's identity and the asset_id
         /// are visible on-chain.  The link between the original buyer and the
         /// redeemer is broken only if they are different accounts.
+        ///
+        /// `units` may be less than the purchased amount; the remainder can be
+        /// redeemed by later calls with the same `blinding`.
         #[pallet::weight(10_000)]
         #[pallet::call_index(5)]
         pub fn redeem_rwa_ownership(
             origin: OriginFor<T>,
             tx_id: [u8; 16],
             blinding: [u8; 32],
+            units: u64,
         ) -> DispatchResult {
             let redeemer = ensure_signed(origin)?;
+            ensure!(units > 0, Error::<T>::ZeroUnits);
 
             let record = ProofHubPurchases::<T>::get(tx_id)
                 .ok_or(Error::<T>::PurchaseNotFound)?;
-
             ensure!(
-                !OwnershipRedeemed::<T>::get(tx_id),
-                Error::<T>::OwnershipAlreadyRedeemed
+                ProofHubPurchaseResolutions::<T>::get(tx_id) != Some(PurchaseResolution::Cancelled),
+                Error::<T>::PurchaseCancelled
             );
 
+            let already = OwnershipUnitsRedeemed::<T>::get(tx_id);
+            ensure!(already < record.units, Error::<T>::OwnershipAlreadyRedeemed);
+            let redeemed = already
+                .checked_add(units)
+                .filter(|r| *r <= record.units)
+                .ok_or(Error::<T>::InsufficientUnits)?;
+
             // Verify the ownership commitment: BLAKE3(domain || rwa_id || blinding)
             let expected = Self::compute_ownership_commitment(record.rwa_id, blinding);
             ensure!(expected == record.ownership_commitment, Error::<T>::InvalidOwnershipProof);
 
-            OwnershipRedeemed::<T>::insert(tx_id, true);
+            OwnershipUnitsRedeemed::<T>::insert(tx_id, redeemed);
 
-            let asset_id = u32::from_le_bytes([
-                record.rwa_id[0], record.rwa_id[1], record.rwa_id[2], record.rwa_id[3],
-            ]);
-            Self::deposit_event(Event::OwnershipRedeemed { tx_id, asset_id, redeemer });
+            let asset_id = Self::asset_of(&record.rwa_id);
+            Self::deposit_event(Event::OwnershipRedeemed { tx_id, asset_id, redeemer, units });
+            Ok(())
+        }
+
+        /// Record a v2 ownership-note redemption proven on ProofHub.
+        ///
+        /// MUST only be called via XCM `Transact` by the ProofHub sovereign
+        /// account. ProofHub has already verified the zk-membership spend of the
+        /// ownership note; this chain guards against a replayed nullifier and
+        /// emits `ProofHubRedemptionReceived` for the company to act on.
+        /// `units` may be a partial quantity — the holder's change stays on ProofHub.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(6)]
+        pub fn xcm_record_redemption(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            ownership_nullifier: [u8; 32],
+            claimant: [u8; 32],
+            tx_id: [u8; 16],
+            units: u64,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(caller == T::ProofHubSovereign::get(), Error::<T>::NotProofHubSovereign);
+            ensure!(units > 0, Error::<T>::ZeroUnits);
+            ensure!(
+                !ProofHubRedemptions::<T>::contains_key(ownership_nullifier),
+                Error::<T>::RedemptionAlreadyRecorded
+            );
+
+            ProofHubRedemptions::<T>::insert(
+                ownership_nullifier,
+                ProofHubRedemptionRecord { asset_id, claimant, tx_id, units },
+            );
+
+            Self::deposit_event(Event::ProofHubRedemptionReceived {
+                tx_id,
+                asset_id,
+                claimant,
+                ownership_nullifier,
+                units,
+            });
+            Ok(())
+        }
+
+        /// Record the result of a sealed-bid auction settled on ProofHub.
+        ///
+        /// MUST only be called via XCM `Transact` by the ProofHub sovereign
+        /// account. Only the clearing price and the winner's ownership leaf are
+        /// disclosed. The units are counted as sold like a lane purchase.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(7)]
+        pub fn xcm_record_auction_result(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            auction_id: u32,
+            price: u64,
+            units: u64,
+            ownership_commitment: [u8; 32],
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(caller == T::ProofHubSovereign::get(), Error::<T>::NotProofHubSovereign);
+            ensure!(units > 0, Error::<T>::ZeroUnits);
+            ensure!(
+                !ProofHubAuctionResults::<T>::contains_key(auction_id),
+                Error::<T>::AuctionAlreadyRecorded
+            );
+
+            ProofHubAuctionResults::<T>::insert(
+                auction_id,
+                ProofHubAuctionRecord { asset_id, price, units, ownership_commitment },
+            );
+            Self::record_lane_sale(asset_id, units)?;
+
+            Self::deposit_event(Event::ProofHubAuctionSettled {
+                auction_id,
+                asset_id,
+                price,
+                units,
+                ownership_commitment,
+            });
+            Ok(())
+        }
+
+        /// Confirm fulfilment of a ProofHub purchase (Admin or the issuer admin).
+        ///
+        /// The purchase must have been recorded by `xcm_record_purchase`. Sends
+        /// an XCM to ProofHub (`xcm_confirm_escrow`) that releases the buyer's
+        /// ownership note from escrow.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(8)]
+        pub fn fulfil_proofhub_purchase(origin: OriginFor<T>, tx_id: [u8; 16]) -> DispatchResult {
+            let record = ProofHubPurchases::<T>::get(tx_id).ok_or(Error::<T>::PurchaseNotFound)?;
+            registry::Pallet::<T>::ensure_asset_role(origin, Self::asset_of(&record.rwa_id), IssuerRole::Admin)?;
+            ensure!(
+                !ProofHubPurchaseResolutions::<T>::contains_key(tx_id),
+                Error::<T>::PurchaseAlreadyResolved
+            );
+
+            ProofHubPurchaseResolutions::<T>::insert(tx_id, PurchaseResolution::Fulfilled);
+            T::EscrowDispatch::confirm(tx_id);
+
+            Self::deposit_event(Event::ProofHubPurchaseFulfilled { tx_id });
+            Ok(())
+        }
+
+        /// Cancel a ProofHub purchase the issuer cannot fulfil (Admin or the issuer admin).
+        ///
+        /// Sends an XCM to ProofHub (`xcm_cancel_escrow`) that refunds the
+        /// buyer's payment as a fresh note. The purchase record is kept but can
+        /// no longer be redeemed.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(9)]
+        pub fn cancel_proofhub_purchase(origin: OriginFor<T>, tx_id: [u8; 16]) -> DispatchResult {
+            let record = ProofHubPurchases::<T>::get(tx_id).ok_or(Error::<T>::PurchaseNotFound)?;
+            registry::Pallet::<T>::ensure_asset_role(origin, Self::asset_of(&record.rwa_id), IssuerRole::Admin)?;
+            ensure!(
+                !ProofHubPurchaseResolutions::<T>::contains_key(tx_id),
+                Error::<T>::PurchaseAlreadyResolved
+            );
+
+            ProofHubPurchaseResolutions::<T>::insert(tx_id, PurchaseResolution::Cancelled);
+            T::EscrowDispatch::cancel(tx_id);
+
+            Self::deposit_event(Event::ProofHubPurchaseCancelled { tx_id });
+            Ok(())
+        }
+
+        /// Change the per-unit price of an active listing (Admin or the issuer admin).
+        ///
+        /// The new price is pushed to every lane in `PriceSyncLanes`.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(10)]
+        pub fn set_price_hint(origin: OriginFor<T>, asset_id: u32, price_hint: u128) -> DispatchResult {
+            registry::Pallet::<T>::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            Listings::<T>::try_mutate(asset_id, |maybe| -> DispatchResult {
+                let listing = maybe.as_mut().ok_or(Error::<T>::ListingNotFound)?;
+                ensure!(listing.status == ListingStatus::Active, Error::<T>::NotActive);
+                listing.price_hint = price_hint;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::PriceHintUpdated { asset_id, price_hint });
+            Self::sync_price(asset_id, price_hint)
+        }
+
+        /// Add a lane to `PriceSyncLanes` (Admin only).
+        ///
+        /// The lane must accept unpaid `Transact` from this chain and map its
+        /// sovereign account to the origin checked by `xcm_sync_rwa_price`.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(11)]
+        pub fn add_price_sync_lane(origin: OriginFor<T>, lane: PriceSyncLane) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            PriceSyncLanes::<T>::try_mutate(|lanes| -> DispatchResult {
+                ensure!(
+                    !lanes.iter().any(|l| l.para_id == lane.para_id),
+                    Error::<T>::PriceSyncLaneExists
+                );
+                lanes.try_push(lane.clone()).map_err(|_| Error::<T>::TooManyPriceSyncLanes)?;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::PriceSyncLaneAdded { lane });
+            Ok(())
+        }
+
+        /// Remove a lane from `PriceSyncLanes` (Admin only).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(12)]
+        pub fn remove_price_sync_lane(origin: OriginFor<T>, para_id: u32) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            PriceSyncLanes::<T>::try_mutate(|lanes| -> DispatchResult {
+                let before = lanes.len();
+                lanes.retain(|l| l.para_id != para_id);
+                ensure!(lanes.len() < before, Error::<T>::PriceSyncLaneNotFound);
+                Ok(())
+            })?;
+            Self::deposit_event(Event::PriceSyncLaneRemoved { para_id });
             Ok(())
         }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Push a per-unit price to every lane in `PriceSyncLanes`.
+        /// Lane prices are u64; a larger `price_hint` is rejected.
+        fn sync_price(asset_id: u32, price_hint: u128) -> DispatchResult {
+            let price = u64::try_from(price_hint).map_err(|_| Error::<T>::PriceHintTooLarge)?;
+            let lanes = PriceSyncLanes::<T>::get();
+            if lanes.is_empty() {
+                return Ok(());
+            }
+            let mut rwa_id = [0u8; 32];
+            rwa_id[..4].copy_from_slice(&asset_id.to_le_bytes());
+            for lane in lanes.iter() {
+                T::PriceDispatch::send(lane, rwa_id, price);
+            }
+            Self::deposit_event(Event::PriceSynced { asset_id, price, lanes: lanes.len() as u32 });
+            Ok(())
+        }
+
+        /// Count `units` sold on a lane against the asset's active listing and
+        /// the registry. The lane has already sold them, so units beyond what
+        /// the listing still offers are not counted rather than refused. A
+        /// sell-out de-lists the asset on every lane, the seller included.
+        fn record_lane_sale(asset_id: u32, units: u64) -> DispatchResult {
+            let sold_out = Listings::<T>::mutate(asset_id, |maybe| {
+                let Some(listing) = maybe.as_mut() else { return false };
+                if listing.status != ListingStatus::Active {
+                    return false;
+                }
+                let counted = units.min(listing.units.saturating_sub(listing.units_sold));
+                if counted == 0 || registry::Pallet::<T>::internal_sell_units(asset_id, counted).is_err() {
+                    return false;
+                }
+                listing.units_sold = listing.units_sold.saturating_add(counted);
+                if listing.units_sold == listing.units {
+                    listing.status = ListingStatus::SoldPrivately;
+                }
+                listing.units_sold == listing.units
+            });
+            if sold_out {
+                Self::sync_price(asset_id, 0)?;
+            }
+            Ok(())
+        }
+
+        /// asset_id carried in the first 4 bytes (LE) of a ProofHub `rwa_id`.
+        fn asset_of(rwa_id: &[u8; 32]) -> u32 {
+            u32::from_le_bytes([rwa_id[0], rwa_id[1], rwa_id[2], rwa_id[3]])
+        }
+
         /// BLAKE3("nulla_rwa_ownership_v1" || rwa_id || blinding)
         fn compute_ownership_commitment(rwa_id: [u8; 32], blinding: [u8; 32]) -> [u8; 32] {
             use blake3::Hasher;
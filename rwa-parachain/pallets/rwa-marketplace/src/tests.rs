@@ -0,0 +1,343 @@
+use crate as pallet_rwa_marketplace;
+use crate::{
+    Error, Event, LanePriceDispatch, ListingStatus, Listings, PriceSyncLane, ProofHubPurchaseResolutions,
+    PurchaseResolution,
+};
+use frame_support::{
+    assert_noop, assert_ok, derive_impl, parameter_types,
+    traits::{ConstU64, UnixTime},
+};
+use pallet_rwa_registry::{AssetCategory, Error as RegistryError};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Registry: pallet_rwa_registry,
+        Marketplace: pallet_rwa_marketplace,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type ExistentialDeposit = ConstU64<10>;
+}
+
+pub struct MockTime;
+impl UnixTime for MockTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(1_700_000_000)
+    }
+}
+
+impl pallet_rwa_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type Time = MockTime;
+    type Currency = Balances;
+    type IssuerDeposit = ConstU64<100>;
+    type EligibilityDispatch = ();
+    type AssetFunds = ();
+}
+
+parameter_types! {
+    pub const ProofHubSovereign: u64 = SOVEREIGN;
+    pub static PriceSent: Vec<(u32, [u8; 32], u64)> = vec![];
+}
+
+/// Records the prices pushed to each lane.
+pub struct RecordingPriceDispatch;
+impl LanePriceDispatch for RecordingPriceDispatch {
+    fn send(lane: &PriceSyncLane, rwa_id: [u8; 32], price: u64) {
+        PriceSent::mutate(|sent| sent.push((lane.para_id, rwa_id, price)));
+    }
+}
+
+impl pallet_rwa_marketplace::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type ProofHubSovereign = ProofHubSovereign;
+    type EscrowDispatch = ();
+    type PriceDispatch = RecordingPriceDispatch;
+}
+
+const SELLER: u64 = 1;
+const ADMIN: u64 = 2;
+const CUSTODIAN: u64 = 3;
+const RIVAL: u64 = 4;
+const BUYER: u64 = 5;
+const SOVEREIGN: u64 = 8;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ADMIN, 10_000), (RIVAL, 10_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Registers a root-administered asset of `total_units` and locks it for sale.
+fn locked_asset(total_units: u64) -> u32 {
+    let asset_id = pallet_rwa_registry::NextAssetId::<Test>::get();
+    assert_ok!(Registry::register_asset(
+        RuntimeOrigin::root(),
+        SELLER,
+        b"Warehouse".to_vec().try_into().unwrap(),
+        b"Logistics warehouse".to_vec().try_into().unwrap(),
+        AssetCategory::RealEstate,
+        1_000_000,
+        Default::default(),
+        total_units,
+    ));
+    assert_ok!(Registry::lock_for_sale(RuntimeOrigin::root(), asset_id));
+    asset_id
+}
+
+#[test]
+fn locked_asset_is_listed() {
+    new_test_ext().execute_with(|| {
+        let asset_id = locked_asset(10);
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, 500, 11),
+            Error::<Test>::InsufficientUnits
+        );
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, 500, 10));
+        System::assert_last_event(Event::AssetListed { asset_id, seller: SELLER, price_hint: 500, units: 10 }.into());
+        assert_eq!(Listings::<Test>::get(asset_id).unwrap().status, ListingStatus::Active);
+    });
+}
+
+#[test]
+fn retired_asset_is_not_listed() {
+    new_test_ext().execute_with(|| {
+        let asset_id = locked_asset(1);
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::root(), asset_id));
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, 500, 1),
+            Error::<Test>::AssetNotAvailableForSale
+        );
+        assert!(Listings::<Test>::get(asset_id).is_none());
+    });
+}
+
+/// Onboards an issuer run by `admin` / `CUSTODIAN`, registers one asset of
+/// `total_units` under it and locks it for sale.
+fn issuer_asset(admin: u64, total_units: u64) -> u32 {
+    let issuer_id = pallet_rwa_registry::NextIssuerId::<Test>::get();
+    assert_ok!(Registry::register_issuer(
+        RuntimeOrigin::signed(admin),
+        b"Acme".to_vec().try_into().unwrap(),
+        Default::default(),
+        CUSTODIAN,
+    ));
+    let asset_id = pallet_rwa_registry::NextAssetId::<Test>::get();
+    assert_ok!(Registry::register_issuer_asset(
+        RuntimeOrigin::signed(admin),
+        issuer_id,
+        admin,
+        b"Truck".to_vec().try_into().unwrap(),
+        Default::default(),
+        AssetCategory::Fleet,
+        50_000,
+        Default::default(),
+        total_units,
+    ));
+    assert_ok!(Registry::lock_for_sale(RuntimeOrigin::signed(CUSTODIAN), asset_id));
+    asset_id
+}
+
+#[test]
+fn issuer_admin_manages_its_own_listings() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset(ADMIN, 1);
+        let rival_asset = issuer_asset(RIVAL, 1);
+
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::signed(CUSTODIAN), asset_id, ADMIN, 500, 1),
+            RegistryError::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::signed(RIVAL), asset_id, RIVAL, 500, 1),
+            RegistryError::<Test>::Unauthorized
+        );
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::signed(ADMIN), asset_id, ADMIN, 500, 1));
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::signed(RIVAL), rival_asset, RIVAL, 700, 1));
+
+        assert_noop!(
+            Marketplace::cancel_listing(RuntimeOrigin::signed(RIVAL), asset_id),
+            RegistryError::<Test>::Unauthorized
+        );
+        assert_ok!(Marketplace::cancel_listing(RuntimeOrigin::signed(ADMIN), asset_id));
+        assert_eq!(Listings::<Test>::get(asset_id).unwrap().status, ListingStatus::Cancelled);
+        assert!(!Registry::is_locked(asset_id));
+    });
+}
+
+#[test]
+fn suspended_issuer_cannot_list_or_sell() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset(ADMIN, 2);
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::signed(ADMIN), asset_id, ADMIN, 500, 1));
+        assert_ok!(Registry::suspend_issuer(RuntimeOrigin::root(), 0));
+
+        assert_noop!(
+            Marketplace::buy_privately(RuntimeOrigin::signed(BUYER), asset_id, 1, [1; 32]),
+            Error::<Test>::AssetNotAvailableForSale
+        );
+        assert_noop!(
+            Marketplace::cancel_listing(RuntimeOrigin::signed(ADMIN), asset_id),
+            RegistryError::<Test>::IssuerSuspended
+        );
+        // Root may cancel the listing, but not list the asset again.
+        assert_ok!(Marketplace::cancel_listing(RuntimeOrigin::root(), asset_id));
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::root(), asset_id));
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::root(), asset_id, ADMIN, 500, 1),
+            Error::<Test>::AssetNotAvailableForSale
+        );
+
+        assert_ok!(Registry::resume_issuer(RuntimeOrigin::root(), 0));
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::signed(ADMIN), asset_id, ADMIN, 500, 2));
+        assert_ok!(Marketplace::buy_privately(RuntimeOrigin::signed(BUYER), asset_id, 2, [1; 32]));
+        assert_eq!(Listings::<Test>::get(asset_id).unwrap().status, ListingStatus::SoldPrivately);
+    });
+}
+
+#[test]
+fn issuer_admin_resolves_proofhub_purchases() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset(ADMIN, 1);
+        assert_ok!(Marketplace::xcm_record_purchase(
+            RuntimeOrigin::signed(SOVEREIGN),
+            asset_id,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            [7; 16],
+            [3; 32],
+            1,
+        ));
+        assert_noop!(
+            Marketplace::fulfil_proofhub_purchase(RuntimeOrigin::signed(CUSTODIAN), [7; 16]),
+            RegistryError::<Test>::Unauthorized
+        );
+        assert_ok!(Marketplace::fulfil_proofhub_purchase(RuntimeOrigin::signed(ADMIN), [7; 16]));
+        assert_eq!(ProofHubPurchaseResolutions::<Test>::get([7; 16]), Some(PurchaseResolution::Fulfilled));
+        assert_noop!(
+            Marketplace::cancel_proofhub_purchase(RuntimeOrigin::root(), [7; 16]),
+            Error::<Test>::PurchaseAlreadyResolved
+        );
+    });
+}
+
+fn rwa_id(asset_id: u32) -> [u8; 32] {
+    let mut id = [0u8; 32];
+    id[..4].copy_from_slice(&asset_id.to_le_bytes());
+    id
+}
+
+/// Lists a 3-unit asset at 500 per unit with lanes 2000 and 3000 syncing prices.
+fn lane_listed_asset() -> u32 {
+    let asset_id = locked_asset(3);
+    for para_id in [2000, 3000] {
+        let lane = PriceSyncLane { para_id, pallet_index: 51, call_index: 14 };
+        assert_ok!(Marketplace::add_price_sync_lane(RuntimeOrigin::root(), lane));
+    }
+    assert_ok!(Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, 500, 3));
+    assert_eq!(PriceSent::take(), vec![(2000, rwa_id(asset_id), 500), (3000, rwa_id(asset_id), 500)]);
+    asset_id
+}
+
+#[test]
+fn lane_sales_count_against_listing_and_registry() {
+    new_test_ext().execute_with(|| {
+        let asset_id = lane_listed_asset();
+        assert_ok!(Marketplace::xcm_record_purchase(
+            RuntimeOrigin::signed(SOVEREIGN),
+            asset_id,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            [7; 16],
+            [3; 32],
+            2,
+        ));
+        let listing = Listings::<Test>::get(asset_id).unwrap();
+        assert_eq!((listing.units_sold, listing.status), (2, ListingStatus::Active));
+        assert_eq!(pallet_rwa_registry::Assets::<Test>::get(asset_id).unwrap().units_sold, 2);
+        assert_eq!(pallet_rwa_registry::Pallet::<Test>::units_available(asset_id), 1);
+        // Still listed: no price pushed.
+        assert!(PriceSent::get().is_empty());
+        // The remaining unit is all a public buyer can still take.
+        assert_noop!(
+            Marketplace::buy_privately(RuntimeOrigin::signed(BUYER), asset_id, 2, [9; 32]),
+            Error::<Test>::InsufficientUnits
+        );
+    });
+}
+
+#[test]
+fn lane_sell_out_delists_on_every_lane() {
+    new_test_ext().execute_with(|| {
+        let asset_id = lane_listed_asset();
+        assert_ok!(Marketplace::xcm_record_purchase(
+            RuntimeOrigin::signed(SOVEREIGN),
+            asset_id,
+            [0; 32],
+            [1; 32],
+            [2; 32],
+            [7; 16],
+            [3; 32],
+            2,
+        ));
+        // An auction settled on a lane sells the last unit.
+        assert_ok!(Marketplace::xcm_record_auction_result(
+            RuntimeOrigin::signed(SOVEREIGN),
+            asset_id,
+            0,
+            600,
+            1,
+            [4; 32],
+        ));
+        assert_eq!(Listings::<Test>::get(asset_id).unwrap().status, ListingStatus::SoldPrivately);
+        assert!(pallet_rwa_registry::Assets::<Test>::get(asset_id).unwrap().is_sold);
+        assert_eq!(PriceSent::get(), vec![(2000, rwa_id(asset_id), 0), (3000, rwa_id(asset_id), 0)]);
+        System::assert_last_event(Event::PriceSynced { asset_id, price: 0, lanes: 2 }.into());
+        assert_noop!(
+            Marketplace::buy_privately(RuntimeOrigin::signed(BUYER), asset_id, 1, [9; 32]),
+            Error::<Test>::NotActive
+        );
+    });
+}
+
+#[test]
+fn price_hint_above_u64_is_rejected() {
+    new_test_ext().execute_with(|| {
+        let asset_id = locked_asset(1);
+        let too_large = u64::MAX as u128 + 1;
+        assert_noop!(
+            Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, too_large, 1),
+            Error::<Test>::PriceHintTooLarge
+        );
+        assert_ok!(Marketplace::list_asset(RuntimeOrigin::root(), asset_id, SELLER, u64::MAX as u128, 1));
+        assert_noop!(
+            Marketplace::set_price_hint(RuntimeOrigin::root(), asset_id, too_large),
+            Error::<Test>::PriceHintTooLarge
+        );
+        assert_eq!(Listings::<Test>::get(asset_id).unwrap().price_hint, u64::MAX as u128);
+    });
+}
@@ -12,7 +12,7 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
     use codec::DecodeWithMemTracking;
-    use frame_support::traits::BuildGenesisConfig;
+    use frame_support::traits::{BuildGenesisConfig, Currency, ReservableCurrency, UnixTime};
 
     // ── Constants ────────────────────────────────────────────────────────────
     pub const MAX_NAME_LEN: u32 = 64;
@@ -20,6 +20,10 @@ pub mod pallet {
     pub const MAX_META_LEN: u32 = 512;
     pub const MAX_DISCLAIMER_LEN: u32 = 1024;
     pub const MAX_INFO_STR_LEN: u32 = 128;
+    /// Valuation records kept per asset; the oldest is dropped when full.
+    pub const MAX_VALUATIONS: u32 = 32;
+    /// Document attestations per asset.
+    pub const MAX_DOCUMENTS: u32 = 32;
 
     // ── Types ────────────────────────────────────────────────────────────────
 
@@ -49,10 +53,125 @@ pub mod pallet {
         pub is_locked: bool,
         /// `true` once a `SoldPrivately` or `SoldPublicly` event has been emitted.
         pub is_sold: bool,
+        /// Number of fractional units the asset is split into (`1` = indivisible).
+        pub total_units: u64,
+        /// Units already sold; `is_sold` flips once this reaches `total_units`.
+        pub units_sold: u64,
         /// Optional freeform metadata blob (JSON-encoded details, images URL, ISIN …)
         pub metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
     }
 
+    /// `RWAAsset` before storage version 1, without fractional units.
+    #[derive(Decode)]
+    struct RWAAssetV0<AccountId> {
+        asset_id: u32,
+        name: BoundedVec<u8, ConstU32<{ MAX_NAME_LEN }>>,
+        description: BoundedVec<u8, ConstU32<{ MAX_DESC_LEN }>>,
+        category: AssetCategory,
+        usd_value_cents: u128,
+        owner: AccountId,
+        is_locked: bool,
+        is_sold: bool,
+        metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
+    }
+
+    /// An appraisal of an asset. The latest one also updates `usd_value_cents`.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct ValuationRecord<AccountId> {
+        /// USD value in cents.
+        pub usd_value_cents: u128,
+        pub appraiser: AccountId,
+        /// Hash of the appraisal report kept off-chain.
+        pub report_hash: [u8; 32],
+        /// Unix time (seconds) at which the valuation was recorded.
+        pub recorded_at: u64,
+    }
+
+    /// Kind of legal document anchored against an asset.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub enum DocumentKind {
+        Deed,
+        Audit,
+        Insurance,
+        Other,
+    }
+
+    /// Hash of an off-chain document, attested by the admin.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct DocumentAttestation {
+        pub kind: DocumentKind,
+        pub doc_hash: [u8; 32],
+        /// Unix time (seconds) at which the hash was attested.
+        pub attested_at: u64,
+    }
+
+    /// Whether an issuer may currently act on its assets.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub enum IssuerStatus {
+        Active,
+        /// Suspended by root/governance: its accounts lose every role and its
+        /// assets cannot be listed or bought until resumed.
+        Suspended,
+    }
+
+    /// Role an issuer account needs for an operation on one of its assets.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub enum IssuerRole {
+        /// Registration, edits, valuations, retirement, listings and sales.
+        Admin,
+        /// Physical custody: locking, release after redemption, document
+        /// attestations. The admin account may also act as custodian.
+        Custodian,
+    }
+
+    /// An onboarded issuer with its own company info and accounts.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct Issuer<AccountId, Balance> {
+        pub info: CompanyInfo,
+        pub admin: AccountId,
+        pub custodian: AccountId,
+        /// Account whose `deposit` is reserved; refunded on deregistration.
+        pub depositor: AccountId,
+        pub deposit: Balance,
+        pub status: IssuerStatus,
+    }
+
+    /// Buyer eligibility for an asset: buyers on the lanes must present a
+    /// credential from ProofHub credential registry `registry_id` whose class
+    /// holds every flag in `required_class`. Same SCALE layout as the lanes'.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub struct EligibilityRequirement {
+        pub registry_id: u32,
+        pub required_class: u64,
+    }
+
+    /// Trait implemented by the runtime to push an asset's buyer eligibility
+    /// to the lanes (`xcm_sync_rwa_eligibility`).
+    ///
+    /// Delivery failures are logged and swallowed.
+    pub trait EligibilityDispatch {
+        fn send(asset_id: u32, requirement: Option<EligibilityRequirement>);
+    }
+
+    /// No-op implementation used when XCM is not wired (e.g. tests).
+    impl EligibilityDispatch for () {
+        fn send(_: u32, _: Option<EligibilityRequirement>) {}
+    }
+
+    /// Trait implemented by the runtime to report funds still held against an
+    /// asset outside the registry (`pallet-rwa-vault`). `burn_asset` refuses
+    /// an asset that holds any, since nothing could draw them down afterwards.
+    pub trait AssetFunds {
+        fn holds_funds(asset_id: u32) -> bool;
+    }
+
+    impl AssetFunds for () {
+        fn holds_funds(_: u32) -> bool { false }
+    }
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
     /// Company-level information stored once at genesis.
     #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, frame_support::Serialize, frame_support::Deserialize)]
     pub struct CompanyInfo {
@@ -61,7 +180,10 @@ pub mod pallet {
     }
 
     // ── Pallet ───────────────────────────────────────────────────────────────
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
@@ -69,6 +191,18 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// Origin allowed to register/lock assets (typically `EnsureRoot`).
         type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Wall-clock time for valuation and attestation timestamps.
+        type Time: UnixTime;
+        /// Currency in which the issuer onboarding deposit is reserved.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Deposit reserved from an issuer's depositor while it is registered.
+        #[pallet::constant]
+        type IssuerDeposit: Get<BalanceOf<Self>>;
+        /// XCM dispatch of buyer eligibility to the lanes.
+        /// Use `()` when XCM is not needed.
+        type EligibilityDispatch: EligibilityDispatch;
+        /// Funds held against an asset elsewhere; `()` when none are.
+        type AssetFunds: AssetFunds;
     }
 
     // ── Storage ──────────────────────────────────────────────────────────────
@@ -80,9 +214,58 @@ pub mod pallet {
     #[pallet::storage]
     pub type NextAssetId<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    /// Company info of the chain operator, for assets without an issuer.
+    /// Onboarded issuers keep their own `CompanyInfo` in `Issuers`.
     #[pallet::storage]
     pub type Company<T: Config> = StorageValue<_, CompanyInfo>;
 
+    /// Valuation history per asset, oldest first.
+    #[pallet::storage]
+    pub type Valuations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        BoundedVec<ValuationRecord<T::AccountId>, ConstU32<{ MAX_VALUATIONS }>>,
+        ValueQuery,
+    >;
+
+    /// Document attestations per asset, in attestation order.
+    #[pallet::storage]
+    pub type Documents<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        BoundedVec<DocumentAttestation, ConstU32<{ MAX_DOCUMENTS }>>,
+        ValueQuery,
+    >;
+
+    /// Onboarded issuers keyed by issuer_id.
+    #[pallet::storage]
+    pub type Issuers<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, Issuer<T::AccountId, BalanceOf<T>>>;
+
+    #[pallet::storage]
+    pub type NextIssuerId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Issuer of each asset registered by an issuer. Assets without an entry
+    /// (genesis, `register_asset`) are administered by `AdminOrigin` only.
+    #[pallet::storage]
+    pub type AssetIssuer<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32>;
+
+    /// Live (not burned) assets per issuer; an issuer can deregister at zero.
+    #[pallet::storage]
+    pub type IssuerAssetCount<T: Config> = StorageMap<_, Blake2_128Concat, u32, u32, ValueQuery>;
+
+    /// Buyer eligibility per asset; absent means open to all buyers.
+    #[pallet::storage]
+    pub type AssetEligibility<T: Config> =
+        StorageMap<_, Blake2_128Concat, u32, EligibilityRequirement>;
+
+    /// Retired assets: asset_id → Unix time (seconds) of retirement.
+    /// A retired asset can no longer be listed or edited, only redeemed and burned.
+    #[pallet::storage]
+    pub type RetiredAssets<T: Config> = StorageMap<_, Blake2_128Concat, u32, u64>;
+
     // ── Events ───────────────────────────────────────────────────────────────
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -92,6 +275,22 @@ pub mod pallet {
         AssetUnlocked { asset_id: u32 },
         AssetTransferred { asset_id: u32, to: T::AccountId },
         MarkedSold { asset_id: u32 },
+        UnitsSold { asset_id: u32, units: u64, remaining: u64 },
+        DescriptionUpdated { asset_id: u32 },
+        MetadataUpdated { asset_id: u32 },
+        ValuationRecorded { asset_id: u32, usd_value_cents: u128, appraiser: T::AccountId },
+        DocumentAttested { asset_id: u32, kind: DocumentKind, doc_hash: [u8; 32] },
+        AssetRetired { asset_id: u32 },
+        AssetBurned { asset_id: u32 },
+        IssuerRegistered { issuer_id: u32, admin: T::AccountId, deposit: BalanceOf<T> },
+        IssuerInfoUpdated { issuer_id: u32 },
+        IssuerAccountsUpdated { issuer_id: u32, admin: T::AccountId, custodian: T::AccountId },
+        IssuerSuspended { issuer_id: u32 },
+        IssuerResumed { issuer_id: u32 },
+        IssuerDeregistered { issuer_id: u32 },
+        IssuerAssetRegistered { issuer_id: u32, asset_id: u32 },
+        /// Buyer eligibility changed and was pushed to the lanes (`None` = open).
+        AssetEligibilitySet { asset_id: u32, requirement: Option<EligibilityRequirement> },
     }
 
     // ── Errors ───────────────────────────────────────────────────────────────
@@ -103,12 +302,71 @@ pub mod pallet {
         NotLocked,
         Unauthorized,
         TooLong,
+        /// `total_units` must be at least 1.
+        ZeroUnits,
+        /// Fewer units remain unsold than were requested.
+        InsufficientUnits,
+        /// The asset is retired and can no longer be changed or listed.
+        AssetRetired,
+        /// The asset is already retired.
+        AlreadyRetired,
+        /// Only a retired asset can be burned.
+        NotRetired,
+        /// A burned asset must be unlocked first: note-holders still hold a claim.
+        StillLocked,
+        /// A burned asset's vault must be emptied first.
+        FundsStillHeld,
+        /// The asset already has `MAX_DOCUMENTS` attestations.
+        TooManyDocuments,
+        /// This document hash is already attested for the asset.
+        DuplicateDocument,
+        IssuerNotFound,
+        /// The issuer is suspended by root/governance.
+        IssuerSuspended,
+        /// The issuer is not suspended.
+        IssuerNotSuspended,
+        /// The issuer still has live assets and cannot deregister.
+        IssuerHasAssets,
+    }
+
+    // ── Hooks ────────────────────────────────────────────────────────────────
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+                return Weight::zero();
+            }
+            // v0 → v1: assets gained fractional units. An existing asset is
+            // one indivisible unit, sold if the asset was.
+            let mut migrated = 0u64;
+            Assets::<T>::translate::<RWAAssetV0<T::AccountId>, _>(|_, old| {
+                migrated += 1;
+                Some(RWAAsset {
+                    asset_id: old.asset_id,
+                    name: old.name,
+                    description: old.description,
+                    category: old.category,
+                    usd_value_cents: old.usd_value_cents,
+                    owner: old.owner,
+                    is_locked: old.is_locked,
+                    is_sold: old.is_sold,
+                    total_units: 1,
+                    units_sold: u64::from(old.is_sold),
+                    metadata: old.metadata,
+                })
+            });
+            STORAGE_VERSION.put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+        }
     }
 
     // ── Calls ────────────────────────────────────────────────────────────────
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Register a new RWA (Admin only).
+        ///
+        /// `total_units` splits the asset into fungible shares; pass `1` for an
+        /// indivisible asset.
         #[pallet::weight(10_000)]
         #[pallet::call_index(0)]
         pub fn register_asset(
@@ -119,31 +377,20 @@ pub mod pallet {
             category: AssetCategory,
             usd_value_cents: u128,
             metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
+            total_units: u64,
         ) -> DispatchResult {
             T::AdminOrigin::ensure_origin(origin)?;
-            let asset_id = NextAssetId::<T>::get();
-            let asset = RWAAsset {
-                asset_id,
-                name: name.clone(),
-                description,
-                category,
-                usd_value_cents,
-                owner,
-                is_locked: false,
-                is_sold: false,
-                metadata,
-            };
-            Assets::<T>::insert(asset_id, asset);
-            NextAssetId::<T>::put(asset_id.saturating_add(1));
-            Self::deposit_event(Event::AssetRegistered { asset_id, name });
+            Self::do_register_asset(owner, name, description, category, usd_value_cents, metadata, total_units)?;
             Ok(())
         }
 
-        /// Lock asset for sale (Admin only). Once locked only the note-holder can unlock.
+        /// Lock asset for sale (Admin or the issuer's custodian).
+        /// Once locked only the note-holder can unlock.
         #[pallet::weight(5_000)]
         #[pallet::call_index(1)]
         pub fn lock_for_sale(origin: OriginFor<T>, asset_id: u32) -> DispatchResult {
-            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Custodian)?;
+            ensure!(!RetiredAssets::<T>::contains_key(asset_id), Error::<T>::AssetRetired);
             Assets::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
                 let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
                 ensure!(!asset.is_locked, Error::<T>::AlreadyLocked);
@@ -164,17 +411,18 @@ pub mod pallet {
                 let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
                 ensure!(!asset.is_sold, Error::<T>::AlreadySold);
                 asset.is_sold = true;
+                asset.units_sold = asset.total_units;
                 Ok(())
             })?;
             Self::deposit_event(Event::MarkedSold { asset_id });
             Ok(())
         }
 
-        /// Unlock asset after valid redemption (Admin only after ZK proof verified off-chain).
+        /// Unlock asset after valid redemption (Admin or the issuer's custodian, after ZK proof verified off-chain).
         #[pallet::weight(5_000)]
         #[pallet::call_index(3)]
         pub fn release_after_redemption(origin: OriginFor<T>, asset_id: u32) -> DispatchResult {
-            T::AdminOrigin::ensure_origin(origin)?;
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Custodian)?;
             Assets::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
                 let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
                 ensure!(asset.is_locked, Error::<T>::NotLocked);
@@ -185,7 +433,7 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Set company info (Admin only).
+        /// Set company info for root-administered assets (Admin only).
         #[pallet::weight(5_000)]
         #[pallet::call_index(4)]
         pub fn set_company_info(
@@ -197,6 +445,281 @@ pub mod pallet {
             Company::<T>::put(CompanyInfo { name, disclaimer });
             Ok(())
         }
+
+        /// Replace an asset's description (Admin or the issuer admin).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(5)]
+        pub fn update_description(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            description: BoundedVec<u8, ConstU32<{ MAX_DESC_LEN }>>,
+        ) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            Self::mutate_active(asset_id, |asset| asset.description = description)?;
+            Self::deposit_event(Event::DescriptionUpdated { asset_id });
+            Ok(())
+        }
+
+        /// Replace an asset's metadata blob (Admin or the issuer admin).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(6)]
+        pub fn update_metadata(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
+        ) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            Self::mutate_active(asset_id, |asset| asset.metadata = metadata)?;
+            Self::deposit_event(Event::MetadataUpdated { asset_id });
+            Ok(())
+        }
+
+        /// Append a valuation by `appraiser` and make it the asset's current
+        /// `usd_value_cents` (Admin or the issuer admin). Keeps the last `MAX_VALUATIONS` records.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(7)]
+        pub fn record_valuation(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            usd_value_cents: u128,
+            appraiser: T::AccountId,
+            report_hash: [u8; 32],
+        ) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            Self::mutate_active(asset_id, |asset| asset.usd_value_cents = usd_value_cents)?;
+            let record = ValuationRecord {
+                usd_value_cents,
+                appraiser: appraiser.clone(),
+                report_hash,
+                recorded_at: T::Time::now().as_secs(),
+            };
+            Valuations::<T>::mutate(asset_id, |history| {
+                if history.is_full() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(record);
+            });
+            Self::deposit_event(Event::ValuationRecorded { asset_id, usd_value_cents, appraiser });
+            Ok(())
+        }
+
+        /// Anchor the hash of a deed, audit, insurance policy or other document
+        /// against an asset (Admin or the issuer's custodian).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(8)]
+        pub fn attest_document(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            kind: DocumentKind,
+            doc_hash: [u8; 32],
+        ) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Custodian)?;
+            Self::mutate_active(asset_id, |_| {})?;
+            Documents::<T>::try_mutate(asset_id, |docs| -> DispatchResult {
+                ensure!(!docs.iter().any(|d| d.doc_hash == doc_hash), Error::<T>::DuplicateDocument);
+                docs.try_push(DocumentAttestation { kind, doc_hash, attested_at: T::Time::now().as_secs() })
+                    .map_err(|_| Error::<T>::TooManyDocuments)?;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::DocumentAttested { asset_id, kind, doc_hash });
+            Ok(())
+        }
+
+        /// Retire an asset (Admin or the issuer admin): it can no longer be listed or edited.
+        /// Holders of sold units can still redeem.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(9)]
+        pub fn retire_asset(origin: OriginFor<T>, asset_id: u32) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
+            ensure!(!RetiredAssets::<T>::contains_key(asset_id), Error::<T>::AlreadyRetired);
+            RetiredAssets::<T>::insert(asset_id, T::Time::now().as_secs());
+            Self::deposit_event(Event::AssetRetired { asset_id });
+            Ok(())
+        }
+
+        /// Burn a retired, unlocked asset with an empty vault (Admin or the issuer admin),
+        /// removing it together with its valuations and document attestations.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(10)]
+        pub fn burn_asset(origin: OriginFor<T>, asset_id: u32) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            let asset = Assets::<T>::get(asset_id).ok_or(Error::<T>::AssetNotFound)?;
+            ensure!(RetiredAssets::<T>::contains_key(asset_id), Error::<T>::NotRetired);
+            ensure!(!asset.is_locked, Error::<T>::StillLocked);
+            ensure!(!T::AssetFunds::holds_funds(asset_id), Error::<T>::FundsStillHeld);
+            Assets::<T>::remove(asset_id);
+            Valuations::<T>::remove(asset_id);
+            Documents::<T>::remove(asset_id);
+            AssetEligibility::<T>::remove(asset_id);
+            RetiredAssets::<T>::remove(asset_id);
+            if let Some(issuer_id) = AssetIssuer::<T>::take(asset_id) {
+                IssuerAssetCount::<T>::mutate(issuer_id, |n| *n = n.saturating_sub(1));
+            }
+            Self::deposit_event(Event::AssetBurned { asset_id });
+            Ok(())
+        }
+
+        /// Onboard an issuer. The caller becomes its admin and depositor and
+        /// `IssuerDeposit` is reserved until the issuer deregisters.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(11)]
+        pub fn register_issuer(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, ConstU32<{ MAX_INFO_STR_LEN }>>,
+            disclaimer: BoundedVec<u8, ConstU32<{ MAX_DISCLAIMER_LEN }>>,
+            custodian: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let deposit = T::IssuerDeposit::get();
+            T::Currency::reserve(&who, deposit)?;
+            let issuer_id = NextIssuerId::<T>::get();
+            Issuers::<T>::insert(issuer_id, Issuer {
+                info: CompanyInfo { name, disclaimer },
+                admin: who.clone(),
+                custodian,
+                depositor: who.clone(),
+                deposit,
+                status: IssuerStatus::Active,
+            });
+            NextIssuerId::<T>::put(issuer_id.saturating_add(1));
+            Self::deposit_event(Event::IssuerRegistered { issuer_id, admin: who, deposit });
+            Ok(())
+        }
+
+        /// Replace an issuer's company info (issuer admin only).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(12)]
+        pub fn set_issuer_info(
+            origin: OriginFor<T>,
+            issuer_id: u32,
+            name: BoundedVec<u8, ConstU32<{ MAX_INFO_STR_LEN }>>,
+            disclaimer: BoundedVec<u8, ConstU32<{ MAX_DISCLAIMER_LEN }>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::mutate_issuer(issuer_id, &who, |issuer| issuer.info = CompanyInfo { name, disclaimer })?;
+            Self::deposit_event(Event::IssuerInfoUpdated { issuer_id });
+            Ok(())
+        }
+
+        /// Hand the issuer's admin and custodian roles to new accounts
+        /// (issuer admin only). The deposit stays with the original depositor.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(13)]
+        pub fn set_issuer_accounts(
+            origin: OriginFor<T>,
+            issuer_id: u32,
+            admin: T::AccountId,
+            custodian: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::mutate_issuer(issuer_id, &who, |issuer| {
+                issuer.admin = admin.clone();
+                issuer.custodian = custodian.clone();
+            })?;
+            Self::deposit_event(Event::IssuerAccountsUpdated { issuer_id, admin, custodian });
+            Ok(())
+        }
+
+        /// Suspend an issuer (Admin only): its accounts lose every role and its
+        /// assets cannot be listed or bought.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(14)]
+        pub fn suspend_issuer(origin: OriginFor<T>, issuer_id: u32) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Issuers::<T>::try_mutate(issuer_id, |maybe| -> DispatchResult {
+                let issuer = maybe.as_mut().ok_or(Error::<T>::IssuerNotFound)?;
+                ensure!(issuer.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+                issuer.status = IssuerStatus::Suspended;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::IssuerSuspended { issuer_id });
+            Ok(())
+        }
+
+        /// Lift an issuer suspension (Admin only).
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(15)]
+        pub fn resume_issuer(origin: OriginFor<T>, issuer_id: u32) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            Issuers::<T>::try_mutate(issuer_id, |maybe| -> DispatchResult {
+                let issuer = maybe.as_mut().ok_or(Error::<T>::IssuerNotFound)?;
+                ensure!(issuer.status == IssuerStatus::Suspended, Error::<T>::IssuerNotSuspended);
+                issuer.status = IssuerStatus::Active;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::IssuerResumed { issuer_id });
+            Ok(())
+        }
+
+        /// Deregister an issuer with no live assets (issuer admin only) and
+        /// unreserve its deposit.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(16)]
+        pub fn deregister_issuer(origin: OriginFor<T>, issuer_id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let issuer = Issuers::<T>::get(issuer_id).ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.admin == who, Error::<T>::Unauthorized);
+            ensure!(issuer.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+            ensure!(IssuerAssetCount::<T>::get(issuer_id) == 0, Error::<T>::IssuerHasAssets);
+            T::Currency::unreserve(&issuer.depositor, issuer.deposit);
+            Issuers::<T>::remove(issuer_id);
+            IssuerAssetCount::<T>::remove(issuer_id);
+            Self::deposit_event(Event::IssuerDeregistered { issuer_id });
+            Ok(())
+        }
+
+        /// Register a new RWA under an issuer (issuer admin only). The asset is
+        /// then administered by the issuer's admin and custodian accounts.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(17)]
+        pub fn register_issuer_asset(
+            origin: OriginFor<T>,
+            issuer_id: u32,
+            owner: T::AccountId,
+            name: BoundedVec<u8, ConstU32<{ MAX_NAME_LEN }>>,
+            description: BoundedVec<u8, ConstU32<{ MAX_DESC_LEN }>>,
+            category: AssetCategory,
+            usd_value_cents: u128,
+            metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
+            total_units: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let issuer = Issuers::<T>::get(issuer_id).ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+            ensure!(issuer.admin == who, Error::<T>::Unauthorized);
+            let asset_id =
+                Self::do_register_asset(owner, name, description, category, usd_value_cents, metadata, total_units)?;
+            AssetIssuer::<T>::insert(asset_id, issuer_id);
+            IssuerAssetCount::<T>::mutate(issuer_id, |n| *n = n.saturating_add(1));
+            Self::deposit_event(Event::IssuerAssetRegistered { issuer_id, asset_id });
+            Ok(())
+        }
+
+        /// Restrict an asset to buyers holding an eligibility credential, or
+        /// open it again with `None` (Admin or the issuer admin).
+        ///
+        /// The requirement is pushed to the lanes over XCM. ProofHub checks a
+        /// zero-knowledge credential presentation on every purchase and resale;
+        /// lanes that cannot check credentials refuse restricted assets.
+        #[pallet::weight(5_000)]
+        #[pallet::call_index(18)]
+        pub fn set_asset_eligibility(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            requirement: Option<EligibilityRequirement>,
+        ) -> DispatchResult {
+            Self::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
+            ensure!(!RetiredAssets::<T>::contains_key(asset_id), Error::<T>::AssetRetired);
+            match requirement {
+                Some(req) => AssetEligibility::<T>::insert(asset_id, req),
+                None => AssetEligibility::<T>::remove(asset_id),
+            }
+            T::EligibilityDispatch::send(asset_id, requirement);
+            Self::deposit_event(Event::AssetEligibilitySet { asset_id, requirement });
+            Ok(())
+        }
     }
 
     // ── Genesis Config ────────────────────────────────────────────────────
@@ -229,23 +752,146 @@ pub mod pallet {
 
     // ── Internal helpers (used by marketplace pallet) ─────────────────────
     impl<T: Config> Pallet<T> {
-        /// Returns true if asset exists and `is_locked = true, is_sold = false`.
+        /// Returns true if asset exists, is not retired, its issuer (if any) is
+        /// not suspended, and `is_locked = true, is_sold = false`.
         pub fn is_available_for_sale(asset_id: u32) -> bool {
-            Assets::<T>::get(asset_id)
-                .map(|a| a.is_locked && !a.is_sold)
+            !RetiredAssets::<T>::contains_key(asset_id)
+                && !Self::issuer_suspended(asset_id)
+                && Assets::<T>::get(asset_id)
+                    .map(|a| a.is_locked && !a.is_sold)
+                    .unwrap_or(false)
+        }
+
+        /// Returns `true` once `retire_asset` has been called for the asset.
+        pub fn is_retired(asset_id: u32) -> bool {
+            RetiredAssets::<T>::contains_key(asset_id)
+        }
+
+        /// Accept `AdminOrigin`, or a signed account holding `role` for the
+        /// asset's issuer. Used by the registry and the marketplace.
+        ///
+        /// Assets without an issuer are administered by `AdminOrigin` only, and a
+        /// suspended issuer's accounts hold no role.
+        pub fn ensure_asset_role(origin: OriginFor<T>, asset_id: u32, role: IssuerRole) -> DispatchResult {
+            let origin = match T::AdminOrigin::try_origin(origin) {
+                Ok(_) => return Ok(()),
+                Err(origin) => origin,
+            };
+            let who = ensure_signed(origin)?;
+            let issuer_id = AssetIssuer::<T>::get(asset_id).ok_or(Error::<T>::Unauthorized)?;
+            let issuer = Issuers::<T>::get(issuer_id).ok_or(Error::<T>::IssuerNotFound)?;
+            ensure!(issuer.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+            let allowed = who == issuer.admin || (role == IssuerRole::Custodian && who == issuer.custodian);
+            ensure!(allowed, Error::<T>::Unauthorized);
+            Ok(())
+        }
+
+        /// Returns `true` if the asset belongs to a suspended issuer.
+        pub fn issuer_suspended(asset_id: u32) -> bool {
+            AssetIssuer::<T>::get(asset_id)
+                .and_then(Issuers::<T>::get)
+                .map(|i| i.status == IssuerStatus::Suspended)
                 .unwrap_or(false)
         }
 
+        fn do_register_asset(
+            owner: T::AccountId,
+            name: BoundedVec<u8, ConstU32<{ MAX_NAME_LEN }>>,
+            description: BoundedVec<u8, ConstU32<{ MAX_DESC_LEN }>>,
+            category: AssetCategory,
+            usd_value_cents: u128,
+            metadata: BoundedVec<u8, ConstU32<{ MAX_META_LEN }>>,
+            total_units: u64,
+        ) -> Result<u32, DispatchError> {
+            ensure!(total_units > 0, Error::<T>::ZeroUnits);
+            let asset_id = NextAssetId::<T>::get();
+            let asset = RWAAsset {
+                asset_id,
+                name: name.clone(),
+                description,
+                category,
+                usd_value_cents,
+                owner,
+                is_locked: false,
+                is_sold: false,
+                total_units,
+                units_sold: 0,
+                metadata,
+            };
+            Assets::<T>::insert(asset_id, asset);
+            NextAssetId::<T>::put(asset_id.saturating_add(1));
+            Self::deposit_event(Event::AssetRegistered { asset_id, name });
+            Ok(asset_id)
+        }
+
+        /// Apply `f` to an issuer, provided `who` is its admin and it is active.
+        fn mutate_issuer(
+            issuer_id: u32,
+            who: &T::AccountId,
+            f: impl FnOnce(&mut Issuer<T::AccountId, BalanceOf<T>>),
+        ) -> DispatchResult {
+            Issuers::<T>::try_mutate(issuer_id, |maybe| -> DispatchResult {
+                let issuer = maybe.as_mut().ok_or(Error::<T>::IssuerNotFound)?;
+                ensure!(issuer.status == IssuerStatus::Active, Error::<T>::IssuerSuspended);
+                ensure!(&issuer.admin == who, Error::<T>::Unauthorized);
+                f(issuer);
+                Ok(())
+            })
+        }
+
+        /// Apply `f` to an existing, non-retired asset.
+        fn mutate_active(asset_id: u32, f: impl FnOnce(&mut RWAAsset<T::AccountId>)) -> DispatchResult {
+            ensure!(!RetiredAssets::<T>::contains_key(asset_id), Error::<T>::AssetRetired);
+            Assets::<T>::try_mutate(asset_id, |maybe| -> DispatchResult {
+                let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+                f(asset);
+                Ok(())
+            })
+        }
+
         /// Mark as sold + stays locked.  Called by marketplace pallet directly.
         pub fn internal_mark_sold(asset_id: u32) -> DispatchResult {
             Assets::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
                 let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
                 ensure!(!asset.is_sold, Error::<T>::AlreadySold);
                 asset.is_sold = true;
+                asset.units_sold = asset.total_units;
                 Ok(())
             })
         }
 
+        /// Units of `asset_id` not yet sold (0 if the asset does not exist).
+        pub fn units_available(asset_id: u32) -> u64 {
+            Assets::<T>::get(asset_id)
+                .map(|a| a.total_units.saturating_sub(a.units_sold))
+                .unwrap_or(0)
+        }
+
+        /// Sell `units` shares of the asset; marks it sold once every unit is gone.
+        /// Called by the marketplace for fractional sales.
+        pub fn internal_sell_units(asset_id: u32, units: u64) -> DispatchResult {
+            ensure!(units > 0, Error::<T>::ZeroUnits);
+            let remaining = Assets::<T>::try_mutate(asset_id, |maybe| -> Result<u64, DispatchError> {
+                let asset = maybe.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+                ensure!(!asset.is_sold, Error::<T>::AlreadySold);
+                let remaining = asset
+                    .total_units
+                    .saturating_sub(asset.units_sold)
+                    .checked_sub(units)
+                    .ok_or(Error::<T>::InsufficientUnits)?;
+                asset.units_sold = asset.units_sold.saturating_add(units);
+                if remaining == 0 {
+                    asset.is_sold = true;
+                }
+                Ok(remaining)
+            })?;
+            Self::deposit_event(Event::UnitsSold { asset_id, units, remaining });
+            if remaining == 0 {
+                Self::deposit_event(Event::MarkedSold { asset_id });
+            }
+            Ok(())
+        }
+
         /// Unlock asset — called by marketplace after valid redemption.
         pub fn internal_release(asset_id: u32) -> DispatchResult {
             Assets::<T>::try_mutate(asset_id, |maybe| -> Result<(), DispatchError> {
@@ -0,0 +1,494 @@
+use crate as pallet_rwa_registry;
+use crate::{
+    AssetCategory, AssetIssuer, Assets, DocumentAttestation, DocumentKind, Documents, Error, Event,
+    IssuerAssetCount, IssuerStatus, Issuers, RetiredAssets, Valuations, MAX_DOCUMENTS, MAX_VALUATIONS,
+};
+use frame_support::{
+    assert_noop, assert_ok, derive_impl,
+    traits::{ConstU32, ConstU64, Hooks, StorageVersion, UnixTime},
+    BoundedVec,
+};
+use sp_runtime::{traits::BadOrigin, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Registry: pallet_rwa_registry,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type ExistentialDeposit = ConstU64<10>;
+}
+
+const NOW: u64 = 1_700_000_000;
+
+pub struct MockTime;
+impl UnixTime for MockTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(NOW)
+    }
+}
+
+impl pallet_rwa_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type Time = MockTime;
+    type Currency = Balances;
+    type IssuerDeposit = ConstU64<100>;
+    type EligibilityDispatch = ();
+    type AssetFunds = ();
+}
+
+const OWNER: u64 = 1;
+const ADMIN: u64 = 2;
+const CUSTODIAN: u64 = 3;
+const NEW_ADMIN: u64 = 4;
+const APPRAISER: u64 = 5;
+const POOR: u64 = 6;
+const OTHER: u64 = 9;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(OWNER, 10_000), (ADMIN, 10_000), (POOR, 50), (OTHER, 10_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Registers a root-administered asset.
+fn root_asset() -> u32 {
+    let asset_id = crate::NextAssetId::<Test>::get();
+    assert_ok!(Registry::register_asset(
+        RuntimeOrigin::root(),
+        OWNER,
+        b"Warehouse".to_vec().try_into().unwrap(),
+        b"Logistics warehouse".to_vec().try_into().unwrap(),
+        AssetCategory::RealEstate,
+        1_000_000,
+        Default::default(),
+        1,
+    ));
+    asset_id
+}
+
+#[test]
+fn description_and_metadata_are_replaced() {
+    new_test_ext().execute_with(|| {
+        let asset_id = root_asset();
+        assert_ok!(Registry::update_description(
+            RuntimeOrigin::root(),
+            asset_id,
+            b"Refurbished warehouse".to_vec().try_into().unwrap(),
+        ));
+        System::assert_last_event(Event::DescriptionUpdated { asset_id }.into());
+        assert_ok!(Registry::update_metadata(
+            RuntimeOrigin::root(),
+            asset_id,
+            b"{\"isin\":\"XS0000000000\"}".to_vec().try_into().unwrap(),
+        ));
+        System::assert_last_event(Event::MetadataUpdated { asset_id }.into());
+
+        let asset = Assets::<Test>::get(asset_id).unwrap();
+        assert_eq!(asset.description.to_vec(), b"Refurbished warehouse".to_vec());
+        assert_eq!(asset.metadata.to_vec(), b"{\"isin\":\"XS0000000000\"}".to_vec());
+
+        // Assets without an issuer answer to the admin origin only.
+        assert_noop!(
+            Registry::update_metadata(RuntimeOrigin::signed(OWNER), asset_id, Default::default()),
+            Error::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Registry::update_metadata(RuntimeOrigin::root(), 7, Default::default()),
+            Error::<Test>::AssetNotFound
+        );
+    });
+}
+
+#[test]
+fn valuations_update_the_value_and_keep_a_bounded_history() {
+    new_test_ext().execute_with(|| {
+        let asset_id = root_asset();
+        assert_ok!(Registry::record_valuation(RuntimeOrigin::root(), asset_id, 1_200_000, APPRAISER, [1; 32]));
+        System::assert_last_event(
+            Event::ValuationRecorded { asset_id, usd_value_cents: 1_200_000, appraiser: APPRAISER }.into(),
+        );
+        assert_eq!(Assets::<Test>::get(asset_id).unwrap().usd_value_cents, 1_200_000);
+        let first = &Valuations::<Test>::get(asset_id)[0];
+        assert_eq!(first.appraiser, APPRAISER);
+        assert_eq!(first.report_hash, [1; 32]);
+        assert_eq!(first.recorded_at, NOW);
+
+        for value in 1..=MAX_VALUATIONS as u128 {
+            assert_ok!(Registry::record_valuation(RuntimeOrigin::root(), asset_id, value, APPRAISER, [2; 32]));
+        }
+        // Full: the oldest record was dropped.
+        let history = Valuations::<Test>::get(asset_id);
+        assert_eq!(history.len() as u32, MAX_VALUATIONS);
+        assert_eq!(history[0].usd_value_cents, 1);
+        assert_eq!(history.last().unwrap().usd_value_cents, MAX_VALUATIONS as u128);
+        assert_eq!(Assets::<Test>::get(asset_id).unwrap().usd_value_cents, MAX_VALUATIONS as u128);
+    });
+}
+
+#[test]
+fn documents_are_attested_once_and_bounded() {
+    new_test_ext().execute_with(|| {
+        let asset_id = root_asset();
+        assert_ok!(Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Deed, [1; 32]));
+        System::assert_last_event(
+            Event::DocumentAttested { asset_id, kind: DocumentKind::Deed, doc_hash: [1; 32] }.into(),
+        );
+        assert_eq!(
+            Documents::<Test>::get(asset_id).to_vec(),
+            vec![DocumentAttestation { kind: DocumentKind::Deed, doc_hash: [1; 32], attested_at: NOW }]
+        );
+        assert_noop!(
+            Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Audit, [1; 32]),
+            Error::<Test>::DuplicateDocument
+        );
+
+        for n in 2..=MAX_DOCUMENTS as u8 {
+            assert_ok!(Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Insurance, [n; 32]));
+        }
+        assert_noop!(
+            Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Other, [0xff; 32]),
+            Error::<Test>::TooManyDocuments
+        );
+    });
+}
+
+#[test]
+fn retired_asset_cannot_be_listed_or_edited() {
+    new_test_ext().execute_with(|| {
+        let asset_id = root_asset();
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::root(), asset_id));
+        assert!(Registry::is_available_for_sale(asset_id));
+
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::root(), asset_id));
+        System::assert_last_event(Event::AssetRetired { asset_id }.into());
+        assert_eq!(RetiredAssets::<Test>::get(asset_id), Some(NOW));
+        assert!(Registry::is_retired(asset_id));
+        // Already locked, but no longer offered: the marketplace refuses the listing.
+        assert!(!Registry::is_available_for_sale(asset_id));
+
+        assert_noop!(
+            Registry::retire_asset(RuntimeOrigin::root(), asset_id),
+            Error::<Test>::AlreadyRetired
+        );
+        assert_noop!(
+            Registry::update_description(RuntimeOrigin::root(), asset_id, Default::default()),
+            Error::<Test>::AssetRetired
+        );
+        assert_noop!(
+            Registry::record_valuation(RuntimeOrigin::root(), asset_id, 1, APPRAISER, [0; 32]),
+            Error::<Test>::AssetRetired
+        );
+        assert_noop!(
+            Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Audit, [0; 32]),
+            Error::<Test>::AssetRetired
+        );
+        assert_noop!(
+            Registry::set_asset_eligibility(RuntimeOrigin::root(), asset_id, None),
+            Error::<Test>::AssetRetired
+        );
+
+        // Holders can still redeem: the custodian releases the lock.
+        assert_ok!(Registry::release_after_redemption(RuntimeOrigin::root(), asset_id));
+        assert_noop!(Registry::lock_for_sale(RuntimeOrigin::root(), asset_id), Error::<Test>::AssetRetired);
+    });
+}
+
+#[test]
+fn only_a_retired_unlocked_asset_is_burned() {
+    new_test_ext().execute_with(|| {
+        let asset_id = root_asset();
+        assert_ok!(Registry::record_valuation(RuntimeOrigin::root(), asset_id, 1, APPRAISER, [0; 32]));
+        assert_ok!(Registry::attest_document(RuntimeOrigin::root(), asset_id, DocumentKind::Deed, [1; 32]));
+        assert_noop!(Registry::burn_asset(RuntimeOrigin::root(), asset_id), Error::<Test>::NotRetired);
+
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::root(), asset_id));
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::root(), asset_id));
+        assert_noop!(Registry::burn_asset(RuntimeOrigin::root(), asset_id), Error::<Test>::StillLocked);
+
+        assert_ok!(Registry::release_after_redemption(RuntimeOrigin::root(), asset_id));
+        assert_noop!(Registry::burn_asset(RuntimeOrigin::signed(OWNER), asset_id), Error::<Test>::Unauthorized);
+        assert_ok!(Registry::burn_asset(RuntimeOrigin::root(), asset_id));
+        System::assert_last_event(Event::AssetBurned { asset_id }.into());
+        assert!(!Assets::<Test>::contains_key(asset_id));
+        assert!(Valuations::<Test>::get(asset_id).is_empty());
+        assert!(Documents::<Test>::get(asset_id).is_empty());
+        assert!(!RetiredAssets::<Test>::contains_key(asset_id));
+    });
+}
+
+#[test]
+fn register_asset_needs_admin_origin_and_units() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Registry::register_asset(
+                RuntimeOrigin::signed(OTHER),
+                OWNER,
+                Default::default(),
+                Default::default(),
+                AssetCategory::Fleet,
+                0,
+                Default::default(),
+                1,
+            ),
+            BadOrigin
+        );
+        assert_noop!(
+            Registry::register_asset(
+                RuntimeOrigin::root(),
+                OWNER,
+                Default::default(),
+                Default::default(),
+                AssetCategory::Fleet,
+                0,
+                Default::default(),
+                0,
+            ),
+            Error::<Test>::ZeroUnits
+        );
+    });
+}
+
+#[test]
+fn upgrade_makes_existing_assets_one_unit() {
+    new_test_ext().execute_with(|| {
+        let name: BoundedVec<u8, ConstU32<{ crate::MAX_NAME_LEN }>> = b"Barge".to_vec().try_into().unwrap();
+        let empty = BoundedVec::<u8, ConstU32<256>>::default();
+        // Pre-unit layout: asset_id, name, description, category, value,
+        // owner, is_locked, is_sold, metadata.
+        for (asset_id, is_sold) in [(0u32, false), (1, true)] {
+            let old = (asset_id, &name, &empty, AssetCategory::Fleet, 5_000u128, OWNER, true, is_sold, &empty);
+            frame_support::storage::unhashed::put(&Assets::<Test>::hashed_key_for(asset_id), &old);
+        }
+        StorageVersion::new(0).put::<Registry>();
+
+        Registry::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get::<Registry>(), 1);
+        let unsold = Assets::<Test>::get(0).unwrap();
+        assert_eq!((unsold.total_units, unsold.units_sold, unsold.is_sold), (1, 0, false));
+        assert_eq!(unsold.name, name);
+        assert_eq!(unsold.owner, OWNER);
+        let sold = Assets::<Test>::get(1).unwrap();
+        assert_eq!((sold.total_units, sold.units_sold, sold.is_sold), (1, 1, true));
+        assert_eq!(Registry::units_available(1), 0);
+    });
+}
+
+/// Onboards an issuer run by `ADMIN` / `CUSTODIAN`; returns its id.
+fn issuer() -> u32 {
+    let issuer_id = crate::NextIssuerId::<Test>::get();
+    assert_ok!(Registry::register_issuer(
+        RuntimeOrigin::signed(ADMIN),
+        b"Acme".to_vec().try_into().unwrap(),
+        b"Not investment advice".to_vec().try_into().unwrap(),
+        CUSTODIAN,
+    ));
+    issuer_id
+}
+
+/// Registers an asset under `issuer_id`.
+fn issuer_asset(issuer_id: u32) -> u32 {
+    let asset_id = crate::NextAssetId::<Test>::get();
+    assert_ok!(Registry::register_issuer_asset(
+        RuntimeOrigin::signed(ADMIN),
+        issuer_id,
+        OWNER,
+        b"Truck".to_vec().try_into().unwrap(),
+        Default::default(),
+        AssetCategory::Fleet,
+        50_000,
+        Default::default(),
+        1,
+    ));
+    asset_id
+}
+
+#[test]
+fn issuer_deposit_is_reserved_and_returned() {
+    new_test_ext().execute_with(|| {
+        let issuer_id = issuer();
+        System::assert_last_event(Event::IssuerRegistered { issuer_id, admin: ADMIN, deposit: 100 }.into());
+        assert_eq!(Balances::reserved_balance(ADMIN), 100);
+        assert_eq!(Balances::free_balance(ADMIN), 9_900);
+        let info = Issuers::<Test>::get(issuer_id).unwrap();
+        assert_eq!((info.admin, info.custodian, info.depositor), (ADMIN, CUSTODIAN, ADMIN));
+        assert_eq!(info.status, IssuerStatus::Active);
+
+        let asset_id = issuer_asset(issuer_id);
+        assert_eq!(AssetIssuer::<Test>::get(asset_id), Some(issuer_id));
+        assert_noop!(
+            Registry::deregister_issuer(RuntimeOrigin::signed(ADMIN), issuer_id),
+            Error::<Test>::IssuerHasAssets
+        );
+
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::signed(ADMIN), asset_id));
+        assert_ok!(Registry::burn_asset(RuntimeOrigin::signed(ADMIN), asset_id));
+        assert_eq!(IssuerAssetCount::<Test>::get(issuer_id), 0);
+        assert_noop!(
+            Registry::deregister_issuer(RuntimeOrigin::signed(CUSTODIAN), issuer_id),
+            Error::<Test>::Unauthorized
+        );
+        assert_ok!(Registry::deregister_issuer(RuntimeOrigin::signed(ADMIN), issuer_id));
+        System::assert_last_event(Event::IssuerDeregistered { issuer_id }.into());
+        assert_eq!(Balances::reserved_balance(ADMIN), 0);
+        assert_eq!(Balances::free_balance(ADMIN), 10_000);
+        assert!(!Issuers::<Test>::contains_key(issuer_id));
+    });
+}
+
+#[test]
+fn issuer_without_the_deposit_is_not_onboarded() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Registry::register_issuer(
+                RuntimeOrigin::signed(POOR),
+                Default::default(),
+                Default::default(),
+                CUSTODIAN,
+            ),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+        assert!(!Issuers::<Test>::contains_key(0));
+    });
+}
+
+#[test]
+fn issuer_roles_split_admin_and_custody() {
+    new_test_ext().execute_with(|| {
+        let issuer_id = issuer();
+        assert_noop!(
+            Registry::register_issuer_asset(
+                RuntimeOrigin::signed(CUSTODIAN),
+                issuer_id,
+                OWNER,
+                Default::default(),
+                Default::default(),
+                AssetCategory::Fleet,
+                0,
+                Default::default(),
+                1,
+            ),
+            Error::<Test>::Unauthorized
+        );
+        let asset_id = issuer_asset(issuer_id);
+
+        // Custody: the custodian, the admin and root.
+        let (custodian, admin) = (RuntimeOrigin::signed(CUSTODIAN), RuntimeOrigin::signed(ADMIN));
+        assert_ok!(Registry::attest_document(custodian, asset_id, DocumentKind::Deed, [1; 32]));
+        assert_ok!(Registry::attest_document(admin, asset_id, DocumentKind::Audit, [2; 32]));
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::signed(CUSTODIAN), asset_id));
+        assert_ok!(Registry::release_after_redemption(RuntimeOrigin::root(), asset_id));
+
+        // Admin: not the custodian.
+        assert_noop!(
+            Registry::update_description(RuntimeOrigin::signed(CUSTODIAN), asset_id, Default::default()),
+            Error::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Registry::retire_asset(RuntimeOrigin::signed(CUSTODIAN), asset_id),
+            Error::<Test>::Unauthorized
+        );
+        assert_ok!(Registry::update_description(RuntimeOrigin::signed(ADMIN), asset_id, Default::default()));
+
+        // Accounts of another issuer hold no role here.
+        assert_noop!(
+            Registry::lock_for_sale(RuntimeOrigin::signed(OTHER), asset_id),
+            Error::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Registry::set_issuer_info(
+                RuntimeOrigin::signed(CUSTODIAN),
+                issuer_id,
+                Default::default(),
+                Default::default(),
+            ),
+            Error::<Test>::Unauthorized
+        );
+    });
+}
+
+#[test]
+fn suspended_issuer_loses_its_roles() {
+    new_test_ext().execute_with(|| {
+        let issuer_id = issuer();
+        let asset_id = issuer_asset(issuer_id);
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::signed(CUSTODIAN), asset_id));
+
+        assert_noop!(Registry::suspend_issuer(RuntimeOrigin::signed(ADMIN), issuer_id), BadOrigin);
+        assert_ok!(Registry::suspend_issuer(RuntimeOrigin::root(), issuer_id));
+        System::assert_last_event(Event::IssuerSuspended { issuer_id }.into());
+        assert!(Registry::issuer_suspended(asset_id));
+        assert!(!Registry::is_available_for_sale(asset_id));
+        assert_noop!(
+            Registry::update_metadata(RuntimeOrigin::signed(ADMIN), asset_id, Default::default()),
+            Error::<Test>::IssuerSuspended
+        );
+        assert_noop!(
+            Registry::release_after_redemption(RuntimeOrigin::signed(CUSTODIAN), asset_id),
+            Error::<Test>::IssuerSuspended
+        );
+        assert_noop!(
+            Registry::deregister_issuer(RuntimeOrigin::signed(ADMIN), issuer_id),
+            Error::<Test>::IssuerSuspended
+        );
+        // Root still acts on the asset.
+        assert_ok!(Registry::update_metadata(RuntimeOrigin::root(), asset_id, Default::default()));
+
+        assert_ok!(Registry::resume_issuer(RuntimeOrigin::root(), issuer_id));
+        assert_noop!(
+            Registry::resume_issuer(RuntimeOrigin::root(), issuer_id),
+            Error::<Test>::IssuerNotSuspended
+        );
+        assert!(Registry::is_available_for_sale(asset_id));
+        assert_ok!(Registry::update_metadata(RuntimeOrigin::signed(ADMIN), asset_id, Default::default()));
+    });
+}
+
+#[test]
+fn handed_over_roles_keep_the_deposit_with_the_depositor() {
+    new_test_ext().execute_with(|| {
+        let issuer_id = issuer();
+        let asset_id = issuer_asset(issuer_id);
+        assert_ok!(Registry::set_issuer_accounts(RuntimeOrigin::signed(ADMIN), issuer_id, NEW_ADMIN, NEW_ADMIN));
+        System::assert_last_event(
+            Event::IssuerAccountsUpdated { issuer_id, admin: NEW_ADMIN, custodian: NEW_ADMIN }.into(),
+        );
+        assert_noop!(
+            Registry::update_description(RuntimeOrigin::signed(ADMIN), asset_id, Default::default()),
+            Error::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Registry::lock_for_sale(RuntimeOrigin::signed(CUSTODIAN), asset_id),
+            Error::<Test>::Unauthorized
+        );
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::signed(NEW_ADMIN), asset_id));
+
+        assert_ok!(Registry::release_after_redemption(RuntimeOrigin::signed(NEW_ADMIN), asset_id));
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::signed(NEW_ADMIN), asset_id));
+        assert_ok!(Registry::burn_asset(RuntimeOrigin::signed(NEW_ADMIN), asset_id));
+        assert_ok!(Registry::deregister_issuer(RuntimeOrigin::signed(NEW_ADMIN), issuer_id));
+        assert_eq!(Balances::reserved_balance(ADMIN), 0);
+        assert_eq!(Balances::free_balance(ADMIN), 10_000);
+    });
+}
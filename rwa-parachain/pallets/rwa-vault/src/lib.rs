@@ -0,0 +1,175 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! RWA Vault pallet.
+//!
+//! Holds native funds backing each asset of `pallet-rwa-registry` in an account
+//! derived from `VaultPalletId` and the asset id. Every vault keeps two pots:
+//! - `Collateral` — posted by the issuer while the asset is offered;
+//! - `Proceeds`   — sale proceeds credited against the asset.
+//!
+//! Any signed account may pay into an asset's vault. Nothing on this chain
+//! credits a pot automatically: ProofHub sales are paid into the ProofHub
+//! pool and never reach this chain, so the issuer deposits proceeds itself. Withdrawals need
+//! `AdminOrigin` or the admin account of the asset's issuer, and are refused
+//! while `pallet_rwa_registry::Pallet::is_locked` reports the asset as locked,
+//! i.e. while a note-holder may still redeem it. The lock state is read from
+//! the registry on every withdrawal, so the two pallets never need syncing.
+//! In turn the registry asks the vault (`AssetFunds`) before burning an asset
+//! and refuses while either pot holds funds.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::fungible::{Inspect, Mutate};
+    use frame_support::traits::tokens::{Balance, Preservation};
+    use frame_support::PalletId;
+    use frame_system::pallet_prelude::*;
+    use pallet_rwa_registry::pallet as registry;
+    use registry::IssuerRole;
+    use codec::DecodeWithMemTracking;
+    use sp_runtime::traits::{AccountIdConversion, CheckedAdd, Saturating, Zero};
+
+    /// Pot of an asset vault.
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, DecodeWithMemTracking, TypeInfo, MaxEncodedLen)]
+    pub enum VaultPot {
+        /// Collateral posted by the issuer.
+        Collateral,
+        /// Sale proceeds credited against the asset.
+        Proceeds,
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + registry::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Native currency held in the vaults.
+        type NativeBalance: Mutate<Self::AccountId, Balance = Self::NativeBalanceAmount>;
+        /// Balance type of `NativeBalance`.
+        type NativeBalanceAmount: Balance;
+        /// Seed of the per-asset vault accounts.
+        #[pallet::constant]
+        type VaultPalletId: Get<PalletId>;
+    }
+
+    /// (asset_id, pot) → amount held.
+    #[pallet::storage]
+    pub type VaultBalances<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u32,
+        Blake2_128Concat,
+        VaultPot,
+        T::NativeBalanceAmount,
+        ValueQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        Deposited { asset_id: u32, pot: VaultPot, who: T::AccountId, amount: T::NativeBalanceAmount },
+        Withdrawn { asset_id: u32, pot: VaultPot, beneficiary: T::AccountId, amount: T::NativeBalanceAmount },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        AssetNotFound,
+        /// The asset is locked; its vault cannot be drawn down.
+        AssetLocked,
+        ZeroAmount,
+        /// The pot holds less than the amount requested.
+        InsufficientVaultBalance,
+        /// The withdrawal would leave the vault account below the existential deposit.
+        BelowMinimum,
+        Overflow,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Pay `amount` into a pot of an asset's vault (any signed account).
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(0)]
+        pub fn deposit(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            pot: VaultPot,
+            amount: T::NativeBalanceAmount,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(registry::Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
+            Self::do_deposit(&who, asset_id, pot, amount)
+        }
+
+        /// Pay `amount` out of a pot of an asset's vault to `beneficiary`
+        /// (Admin or the issuer admin). Refused while the asset is locked.
+        #[pallet::weight(10_000)]
+        #[pallet::call_index(1)]
+        pub fn withdraw(
+            origin: OriginFor<T>,
+            asset_id: u32,
+            pot: VaultPot,
+            amount: T::NativeBalanceAmount,
+            beneficiary: T::AccountId,
+        ) -> DispatchResult {
+            registry::Pallet::<T>::ensure_asset_role(origin, asset_id, IssuerRole::Admin)?;
+            ensure!(!registry::Pallet::<T>::is_locked(asset_id), Error::<T>::AssetLocked);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            let held = VaultBalances::<T>::get(asset_id, pot);
+            ensure!(held >= amount, Error::<T>::InsufficientVaultBalance);
+            let vault = Self::vault_account(asset_id);
+            let remaining = T::NativeBalance::balance(&vault).saturating_sub(amount);
+            ensure!(
+                remaining.is_zero() || remaining >= T::NativeBalance::minimum_balance(),
+                Error::<T>::BelowMinimum
+            );
+
+            T::NativeBalance::transfer(&vault, &beneficiary, amount, Preservation::Expendable)?;
+            VaultBalances::<T>::insert(asset_id, pot, held - amount);
+            Self::deposit_event(Event::Withdrawn { asset_id, pot, beneficiary, amount });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Vault account of `asset_id`.
+        pub fn vault_account(asset_id: u32) -> T::AccountId {
+            T::VaultPalletId::get().into_sub_account_truncating(asset_id)
+        }
+
+        /// Amount held in both pots of an asset's vault.
+        pub fn total_held(asset_id: u32) -> T::NativeBalanceAmount {
+            VaultBalances::<T>::get(asset_id, VaultPot::Collateral)
+                .saturating_add(VaultBalances::<T>::get(asset_id, VaultPot::Proceeds))
+        }
+
+        /// Move `amount` from `who` into a pot of an asset's vault.
+        fn do_deposit(
+            who: &T::AccountId,
+            asset_id: u32,
+            pot: VaultPot,
+            amount: T::NativeBalanceAmount,
+        ) -> DispatchResult {
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            let held = VaultBalances::<T>::get(asset_id, pot)
+                .checked_add(&amount)
+                .ok_or(Error::<T>::Overflow)?;
+            T::NativeBalance::transfer(who, &Self::vault_account(asset_id), amount, Preservation::Preserve)?;
+            VaultBalances::<T>::insert(asset_id, pot, held);
+            Self::deposit_event(Event::Deposited { asset_id, pot, who: who.clone(), amount });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> registry::AssetFunds for Pallet<T> {
+        fn holds_funds(asset_id: u32) -> bool {
+            !Self::total_held(asset_id).is_zero()
+        }
+    }
+}
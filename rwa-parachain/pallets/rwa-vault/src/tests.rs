@@ -0,0 +1,261 @@
+use crate as pallet_rwa_vault;
+use crate::{Error, Event, VaultBalances, VaultPot};
+use frame_support::{
+    assert_noop, assert_ok, derive_impl, parameter_types,
+    traits::{ConstU64, UnixTime},
+    PalletId,
+};
+use pallet_rwa_registry::{AssetCategory, Error as RegistryError};
+use sp_runtime::{traits::BadOrigin, BuildStorage};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Registry: pallet_rwa_registry,
+        Vault: pallet_rwa_vault,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type ExistentialDeposit = ConstU64<10>;
+}
+
+pub struct MockTime;
+impl UnixTime for MockTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(1_700_000_000)
+    }
+}
+
+impl pallet_rwa_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type AdminOrigin = frame_system::EnsureRoot<u64>;
+    type Time = MockTime;
+    type Currency = Balances;
+    type IssuerDeposit = ConstU64<100>;
+    type EligibilityDispatch = ();
+    type AssetFunds = Vault;
+}
+
+parameter_types! {
+    pub const VaultPalletId: PalletId = PalletId(*b"rwa/vllt");
+}
+
+impl pallet_rwa_vault::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type NativeBalance = Balances;
+    type NativeBalanceAmount = u64;
+    type VaultPalletId = VaultPalletId;
+}
+
+const ADMIN: u64 = 1;
+const CUSTODIAN: u64 = 2;
+const OTHER: u64 = 3;
+const BENEFICIARY: u64 = 4;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ADMIN, 10_000), (CUSTODIAN, 10_000), (OTHER, 10_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Onboards an issuer run by `ADMIN` / `CUSTODIAN` and registers one asset.
+fn issuer_asset() -> u32 {
+    assert_ok!(Registry::register_issuer(
+        RuntimeOrigin::signed(ADMIN),
+        b"Acme".to_vec().try_into().unwrap(),
+        b"Not investment advice".to_vec().try_into().unwrap(),
+        CUSTODIAN,
+    ));
+    let asset_id = pallet_rwa_registry::NextAssetId::<Test>::get();
+    assert_ok!(Registry::register_issuer_asset(
+        RuntimeOrigin::signed(ADMIN),
+        0,
+        ADMIN,
+        b"Warehouse".to_vec().try_into().unwrap(),
+        b"Logistics warehouse".to_vec().try_into().unwrap(),
+        AssetCategory::RealEstate,
+        1_000_000,
+        Default::default(),
+        1,
+    ));
+    asset_id
+}
+
+#[test]
+fn deposit_moves_funds_into_the_asset_vault() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 500));
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(OTHER), asset_id, VaultPot::Proceeds, 200));
+
+        let vault = Vault::vault_account(asset_id);
+        assert_eq!(Balances::free_balance(vault), 700);
+        assert_eq!(VaultBalances::<Test>::get(asset_id, VaultPot::Collateral), 500);
+        assert_eq!(VaultBalances::<Test>::get(asset_id, VaultPot::Proceeds), 200);
+        assert_eq!(Vault::total_held(asset_id), 700);
+        System::assert_last_event(
+            Event::Deposited { asset_id, pot: VaultPot::Proceeds, who: OTHER, amount: 200 }.into(),
+        );
+    });
+}
+
+#[test]
+fn vault_accounts_are_per_asset() {
+    new_test_ext().execute_with(|| {
+        let a = issuer_asset();
+        let b = issuer_asset();
+        assert_ne!(Vault::vault_account(a), Vault::vault_account(b));
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), a, VaultPot::Collateral, 500));
+        assert_eq!(Balances::free_balance(Vault::vault_account(b)), 0);
+        assert_eq!(Vault::total_held(b), 0);
+    });
+}
+
+#[test]
+fn deposit_rejects_unknown_asset_and_zero_amount() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Vault::deposit(RuntimeOrigin::signed(ADMIN), 7, VaultPot::Collateral, 500),
+            Error::<Test>::AssetNotFound
+        );
+        let asset_id = issuer_asset();
+        assert_noop!(
+            Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 0),
+            Error::<Test>::ZeroAmount
+        );
+        assert_noop!(
+            Vault::deposit(RuntimeOrigin::root(), asset_id, VaultPot::Collateral, 500),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn issuer_admin_withdraws_while_unlocked() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 500));
+
+        assert_ok!(Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 300, BENEFICIARY));
+        assert_eq!(Balances::free_balance(BENEFICIARY), 300);
+        assert_eq!(VaultBalances::<Test>::get(asset_id, VaultPot::Collateral), 200);
+        System::assert_last_event(
+            Event::Withdrawn { asset_id, pot: VaultPot::Collateral, beneficiary: BENEFICIARY, amount: 300 }.into(),
+        );
+
+        // Admin origin may drain the vault completely.
+        assert_ok!(Vault::withdraw(RuntimeOrigin::root(), asset_id, VaultPot::Collateral, 200, BENEFICIARY));
+        assert_eq!(Balances::free_balance(Vault::vault_account(asset_id)), 0);
+        assert_eq!(Vault::total_held(asset_id), 0);
+    });
+}
+
+#[test]
+fn withdraw_is_refused_while_the_asset_is_locked() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 500));
+        assert_ok!(Registry::lock_for_sale(RuntimeOrigin::signed(CUSTODIAN), asset_id));
+
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 500, BENEFICIARY),
+            Error::<Test>::AssetLocked
+        );
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::root(), asset_id, VaultPot::Proceeds, 500, BENEFICIARY),
+            Error::<Test>::AssetLocked
+        );
+
+        assert_ok!(Registry::release_after_redemption(RuntimeOrigin::signed(CUSTODIAN), asset_id));
+        assert_ok!(Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 500, BENEFICIARY));
+        assert_eq!(Balances::free_balance(BENEFICIARY), 500);
+    });
+}
+
+#[test]
+fn only_the_issuer_admin_withdraws() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(OTHER), asset_id, VaultPot::Collateral, 500));
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(OTHER), asset_id, VaultPot::Collateral, 500, OTHER),
+            RegistryError::<Test>::Unauthorized
+        );
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(CUSTODIAN), asset_id, VaultPot::Collateral, 500, CUSTODIAN),
+            RegistryError::<Test>::Unauthorized
+        );
+    });
+}
+
+#[test]
+fn withdraw_is_bounded_by_the_pot() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 500));
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 100));
+
+        // The vault account holds 600, but the proceeds pot only 100.
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 150, BENEFICIARY),
+            Error::<Test>::InsufficientVaultBalance
+        );
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 0, BENEFICIARY),
+            Error::<Test>::ZeroAmount
+        );
+    });
+}
+
+#[test]
+fn withdraw_never_leaves_dust_in_the_vault() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 500));
+        // 495 would leave 5 behind, below the existential deposit of 10.
+        assert_noop!(
+            Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 495, BENEFICIARY),
+            Error::<Test>::BelowMinimum
+        );
+        assert_ok!(Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Collateral, 490, BENEFICIARY));
+        assert_eq!(Balances::free_balance(Vault::vault_account(asset_id)), 10);
+    });
+}
+
+#[test]
+fn asset_is_not_burned_while_its_vault_holds_funds() {
+    new_test_ext().execute_with(|| {
+        let asset_id = issuer_asset();
+        assert_ok!(Vault::deposit(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 500));
+        assert_ok!(Registry::retire_asset(RuntimeOrigin::signed(ADMIN), asset_id));
+        assert_noop!(
+            Registry::burn_asset(RuntimeOrigin::signed(ADMIN), asset_id),
+            RegistryError::<Test>::FundsStillHeld
+        );
+
+        // Once drawn down, the asset burns and its vault stays empty.
+        assert_ok!(Vault::withdraw(RuntimeOrigin::signed(ADMIN), asset_id, VaultPot::Proceeds, 500, BENEFICIARY));
+        assert_ok!(Registry::burn_asset(RuntimeOrigin::signed(ADMIN), asset_id));
+        assert!(!pallet_rwa_registry::Assets::<Test>::contains_key(asset_id));
+        assert_eq!(Balances::free_balance(BENEFICIARY), 500);
+    });
+}
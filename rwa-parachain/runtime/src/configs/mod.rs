@@ -35,9 +35,10 @@ use super::{
     weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
     AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook,
     Hash, MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
-    RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-    System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, EXISTENTIAL_DEPOSIT,
-    HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+    RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, RwaVault, Session,
+    SessionKeys, System, Timestamp, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS,
+    EXISTENTIAL_DEPOSIT, HOURS, MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO,
+    SLOT_DURATION, UNIT, VERSION,
 };
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -327,6 +328,16 @@ impl pallet_xcm::Config for Runtime {
 impl pallet_rwa_registry::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type AdminOrigin = EnsureRoot<AccountId>;
+    type Time = Timestamp;
+    type Currency = Balances;
+    type IssuerDeposit = IssuerDeposit;
+    type EligibilityDispatch = LaneEligibilityXcmDispatch;
+    type AssetFunds = RwaVault;
+}
+
+parameter_types! {
+    /// Reserved from an issuer's depositor while the issuer is registered.
+    pub const IssuerDeposit: Balance = 1_000 * UNIT;
 }
 
 parameter_types! {
@@ -355,4 +366,158 @@ parameter_types! {
 impl pallet_rwa_marketplace::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type ProofHubSovereign = ProofHubSovereign;
+    type EscrowDispatch = ProofHubEscrowXcmDispatch;
+    type PriceDispatch = LanePriceXcmDispatch;
+}
+
+/// XCM dispatcher: sends a `Transact` to the ProofHub parachain (para 2000)
+/// resolving an escrowed v2 purchase.
+///
+/// Encodes: pallet_index=51 (Proofs), call_index=20 (`xcm_confirm_escrow`)
+/// or 21 (`xcm_cancel_escrow`), followed by the SCALE `tx_id`. ProofHub's
+/// proofs pallet is in dev_mode, so these follow its call declaration order.
+/// The origin arriving at ProofHub is this chain's sovereign account.
+///
+/// Delivery errors are logged at DEBUG and swallowed — ProofHub refunds any
+/// escrow still pending at its deadline.
+pub struct ProofHubEscrowXcmDispatch;
+impl ProofHubEscrowXcmDispatch {
+    fn send(call_index: u8, tx_id: [u8; 16]) {
+        use codec::Encode;
+        use xcm::latest::prelude::*;
+
+        let mut call_data = alloc::vec::Vec::new();
+        call_data.push(51u8); // Proofs pallet index on ProofHub
+        call_data.push(call_index);
+        tx_id.encode_to(&mut call_data);
+
+        let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2000)]));
+        let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
+            Instruction::<()>::UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None },
+            Instruction::<()>::Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                fallback_max_weight: Some(Weight::from_parts(500_000_000, 64 * 1024)),
+                call: call_data.into(),
+            },
+        ]);
+
+        let mut dest_opt = Some(dest);
+        let mut msg_opt = Some(xcm_msg);
+        match xcm_config::XcmRouter::validate(&mut dest_opt, &mut msg_opt) {
+            Ok((ticket, _)) => {
+                if let Err(e) = xcm_config::XcmRouter::deliver(ticket) {
+                    log::debug!(target: "rwa::xcm", "ProofHub escrow XCM deliver failed: {:?}", e);
+                }
+            }
+            Err(e) => {
+                log::debug!(target: "rwa::xcm", "ProofHub escrow XCM validate failed: {:?}", e);
+            }
+        }
+    }
+}
+
+impl pallet_rwa_marketplace::ProofHubEscrowDispatch for ProofHubEscrowXcmDispatch {
+    fn confirm(tx_id: [u8; 16]) {
+        Self::send(20, tx_id);
+    }
+    fn cancel(tx_id: [u8; 16]) {
+        Self::send(21, tx_id);
+    }
+}
+
+/// XCM dispatcher: sends a `Transact` carrying
+/// `xcm_sync_rwa_eligibility(rwa_id, requirement)` to both lanes.
+///
+/// ProofHub (para 2000) enforces credential presentations; ScanProof
+/// (para 2002) refuses restricted assets. Both proofs pallets are in
+/// dev_mode, so the call indices follow declaration order.
+///
+/// Delivery errors are logged at DEBUG and swallowed.
+pub struct LaneEligibilityXcmDispatch;
+impl LaneEligibilityXcmDispatch {
+    /// (para_id, pallet_index, call_index) of `xcm_sync_rwa_eligibility`.
+    const LANES: [(u32, u8, u8); 2] = [(2000, 51, 27), (2002, 51, 15)];
+}
+
+impl pallet_rwa_registry::EligibilityDispatch for LaneEligibilityXcmDispatch {
+    fn send(asset_id: u32, requirement: Option<pallet_rwa_registry::EligibilityRequirement>) {
+        use codec::Encode;
+        use xcm::latest::prelude::*;
+
+        let mut rwa_id = [0u8; 32];
+        rwa_id[..4].copy_from_slice(&asset_id.to_le_bytes());
+
+        for (para_id, pallet_index, call_index) in Self::LANES {
+            let mut call_data = alloc::vec::Vec::new();
+            call_data.push(pallet_index);
+            call_data.push(call_index);
+            rwa_id.encode_to(&mut call_data);
+            requirement.encode_to(&mut call_data);
+
+            let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(para_id)]));
+            let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
+                Instruction::<()>::UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None },
+                Instruction::<()>::Transact {
+                    origin_kind: OriginKind::SovereignAccount,
+                    fallback_max_weight: Some(Weight::from_parts(500_000_000, 64 * 1024)),
+                    call: call_data.into(),
+                },
+            ]);
+
+            let mut dest_opt = Some(dest);
+            let mut msg_opt = Some(xcm_msg);
+            match xcm_config::XcmRouter::validate(&mut dest_opt, &mut msg_opt) {
+                Ok((ticket, _)) => {
+                    if let Err(e) = xcm_config::XcmRouter::deliver(ticket) {
+                        log::debug!(target: "rwa::xcm", "Eligibility XCM deliver to {} failed: {:?}", para_id, e);
+                    }
+                }
+                Err(e) => {
+                    log::debug!(target: "rwa::xcm", "Eligibility XCM validate for {} failed: {:?}", para_id, e);
+                }
+            }
+        }
+    }
+}
+
+/// XCM dispatcher: sends a `Transact` carrying `xcm_sync_rwa_price(rwa_id, price)`
+/// to a lane in `PriceSyncLanes`, at the pallet/call index stored for that lane
+/// (ProofHub: 51/23, ScanProof: 51/13).
+///
+/// Delivery errors are logged at DEBUG and swallowed.
+pub struct LanePriceXcmDispatch;
+impl pallet_rwa_marketplace::LanePriceDispatch for LanePriceXcmDispatch {
+    fn send(lane: &pallet_rwa_marketplace::PriceSyncLane, rwa_id: [u8; 32], price: u64) {
+        use codec::Encode;
+        use xcm::latest::prelude::*;
+
+        let mut call_data = alloc::vec::Vec::new();
+        call_data.push(lane.pallet_index);
+        call_data.push(lane.call_index);
+        rwa_id.encode_to(&mut call_data);
+        price.encode_to(&mut call_data);
+
+        let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(lane.para_id)]));
+        let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
+            Instruction::<()>::UnpaidExecution { weight_limit: WeightLimit::Unlimited, check_origin: None },
+            Instruction::<()>::Transact {
+                origin_kind: OriginKind::SovereignAccount,
+                fallback_max_weight: Some(Weight::from_parts(500_000_000, 64 * 1024)),
+                call: call_data.into(),
+            },
+        ]);
+
+        let mut dest_opt = Some(dest);
+        let mut msg_opt = Some(xcm_msg);
+        match xcm_config::XcmRouter::validate(&mut dest_opt, &mut msg_opt) {
+            Ok((ticket, _)) => {
+                if let Err(e) = xcm_config::XcmRouter::deliver(ticket) {
+                    log::debug!(target: "rwa::xcm", "Lane price XCM deliver failed: {:?}", e);
+                }
+            }
+            Err(e) => {
+                log::debug!(target: "rwa::xcm", "Lane price XCM validate failed: {:?}", e);
+            }
+        }
+    }
 }
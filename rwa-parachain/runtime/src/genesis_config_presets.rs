@@ -57,6 +57,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1_000,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Berlin,DE\",\"sqm\":4200,\"floors\":18}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -71,6 +73,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1_000,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Prague,CZ\",\"units\":48}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -85,6 +89,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1_000,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Rotterdam,NL\",\"sqm\":12000}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -99,6 +105,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1_000,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Madrid,ES\",\"tenants\":120}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -113,6 +121,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1_000,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Lisbon,PT\",\"rooms\":85,\"stars\":4}", ConstU32::<{ MAX_META_LEN }>),
         },
         // ── Fleet (5) ────────────────────────────────────────────────
@@ -128,6 +138,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 100,
+            units_sold: 0,
             metadata: bvec!("{\"imo\":\"9812345\",\"dwt\":76000,\"flag\":\"MT\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -142,6 +154,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 100,
+            units_sold: 0,
             metadata: bvec!("{\"make\":\"Mercedes-Benz\",\"model\":\"Actros\",\"count\":50,\"year\":2021}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -156,6 +170,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 100,
+            units_sold: 0,
             metadata: bvec!("{\"make\":\"Caterpillar\",\"model\":\"336\",\"count\":6}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -170,6 +186,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 100,
+            units_sold: 0,
             metadata: bvec!("{\"location\":\"Andalusia,ES\",\"capacity_mw\":100,\"type\":\"PV\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -184,6 +202,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 100,
+            units_sold: 0,
             metadata: bvec!("{\"make\":\"John Deere\",\"type\":\"combine\",\"count\":12}", ConstU32::<{ MAX_META_LEN }>),
         },
         // ── Financial Instruments (5) ─────────────────────────────────
@@ -199,6 +219,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"batch\":\"A\",\"borrowers\":10,\"maturity_days\":90}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -213,6 +235,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"suppliers\":25,\"avg_days\":60,\"region\":\"EU\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -227,6 +251,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"isin\":\"XS0000000001\",\"coupon\":\"5%\",\"maturity\":\"2026\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -241,6 +267,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"type\":\"revenue_share\",\"road\":\"A8\",\"years\":15}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -255,6 +283,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"type\":\"export_credit\",\"eca\":\"EKF\",\"tenor_months\":24}", ConstU32::<{ MAX_META_LEN }>),
         },
         // ── IP & Commodities (5) ──────────────────────────────────────
@@ -270,6 +300,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"patents\":12,\"domain\":\"mRNA\",\"licensees\":3}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -284,6 +316,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"standard\":\"VCS\",\"tco2\":50000,\"origin\":\"BR\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -298,6 +332,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"purity\":\"99.99%\",\"weight_kg\":100,\"vault\":\"Zurich\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -312,6 +348,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"products\":3,\"seats\":800,\"type\":\"perpetual\"}", ConstU32::<{ MAX_META_LEN }>),
         },
         RWAAsset {
@@ -326,6 +364,8 @@ fn demo_assets(admin: AccountId) -> Vec<RWAAsset<AccountId>> {
             owner: admin.clone(),
             is_locked: true,
             is_sold: false,
+            total_units: 1,
+            units_sold: 0,
             metadata: bvec!("{\"pieces\":8,\"insured\":true,\"provenance\":\"verified\"}", ConstU32::<{ MAX_META_LEN }>),
         },
     ]
@@ -0,0 +1,50 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API for wallets to query ScanProof privacy-lane state.
+//!
+//! A private transfer anchors to a Merkle root. A wallet that builds a proof
+//! against an older root checks it with `root_acceptable` (or against
+//! `oldest_acceptable_root`) first: roots recorded before the retention window
+//! have expired. `dry_run` runs an unsigned lane call on the block's state and
+//! returns the pallet error it would fail with, without submitting it.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::Codec;
+pub use pallet_proofs::{AppConfig, CoinGroup, RwaListing};
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	pub trait PrivacyLaneApi<BlockNumber, LaneCall> where BlockNumber: Codec, LaneCall: Codec {
+		/// Current Merkle root.
+		fn current_root() -> [u8; 32];
+		/// Number of leaves in the Merkle tree.
+		fn leaf_count() -> u32;
+		/// Roots recorded within the retention window, oldest first, as
+		/// `(block, root)`.
+		fn recent_roots() -> Vec<(BlockNumber, [u8; 32])>;
+		/// Oldest root a proof may still anchor to.
+		fn oldest_acceptable_root() -> [u8; 32];
+		/// Whether a proof may anchor to `root` now.
+		fn root_acceptable(root: [u8; 32]) -> bool;
+		/// Index of a commitment in the Merkle tree.
+		fn leaf_index(commitment: [u8; 32]) -> Option<u32>;
+		/// `(group_id, index)` of a deposited coin.
+		fn coin_location(coin: [u8; 32]) -> Option<(u32, u32)>;
+		/// Whether a private-transfer nullifier has been used.
+		fn nullifier_used(nullifier: [u8; 32]) -> bool;
+		/// Whether a coin serial has been spent.
+		fn serial_used(serial: [u8; 32]) -> bool;
+		/// The group currently accepting deposits.
+		fn current_group() -> u32;
+		/// Coins of a group and the padded set spends range over.
+		fn coin_group(group_id: u32) -> Option<CoinGroup>;
+		/// Prices, supply and eligibility of an RWA on this lane.
+		fn rwa_listing(rwa_id: [u8; 32]) -> RwaListing;
+		/// Access-key config of an app.
+		fn access_config(app_id: [u8; 32]) -> Option<AppConfig>;
+		/// Dispatch `call` unsigned and roll it back; returns the pallet error.
+		fn dry_run(call: LaneCall) -> Result<(), DispatchError>;
+	}
+}
@@ -11,7 +11,8 @@ use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
 use sp_io::hashing::blake2_256;
 use sp_runtime::transaction_validity::{
-	InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+	InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+	ValidTransaction, ValidTransactionBuilder,
 };
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
@@ -41,6 +42,9 @@ pub trait ProofVerify {
 		public_inputs: &[u8],
 		nbits: u32,
 	) -> bool;
+	/// Batched range verification over `(range_proof, commitments, public_inputs)`
+	/// items sharing one generator table; true only if every item verifies.
+	fn verify_range_proofs(items: &[(&[u8], &[[u8; 32]], &[u8])], nbits: u32) -> bool;
 	/// Ristretto point subtraction: returns compressed(C_a - C_b), or None if
 	/// either point is not a valid compressed Ristretto point.
 	fn pedersen_subtract(c_a: &[u8; 32], c_b: &[u8; 32]) -> Option<[u8; 32]>;
@@ -79,13 +83,14 @@ pub trait RwaPurchaseDispatch {
 		note_value: u64,
 		tx_id: [u8; 16],
 		ownership_commitment: [u8; 32],
+		units: u64,
 	);
 }
 
 /// No-op implementation used when XCM is not wired (e.g. tests).
 pub struct NoopRwaDispatch;
 impl RwaPurchaseDispatch for NoopRwaDispatch {
-	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 32], _: u64, _: [u8; 16], _: [u8; 32]) {}
+	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 32], _: u64, _: [u8; 16], _: [u8; 32], _: u64) {}
 }
 
 /// Trait implemented by the runtime to send an XCM `Transact` to the AuthGate
@@ -100,24 +105,121 @@ pub trait AccessKeyDispatch {
 		nullifier: [u8; 32],
 		tx_id: [u8; 16],
 		access_key_commitment: [u8; 32],
+		terms: Option<AccessTerms>,
 	);
 }
 
 /// No-op implementation used when XCM is not wired (e.g. tests).
 pub struct NoopAccessDispatch;
 impl AccessKeyDispatch for NoopAccessDispatch {
-	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 16], _: [u8; 32]) {}
+	fn send(_: [u8; 32], _: [u8; 32], _: [u8; 16], _: [u8; 32], _: Option<AccessTerms>) {}
+}
+
+/// NULLA/USD conversion for RWAs priced in USD cents, implemented by the
+/// runtime over `pallet_price_oracle`.
+pub trait FiatRate {
+	/// Planck per USD cent in oracle round `round`, or `None` when the round
+	/// is unknown or no longer usable.
+	fn planck_per_cent(round: u32) -> Option<u64>;
+}
+
+/// No oracle wired: USD-priced RWAs cannot be bought.
+pub struct NoFiatRate;
+impl FiatRate for NoFiatRate {
+	fn planck_per_cent(_: u32) -> Option<u64> { None }
+}
+
+/// Buyer eligibility for an RWA, configured on the RWA chain registry and
+/// synced here by `xcm_sync_rwa_eligibility`. Same SCALE layout as ProofHub's.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, codec::MaxEncodedLen)]
+pub struct EligibilityRequirement {
+	/// Credential registry on ProofHub whose tree buyers prove membership in.
+	pub registry_id: u32,
+	/// Eligibility flags the credential's class must contain.
+	pub required_class: u64,
+}
+
+/// Maximum number of subscription plans per access-key app.
+pub const MAX_ACCESS_PLANS: u32 = 8;
+
+/// A subscription plan of a Web2 app. Same SCALE layout on both lanes.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, codec::MaxEncodedLen)]
+pub struct AccessPlan {
+	/// Price in planck the spend must cover. Zero withdraws the plan from sale.
+	pub price: u64,
+	/// Key lifetime in AuthGate blocks, counted from activation.
+	pub duration: u32,
+	/// App-defined service tier.
+	pub tier: u8,
+	/// Uses the key allows; `0` = unlimited.
+	pub max_uses: u32,
+}
+
+impl AccessPlan {
+	pub fn terms(&self) -> AccessTerms {
+		AccessTerms { duration: self.duration, tier: self.tier, max_uses: self.max_uses }
+	}
+}
+
+/// Plan terms relayed to AuthGate with a grant; `pallet_access_keys::AccessTerms`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct AccessTerms {
+	pub duration: u32,
+	pub tier: u8,
+	pub max_uses: u32,
 }
 
 /// Configuration for a registered Web2 app on ProofHub.
-/// Set by sudo via `set_access_config`. The `payment_account` receives the
-/// note value when a user successfully purchases an access key.
+/// Set by sudo via `set_access_config` and `set_access_plans`. The
+/// `payment_account` receives the note value when a user successfully
+/// purchases an access key.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, codec::MaxEncodedLen)]
 pub struct AppConfig {
-	/// Price in planck that the Pedersen / Lelantus note must equal.
+	/// Price in planck of a plan-less key that the Pedersen / Lelantus note
+	/// must equal. Zero stops plan-less sales.
 	pub price: u64,
 	/// AccountId (raw 32 bytes) on ProofHub that receives the payment.
 	pub payment_account: [u8; 32],
+	/// Subscription plans, bought by index. Set via `set_access_plans`.
+	pub plans: BoundedVec<AccessPlan, ConstU32<MAX_ACCESS_PLANS>>,
+}
+
+impl AppConfig {
+	/// Price and AuthGate terms of a purchase: `None` buys a plan-less key at
+	/// `price`, `Some(i)` buys `plans[i]`. `None` when not for sale.
+	pub fn quote(&self, plan: Option<u8>) -> Option<(u64, Option<AccessTerms>)> {
+		match plan {
+			None => (self.price > 0).then_some((self.price, None)),
+			Some(i) => self
+				.plans
+				.get(i as usize)
+				.filter(|p| p.price > 0)
+				.map(|p| (p.price, Some(p.terms()))),
+		}
+	}
+}
+
+/// An RWA's terms on this lane, as `PrivacyLaneApi::rwa_listing` returns
+/// them. Same SCALE layout on both lanes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct RwaListing {
+	/// Price per unit in planck (`RwaPrices`).
+	pub price: u64,
+	/// Price per unit in USD cents (`RwaUsdPrices`); non-zero overrides `price`.
+	pub usd_cents: u64,
+	/// Units still for sale here, when the lane tracks supply.
+	pub units: Option<u64>,
+	/// Credential buyers must present, when restricted.
+	pub eligibility: Option<EligibilityRequirement>,
+}
+
+/// A coin group as `PrivacyLaneApi::coin_group` returns it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct CoinGroup {
+	/// Coins deposited into the group, in insertion order.
+	pub coins: Vec<[u8; 32]>,
+	/// The set a spend proof ranges over (`ProofVerifier::pad_group`).
+	pub padded: Vec<[u8; 32]>,
 }
 
 /// Phase 10: public inputs for a v2 one-of-many RWA purchase.
@@ -129,6 +231,16 @@ pub struct CoinSpendPublic {
 	/// Revealed serial — the nullifier. One spend per serial, ever.
 	pub serial: [u8; 32],
 	pub rwa_id: [u8; 32],
+	/// RWA lane: units bought; the proof is checked against
+	/// `RwaPrices[rwa_id] × quantity`. Access lane: must be 1.
+	pub quantity: u64,
+	/// Oracle round converting a USD-priced RWA (`RwaUsdPrices`) to planck;
+	/// binding it here keeps the proof valid if a newer round opens before
+	/// inclusion. Ignored for planck-priced RWAs and on the access lane.
+	pub rate_round: Option<u32>,
+	/// Access lane: index of the plan bought in the app's `AppConfig::plans`,
+	/// or `None` for a plan-less key at `AppConfig::price`. Ignored on the RWA lane.
+	pub access_plan: Option<u8>,
 	pub tx_id: [u8; 16],
 	pub ownership_commitment: [u8; 32],
 	/// Plain Pedersen change output (v'·G + r'·H), or [0u8;32] when none.
@@ -149,14 +261,34 @@ pub struct CoinWithdrawPublic {
 	pub tx_id: [u8; 16],
 }
 
+/// Paymaster fee credit presented by an unsigned spend.
+///
+/// `proof` is a one-of-many proof that some fee coin of `FeeCoinGroups[group_id]`
+/// opens to `serial`·G1 + `BaseFee`·G + r·H, with the BLAKE2-256 of the
+/// spend's public inputs as context. The coin is never named, so the spend
+/// cannot be traced to the `deposit_fee` that funded it, and the credit
+/// cannot be lifted into another transaction.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct FeeTicket {
+	pub group_id: u32,
+	pub serial: [u8; 32],
+	pub proof: Vec<u8>,
+}
+
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::ConstU32;
-	use frame_support::traits::{Currency, ExistenceRequirement};
-	use sp_runtime::traits::UniqueSaturatedInto;
+	use frame_support::traits::{
+		Currency, ExistenceRequirement, FindAuthor, UnfilteredDispatchable, WithdrawReasons,
+	};
+	use sp_runtime::traits::{One, Saturating, UniqueSaturatedInto, Zero};
+	use sp_runtime::Perbill;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -178,6 +310,29 @@ pub mod pallet {
 		/// `purchase_access` or `purchase_access_coin`.
 		/// Use `NoopAccessDispatch` when XCM is not needed (e.g. tests).
 		type AccessDispatch: super::AccessKeyDispatch;
+		/// Paymaster: base fee charged per unsigned spend, prepaid via `deposit_fee`.
+		/// Zero disables the fee lane (spends need no `FeeTicket`).
+		#[pallet::constant]
+		type BaseFee: Get<BalanceOf<Self>>;
+		/// Paymaster: share of each consumed base fee that is burned; the rest
+		/// is paid to the block author.
+		#[pallet::constant]
+		type FeeBurn: Get<Perbill>;
+		/// Paymaster account holding prepaid fee credits.
+		type FeePayer: Get<<Self as frame_system::Config>::AccountId>;
+		type FindAuthor: FindAuthor<Self::AccountId>;
+		/// AccountId of the RWA chain sovereign account on this chain.
+		/// Only this account may call `xcm_sync_rwa_price`.
+		type RwaChainSovereign: Get<<Self as frame_system::Config>::AccountId>;
+		/// NULLA/USD rate for USD-priced RWAs. Use `NoFiatRate` when no oracle is wired.
+		type FiatRate: super::FiatRate;
+		/// Blocks a root stays acceptable after it stops being current, until
+		/// governance sets `RootRetention`.
+		#[pallet::constant]
+		type DefaultRootRetention: Get<BlockNumberFor<Self>>;
+		/// Upper bound for `set_root_retention`.
+		#[pallet::constant]
+		type MaxRootRetention: Get<BlockNumberFor<Self>>;
 	}
 
 	pub type BalanceOf<T> =
@@ -196,10 +351,25 @@ pub mod pallet {
 	#[pallet::getter(fn current_root)]
 	pub type CurrentRoot<T: Config> = StorageValue<_, [u8; 32], ValueQuery>;
 
+	/// Root history: block → root current at the end of that block. Only
+	/// blocks after which the root changed have an entry, and entries older
+	/// than `RootRetention` are pruned.
+	#[pallet::storage]
+	pub type RootHistory<T: Config> =
+		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, [u8; 32], OptionQuery>;
+
+	/// Root → its `RootHistory` block. Proofs may anchor to a root for
+	/// `RootRetention` blocks after that block.
 	#[pallet::storage]
-	#[pallet::getter(fn recent_roots)]
-	pub type RecentRoots<T: Config> =
-		StorageValue<_, BoundedVec<[u8; 32], ConstU32<64>>, ValueQuery>;
+	#[pallet::getter(fn root_recorded_at)]
+	pub type RootRecordedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], BlockNumberFor<T>, OptionQuery>;
+
+	/// Blocks a root stays acceptable after it stops being current.
+	#[pallet::storage]
+	#[pallet::getter(fn root_retention)]
+	pub type RootRetention<T: Config> =
+		StorageValue<_, BlockNumberFor<T>, ValueQuery, T::DefaultRootRetention>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn leaves)]
@@ -216,14 +386,40 @@ pub mod pallet {
 	pub type CommitmentIndex<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], u32, OptionQuery>;
 
-	/// RWA price registry: rwa_id → price as u64 planck.
+	/// RWA price registry: rwa_id → price per unit as u64 planck.
 	/// Set by sudo. Zero means the RWA is not available for purchase.
+	/// Indivisible assets have a single unit, so this is the whole-asset price.
 	#[pallet::storage]
 	#[pallet::getter(fn rwa_prices)]
 	pub type RwaPrices<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], u64, ValueQuery>;
 
-	/// Access-key config: app_id → AppConfig { price, payment_account }.
+	/// USD-priced RWAs: rwa_id → price per unit in USD cents.
+	/// Set by sudo via `set_rwa_usd_price`, cleared when the RWA chain de-lists
+	/// the RWA (`xcm_sync_rwa_price` with price 0). Non-zero overrides `RwaPrices`:
+	/// the price is converted to planck at spend time via `FiatRate`.
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_usd_prices)]
+	pub type RwaUsdPrices<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u64, ValueQuery>;
+
+	/// RWAs restricted to credentialed buyers, synced from the RWA chain.
+	/// Coin spends carry no credential presentation, so this lane refuses them;
+	/// they are sold through ProofHub's `purchase_rwa_v2`.
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_eligibility)]
+	pub type RwaEligibility<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], EligibilityRequirement, OptionQuery>;
+
+	/// Units of an RWA still for sale on this lane: rwa_id → units.
+	/// Set by sudo via `set_rwa_units`; `purchase_coin` draws it down.
+	/// Absent means supply is not tracked here (the RWA chain remains authoritative).
+	#[pallet::storage]
+	#[pallet::getter(fn rwa_units)]
+	pub type RwaUnits<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], u64, OptionQuery>;
+
+	/// Access-key config: app_id → AppConfig { price, payment_account, plans }.
 	/// Set by sudo. Zero price means the app is not available for purchase.
 	#[pallet::storage]
 	#[pallet::getter(fn access_key_configs)]
@@ -253,6 +449,40 @@ pub mod pallet {
 	pub type CoinLocation<T: Config> =
 		StorageMap<_, Blake2_128Concat, [u8; 32], (u32, u32), OptionQuery>;
 
+	// --- Paymaster fee lane ---
+
+	/// Fee coins per group, in insertion order — the anonymity sets of fee
+	/// credits. Same group size as `CoinGroups`.
+	#[pallet::storage]
+	pub type FeeCoinGroups<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, BoundedVec<[u8; 32], ConstU32<1024>>, ValueQuery>;
+
+	/// The fee coin group currently accepting deposits.
+	#[pallet::storage]
+	#[pallet::getter(fn current_fee_group)]
+	pub type CurrentFeeGroup<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Fee coin → (group, index) registry — duplicate prevention + wallet sync.
+	#[pallet::storage]
+	pub type FeeCoinLocation<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], (u32, u32), OptionQuery>;
+
+	/// Consumed fee credits, keyed by fee coin serial.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_nullifier_used)]
+	pub type FeeNullifierUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, [u8; 32], bool, ValueQuery>;
+
+	// --- Proof versions ---
+
+	/// Proof versions governance has retired: (AIR id, version) → true.
+	/// The verifier's envelope registry lists what can be verified at all;
+	/// the runtime rejects proofs of a retired version before verifying.
+	#[pallet::storage]
+	#[pallet::getter(fn proof_version_retired)]
+	pub type RetiredProofVersions<T: Config> =
+		StorageMap<_, Blake2_128Concat, (u8, u16), bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -264,12 +494,52 @@ pub mod pallet {
 		fn build(&self) {
 			MerkleRoot::<T>::put([0u8; 32]);
 			CurrentRoot::<T>::put([0u8; 32]);
-			RecentRoots::<T>::put(BoundedVec::default());
 			Leaves::<T>::put(BoundedVec::default());
 			RootLeafCount::<T>::insert([0u8; 32], 0u32);
 		}
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			// The root recorded at `n - retention - 1` expires in this block.
+			let retention = RootRetention::<T>::get();
+			if n > retention {
+				Self::prune_root(n - retention - One::one());
+			}
+			T::DbWeight::get().reads_writes(3, 2)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = StorageVersion::get::<Pallet<T>>();
+			if on_chain >= STORAGE_VERSION {
+				return Weight::zero();
+			}
+			let mut migrated = 0u64;
+			// v0 → v1: `AppConfig` gained `plans`.
+			if on_chain < 1 {
+				AccessKeyConfigs::<T>::translate::<(u64, [u8; 32]), _>(|_, (price, payment_account)| {
+					migrated += 1;
+					Some(super::AppConfig { price, payment_account, plans: BoundedVec::default() })
+				});
+			}
+			// v1 → v2: the 64-root `RecentRoots` window gave way to the
+			// block-keyed `RootHistory`. The window's roots move into the
+			// history so in-flight proofs stay valid.
+			if on_chain < 2 {
+				let prefix = <Pallet<T> as frame_support::traits::PalletInfoAccess>::name();
+				let window = frame_support::storage::migration::take_storage_value::<
+					BoundedVec<[u8; 32], ConstU32<64>>,
+				>(prefix.as_bytes(), b"RecentRoots", &[])
+				.unwrap_or_default();
+				migrated += window.len() as u64 + 1;
+				Self::import_roots(window.into_inner());
+			}
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -290,8 +560,16 @@ pub mod pallet {
 			rwa_id: [u8; 32],
 			tx_id: [u8; 16],
 		},
-		/// Sudo set a new price for an RWA.
+		/// Sudo set a new per-unit price for an RWA.
 		RwaPriceSet { rwa_id: [u8; 32], price: u64 },
+		/// Sudo set the units of an RWA for sale on this lane (`None` = untracked).
+		RwaUnitsSet { rwa_id: [u8; 32], units: Option<u64> },
+		/// The RWA chain synced a per-unit price (`0` = de-listed).
+		RwaPriceSynced { rwa_id: [u8; 32], price: u64 },
+		/// Sudo set a per-unit USD price for an RWA (`0` = priced in planck).
+		RwaUsdPriceSet { rwa_id: [u8; 32], usd_cents: u64 },
+		/// The RWA chain synced an asset's buyer eligibility (`None` = open to all).
+		RwaEligibilitySynced { rwa_id: [u8; 32], requirement: Option<EligibilityRequirement> },
 		/// A Pedersen note was used to authorise an access-key grant via XCM.
 		AccessPurchaseAuthorized {
 			app_id: [u8; 32],
@@ -299,6 +577,8 @@ pub mod pallet {
 		},
 		/// Sudo set or updated an access-key app config.
 		AccessConfigSet { app_id: [u8; 32], price: u64 },
+		/// Sudo replaced the subscription plans of an access-key app.
+		AccessPlansSet { app_id: [u8; 32], plans: u32 },
 		/// A Pedersen note was withdrawn from the pool.
 		WithdrawCompleted {
 			nullifier: [u8; 32],
@@ -315,6 +595,7 @@ pub mod pallet {
 		/// hidden inside group `group_id`; only the serial is revealed.
 		CoinPurchaseAuthorized {
 			rwa_id: [u8; 32],
+			quantity: u64,
 			tx_id: [u8; 16],
 			group_id: u32,
 			/// New coin from change conversion, if any ([0u8;32] = none).
@@ -322,6 +603,18 @@ pub mod pallet {
 		},
 		/// Phase 10: a one-of-many withdrawal completed.
 		CoinWithdrawCompleted { tx_id: [u8; 16], amount: u64 },
+		/// Paymaster: a fee credit was prepaid.
+		FeeDeposited { coin: [u8; 32], group_id: u32, index_in_group: u32 },
+		/// Paymaster: the block author's share of a consumed base fee.
+		FeePaid { author: <T as frame_system::Config>::AccountId, amount: BalanceOf<T> },
+		/// Paymaster: the author payout failed; the share stays with the Paymaster.
+		FeePayoutFailed { author: <T as frame_system::Config>::AccountId, amount: BalanceOf<T> },
+		/// Paymaster: the burned share of a consumed base fee.
+		FeeBurned { amount: BalanceOf<T> },
+		/// Sudo retired (or restored) a proof version of an AIR.
+		ProofVersionRetired { air: u8, version: u16, retired: bool },
+		/// Sudo set how many blocks a root stays acceptable.
+		RootRetentionSet { blocks: BlockNumberFor<T> },
 	}
 
 	#[pallet::error]
@@ -352,6 +645,40 @@ pub mod pallet {
 		ChangeMismatch,
 		/// The requested app has no config set — not available for purchase.
 		AccessAppNotConfigured,
+		/// The requested access plan does not exist or is not for sale.
+		AccessPlanUnavailable,
+		/// More than `MAX_ACCESS_PLANS` plans.
+		TooManyAccessPlans,
+		/// A plan's duration must be non-zero.
+		InvalidAccessPlan,
+		/// Quantity is zero, or not 1 on the access lane.
+		InvalidQuantity,
+		/// Per-unit price × quantity overflows u64.
+		PriceOverflow,
+		/// The RWA is priced in USD but the spend names no oracle round.
+		RateRoundRequired,
+		/// The named oracle round is unknown, superseded too long ago, or stale.
+		RateUnavailable,
+		/// The RWA is restricted to credentialed buyers, which this lane cannot check.
+		EligibilityRestricted,
+		/// Fewer units are for sale on this lane than requested.
+		InsufficientUnits,
+		/// Caller is not the RWA chain sovereign account.
+		NotRwaChainSovereign,
+		/// Paymaster: a base fee is charged but no `FeeTicket` was supplied.
+		FeeRequired,
+		/// Paymaster: the fee ticket names a fee coin group that does not exist.
+		FeeGroupNotFound,
+		/// Paymaster: this fee credit has already been consumed.
+		FeeNullifierAlreadyUsed,
+		/// Paymaster: the fee credit opening proof is invalid.
+		FeeProofInvalid,
+		/// Paymaster: this fee coin is already registered.
+		DuplicateFeeCredit,
+		/// Root retention must be between 1 block and `MaxRootRetention`.
+		InvalidRootRetention,
+		/// Paymaster: `BaseFee` does not fit the 64-bit committed amount.
+		FeeAmountOverflow,
 	}
 
 	#[pallet::validate_unsigned]
@@ -360,20 +687,15 @@ pub mod pallet {
 
 		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
 			match call {
-				Call::submit_proof { proof, range_proof, public_inputs, .. } => {
+				Call::submit_proof { proof, range_proof, public_inputs, fee, .. } => {
 					let max_size = T::MaxProofSize::get() as usize;
 					if proof.len() > max_size { return InvalidTransaction::ExhaustsResources.into(); }
 					let max_rp = T::MaxRangeProofSize::get() as usize;
 					if range_proof.len() > max_rp { return InvalidTransaction::ExhaustsResources.into(); }
 
 					if let Ok(inputs) = ProofPublicInputs::decode(&mut &public_inputs[..]) {
-						let anchor = inputs.merkle_root;
-						let cur = CurrentRoot::<T>::get();
-						if anchor != cur {
-							let window = RecentRoots::<T>::get();
-							if !window.iter().any(|r| *r == anchor) {
-								return InvalidTransaction::Stale.into();
-							}
+						if !Self::root_acceptable(&inputs.merkle_root) {
+							return InvalidTransaction::Stale.into();
 						}
 						if inputs.nullifiers.len() != inputs.input_commitments.len() {
 							return InvalidTransaction::BadMandatory.into();
@@ -389,13 +711,14 @@ pub mod pallet {
 						for nullifier in inputs.nullifiers.iter() {
 							if NullifierUsed::<T>::get(nullifier) { return InvalidTransaction::Stale.into(); }
 						}
-						ValidTransaction::with_tag_prefix("ProofSubmission")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("ProofSubmission")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.nullifiers.clone())
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
 				Call::purchase_rwa {
@@ -489,7 +812,7 @@ pub mod pallet {
 						.propagate(true)
 						.build()
 				}
-				Call::purchase_coin { public_inputs, .. } => {
+				Call::purchase_coin { public_inputs, fee, .. } => {
 					if let Ok(inputs) = CoinSpendPublic::decode(&mut &public_inputs[..]) {
 						if SerialUsed::<T>::get(inputs.serial) {
 							return InvalidTransaction::Stale.into();
@@ -497,16 +820,17 @@ pub mod pallet {
 						if !CoinGroups::<T>::contains_key(inputs.group_id) {
 							return InvalidTransaction::BadMandatory.into();
 						}
-						ValidTransaction::with_tag_prefix("ScanCoinPurchase")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("ScanCoinPurchase")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.serial)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
-				Call::withdraw_coin { public_inputs, .. } => {
+				Call::withdraw_coin { public_inputs, fee, .. } => {
 					if let Ok(inputs) = CoinWithdrawPublic::decode(&mut &public_inputs[..]) {
 						if SerialUsed::<T>::get(inputs.serial) {
 							return InvalidTransaction::Stale.into();
@@ -514,13 +838,14 @@ pub mod pallet {
 						if !CoinGroups::<T>::contains_key(inputs.group_id) {
 							return InvalidTransaction::BadMandatory.into();
 						}
-						ValidTransaction::with_tag_prefix("ScanCoinWithdraw")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("ScanCoinWithdraw")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.serial)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
 				// ── Access-key lanes ─────────────────────────────────────────────────
@@ -584,7 +909,7 @@ pub mod pallet {
 						.propagate(true)
 						.build()
 				}
-				Call::purchase_access_coin { public_inputs, .. } => {
+				Call::purchase_access_coin { public_inputs, fee, .. } => {
 					if let Ok(inputs) = CoinSpendPublic::decode(&mut &public_inputs[..]) {
 						if SerialUsed::<T>::get(inputs.serial) {
 							return InvalidTransaction::Stale.into();
@@ -592,13 +917,14 @@ pub mod pallet {
 						if !CoinGroups::<T>::contains_key(inputs.group_id) {
 							return InvalidTransaction::BadMandatory.into();
 						}
-						ValidTransaction::with_tag_prefix("ScanAccessCoinPurchase")
+						let fee_tag = Self::validate_fee(fee, &blake2_256(public_inputs))?;
+						let tx = ValidTransaction::with_tag_prefix("ScanAccessCoinPurchase")
 							.and_provides(inputs.tx_id)
 							.and_provides(inputs.serial)
 							.priority(100)
 							.longevity(64)
-							.propagate(true)
-							.build()
+							.propagate(true);
+						Self::with_fee_tag(tx, fee_tag).build()
 					} else { InvalidTransaction::Call.into() }
 				}
 				_ => InvalidTransaction::Call.into(),
@@ -607,6 +933,109 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// A proof may anchor to the current root, or to any root recorded in
+		/// `RootHistory` within `RootRetention` blocks.
+		pub fn root_acceptable(root: &[u8; 32]) -> bool {
+			if *root == CurrentRoot::<T>::get() { return true; }
+			let now = frame_system::Pallet::<T>::block_number();
+			RootRecordedAt::<T>::get(root)
+				.is_some_and(|at| now.saturating_sub(at) <= RootRetention::<T>::get())
+		}
+
+		/// Record `prev`, the root current at the end of the previous block, on
+		/// the first tree change of a block. Later changes in the same block
+		/// record nothing, so the history holds at most one root per block.
+		fn record_root(prev: [u8; 32]) {
+			let at = frame_system::Pallet::<T>::block_number().saturating_sub(One::one());
+			if RootHistory::<T>::contains_key(at) { return; }
+			RootHistory::<T>::insert(at, prev);
+			RootRecordedAt::<T>::insert(prev, at);
+		}
+
+		/// Record roots of the pre-v2 window, oldest first, newest at
+		/// `now - 2`, one block apart. `now - 1` stays free for the root
+		/// current at the upgrade, recorded by the block's first tree change.
+		/// Roots that would fall outside `RootRetention` are dropped.
+		fn import_roots(roots: Vec<[u8; 32]>) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let floor = now.saturating_sub(RootRetention::<T>::get());
+			let mut at = now.saturating_sub(One::one());
+			for root in roots.into_iter().rev() {
+				if at <= floor { break; }
+				at = at.saturating_sub(One::one());
+				RootHistory::<T>::insert(at, root);
+				RootRecordedAt::<T>::insert(root, at);
+			}
+		}
+
+		/// Drop the root recorded at block `at`, unless it was recorded again
+		/// later.
+		fn prune_root(at: BlockNumberFor<T>) {
+			if let Some(root) = RootHistory::<T>::take(at) {
+				if RootRecordedAt::<T>::get(root) == Some(at) {
+					RootRecordedAt::<T>::remove(root);
+				}
+			}
+		}
+
+		/// Oldest root a proof may still anchor to; the current root when no
+		/// older root is retained.
+		pub fn oldest_acceptable_root() -> [u8; 32] {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(RootRetention::<T>::get());
+			while at < now {
+				if let Some(root) = RootHistory::<T>::get(at) {
+					return root;
+				}
+				at = at.saturating_add(One::one());
+			}
+			CurrentRoot::<T>::get()
+		}
+
+		/// Roots recorded within `RootRetention` blocks, oldest first, as
+		/// (block, root). The current root is not among them.
+		pub fn recent_roots() -> Vec<(BlockNumberFor<T>, [u8; 32])> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(RootRetention::<T>::get());
+			let mut roots = Vec::new();
+			while at < now {
+				if let Some(root) = RootHistory::<T>::get(at) {
+					roots.push((at, root));
+				}
+				at = at.saturating_add(One::one());
+			}
+			roots
+		}
+
+		/// Coins of `group_id` and their padded anonymity set; `None` for a
+		/// group no coin has entered.
+		pub fn coin_group(group_id: u32) -> Option<super::CoinGroup> {
+			let coins = CoinGroups::<T>::get(group_id).into_inner();
+			if coins.is_empty() { return None; }
+			let padded = T::ProofVerifier::pad_group(&coins, group_id);
+			Some(super::CoinGroup { coins, padded })
+		}
+
+		/// Prices, supply and eligibility of `rwa_id` on this lane.
+		pub fn rwa_listing(rwa_id: &[u8; 32]) -> super::RwaListing {
+			super::RwaListing {
+				price: RwaPrices::<T>::get(rwa_id),
+				usd_cents: RwaUsdPrices::<T>::get(rwa_id),
+				units: RwaUnits::<T>::get(rwa_id),
+				eligibility: RwaEligibility::<T>::get(rwa_id),
+			}
+		}
+
+		/// Dispatch `call` as an unsigned extrinsic on the current state and
+		/// roll every change back. Returns the exact error the call would
+		/// fail with; calls that are not unsigned fail with `BadOrigin`.
+		pub fn dry_run(call: Call<T>) -> DispatchResult {
+			frame_support::storage::with_transaction(|| {
+				let result = call.dispatch_bypass_filter(frame_system::RawOrigin::None.into());
+				sp_runtime::TransactionOutcome::Rollback(result.map(|_| ()).map_err(|e| e.error))
+			})
+		}
+
 		fn hash2(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
 			let mut data = [0u8; 64];
 			data[..32].copy_from_slice(&left);
@@ -635,6 +1064,97 @@ pub mod pallet {
 			}
 			cur[0]
 		}
+
+		/// Price of `quantity` units of `rwa_id`: the per-unit price × quantity.
+		/// The per-unit price is `RwaUsdPrices[rwa_id]` converted at oracle round
+		/// `rate_round` when the RWA is priced in USD, `RwaPrices[rwa_id]` otherwise.
+		/// Also checks the lane's remaining supply when `RwaUnits` is set.
+		fn rwa_total_price(rwa_id: &[u8; 32], quantity: u64, rate_round: Option<u32>) -> Result<u64, Error<T>> {
+			ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+			let usd_cents = RwaUsdPrices::<T>::get(rwa_id);
+			let unit_price = if usd_cents > 0 {
+				let round = rate_round.ok_or(Error::<T>::RateRoundRequired)?;
+				let rate = T::FiatRate::planck_per_cent(round).ok_or(Error::<T>::RateUnavailable)?;
+				usd_cents.checked_mul(rate).ok_or(Error::<T>::PriceOverflow)?
+			} else {
+				RwaPrices::<T>::get(rwa_id)
+			};
+			ensure!(unit_price > 0, Error::<T>::RwaPriceNotSet);
+			if let Some(units) = RwaUnits::<T>::get(rwa_id) {
+				ensure!(quantity <= units, Error::<T>::InsufficientUnits);
+			}
+			unit_price.checked_mul(quantity).ok_or(Error::<T>::PriceOverflow)
+		}
+
+		/// Draw `quantity` down from the lane's supply (no-op when untracked).
+		/// Call only after `rwa_total_price` has passed.
+		fn take_rwa_units(rwa_id: &[u8; 32], quantity: u64) {
+			RwaUnits::<T>::mutate(rwa_id, |units| {
+				if let Some(u) = units.as_mut() { *u = u.saturating_sub(quantity); }
+			});
+		}
+
+		/// Paymaster: check `fee` for a spend whose public inputs hash to `ctx`.
+		/// Returns the fee nullifier to consume, or `None` when no base fee is
+		/// charged.
+		fn check_fee(fee: &Option<FeeTicket>, ctx: &[u8; 32]) -> Result<Option<[u8; 32]>, Error<T>> {
+			let base_fee = T::BaseFee::get();
+			if base_fee.is_zero() { return Ok(None); }
+			let ticket = fee.as_ref().ok_or(Error::<T>::FeeRequired)?;
+			let nf = ticket.serial;
+			ensure!(!FeeNullifierUsed::<T>::get(nf), Error::<T>::FeeNullifierAlreadyUsed);
+			ensure!(FeeCoinGroups::<T>::contains_key(ticket.group_id), Error::<T>::FeeGroupNotFound);
+			let amount: u64 = base_fee.try_into().map_err(|_| Error::<T>::FeeAmountOverflow)?;
+			let group = FeeCoinGroups::<T>::get(ticket.group_id);
+			let coins = T::ProofVerifier::pad_group(&group, ticket.group_id);
+			ensure!(
+				T::ProofVerifier::verify_one_of_many(&ticket.proof, &coins, &nf, amount, &[0u8; 32], ctx),
+				Error::<T>::FeeProofInvalid
+			);
+			Ok(Some(nf))
+		}
+
+		/// Paymaster: `check_fee` for the transaction pool.
+		fn validate_fee(
+			fee: &Option<FeeTicket>,
+			ctx: &[u8; 32],
+		) -> Result<Option<[u8; 32]>, TransactionValidityError> {
+			Self::check_fee(fee, ctx).map_err(|e| match e {
+				Error::<T>::FeeNullifierAlreadyUsed => InvalidTransaction::Stale.into(),
+				_ => InvalidTransaction::Payment.into(),
+			})
+		}
+
+		/// Paymaster: spends sharing a fee credit conflict in the pool.
+		fn with_fee_tag(tx: ValidTransactionBuilder, fee_tag: Option<[u8; 32]>) -> ValidTransactionBuilder {
+			match fee_tag {
+				Some(nf) => tx.and_provides(nf),
+				None => tx,
+			}
+		}
+
+		/// Paymaster: consume the fee credit checked by `check_fee` and split
+		/// `BaseFee` between burn and the block author.
+		fn charge_fee(fee: &Option<FeeTicket>, fee_nullifier: Option<[u8; 32]>) {
+			let (Some(_), Some(nf)) = (fee, fee_nullifier) else { return };
+			FeeNullifierUsed::<T>::insert(nf, true);
+
+			let payer = T::FeePayer::get();
+			let amount = T::BaseFee::get();
+			let burn_amount = T::FeeBurn::get() * amount;
+			let author_amount = amount.saturating_sub(burn_amount);
+			// Dropping the withdrawn imbalance reduces total issuance.
+			if T::Currency::withdraw(&payer, burn_amount, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive).is_ok() {
+				Self::deposit_event(Event::FeeBurned { amount: burn_amount });
+			}
+			let digest = frame_system::Pallet::<T>::digest();
+			if let Some(author) = T::FindAuthor::find_author(digest.logs.iter().filter_map(|d| d.as_pre_runtime())) {
+				match T::Currency::transfer(&payer, &author, author_amount, ExistenceRequirement::KeepAlive) {
+					Ok(_) => Self::deposit_event(Event::FeePaid { author, amount: author_amount }),
+					Err(_) => Self::deposit_event(Event::FeePayoutFailed { author, amount: author_amount }),
+				}
+			}
+		}
 	}
 
 	#[pallet::call]
@@ -681,14 +1201,7 @@ pub mod pallet {
 			MerkleRoot::<T>::put(computed_root);
 			let leaf_count = Leaves::<T>::get().len() as u32;
 			RootLeafCount::<T>::insert(computed_root, leaf_count);
-			let mut window = RecentRoots::<T>::get();
-			if window.len() >= 64 {
-				let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
-				for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
-				window = shifted;
-			}
-			let _ = window.try_push(prev);
-			RecentRoots::<T>::put(&window);
+			Self::record_root(prev);
 			Self::deposit_event(Event::DepositAccepted { commitment: c_arr, new_merkle_root: computed_root, hints_blob });
 			Ok(())
 		}
@@ -700,19 +1213,17 @@ pub mod pallet {
 			range_proof: BoundedVec<u8, <T as Config>::MaxRangeProofSize>,
 			public_inputs: Vec<u8>,
 			hints_blob: BoundedVec<u8, ConstU32<4096>>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 			let max_size = T::MaxProofSize::get() as usize;
 			ensure!(proof.len() <= max_size, Error::<T>::ProofTooLarge);
 			ensure!(range_proof.len() <= T::MaxRangeProofSize::get() as usize, Error::<T>::RangeProofTooLarge);
 			let inputs = ProofPublicInputs::decode(&mut &public_inputs[..]).map_err(|_| Error::<T>::ProofVerificationFailed)?;
+			let fee_nullifier = Self::check_fee(&fee, &blake2_256(&public_inputs))?;
 
 			let anchor = inputs.merkle_root;
-			let current = CurrentRoot::<T>::get();
-			if anchor != current {
-				let window = RecentRoots::<T>::get();
-				ensure!(window.iter().any(|r| *r == anchor), Error::<T>::ProofVerificationFailed);
-			}
+			ensure!(Self::root_acceptable(&anchor), Error::<T>::ProofVerificationFailed);
 			{
 				let cmts: alloc::vec::Vec<[u8; 32]> = inputs.new_commitments.clone();
 				ensure!(cmts.len() as u32 <= T::MaxOutputs::get(), Error::<T>::ProofVerificationFailed);
@@ -766,15 +1277,9 @@ pub mod pallet {
 			MerkleRoot::<T>::put(computed_root);
 			let leaf_count = Leaves::<T>::get().len() as u32;
 			RootLeafCount::<T>::insert(computed_root, leaf_count);
-			let mut window = RecentRoots::<T>::get();
-			if window.len() >= 64 {
-				let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
-				for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
-				window = shifted;
-			}
-			let _ = window.try_push(prev);
-			RecentRoots::<T>::put(&window);
+			Self::record_root(prev);
 			Self::deposit_event(Event::ProofAccepted { tx_id: inputs.tx_id, new_merkle_root: computed_root, outputs: inputs.new_commitments.clone(), hints_blob });
+			Self::charge_fee(&fee, fee_nullifier);
 			Ok(())
 		}
 
@@ -819,6 +1324,7 @@ pub mod pallet {
 			ensure!(!NullifierUsed::<T>::get(nullifier), Error::<T>::NullifierAlreadyUsed);
 			ensure!(CommitmentIndex::<T>::contains_key(&input_commitment), Error::<T>::CommitmentNotFound);
 
+			ensure!(!RwaEligibility::<T>::contains_key(rwa_id), Error::<T>::EligibilityRestricted);
 			let price = RwaPrices::<T>::get(rwa_id);
 			ensure!(price > 0, Error::<T>::RwaPriceNotSet);
 
@@ -882,21 +1388,15 @@ pub mod pallet {
 				MerkleRoot::<T>::put(computed_root);
 				let leaf_count = Leaves::<T>::get().len() as u32;
 				RootLeafCount::<T>::insert(computed_root, leaf_count);
-				let mut window = RecentRoots::<T>::get();
-				if window.len() >= 64 {
-					let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
-					for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
-					window = shifted;
-				}
-				let _ = window.try_push(prev);
-				RecentRoots::<T>::put(&window);
+				Self::record_root(prev);
 			}
 
 			Self::deposit_event(Event::RwaPurchaseAuthorized { rwa_id, tx_id });
 
 			// Dispatch XCM to RWA chain; failure is swallowed (logged at runtime level).
 			// spend_tag is zero — Pedersen scheme does not use ephemeral spend tags.
-			T::RwaDispatch::send(rwa_id, nullifier, [0u8; 32], price, tx_id, ownership_commitment);
+			// v1 purchases are always for the whole asset.
+			T::RwaDispatch::send(rwa_id, nullifier, [0u8; 32], price, tx_id, ownership_commitment, 1);
 
 			Ok(())
 		}
@@ -951,7 +1451,7 @@ pub mod pallet {
 		/// Set the price for an RWA (sudo only).
 		///
 		/// `rwa_id`: first 4 bytes are asset_id as LE u32, remaining bytes are zero.
-		/// `price`: value in planck that a Pedersen note must equal to purchase this RWA.
+		/// `price`: value in planck per unit; a Pedersen note must equal it to buy one unit.
 		/// Setting `price` to zero effectively de-lists the RWA.
 		#[pallet::weight(Weight::zero())]
 		pub fn set_rwa_price(
@@ -980,8 +1480,13 @@ pub mod pallet {
 			payment_account: [u8; 32],
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			let cfg = super::AppConfig { price, payment_account };
-			AccessKeyConfigs::<T>::insert(app_id, cfg);
+			AccessKeyConfigs::<T>::mutate(app_id, |cfg| match cfg {
+				Some(cfg) => {
+					cfg.price = price;
+					cfg.payment_account = payment_account;
+				}
+				None => *cfg = Some(super::AppConfig { price, payment_account, plans: BoundedVec::default() }),
+			});
 			Self::deposit_event(Event::AccessConfigSet { app_id, price });
 			Ok(())
 		}
@@ -1083,20 +1588,13 @@ pub mod pallet {
 				MerkleRoot::<T>::put(computed_root);
 				let leaf_count = Leaves::<T>::get().len() as u32;
 				RootLeafCount::<T>::insert(computed_root, leaf_count);
-				let mut window = RecentRoots::<T>::get();
-				if window.len() >= 64 {
-					let mut shifted: BoundedVec<[u8; 32], ConstU32<64>> = BoundedVec::default();
-					for i in 1..window.len() { let _ = shifted.try_push(window[i]); }
-					window = shifted;
-				}
-				let _ = window.try_push(prev);
-				RecentRoots::<T>::put(&window);
+				Self::record_root(prev);
 			}
 
 			Self::deposit_event(Event::AccessPurchaseAuthorized { app_id, tx_id });
 
 			// XCM to AuthGate (para 2003): nullifier, tx_id, access_key_commitment.
-			T::AccessDispatch::send(app_id, nullifier, tx_id, access_key_commitment);
+			T::AccessDispatch::send(app_id, nullifier, tx_id, access_key_commitment, None);
 
 			Ok(())
 		}
@@ -1159,6 +1657,7 @@ pub mod pallet {
 			public_inputs: Vec<u8>,
 			one_of_many_proof: Vec<u8>,
 			g1_pok: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
@@ -1168,8 +1667,8 @@ pub mod pallet {
 			ensure!(!SerialUsed::<T>::get(inputs.serial), Error::<T>::SerialAlreadyUsed);
 			ensure!(CoinGroups::<T>::contains_key(inputs.group_id), Error::<T>::GroupNotFound);
 
-			let price = RwaPrices::<T>::get(inputs.rwa_id);
-			ensure!(price > 0, Error::<T>::RwaPriceNotSet);
+			ensure!(!RwaEligibility::<T>::contains_key(inputs.rwa_id), Error::<T>::EligibilityRestricted);
+			let price = Self::rwa_total_price(&inputs.rwa_id, inputs.quantity, inputs.rate_round)?;
 
 			// Change consistency: both present or both absent.
 			let has_change = inputs.change != [0u8; 32];
@@ -1181,6 +1680,7 @@ pub mod pallet {
 
 			// Context binds the proof to this exact transaction.
 			let ctx = blake2_256(&public_inputs);
+			let fee_nullifier = Self::check_fee(&fee, &ctx)?;
 			ensure!(
 				T::ProofVerifier::verify_one_of_many(
 					&one_of_many_proof, &coins, &inputs.serial, price, &inputs.change, &ctx,
@@ -1212,9 +1712,11 @@ pub mod pallet {
 			}
 
 			SerialUsed::<T>::insert(inputs.serial, true);
+			Self::take_rwa_units(&inputs.rwa_id, inputs.quantity);
 
 			Self::deposit_event(Event::CoinPurchaseAuthorized {
 				rwa_id: inputs.rwa_id,
+				quantity: inputs.quantity,
 				tx_id: inputs.tx_id,
 				group_id: inputs.group_id,
 				change_coin: inputs.change_coin,
@@ -1229,8 +1731,11 @@ pub mod pallet {
 				price,
 				inputs.tx_id,
 				inputs.ownership_commitment,
+				inputs.quantity,
 			);
 
+			Self::charge_fee(&fee, fee_nullifier);
+
 			Ok(())
 		}
 
@@ -1242,6 +1747,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			public_inputs: Vec<u8>,
 			one_of_many_proof: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
@@ -1255,6 +1761,7 @@ pub mod pallet {
 			let coins = T::ProofVerifier::pad_group(&group, inputs.group_id);
 
 			let ctx = blake2_256(&public_inputs);
+			let fee_nullifier = Self::check_fee(&fee, &ctx)?;
 			ensure!(
 				T::ProofVerifier::verify_one_of_many(
 					&one_of_many_proof, &coins, &inputs.serial, inputs.amount, &[0u8; 32], &ctx,
@@ -1274,13 +1781,15 @@ pub mod pallet {
 				tx_id: inputs.tx_id,
 				amount: inputs.amount,
 			});
+			Self::charge_fee(&fee, fee_nullifier);
 			Ok(())
 		}
 
 		/// Phase 10 (Lelantus): purchase access to a Web2 app via one-of-many proof.
 		///
 		/// Identical to `purchase_coin` except:
-		/// - Uses `AccessKeyConfigs` (app_id → price) instead of `RwaPrices`
+		/// - Uses `AccessKeyConfigs` (app_id → price) instead of `RwaPrices`:
+		///   `access_plan` picks a subscription plan, `None` a plan-less key
 		/// - Transfers price from pool to the app's `payment_account` on ProofHub
 		/// - Sends XCM to AuthGate (para 2003) via `T::AccessDispatch`
 		///
@@ -1295,6 +1804,7 @@ pub mod pallet {
 			public_inputs: Vec<u8>,
 			one_of_many_proof: Vec<u8>,
 			g1_pok: Vec<u8>,
+			fee: Option<FeeTicket>,
 		) -> DispatchResult {
 			ensure_none(origin)?;
 
@@ -1304,11 +1814,12 @@ pub mod pallet {
 			ensure!(!SerialUsed::<T>::get(inputs.serial), Error::<T>::SerialAlreadyUsed);
 			ensure!(CoinGroups::<T>::contains_key(inputs.group_id), Error::<T>::GroupNotFound);
 
-			// rwa_id field carries app_id in this lane
+			// rwa_id field carries app_id in this lane; one key per spend.
 			let app_id = inputs.rwa_id;
+			ensure!(inputs.quantity == 1, Error::<T>::InvalidQuantity);
 			let cfg = AccessKeyConfigs::<T>::get(app_id)
 				.ok_or(Error::<T>::AccessAppNotConfigured)?;
-			ensure!(cfg.price > 0, Error::<T>::AccessAppNotConfigured);
+			let (price, terms) = cfg.quote(inputs.access_plan).ok_or(Error::<T>::AccessPlanUnavailable)?;
 
 			let has_change = inputs.change != [0u8; 32];
 			ensure!(has_change == (inputs.change_coin != [0u8; 32]), Error::<T>::ChangeMismatch);
@@ -1317,9 +1828,10 @@ pub mod pallet {
 			let coins = T::ProofVerifier::pad_group(&group, inputs.group_id);
 
 			let ctx = blake2_256(&public_inputs);
+			let fee_nullifier = Self::check_fee(&fee, &ctx)?;
 			ensure!(
 				T::ProofVerifier::verify_one_of_many(
-					&one_of_many_proof, &coins, &inputs.serial, cfg.price, &inputs.change, &ctx,
+					&one_of_many_proof, &coins, &inputs.serial, price, &inputs.change, &ctx,
 				),
 				Error::<T>::OneOfManyInvalid
 			);
@@ -1354,7 +1866,7 @@ pub mod pallet {
 				T::AccountId::decode(&mut &cfg.payment_account[..])
 					.map_err(|_| Error::<T>::ProofVerificationFailed)?;
 			let pool = T::PoolAccount::get();
-			let amount: BalanceOf<T> = (cfg.price as u128).unique_saturated_into();
+			let amount: BalanceOf<T> = (price as u128).unique_saturated_into();
 			T::Currency::transfer(&pool, &payment_dest, amount, ExistenceRequirement::AllowDeath)?;
 
 			// ownership_commitment field carries access_key_commitment in this lane
@@ -1364,15 +1876,210 @@ pub mod pallet {
 			Self::deposit_event(Event::AccessPurchaseAuthorized { app_id, tx_id });
 
 			// XCM to AuthGate (para 2003).
-			T::AccessDispatch::send(app_id, inputs.serial, tx_id, access_key_commitment);
+			T::AccessDispatch::send(app_id, inputs.serial, tx_id, access_key_commitment, terms);
+
+			Self::charge_fee(&fee, fee_nullifier);
 
 			Ok(())
 		}
+
+		/// Paymaster: prepay one base fee as a fee credit.
+		///
+		/// SIGNED. Transfers `BaseFee` to the Paymaster account and appends
+		/// `coin` = s·G1 + BaseFee·G + r·H to the current fee coin group;
+		/// `open_proof` proves it holds exactly `BaseFee`, as for `deposit_coin`.
+		/// An unsigned spend consumes the credit by presenting a `FeeTicket` —
+		/// a one-of-many proof over the group revealing only the serial s — so
+		/// the spend reveals neither the coin nor the depositing account. The
+		/// base fee is then split between burn and the block author.
+		#[pallet::weight(Weight::zero())]
+		pub fn deposit_fee(origin: OriginFor<T>, coin: [u8; 32], open_proof: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let base_fee = T::BaseFee::get();
+			let amount: u64 = base_fee.try_into().map_err(|_| Error::<T>::FeeAmountOverflow)?;
+			ensure!(!FeeCoinLocation::<T>::contains_key(&coin), Error::<T>::DuplicateFeeCredit);
+			ensure!(
+				T::ProofVerifier::verify_deposit_open(&coin, amount, &open_proof, &who.encode()),
+				Error::<T>::FeeProofInvalid
+			);
+			let payer = T::FeePayer::get();
+			T::Currency::transfer(&who, &payer, base_fee, ExistenceRequirement::KeepAlive)?;
+
+			let mut gid = CurrentFeeGroup::<T>::get();
+			let mut group = FeeCoinGroups::<T>::get(gid);
+			if group.len() >= 1024 {
+				gid += 1;
+				CurrentFeeGroup::<T>::put(gid);
+				group = FeeCoinGroups::<T>::get(gid);
+			}
+			let idx = group.len() as u32;
+			group.try_push(coin).map_err(|_| Error::<T>::TreeFull)?;
+			FeeCoinGroups::<T>::insert(gid, &group);
+			FeeCoinLocation::<T>::insert(&coin, (gid, idx));
+			Self::deposit_event(Event::FeeDeposited { coin, group_id: gid, index_in_group: idx });
+			Ok(())
+		}
+
+		/// Sudo: set the units of an RWA still for sale on this lane.
+		/// `None` stops tracking supply here; `purchase_coin` then relies on
+		/// the RWA chain alone.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_rwa_units(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			units: Option<u64>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			RwaUnits::<T>::set(rwa_id, units);
+			Self::deposit_event(Event::RwaUnitsSet { rwa_id, units });
+			Ok(())
+		}
+
+		/// Sync an RWA's per-unit price from the RWA chain marketplace.
+		///
+		/// MUST only be called via XCM `Transact` by the RWA chain sovereign
+		/// account, which pushes listing creation, price changes, sell-outs and
+		/// cancellations. `price == 0` de-lists the RWA on this lane and clears
+		/// its USD price too; a re-listed RWA is priced in planck until sudo sets
+		/// a USD price again.
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_sync_rwa_price(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			price: u64,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(caller == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+			if price == 0 {
+				RwaPrices::<T>::remove(rwa_id);
+				RwaUsdPrices::<T>::remove(rwa_id);
+			} else {
+				RwaPrices::<T>::insert(rwa_id, price);
+			}
+			Self::deposit_event(Event::RwaPriceSynced { rwa_id, price });
+			Ok(())
+		}
+
+		/// Sudo: price an RWA per unit in USD cents instead of planck.
+		///
+		/// Spends then name an oracle round (`rate_round`) and pay
+		/// `usd_cents × planck_per_cent(round) × quantity`. Zero returns the RWA
+		/// to planck pricing via `RwaPrices`.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_rwa_usd_price(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			usd_cents: u64,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			if usd_cents == 0 {
+				RwaUsdPrices::<T>::remove(rwa_id);
+			} else {
+				RwaUsdPrices::<T>::insert(rwa_id, usd_cents);
+			}
+			Self::deposit_event(Event::RwaUsdPriceSet { rwa_id, usd_cents });
+			Ok(())
+		}
+
+		/// XCM-only: the RWA chain syncs the buyer eligibility an asset's
+		/// issuer set in the registry. Restricted assets cannot be bought on
+		/// this lane; `None` opens the asset again.
+		#[pallet::weight(Weight::zero())]
+		pub fn xcm_sync_rwa_eligibility(
+			origin: OriginFor<T>,
+			rwa_id: [u8; 32],
+			requirement: Option<EligibilityRequirement>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who == T::RwaChainSovereign::get(), Error::<T>::NotRwaChainSovereign);
+			match requirement {
+				Some(req) => RwaEligibility::<T>::insert(rwa_id, req),
+				None => RwaEligibility::<T>::remove(rwa_id),
+			}
+			Self::deposit_event(Event::RwaEligibilitySynced { rwa_id, requirement });
+			Ok(())
+		}
+
+		/// Replace the subscription plans of a configured access-key app (sudo only).
+		///
+		/// A purchase picks a plan by index in `CoinSpendPublic::access_plan`; the plan's
+		/// duration, tier and use limit are relayed to AuthGate with the grant.
+		/// Price a plan at zero to withdraw it while keeping the indices stable.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_access_plans(
+			origin: OriginFor<T>,
+			app_id: [u8; 32],
+			plans: Vec<super::AccessPlan>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let plans: BoundedVec<super::AccessPlan, ConstU32<{ super::MAX_ACCESS_PLANS }>> =
+				plans.try_into().map_err(|_| Error::<T>::TooManyAccessPlans)?;
+			ensure!(plans.iter().all(|p| p.duration > 0), Error::<T>::InvalidAccessPlan);
+			let count = plans.len() as u32;
+			AccessKeyConfigs::<T>::try_mutate(app_id, |cfg| -> DispatchResult {
+				let cfg = cfg.as_mut().ok_or(Error::<T>::AccessAppNotConfigured)?;
+				cfg.plans = plans;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AccessPlansSet { app_id, plans: count });
+			Ok(())
+		}
+
+		/// Retire or restore a proof version (sudo only).
+		///
+		/// `air` is the verifier's `envelope::AirId` discriminant. Proofs of a
+		/// retired version fail verification; restoring is allowed so a
+		/// retirement can be undone during a migration window.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_proof_version_retired(
+			origin: OriginFor<T>,
+			air: u8,
+			version: u16,
+			retired: bool,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			if retired {
+				RetiredProofVersions::<T>::insert((air, version), true);
+			} else {
+				RetiredProofVersions::<T>::remove((air, version));
+			}
+			Self::deposit_event(Event::ProofVersionRetired { air, version, retired });
+			Ok(())
+		}
+
+		/// Set how many blocks a root stays acceptable after it stops being
+		/// current (sudo only).
+		///
+		/// A raise applies to roots still held; roots already pruned stay
+		/// expired. A cut expires the roots beyond the new window at once.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_root_retention(origin: OriginFor<T>, blocks: BlockNumberFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				!blocks.is_zero() && blocks <= T::MaxRootRetention::get(),
+				Error::<T>::InvalidRootRetention
+			);
+			let old = RootRetention::<T>::get();
+			RootRetention::<T>::put(blocks);
+			// `on_initialize` prunes one block per block at `now - retention - 1`,
+			// so the blocks a cut skips over are pruned here.
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut at = now.saturating_sub(old);
+			while at < now.saturating_sub(blocks) {
+				Self::prune_root(at);
+				at = at.saturating_add(One::one());
+			}
+			Self::deposit_event(Event::RootRetentionSet { blocks });
+			Ok(())
+		}
 	}
 }
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod tests;
+
 pub trait WeightInfo {}
 
 pub mod weights {
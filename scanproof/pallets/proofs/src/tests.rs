@@ -0,0 +1,854 @@
+use crate as pallet_proofs;
+use crate::{
+	CoinLocation, CoinSpendPublic, CoinWithdrawPublic, CommitmentIndex, CurrentFeeGroup, Error, Event,
+	FeeCoinGroups, FeeCoinLocation, FeeNullifierUsed, FeeTicket, ProofVerify, RootHistory, RootRecordedAt,
+	RwaListing, RwaUnits, SerialUsed,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, FindAuthor, Hooks, StorageVersion, UnfilteredDispatchable},
+	BoundedVec,
+};
+use frame_system::EnsureRoot;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	traits::ValidateUnsigned,
+	BuildStorage, ConsensusEngineId, Perbill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Proofs: pallet_proofs,
+		PriceOracle: pallet_price_oracle,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u128;
+}
+
+/// Stand-in for the Pedersen / one-of-many verifier. Every check covers
+/// exactly the statement it is given, so a proof made for one statement
+/// fails against any other:
+/// - coin opening:  `proof == SCALE(coin, amount, context)`;
+/// - one-of-many:   `proof == blake2_256(SCALE(coin, serial, value, change, context))`
+///   for some `coin` of the padded set, which the proof does not name;
+/// - G1 PoK:        `proof == SCALE(new_coin, change, context)`;
+/// - range proof:   `proof == SCALE(commitments)`.
+pub struct MockVerifier;
+
+impl ProofVerify for MockVerifier {
+	fn verify(_: &[u8], _: &[u8]) -> bool { false }
+	fn verify_commitment(_: u64, _: [u8; 32], _: [u8; 32]) -> bool { false }
+	fn verify_opening_knowledge(_: u64, _: [u8; 32], _: &[u8], _: &[u8]) -> bool { false }
+	fn verify_range_proof(range_proof: &[u8], commitments: &[[u8; 32]], _: &[u8], _: u32) -> bool {
+		range_proof == commitments.encode().as_slice()
+	}
+	fn verify_range_proofs(items: &[(&[u8], &[[u8; 32]], &[u8])], nbits: u32) -> bool {
+		!items.is_empty() && items.iter().all(|(rp, c, pi)| Self::verify_range_proof(rp, c, pi, nbits))
+	}
+	fn pedersen_subtract(_: &[u8; 32], _: &[u8; 32]) -> Option<[u8; 32]> { None }
+	fn verify_one_of_many(
+		proof: &[u8],
+		coins: &[[u8; 32]],
+		serial: &[u8; 32],
+		price: u64,
+		change: &[u8; 32],
+		context: &[u8],
+	) -> bool {
+		coins.iter().any(|coin| proof == spend_proof(coin, serial, price, change, context).as_slice())
+	}
+	fn verify_deposit_open(coin: &[u8; 32], amount: u64, proof: &[u8], context: &[u8]) -> bool {
+		proof == open_proof(coin, amount, context).as_slice()
+	}
+	fn verify_g1_pok(new_coin: &[u8; 32], change: &[u8; 32], proof: &[u8], context: &[u8]) -> bool {
+		proof == (new_coin, change, context).encode().as_slice()
+	}
+	fn pad_group(coins: &[[u8; 32]], group_id: u32) -> Vec<[u8; 32]> {
+		let n = core::cmp::max(coins.len(), 2).next_power_of_two() as u32;
+		let mut padded = coins.to_vec();
+		padded.extend((coins.len() as u32..n).map(|i| blake2_256(&(b"pad", group_id, i).encode())));
+		padded
+	}
+}
+
+pub struct MockAuthor;
+impl FindAuthor<u64> for MockAuthor {
+	fn find_author<'a, I>(_: I) -> Option<u64>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(AUTHOR)
+	}
+}
+
+impl pallet_price_oracle::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxReporters = ConstU32<4>;
+	type MinReports = ConstU32<1>;
+	type MaxReportAge = ConstU64<100>;
+	type MaxRoundAge = ConstU64<20>;
+	type RoundGracePeriod = ConstU64<3>;
+}
+
+/// Lane `FiatRate` over the mock's oracle, as the runtime wires it.
+pub struct OracleFiatRate;
+impl crate::FiatRate for OracleFiatRate {
+	fn planck_per_cent(round: u32) -> Option<u64> {
+		pallet_price_oracle::Pallet::<Test>::rate(round)
+	}
+}
+
+parameter_types! {
+	pub const Pool: u64 = POOL;
+	pub const Payer: u64 = PAYER;
+	pub const Sovereign: u64 = SOVEREIGN;
+	pub const Burn: Perbill = Perbill::from_percent(50);
+	pub static BaseFee: u128 = 100;
+	pub static RwaSent: Vec<([u8; 32], u64, u64)> = vec![];
+}
+
+/// Records the (rwa_id, price, units) of each purchase relayed to the RWA chain.
+pub struct RecordingRwaDispatch;
+impl crate::RwaPurchaseDispatch for RecordingRwaDispatch {
+	fn send(rwa_id: [u8; 32], _: [u8; 32], _: [u8; 32], note_value: u64, _: [u8; 16], _: [u8; 32], units: u64) {
+		RwaSent::mutate(|sent| sent.push((rwa_id, note_value, units)));
+	}
+}
+
+impl pallet_proofs::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ProofVerifier = MockVerifier;
+	type Currency = Balances;
+	type MaxProofSize = ConstU32<65536>;
+	type MaxRangeProofSize = ConstU32<4096>;
+	type MaxOutputs = ConstU32<16>;
+	type PoolAccount = Pool;
+	type RwaDispatch = RecordingRwaDispatch;
+	type AccessDispatch = crate::NoopAccessDispatch;
+	type BaseFee = BaseFee;
+	type FeeBurn = Burn;
+	type FeePayer = Payer;
+	type FindAuthor = MockAuthor;
+	type RwaChainSovereign = Sovereign;
+	type FiatRate = OracleFiatRate;
+	type DefaultRootRetention = ConstU64<5>;
+	type MaxRootRetention = ConstU64<100>;
+}
+
+const ALICE: u64 = 1;
+const MALLORY: u64 = 2;
+const POOL: u64 = 10;
+const PAYER: u64 = 11;
+const AUTHOR: u64 = 12;
+const SOVEREIGN: u64 = 13;
+const REPORTER: u64 = 14;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 10_000), (MALLORY, 10_000), (POOL, 10_000), (PAYER, 1)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_price_oracle::GenesisConfig::<Test> { reporters: vec![REPORTER] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn open_proof(coin: &[u8; 32], amount: u64, context: &[u8]) -> Vec<u8> {
+	(coin, amount, context).encode()
+}
+
+fn spend_proof(coin: &[u8; 32], serial: &[u8; 32], value: u64, change: &[u8; 32], context: &[u8]) -> Vec<u8> {
+	blake2_256(&(coin, serial, value, change, context).encode()).to_vec()
+}
+
+/// Serial of the coin opened by `n`.
+fn serial(n: u8) -> [u8; 32] {
+	blake2_256(&(b"serial", n).encode())
+}
+
+/// Stand-in for the coin s·G1 + v·G + r·H whose serial is `serial`.
+fn coin_of(serial: &[u8; 32]) -> [u8; 32] {
+	blake2_256(&(b"coin", serial).encode())
+}
+
+/// Deposits the coin of `serial(n)` holding `amount` from `who`.
+fn deposit_coin(who: u64, n: u8, amount: u64) -> [u8; 32] {
+	let coin = coin_of(&serial(n));
+	let proof = open_proof(&coin, amount, &who.encode());
+	assert_ok!(Proofs::deposit_coin(RuntimeOrigin::signed(who), coin, amount, proof));
+	coin
+}
+
+fn base_fee() -> u64 {
+	u64::try_from(BaseFee::get()).unwrap_or(u64::MAX)
+}
+
+/// Fee coin of the credit opened by `b`.
+fn fee_coin(b: u32) -> [u8; 32] {
+	blake2_256(&(b"fee coin", b).encode())
+}
+
+/// Serial of the fee credit opened by `b`.
+fn fee_serial(b: u32) -> [u8; 32] {
+	blake2_256(&(b"fee serial", b).encode())
+}
+
+/// Deposits the fee credit opened by `b` from `who`.
+fn deposit_fee(who: u64, b: u32) -> [u8; 32] {
+	let coin = fee_coin(b);
+	let proof = open_proof(&coin, base_fee(), &who.encode());
+	assert_ok!(Proofs::deposit_fee(RuntimeOrigin::signed(who), coin, proof));
+	coin
+}
+
+/// A fee ticket spending the credit opened by `b` out of fee group
+/// `group_id`, proven for the spend with `public_inputs`.
+fn ticket(b: u32, group_id: u32, public_inputs: &[u8]) -> FeeTicket {
+	let serial = fee_serial(b);
+	let ctx = blake2_256(public_inputs);
+	FeeTicket { group_id, serial, proof: spend_proof(&fee_coin(b), &serial, base_fee(), &[0u8; 32], &ctx) }
+}
+
+fn contains(haystack: &[u8], needle: &[u8; 32]) -> bool {
+	haystack.windows(32).any(|w| w == needle)
+}
+
+fn account(who: u64) -> [u8; 32] {
+	let mut account = [0u8; 32];
+	account[..8].copy_from_slice(&who.to_le_bytes());
+	account
+}
+
+/// SCALE public inputs of a withdrawal of `amount` to `to`, spending the
+/// coin of `serial(n)` out of group 0.
+fn withdrawal(n: u8, amount: u64, to: u64) -> Vec<u8> {
+	CoinWithdrawPublic { group_id: 0, serial: serial(n), amount, destination: account(to), tx_id: [n; 16] }.encode()
+}
+
+/// Submits `withdraw_coin` with a valid one-of-many proof for `public_inputs`.
+fn withdraw(public_inputs: Vec<u8>, fee: Option<FeeTicket>) -> frame_support::dispatch::DispatchResult {
+	let inputs = <CoinWithdrawPublic as codec::Decode>::decode(&mut &public_inputs[..]).unwrap();
+	let ctx = blake2_256(&public_inputs);
+	let proof = spend_proof(&coin_of(&inputs.serial), &inputs.serial, inputs.amount, &[0u8; 32], &ctx);
+	Proofs::withdraw_coin(RuntimeOrigin::none(), public_inputs, proof, fee)
+}
+
+/// Fee tests spend coins 1 and 2, deposited by Alice and Mallory.
+fn fee_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		deposit_coin(ALICE, 1, 500);
+		deposit_coin(MALLORY, 2, 500);
+	});
+	ext
+}
+
+#[test]
+fn withdraw_consumes_fee_ticket() {
+	fee_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		let w = withdrawal(1, 500, ALICE);
+		assert_ok!(withdraw(w.clone(), Some(ticket(7, 0, &w))));
+
+		assert!(FeeNullifierUsed::<Test>::get(fee_serial(7)));
+		assert!(SerialUsed::<Test>::get(serial(1)));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 500 - 100 + 500);
+		assert_eq!(Balances::free_balance(AUTHOR), 50);
+		System::assert_has_event(Event::FeeBurned { amount: 50 }.into());
+		System::assert_last_event(Event::FeePaid { author: AUTHOR, amount: 50 }.into());
+	});
+}
+
+#[test]
+fn fee_spend_does_not_reveal_deposited_coin() {
+	fee_ext().execute_with(|| {
+		let ours = deposit_fee(ALICE, 7);
+		let other = deposit_fee(MALLORY, 8);
+		System::assert_last_event(Event::FeeDeposited { coin: other, group_id: 0, index_in_group: 1 }.into());
+		System::reset_events();
+
+		let w = withdrawal(1, 500, ALICE);
+		let fee = ticket(7, 0, &w);
+		// The ticket names only the group, which holds both credits.
+		let call = crate::Call::<Test>::withdraw_coin {
+			public_inputs: w.clone(),
+			one_of_many_proof: vec![],
+			fee: Some(fee.clone()),
+		};
+		assert!(!contains(&call.encode(), &ours));
+		assert_ok!(withdraw(w, Some(fee)));
+
+		for record in System::events() {
+			assert!(!contains(&record.event.encode(), &ours));
+		}
+		// Both credits stay in the group; only the serial marks a spend.
+		assert_eq!(FeeCoinLocation::<Test>::get(ours), Some((0, 0)));
+		assert_eq!(FeeCoinLocation::<Test>::get(other), Some((0, 1)));
+		assert_ne!(fee_serial(7), ours);
+	});
+}
+
+#[test]
+fn copied_fee_ticket_fails_in_another_spend() {
+	fee_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		let victim = withdrawal(1, 500, ALICE);
+		let stolen = ticket(7, 0, &victim);
+
+		// Mallory proves her own withdrawal over the copied ticket.
+		let own = withdrawal(2, 500, MALLORY);
+		assert_noop!(withdraw(own.clone(), Some(stolen.clone())), Error::<Test>::FeeProofInvalid);
+		let call = crate::Call::<Test>::withdraw_coin {
+			public_inputs: own,
+			one_of_many_proof: vec![],
+			fee: Some(stolen.clone()),
+		};
+		assert_eq!(
+			Proofs::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// The credit is untouched and still pays for the victim's spend.
+		assert!(!FeeNullifierUsed::<Test>::get(fee_serial(7)));
+		assert_ok!(withdraw(victim, Some(stolen)));
+	});
+}
+
+#[test]
+fn fee_ticket_cannot_be_used_twice() {
+	fee_ext().execute_with(|| {
+		let coin = deposit_fee(ALICE, 7);
+		let first = withdrawal(1, 500, ALICE);
+		assert_ok!(withdraw(first.clone(), Some(ticket(7, 0, &first))));
+
+		// A fresh proof for a second spend finds the credit consumed.
+		let second = withdrawal(2, 500, MALLORY);
+		assert_noop!(
+			withdraw(second.clone(), Some(ticket(7, 0, &second))),
+			Error::<Test>::FeeNullifierAlreadyUsed
+		);
+		let call = crate::Call::<Test>::withdraw_coin {
+			public_inputs: second,
+			one_of_many_proof: vec![],
+			fee: Some(ticket(7, 0, &first)),
+		};
+		assert_eq!(
+			Proofs::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Stale.into())
+		);
+		// Nor can the spent coin be deposited again.
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), coin, open_proof(&coin, 100, &ALICE.encode())),
+			Error::<Test>::DuplicateFeeCredit
+		);
+	});
+}
+
+#[test]
+fn fee_ticket_needs_an_existing_fee_group() {
+	fee_ext().execute_with(|| {
+		let w = withdrawal(1, 500, ALICE);
+		// No credit deposited yet: not even group 0 exists.
+		assert_noop!(withdraw(w.clone(), Some(ticket(7, 0, &w))), Error::<Test>::FeeGroupNotFound);
+
+		deposit_fee(ALICE, 7);
+		assert_noop!(withdraw(w.clone(), Some(ticket(7, 1, &w))), Error::<Test>::FeeGroupNotFound);
+		// A credit outside the named group does not verify.
+		assert_noop!(withdraw(w.clone(), Some(ticket(8, 0, &w))), Error::<Test>::FeeProofInvalid);
+		assert_ok!(withdraw(w, Some(ticket(7, 0, &w))));
+	});
+}
+
+#[test]
+fn full_fee_group_rolls_over() {
+	fee_ext().execute_with(|| {
+		BaseFee::set(1);
+		for b in 0..1024 {
+			deposit_fee(ALICE, b);
+		}
+		assert_eq!(CurrentFeeGroup::<Test>::get(), 0);
+		assert_eq!(FeeCoinGroups::<Test>::get(0).len(), 1024);
+
+		let coin = deposit_fee(ALICE, 1024);
+		System::assert_last_event(Event::FeeDeposited { coin, group_id: 1, index_in_group: 0 }.into());
+		assert_eq!(CurrentFeeGroup::<Test>::get(), 1);
+		assert_eq!(FeeCoinLocation::<Test>::get(coin), Some((1, 0)));
+
+		// Each credit is spent out of its own group.
+		let w = withdrawal(1, 500, ALICE);
+		assert_noop!(withdraw(w.clone(), Some(ticket(1024, 0, &w))), Error::<Test>::FeeProofInvalid);
+		assert_ok!(withdraw(w, Some(ticket(1024, 1, &w))));
+		let w = withdrawal(2, 500, MALLORY);
+		assert_ok!(withdraw(w.clone(), Some(ticket(0, 0, &w))));
+	});
+}
+
+#[test]
+fn fee_deposit_must_hold_the_base_fee() {
+	new_test_ext().execute_with(|| {
+		let coin = fee_coin(7);
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), coin, open_proof(&coin, 99, &ALICE.encode())),
+			Error::<Test>::FeeProofInvalid
+		);
+		// Nor can another account's opening be replayed.
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(MALLORY), coin, open_proof(&coin, 100, &ALICE.encode())),
+			Error::<Test>::FeeProofInvalid
+		);
+		assert!(!FeeCoinGroups::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn base_fee_above_u64_is_rejected() {
+	fee_ext().execute_with(|| {
+		deposit_fee(ALICE, 7);
+		BaseFee::set(u64::MAX as u128 + 1);
+		let w = withdrawal(1, 500, ALICE);
+		assert_noop!(withdraw(w.clone(), Some(ticket(7, 0, &w))), Error::<Test>::FeeAmountOverflow);
+		assert_noop!(
+			Proofs::deposit_fee(RuntimeOrigin::signed(ALICE), fee_coin(8), vec![]),
+			Error::<Test>::FeeAmountOverflow
+		);
+	});
+}
+
+#[test]
+fn spend_without_ticket_needs_zero_base_fee() {
+	fee_ext().execute_with(|| {
+		assert_noop!(withdraw(withdrawal(1, 500, ALICE), None), Error::<Test>::FeeRequired);
+		BaseFee::set(0);
+		assert_ok!(withdraw(withdrawal(1, 500, ALICE), None));
+		System::assert_last_event(Event::CoinWithdrawCompleted { tx_id: [1u8; 16], amount: 500 }.into());
+	});
+}
+
+const UNIT_PRICE: u64 = 300;
+
+fn rwa(asset: u32) -> [u8; 32] {
+	let mut id = [0u8; 32];
+	id[..4].copy_from_slice(&asset.to_le_bytes());
+	id
+}
+
+/// A `purchase_coin` of `quantity` units of asset 1, spending the coin of
+/// `serial(n)` without change.
+fn purchase_inputs(n: u8, quantity: u64) -> CoinSpendPublic {
+	CoinSpendPublic {
+		group_id: 0,
+		serial: serial(n),
+		rwa_id: rwa(1),
+		quantity,
+		rate_round: None,
+		access_plan: None,
+		tx_id: [n; 16],
+		ownership_commitment: [n.wrapping_add(0x40); 32],
+		change: [0u8; 32],
+		change_coin: [0u8; 32],
+	}
+}
+
+/// Submits `purchase_coin` with a one-of-many proof paying `price`.
+fn purchase_at(inputs: &CoinSpendPublic, price: u64) -> frame_support::dispatch::DispatchResult {
+	purchase_call(inputs, price)
+		.dispatch_bypass_filter(RuntimeOrigin::none())
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+/// `purchase_coin` with a one-of-many proof paying `price`.
+fn purchase_call(inputs: &CoinSpendPublic, price: u64) -> crate::Call<Test> {
+	let public_inputs = inputs.encode();
+	let ctx = blake2_256(&public_inputs);
+	let proof = spend_proof(&coin_of(&inputs.serial), &inputs.serial, price, &inputs.change, &ctx);
+	crate::Call::<Test>::purchase_coin { public_inputs, one_of_many_proof: proof, g1_pok: vec![], fee: None }
+}
+
+/// Purchase tests run without the fee lane, with asset 1 listed and coins
+/// 1 to 4 in group 0.
+fn purchase_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		BaseFee::set(0);
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), UNIT_PRICE));
+		for n in 1..=4 {
+			deposit_coin(ALICE, n, 1_000);
+		}
+	});
+	ext
+}
+
+#[test]
+fn purchase_pays_the_unit_price_times_quantity() {
+	purchase_ext().execute_with(|| {
+		assert_noop!(purchase_at(&purchase_inputs(1, 0), 0), Error::<Test>::InvalidQuantity);
+		assert_noop!(purchase_at(&purchase_inputs(1, 2), UNIT_PRICE), Error::<Test>::OneOfManyInvalid);
+		assert_ok!(purchase_at(&purchase_inputs(1, 2), UNIT_PRICE * 2));
+		assert_eq!(RwaSent::get(), vec![(rwa(1), UNIT_PRICE * 2, 2)]);
+		assert_noop!(purchase_at(&purchase_inputs(1, 2), UNIT_PRICE * 2), Error::<Test>::SerialAlreadyUsed);
+	});
+}
+
+#[test]
+fn purchase_draws_down_lane_units() {
+	purchase_ext().execute_with(|| {
+		assert_ok!(Proofs::set_rwa_units(RuntimeOrigin::root(), rwa(1), Some(3)));
+		assert_noop!(purchase_at(&purchase_inputs(1, 4), UNIT_PRICE * 4), Error::<Test>::InsufficientUnits);
+		assert_ok!(purchase_at(&purchase_inputs(1, 2), UNIT_PRICE * 2));
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(1));
+		assert_noop!(purchase_at(&purchase_inputs(2, 2), UNIT_PRICE * 2), Error::<Test>::InsufficientUnits);
+		assert_ok!(purchase_at(&purchase_inputs(2, 1), UNIT_PRICE));
+		assert_eq!(RwaUnits::<Test>::get(rwa(1)), Some(0));
+	});
+}
+
+#[test]
+fn restricted_asset_is_not_sold_on_this_lane() {
+	purchase_ext().execute_with(|| {
+		assert_ok!(Proofs::xcm_sync_rwa_eligibility(
+			RuntimeOrigin::signed(SOVEREIGN),
+			rwa(1),
+			Some(crate::EligibilityRequirement { registry_id: 0, required_class: 1 }),
+		));
+		assert_noop!(purchase_at(&purchase_inputs(1, 1), UNIT_PRICE), Error::<Test>::EligibilityRestricted);
+		assert_ok!(Proofs::xcm_sync_rwa_eligibility(RuntimeOrigin::signed(SOVEREIGN), rwa(1), None));
+		assert_ok!(purchase_at(&purchase_inputs(1, 1), UNIT_PRICE));
+	});
+}
+
+#[test]
+fn coin_group_pads_to_the_proof_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Proofs::coin_group(0), None);
+		let coins: Vec<[u8; 32]> = (1..=3).map(|n| deposit_coin(ALICE, n, 100)).collect();
+		let group = Proofs::coin_group(0).unwrap();
+		assert_eq!(group.coins, coins);
+		assert_eq!(group.padded.len(), 4);
+		assert_eq!(group.padded, MockVerifier::pad_group(&coins, 0));
+		assert_eq!(CoinLocation::<Test>::get(coins[2]), Some((0, 2)));
+		assert_eq!(Proofs::coin_group(1), None);
+	});
+}
+
+#[test]
+fn dry_run_matches_dispatch_without_changing_state() {
+	purchase_ext().execute_with(|| {
+		let valid = purchase_call(&purchase_inputs(1, 1), UNIT_PRICE);
+		assert_ok!(Proofs::dry_run(valid.clone()));
+		assert!(!SerialUsed::<Test>::get(serial(1)));
+
+		assert_ok!(purchase_at(&purchase_inputs(1, 1), UNIT_PRICE));
+		assert_eq!(Proofs::dry_run(valid), Err(Error::<Test>::SerialAlreadyUsed.into()));
+		let underpaid = purchase_inputs(2, 1);
+		assert_eq!(
+			Proofs::dry_run(purchase_call(&underpaid, UNIT_PRICE - 1)),
+			Err(Error::<Test>::OneOfManyInvalid.into())
+		);
+		assert_noop!(purchase_at(&underpaid, UNIT_PRICE - 1), Error::<Test>::OneOfManyInvalid);
+		// Signed calls are refused as they would be unsigned.
+		let deposit = crate::Call::<Test>::deposit_fee { coin: fee_coin(1), open_proof: vec![] };
+		assert_eq!(Proofs::dry_run(deposit), Err(sp_runtime::DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn rwa_listing_reports_the_lane_terms() {
+	purchase_ext().execute_with(|| {
+		let unlisted = RwaListing { price: 0, usd_cents: 0, units: None, eligibility: None };
+		assert_eq!(Proofs::rwa_listing(&rwa(2)), unlisted);
+		assert_ok!(Proofs::set_rwa_units(RuntimeOrigin::root(), rwa(1), Some(5)));
+		assert_ok!(Proofs::set_rwa_usd_price(RuntimeOrigin::root(), rwa(1), 250));
+		assert_eq!(
+			Proofs::rwa_listing(&rwa(1)),
+			RwaListing { price: UNIT_PRICE, usd_cents: 250, units: Some(5), eligibility: None }
+		);
+		let requirement = crate::EligibilityRequirement { registry_id: 0, required_class: 1 };
+		assert_ok!(Proofs::xcm_sync_rwa_eligibility(RuntimeOrigin::signed(SOVEREIGN), rwa(1), Some(requirement)));
+		assert_eq!(Proofs::rwa_listing(&rwa(1)).eligibility, Some(requirement));
+	});
+}
+
+const USD_CENTS: u64 = 250;
+
+/// Asset 1 priced at `USD_CENTS` per unit; oracle round 0 at 2 planck per cent.
+fn usd_ext() -> sp_io::TestExternalities {
+	let mut ext = purchase_ext();
+	ext.execute_with(|| {
+		assert_ok!(Proofs::set_rwa_usd_price(RuntimeOrigin::root(), rwa(1), USD_CENTS));
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 2));
+		assert_eq!(PriceOracle::current_round(), Some(0));
+	});
+	ext
+}
+
+fn usd_purchase(n: u8, quantity: u64, round: u32) -> CoinSpendPublic {
+	CoinSpendPublic { rate_round: Some(round), ..purchase_inputs(n, quantity) }
+}
+
+fn run_to(n: u64) {
+	System::set_block_number(n);
+	Proofs::on_initialize(n);
+}
+
+#[test]
+fn usd_priced_rwa_converts_at_the_named_round() {
+	usd_ext().execute_with(|| {
+		assert_noop!(purchase_at(&purchase_inputs(1, 3), 1_500), Error::<Test>::RateRoundRequired);
+		assert_noop!(purchase_at(&usd_purchase(1, 3, 1), 1_500), Error::<Test>::RateUnavailable);
+		// The planck price no longer applies once the RWA is priced in USD.
+		assert_noop!(purchase_at(&usd_purchase(1, 3, 0), UNIT_PRICE * 3), Error::<Test>::OneOfManyInvalid);
+		assert_ok!(purchase_at(&usd_purchase(1, 3, 0), USD_CENTS * 2 * 3));
+		assert_eq!(RwaSent::get(), vec![(rwa(1), 1_500, 3)]);
+	});
+}
+
+#[test]
+fn replaced_round_is_refused_after_grace_period() {
+	usd_ext().execute_with(|| {
+		// NULLA falls: round 1 asks twice as many planck as round 0.
+		run_to(4);
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 4));
+		assert_eq!(PriceOracle::current_round(), Some(1));
+
+		// Spends built against round 0 still land during the grace period...
+		run_to(7);
+		assert_ok!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2));
+		// ...but a buyer cannot keep picking the cheaper round after it.
+		run_to(8);
+		assert_noop!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2), Error::<Test>::RateUnavailable);
+		assert_noop!(purchase_at(&usd_purchase(2, 1, 1), USD_CENTS * 2), Error::<Test>::OneOfManyInvalid);
+		assert_ok!(purchase_at(&usd_purchase(2, 1, 1), USD_CENTS * 4));
+	});
+}
+
+#[test]
+fn unconfirmed_round_goes_stale() {
+	usd_ext().execute_with(|| {
+		run_to(21);
+		assert_ok!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2));
+		run_to(22);
+		assert_noop!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2), Error::<Test>::RateUnavailable);
+		// An unchanged report re-confirms the round rather than opening one.
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), 2));
+		assert_eq!(PriceOracle::current_round(), Some(0));
+		assert_ok!(purchase_at(&usd_purchase(2, 1, 0), USD_CENTS * 2));
+	});
+}
+
+#[test]
+fn price_overflow_is_rejected() {
+	usd_ext().execute_with(|| {
+		// usd_cents × planck_per_cent overflows.
+		assert_ok!(PriceOracle::report(RuntimeOrigin::signed(REPORTER), u64::MAX / 100));
+		assert_noop!(purchase_at(&usd_purchase(1, 1, 1), 0), Error::<Test>::PriceOverflow);
+		// unit price × quantity overflows.
+		assert_ok!(Proofs::set_rwa_usd_price(RuntimeOrigin::root(), rwa(1), 0));
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), u64::MAX / 2 + 1));
+		assert_noop!(purchase_at(&purchase_inputs(1, 2), 0), Error::<Test>::PriceOverflow);
+	});
+}
+
+#[test]
+fn zero_price_sync_delists_usd_priced_rwa() {
+	usd_ext().execute_with(|| {
+		assert_noop!(
+			Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(MALLORY), rwa(1), 0),
+			Error::<Test>::NotRwaChainSovereign
+		);
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), 0));
+		assert_eq!(Proofs::rwa_usd_prices(rwa(1)), 0);
+		assert_noop!(purchase_at(&usd_purchase(1, 1, 0), USD_CENTS * 2), Error::<Test>::RwaPriceNotSet);
+
+		// Re-listed by the RWA chain, it sells at the synced planck price.
+		assert_ok!(Proofs::xcm_sync_rwa_price(RuntimeOrigin::signed(SOVEREIGN), rwa(1), UNIT_PRICE));
+		assert_ok!(purchase_at(&purchase_inputs(1, 1), UNIT_PRICE));
+	});
+}
+
+/// Inserts commitment `[n; 32]` into the Merkle tree with `deposit_public`.
+fn insert_leaf(n: u8) {
+	let commitment = [n; 32];
+	let proof = vec![commitment].encode();
+	let mut range_proof = vec![1u8];
+	range_proof.extend_from_slice(&(proof.len() as u32).to_le_bytes());
+	range_proof.extend_from_slice(&proof);
+	range_proof.extend_from_slice(&1u64.to_le_bytes());
+	assert_ok!(Proofs::deposit_public(
+		RuntimeOrigin::signed(ALICE),
+		commitment.to_vec(),
+		range_proof.try_into().unwrap(),
+		Default::default(),
+	));
+}
+
+/// One leaf per block from block 1 to `last`; returns the root current at
+/// the end of each block, indexed by block.
+fn roots_through(last: u64) -> Vec<[u8; 32]> {
+	let mut roots = vec![[0u8; 32]];
+	for n in 1..=last {
+		if n > 1 { run_to(n); }
+		insert_leaf(n as u8);
+		roots.push(Proofs::current_root());
+	}
+	roots
+}
+
+#[test]
+fn lane_state_queries_track_the_tree() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(3);
+		assert_eq!(CommitmentIndex::<Test>::get([3u8; 32]), Some(2));
+		assert_eq!(Proofs::recent_roots(), vec![(0, [0u8; 32]), (1, roots[1]), (2, roots[2])]);
+		assert_eq!(Proofs::oldest_acceptable_root(), [0u8; 32]);
+		assert!(Proofs::root_acceptable(&roots[3]));
+		assert!(!Proofs::root_acceptable(&[0xee; 32]));
+	});
+}
+
+#[test]
+fn history_keeps_one_root_per_block() {
+	new_test_ext().execute_with(|| {
+		insert_leaf(1);
+		let end_of_1 = Proofs::current_root();
+		run_to(2);
+		insert_leaf(2);
+		let intermediate = Proofs::current_root();
+		insert_leaf(3);
+		insert_leaf(4);
+
+		assert_eq!(RootHistory::<Test>::get(1), Some(end_of_1));
+		assert_eq!(RootHistory::<Test>::get(2), None);
+		assert_eq!(RootRecordedAt::<Test>::get(end_of_1), Some(1));
+		assert_eq!(RootRecordedAt::<Test>::get(intermediate), None);
+		// A root that was current only within a block is never acceptable
+		// once it is replaced.
+		assert!(!Proofs::root_acceptable(&intermediate));
+		assert!(Proofs::root_acceptable(&end_of_1));
+		// The first deposit recorded the empty root at block 0.
+		assert_eq!(Proofs::recent_roots(), vec![(0, [0u8; 32]), (1, end_of_1)]);
+	});
+}
+
+#[test]
+fn root_expires_after_retention_blocks() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(2);
+		// Recorded at block 1; retention is 5.
+		run_to(6);
+		assert!(Proofs::root_acceptable(&roots[1]));
+		run_to(7);
+		assert!(!Proofs::root_acceptable(&roots[1]));
+		assert_eq!(RootHistory::<Test>::get(1), None);
+		assert_eq!(RootRecordedAt::<Test>::get(roots[1]), None);
+		// The current root never expires.
+		run_to(50);
+		assert!(Proofs::root_acceptable(&roots[2]));
+	});
+}
+
+#[test]
+fn retention_cut_prunes_skipped_blocks() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(6);
+		run_to(7);
+		assert_eq!(RootHistory::<Test>::get(1), None);
+		assert_noop!(
+			Proofs::set_root_retention(RuntimeOrigin::root(), 0),
+			Error::<Test>::InvalidRootRetention
+		);
+		assert_noop!(
+			Proofs::set_root_retention(RuntimeOrigin::root(), 101),
+			Error::<Test>::InvalidRootRetention
+		);
+
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::RootRetentionSet { blocks: 2 }.into());
+		for at in 2..=4 {
+			assert_eq!(RootHistory::<Test>::get(at), None);
+			assert_eq!(RootRecordedAt::<Test>::get(roots[at as usize]), None);
+		}
+		assert_eq!(RootHistory::<Test>::get(5), Some(roots[5]));
+		assert!(Proofs::root_acceptable(&roots[5]));
+		assert!(!Proofs::root_acceptable(&roots[4]));
+
+		// `on_initialize` carries on from the new window.
+		run_to(8);
+		assert_eq!(RootHistory::<Test>::get(5), None);
+		assert!(Proofs::root_acceptable(&roots[6]));
+	});
+}
+
+#[test]
+fn retention_raise_does_not_restore_pruned_roots() {
+	new_test_ext().execute_with(|| {
+		let roots = roots_through(6);
+		run_to(7);
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 2));
+		assert_ok!(Proofs::set_root_retention(RuntimeOrigin::root(), 10));
+		// Within ten blocks, but pruned by the cut.
+		assert!(!Proofs::root_acceptable(&roots[3]));
+		// Still held, so the raise extends it.
+		run_to(14);
+		assert!(Proofs::root_acceptable(&roots[5]));
+		run_to(16);
+		assert!(!Proofs::root_acceptable(&roots[5]));
+	});
+}
+
+#[test]
+fn oldest_acceptable_root_tracks_the_window() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Proofs::oldest_acceptable_root(), [0u8; 32]);
+		let roots = roots_through(5);
+		run_to(6);
+		assert_eq!(Proofs::oldest_acceptable_root(), roots[1]);
+		run_to(7);
+		assert_eq!(Proofs::oldest_acceptable_root(), roots[2]);
+		run_to(20);
+		assert_eq!(Proofs::oldest_acceptable_root(), roots[5]);
+	});
+}
+
+#[test]
+fn upgrade_moves_recent_roots_into_history() {
+	new_test_ext().execute_with(|| {
+		let window: BoundedVec<[u8; 32], ConstU32<64>> =
+			(1..=8u8).map(|n| [n; 32]).collect::<Vec<_>>().try_into().unwrap();
+		frame_support::storage::migration::put_storage_value(b"Proofs", b"RecentRoots", &[], window);
+		StorageVersion::new(1).put::<Proofs>();
+		run_to(10);
+
+		Proofs::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get::<Proofs>(), 2);
+		assert!(!frame_support::storage::migration::have_storage_value(b"Proofs", b"RecentRoots", &[]));
+		// Newest at `now - 2`, one block apart, back to the retention floor.
+		assert_eq!(RootHistory::<Test>::get(8), Some([8u8; 32]));
+		assert_eq!(RootHistory::<Test>::get(5), Some([5u8; 32]));
+		assert_eq!(RootHistory::<Test>::get(4), None);
+		assert!(Proofs::root_acceptable(&[5u8; 32]));
+		assert!(!Proofs::root_acceptable(&[4u8; 32]));
+	});
+}
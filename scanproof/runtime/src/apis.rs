@@ -47,9 +47,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce, ParachainSystem,
-	Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
-	SLOT_DURATION, VERSION,
+	AccountId, Balance, Block, BlockNumber, ConsensusHook, Executive, InherentDataExt, Nonce,
+	ParachainSystem, Proofs, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System,
+	TransactionPayment, SLOT_DURATION, VERSION,
 };
 
 // we move some impls outside so we can easily use them with `docify`.
@@ -227,6 +227,64 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_proofs_runtime_api::PrivacyLaneApi<Block, BlockNumber, pallet_proofs::Call<Runtime>> for Runtime {
+		fn current_root() -> [u8; 32] {
+			pallet_proofs::CurrentRoot::<Runtime>::get()
+		}
+
+		fn leaf_count() -> u32 {
+			pallet_proofs::Leaves::<Runtime>::decode_len().unwrap_or(0) as u32
+		}
+
+		fn recent_roots() -> Vec<(BlockNumber, [u8; 32])> {
+			Proofs::recent_roots()
+		}
+
+		fn oldest_acceptable_root() -> [u8; 32] {
+			Proofs::oldest_acceptable_root()
+		}
+
+		fn root_acceptable(root: [u8; 32]) -> bool {
+			Proofs::root_acceptable(&root)
+		}
+
+		fn leaf_index(commitment: [u8; 32]) -> Option<u32> {
+			pallet_proofs::CommitmentIndex::<Runtime>::get(commitment)
+		}
+
+		fn coin_location(coin: [u8; 32]) -> Option<(u32, u32)> {
+			pallet_proofs::CoinLocation::<Runtime>::get(coin)
+		}
+
+		fn nullifier_used(nullifier: [u8; 32]) -> bool {
+			pallet_proofs::NullifierUsed::<Runtime>::get(nullifier)
+		}
+
+		fn serial_used(serial: [u8; 32]) -> bool {
+			pallet_proofs::SerialUsed::<Runtime>::get(serial)
+		}
+
+		fn current_group() -> u32 {
+			pallet_proofs::CurrentGroup::<Runtime>::get()
+		}
+
+		fn coin_group(group_id: u32) -> Option<pallet_proofs::CoinGroup> {
+			Proofs::coin_group(group_id)
+		}
+
+		fn rwa_listing(rwa_id: [u8; 32]) -> pallet_proofs::RwaListing {
+			Proofs::rwa_listing(&rwa_id)
+		}
+
+		fn access_config(app_id: [u8; 32]) -> Option<pallet_proofs::AppConfig> {
+			pallet_proofs::AccessKeyConfigs::<Runtime>::get(app_id)
+		}
+
+		fn dry_run(call: pallet_proofs::Call<Runtime>) -> Result<(), sp_runtime::DispatchError> {
+			Proofs::dry_run(call)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
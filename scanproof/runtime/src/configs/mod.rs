@@ -63,8 +63,8 @@ use super::{
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, CollatorSelection, ConsensusHook, Hash,
 	MessageQueue, Nonce, PalletInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent,
 	RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask, Session, SessionKeys,
-	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, EXISTENTIAL_DEPOSIT, HOURS,
-	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
+	System, WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, CENTS, DAYS, EXISTENTIAL_DEPOSIT, HOURS,
+	MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, MINUTES, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
 };
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -359,10 +359,54 @@ impl pallet_proofs::Config for Runtime {
 	type PoolAccount = PrivacyPoolAccount;
 	type RwaDispatch = RwaXcmDispatch;
 	type AccessDispatch = AccessGateXcmDispatch;
+	type BaseFee = PrivateBaseFee;
+	type FeeBurn = PrivateFeeBurn;
+	type FeePayer = PaymasterFeePayer;
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type RwaChainSovereign = RwaChainSovereign;
+	type FiatRate = OracleFiatRate;
+	type DefaultRootRetention = DefaultRootRetention;
+	type MaxRootRetention = MaxRootRetention;
+}
+
+/// Configure the NULLA/USD price feed.
+impl pallet_price_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxReporters = ConstU32<16>;
+	type MinReports = ConstU32<3>;
+	type MaxReportAge = PriceReportMaxAge;
+	type MaxRoundAge = PriceRoundMaxAge;
+	type RoundGracePeriod = PriceRoundGracePeriod;
+}
+
+parameter_types! {
+	/// Reports older than ten minutes are left out of the median.
+	pub const PriceReportMaxAge: BlockNumber = 10 * MINUTES;
+	/// A rate nobody re-confirmed for an hour is stale.
+	pub const PriceRoundMaxAge: BlockNumber = HOURS;
+	/// Spends built against a superseded round stay valid for ten blocks.
+	pub const PriceRoundGracePeriod: BlockNumber = 10;
+}
+
+/// Lane `FiatRate` backed by the oracle's rate rounds.
+pub struct OracleFiatRate;
+impl pallet_proofs::FiatRate for OracleFiatRate {
+	fn planck_per_cent(round: u32) -> Option<u64> {
+		pallet_price_oracle::Pallet::<Runtime>::rate(round)
+	}
 }
 
 // Runtime proof verifier wired to the local `verifier` crate.
 pub struct RuntimeProofVerifier;
+impl RuntimeProofVerifier {
+	/// Whether `proof` carries a registered version of `air` that governance
+	/// has not retired.
+	fn accepts(proof: &[u8], air: verifier::envelope::AirId) -> bool {
+		verifier::envelope::version_of(proof, air)
+			.is_some_and(|v| !pallet_proofs::RetiredProofVersions::<Runtime>::get((air as u8, v)))
+	}
+}
 impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 	fn verify(proof: &[u8], public_inputs: &[u8]) -> bool {
 		verifier::verify_bytes(proof, public_inputs)
@@ -386,6 +430,9 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 	) -> bool {
 		verifier::verify_range_proof(range_proof, commitments, public_inputs, nbits)
 	}
+	fn verify_range_proofs(items: &[(&[u8], &[[u8; 32]], &[u8])], nbits: u32) -> bool {
+		verifier::verify_range_proofs(items, nbits)
+	}
 	fn pedersen_subtract(c_a: &[u8; 32], c_b: &[u8; 32]) -> Option<[u8; 32]> {
 		verifier::pedersen_subtract(c_a, c_b)
 	}
@@ -400,7 +447,8 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 		change: &[u8; 32],
 		context: &[u8],
 	) -> bool {
-		verifier::one_of_many::verify(proof, coins, serial, price, change, context)
+		Self::accepts(proof, verifier::envelope::AirId::OneOfMany)
+			&& verifier::one_of_many::verify(proof, coins, serial, price, change, context)
 	}
 	fn verify_deposit_open(coin: &[u8; 32], amount: u64, proof: &[u8], context: &[u8]) -> bool {
 		verifier::one_of_many::verify_deposit_open(coin, amount, proof, context)
@@ -414,7 +462,26 @@ impl pallet_proofs::ProofVerify for RuntimeProofVerifier {
 }
 
 parameter_types! {
+	pub const PrivateBaseFee: Balance = MICRO_UNIT;
+	/// Half of every consumed base fee is burned, the rest goes to the block author.
+	pub const PrivateFeeBurn: Perbill = Perbill::from_percent(50);
+	/// A root stays acceptable for an hour after it stops being current,
+	/// until governance sets `RootRetention`.
+	pub const DefaultRootRetention: BlockNumber = HOURS;
+	pub const MaxRootRetention: BlockNumber = 7 * DAYS;
+	pub const PaymasterPalletId: PalletId = PalletId(*b"nll/pay0");
 	pub const PoolPalletId: PalletId = PalletId(*b"nll/pool");
+	/// Sovereign account of the RWA parachain (para 2001) on this chain.
+	/// Computed as: Sibling(2001).into_account_truncating().
+	pub RwaChainSovereign: AccountId = {
+		use polkadot_parachain_primitives::primitives::Sibling;
+		Sibling::from(2001u32).into_account_truncating()
+	};
+}
+
+pub struct PaymasterFeePayer;
+impl frame_support::traits::Get<AccountId> for PaymasterFeePayer {
+	fn get() -> AccountId { PaymasterPalletId::get().into_account_truncating() }
 }
 
 pub struct PrivacyPoolAccount;
@@ -439,6 +506,7 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 		_note_value: u64,
 		tx_id: [u8; 16],
 		ownership_commitment: [u8; 32],
+		units: u64,
 	) {
 		use codec::Encode;
 		use xcm::latest::prelude::*;
@@ -448,14 +516,17 @@ impl pallet_proofs::RwaPurchaseDispatch for RwaXcmDispatch {
 
 		// Encode call: [pallet_index=51][call_index=4][args SCALE]
 		// Matches RWA runtime: pallet_index(51) = RwaMarketplace, call_index(4) = xcm_record_purchase
+		// The `buyer` argument is zero-filled: the buyer's AccountId never travels cross-chain.
 		let mut call_data = alloc::vec::Vec::new();
 		call_data.push(51u8); // RwaMarketplace pallet index
 		call_data.push(4u8);  // xcm_record_purchase call index
 		asset_id.encode_to(&mut call_data);
+		[0u8; 32].encode_to(&mut call_data); // buyer (withheld)
 		spend_tag.encode_to(&mut call_data);
 		nullifier.encode_to(&mut call_data);
 		tx_id.encode_to(&mut call_data);
 		ownership_commitment.encode_to(&mut call_data);
+		units.encode_to(&mut call_data);
 
 		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2001)]));
 		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
@@ -499,6 +570,7 @@ impl pallet_proofs::AccessKeyDispatch for AccessGateXcmDispatch {
 		nullifier: [u8; 32],
 		tx_id: [u8; 16],
 		access_key_commitment: [u8; 32],
+		terms: Option<pallet_proofs::AccessTerms>,
 	) {
 		use codec::Encode;
 		use xcm::latest::prelude::*;
@@ -512,6 +584,7 @@ impl pallet_proofs::AccessKeyDispatch for AccessGateXcmDispatch {
 		nullifier.encode_to(&mut call_data);
 		tx_id.encode_to(&mut call_data);
 		access_key_commitment.encode_to(&mut call_data);
+		terms.encode_to(&mut call_data);
 
 		let dest: Location = Location::new(1, Junctions::from([Junction::Parachain(2003)]));
 		let xcm_msg: xcm::latest::Xcm<()> = Xcm::<()>(alloc::vec![
@@ -120,6 +120,14 @@ impl Contains<Location> for ParentOrParentsExecutivePlurality {
 	}
 }
 
+/// Allow unpaid execution from the RWA sibling parachain (para 2001).
+pub struct RwaChainLocation;
+impl Contains<Location> for RwaChainLocation {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (1, [Parachain(2001)]))
+	}
+}
+
 pub type Barrier = TrailingSetTopicAsId<
 	(
 		TakeWeightCredit,
@@ -128,6 +136,8 @@ pub type Barrier = TrailingSetTopicAsId<
 				AllowTopLevelPaidExecutionFrom<Everything>,
 				AllowExplicitUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
 				// ^^^ Parent and its exec plurality get free execution
+				// RWA chain (para 2001) sends unpaid Transact to sync RWA prices.
+				AllowExplicitUnpaidExecutionFrom<RwaChainLocation>,
 			),
 			UniversalLocation,
 			ConstU32<8>,
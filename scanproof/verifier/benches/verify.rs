@@ -0,0 +1,91 @@
+//! Verification time for the `deposit_public` and `submit_proof` paths.
+//!
+//! cargo bench
+
+use blake2::{Blake2b512, Digest};
+use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
+use criterion::{criterion_group, criterion_main, Criterion};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as G;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek_ng::constants::RISTRETTO_BASEPOINT_POINT as G_NG;
+use curve25519_dalek_ng::ristretto::CompressedRistretto as CompressedRistrettoNG;
+use curve25519_dalek_ng::scalar::Scalar as ScalarNG;
+use merlin::Transcript;
+use parity_scale_codec::Encode;
+use rand_core::OsRng;
+use scanproof_verifier::{generators, verify_bytes, verify_commitment, verify_range_proof};
+
+fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let mut h = Blake2b512::new();
+    Digest::update(&mut h, data);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&h.finalize()[..32]);
+    out
+}
+
+/// Aggregated range proof over a power-of-two number of `values`, bound to
+/// `public_inputs` as the wallet binds it.
+fn prove_range(values: &[u64], blindings: &[[u8; 32]], public_inputs: &[u8]) -> (Vec<u8>, Vec<[u8; 32]>) {
+    let mut t = Transcript::new(b"NULLA_BULLETPROOF_RANGE");
+    t.append_message(b"pi_hash", &blake2_256(public_inputs));
+    let rs: Vec<ScalarNG> = blindings.iter().map(|b| ScalarNG::from_bytes_mod_order(*b)).collect();
+    let bp_gens = BulletproofGens::new(64, values.len());
+    let h = CompressedRistrettoNG(generators::H_COMPRESSED).decompress().expect("baked H");
+    let pc_gens = PedersenGens { B: G_NG, B_blinding: h };
+    let (proof, cmts) =
+        RangeProof::prove_multiple_with_rng(&bp_gens, &pc_gens, &mut t, values, &rs, 64, &mut OsRng).expect("prove");
+    (proof.to_bytes(), cmts.iter().map(|c| c.to_bytes()).collect())
+}
+
+fn deposit_public(c: &mut Criterion) {
+    let (proof, cmts) = prove_range(&[1_000], &[[0x11; 32]], &[]);
+    let mut group = c.benchmark_group("deposit_public");
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            assert!(verify_range_proof(&proof, &cmts, &[], 64));
+            assert!(verify_commitment(1_000, [0x11; 32], cmts[0]));
+        })
+    });
+    group.finish();
+}
+
+fn submit_proof(c: &mut Criterion) {
+    // One 1_000 input split into two outputs; outputs carry the range proof
+    // and the Schnorr balance proof shows Σin − Σout = w·H.
+    let h = generators::get().h;
+    let r_in = Scalar::from_bytes_mod_order([0x44; 32]);
+    let (r1, r2) = ([0x22; 32], [0x33; 32]);
+    let c_in = (Scalar::from(1_000u64) * G + r_in * h).compress().to_bytes();
+    let (_, outs) = prove_range(&[400, 600], &[r1, r2], &[]);
+    let pi = (
+        [0u8; 32], [0u8; 32], vec![c_in], vec![0u32],
+        vec![Vec::<[u8; 32]>::new()], vec![[0x66u8; 32]], outs, [0x77u8; 16],
+    )
+        .encode();
+    let (range, cmts) = prove_range(&[400, 600], &[r1, r2], &pi);
+
+    let w = r_in - Scalar::from_bytes_mod_order(r1) - Scalar::from_bytes_mod_order(r2);
+    let k = Scalar::from_bytes_mod_order([0x55; 32]);
+    let r_pt = k * h;
+    let mut t = Transcript::new(b"NULLA_SCHNORR_BALANCE");
+    t.append_message(b"pi_hash", &blake2_256(&pi));
+    t.append_message(b"R", &r_pt.compress().to_bytes());
+    t.append_message(b"AGG", &(w * h).compress().to_bytes());
+    let mut buf = [0u8; 64];
+    t.challenge_bytes(b"c", &mut buf);
+    let ch = Scalar::from_bytes_mod_order_wide(&buf);
+    let mut balance = r_pt.compress().to_bytes().to_vec();
+    balance.extend_from_slice(&(k + ch * w).to_bytes());
+
+    let mut group = c.benchmark_group("submit_proof");
+    group.bench_function("verify", |b| {
+        b.iter(|| {
+            assert!(verify_range_proof(&range, &cmts, &pi, 64));
+            assert!(verify_bytes(&balance, &pi));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, deposit_public, submit_proof);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+// Bakes the fixed hash-to-point generators into `$OUT_DIR/generators.rs` so the
+// verifier never runs SHA-512 / SHAKE256 + Elligator for them at runtime. The
+// verifier only decompresses these bytes (once) and unit tests re-derive them.
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use sha2::{Digest, Sha512};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::{env, fmt::Write as _, fs, path::Path};
+
+fn hash_to_point(label: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512::new();
+    hasher.update(label);
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&hasher.finalize());
+    RistrettoPoint::from_uniform_bytes(&bytes).compress().to_bytes()
+}
+
+/// First `n` points of the Bulletproofs `GeneratorsChain` for `label`:
+/// SHAKE256("GeneratorsChain" ‖ label), 64 bytes per point.
+fn generators_chain(label: &[u8], n: usize) -> Vec<[u8; 32]> {
+    let mut shake = Shake256::default();
+    shake.update(b"GeneratorsChain");
+    shake.update(label);
+    let mut reader = shake.finalize_xof();
+    (0..n)
+        .map(|_| {
+            let mut bytes = [0u8; 64];
+            reader.read(&mut bytes);
+            RistrettoPoint::from_uniform_bytes(&bytes).compress().to_bytes()
+        })
+        .collect()
+}
+
+fn emit(out: &mut String, doc: &str, name: &str, bytes: &[u8; 32]) {
+    writeln!(out, "/// {doc}").unwrap();
+    write!(out, "pub const {name}: [u8; 32] = [").unwrap();
+    for (i, b) in bytes.iter().enumerate() {
+        if i % 8 == 0 { out.push_str("\n    "); }
+        write!(out, "0x{b:02x}, ").unwrap();
+    }
+    out.push_str("\n];\n\n");
+}
+
+/// Per-party generator table: `label` is `[tag, party u32 LE]`, as in
+/// `bulletproofs::BulletproofGens`.
+fn emit_party_table(out: &mut String, doc: &str, name: &str, tag: u8, parties: usize, bits: usize) {
+    writeln!(out, "/// {doc}").unwrap();
+    writeln!(out, "pub static {name}: [[[u8; 32]; {bits}]; {parties}] = [").unwrap();
+    for party in 0..parties {
+        let mut label = [tag, 0, 0, 0, 0];
+        label[1..].copy_from_slice(&(party as u32).to_le_bytes());
+        out.push_str("    [\n");
+        for point in generators_chain(&label, bits) {
+            out.push_str("        [");
+            for b in point { write!(out, "0x{b:02x}, ").unwrap(); }
+            out.push_str("],\n");
+        }
+        out.push_str("    ],\n");
+    }
+    out.push_str("];\n\n");
+}
+
+fn main() {
+    let mut out = String::new();
+    emit(&mut out, "H = hash-to-point(\"VERIFIER_H_GENERATOR\") (blinding generator).", "H_COMPRESSED", &hash_to_point(b"VERIFIER_H_GENERATOR"));
+    emit(&mut out, "G1 = hash-to-point(\"NULLA_SERIAL_GENERATOR\") (serial generator).", "G1_COMPRESSED", &hash_to_point(b"NULLA_SERIAL_GENERATOR"));
+    emit_party_table(&mut out, "Bulletproofs G generators, `[party][bit]`.", "BP_G_COMPRESSED", b'G', 8, 64);
+    emit_party_table(&mut out, "Bulletproofs H generators, `[party][bit]`.", "BP_H_COMPRESSED", b'H', 8, 64);
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("generators.rs");
+    fs::write(dest, out).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -26,12 +26,129 @@ pub struct ProofPublicInputs {
 }
 
 fn generator_h() -> RistrettoPoint {
-    let mut hasher = Sha512::new();
-    sha2::Digest::update(&mut hasher, b"VERIFIER_H_GENERATOR");
-    let out = hasher.finalize();
-    let mut bytes = [0u8; 64];
-    bytes.copy_from_slice(&out);
-    RistrettoPoint::from_uniform_bytes(&bytes)
+    generators::get().h
+}
+
+// -------------------------------------------------------------------
+//  Generator tables
+//
+//  H and G1 are fixed hash-to-point outputs, and the Bulletproofs G/H
+//  vectors are the first `MAX_RANGE_BITS` points of each party's SHAKE256
+//  generator chain; build.rs bakes all of their compressed encodings into
+//  `$OUT_DIR/generators.rs`, so the verifier only pays decompression. H and
+//  G1 are decompressed once on first use and shared by every entry point.
+//  Party tables are decompressed per party the first time a proof over
+//  that many parties is verified, so a one-party deposit proof never pays
+//  for the eight-party table.
+// -------------------------------------------------------------------
+pub mod generators {
+    use super::*;
+    use alloc::boxed::Box;
+    use core::sync::atomic::{AtomicPtr, Ordering};
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+    use sha3::Shake256;
+
+    include!(concat!(env!("OUT_DIR"), "/generators.rs"));
+
+    /// Parties with a baked generator table. Aggregated proofs over more
+    /// commitments derive the remaining parties' tables per call.
+    pub const MAX_RANGE_PARTIES: usize = 8;
+    /// Bit capacity of each party's generator table.
+    pub const MAX_RANGE_BITS: usize = 64;
+
+    pub struct Generators {
+        /// Blinding generator H.
+        pub h: RistrettoPoint,
+        /// Serial generator G1.
+        pub g1: RistrettoPoint,
+    }
+
+    impl Generators {
+        fn build() -> Self {
+            let h = CompressedRistretto(H_COMPRESSED).decompress().expect("baked H is a valid point");
+            let g1 = CompressedRistretto(G1_COMPRESSED).decompress().expect("baked G1 is a valid point");
+            Self { h, g1 }
+        }
+    }
+
+    /// Bulletproofs generators of one party: `g[i]`, `h[i]` weight bit `i`
+    /// of that party's value.
+    pub struct PartyGens {
+        pub g: Vec<RistrettoPoint>,
+        pub h: Vec<RistrettoPoint>,
+    }
+
+    impl PartyGens {
+        fn baked(party: usize) -> Self {
+            let decompress = |table: &[[u8; 32]; MAX_RANGE_BITS]| {
+                table
+                    .iter()
+                    .map(|p| CompressedRistretto(*p).decompress().expect("baked generator is a valid point"))
+                    .collect()
+            };
+            Self { g: decompress(&BP_G_COMPRESSED[party]), h: decompress(&BP_H_COMPRESSED[party]) }
+        }
+
+        /// Derive `party`'s table from its `GeneratorsChain`, as
+        /// `bulletproofs::BulletproofGens` does.
+        pub fn derive(party: usize) -> Self {
+            let chain = |tag: u8| {
+                let mut label = [tag, 0, 0, 0, 0];
+                label[1..].copy_from_slice(&(party as u32).to_le_bytes());
+                let mut shake = Shake256::default();
+                shake.update(b"GeneratorsChain");
+                shake.update(label);
+                let mut reader = shake.finalize_xof();
+                (0..MAX_RANGE_BITS)
+                    .map(|_| {
+                        let mut bytes = [0u8; 64];
+                        reader.read(&mut bytes);
+                        RistrettoPoint::from_uniform_bytes(&bytes)
+                    })
+                    .collect()
+            };
+            Self { g: chain(b'G'), h: chain(b'H') }
+        }
+    }
+
+    static GENERATORS: AtomicPtr<Generators> = AtomicPtr::new(core::ptr::null_mut());
+
+    static PARTY_GENS: [AtomicPtr<PartyGens>; MAX_RANGE_PARTIES] =
+        [const { AtomicPtr::new(core::ptr::null_mut()) }; MAX_RANGE_PARTIES];
+
+    /// Return the value published in `slot`, building and publishing it on
+    /// first use.
+    ///
+    /// Racing initialisers each build a value; the first to publish wins
+    /// and the others drop theirs, so no locking is needed in `no_std`.
+    fn get_or_publish<T>(slot: &AtomicPtr<T>, build: impl FnOnce() -> T) -> &'static T {
+        let cur = slot.load(Ordering::Acquire);
+        if !cur.is_null() {
+            // SAFETY: a published pointer is never freed or mutated.
+            return unsafe { &*cur };
+        }
+        let fresh = Box::into_raw(Box::new(build()));
+        match slot.compare_exchange(core::ptr::null_mut(), fresh, Ordering::AcqRel, Ordering::Acquire) {
+            // SAFETY: `fresh` is now published and lives for the rest of the program.
+            Ok(_) => unsafe { &*fresh },
+            Err(existing) => {
+                // SAFETY: `fresh` was never published; `existing` is never freed.
+                drop(unsafe { Box::from_raw(fresh) });
+                unsafe { &*existing }
+            }
+        }
+    }
+
+    /// Shared generators, built on first use.
+    pub fn get() -> &'static Generators {
+        get_or_publish(&GENERATORS, Generators::build)
+    }
+
+    /// Shared baked table of `party`, or `None` past `MAX_RANGE_PARTIES`.
+    pub fn party_gens(party: usize) -> Option<&'static PartyGens> {
+        let slot = PARTY_GENS.get(party)?;
+        Some(get_or_publish(slot, || PartyGens::baked(party)))
+    }
 }
 
 fn decompress_point(bytes: &[u8; 32]) -> Option<RistrettoPoint> {
@@ -119,61 +236,275 @@ pub fn verify_bytes(proof: &[u8], public_inputs: &[u8]) -> bool {
     lhs == rhs
 }
 
-use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
-use curve25519_dalek_ng::constants::RISTRETTO_BASEPOINT_POINT as G_NG;
-use curve25519_dalek_ng::ristretto::{CompressedRistretto as CompressedRistrettoNG, RistrettoPoint as RistrettoPointNG};
+// -------------------------------------------------------------------
+//  Bulletproofs range proofs
+//
+//  Proofs use the dalek `bulletproofs` 4 wire format and transcript
+//  protocol, over Pedersen generators B = basepoint, B_blinding = H and
+//  the baked party tables. Each proof's verification equation is folded
+//  into one multiscalar check: the challenge `c` that combines its two
+//  relations is drawn from the proof transcript after the whole proof is
+//  absorbed, and a batch weights each item by a challenge over every item,
+//  so the shared G/H/B/B_blinding terms are multiplied once per batch.
+// -------------------------------------------------------------------
+
+/// One range verification: `(range_proof, commitments, public_inputs)`.
+pub type RangeProofItem<'a> = (&'a [u8], &'a [[u8; 32]], &'a [u8]);
 
-pub fn verify_range_proof(
+struct RangeProof {
+    a: CompressedRistretto,
+    s: CompressedRistretto,
+    t_1: CompressedRistretto,
+    t_2: CompressedRistretto,
+    t_x: Scalar,
+    t_x_blinding: Scalar,
+    e_blinding: Scalar,
+    l_r: Vec<(CompressedRistretto, CompressedRistretto)>,
+    ipp_a: Scalar,
+    ipp_b: Scalar,
+}
+
+impl RangeProof {
+    /// Parse A, S, T_1, T_2, t_x, t_x_blinding, e_blinding, the inner
+    /// product rounds (L, R) and the final a, b. Scalars must be canonical.
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if !bytes.len().is_multiple_of(32) || bytes.len() < 9 * 32 || !(bytes.len() / 32 - 9).is_multiple_of(2) { return None; }
+        let word = |i: usize| -> [u8; 32] {
+            let mut w = [0u8; 32];
+            w.copy_from_slice(&bytes[i * 32..(i + 1) * 32]);
+            w
+        };
+        let point = |i: usize| CompressedRistretto(word(i));
+        let scalar = |i: usize| Option::<Scalar>::from(Scalar::from_canonical_bytes(word(i)));
+        let lg_n = (bytes.len() / 32 - 9) / 2;
+        if lg_n >= 32 { return None; }
+        Some(Self {
+            a: point(0),
+            s: point(1),
+            t_1: point(2),
+            t_2: point(3),
+            t_x: scalar(4)?,
+            t_x_blinding: scalar(5)?,
+            e_blinding: scalar(6)?,
+            l_r: (0..lg_n).map(|k| (point(7 + 2 * k), point(8 + 2 * k))).collect(),
+            ipp_a: scalar(7 + 2 * lg_n)?,
+            ipp_b: scalar(8 + 2 * lg_n)?,
+        })
+    }
+}
+
+/// Append a proof point, rejecting the identity encoding as the prover's
+/// `validate_and_append_point` does.
+fn append_proof_point(t: &mut Transcript, label: &'static [u8], p: &CompressedRistretto) -> Option<()> {
+    if p.to_bytes() == [0u8; 32] { return None; }
+    t.append_message(label, p.as_bytes());
+    Some(())
+}
+
+fn transcript_scalar(t: &mut Transcript, label: &'static [u8]) -> Scalar {
+    let mut buf = [0u8; 64];
+    t.challenge_bytes(label, &mut buf);
+    Scalar::from_bytes_mod_order_wide(&buf)
+}
+
+/// 1 + x + … + x^(n−1).
+fn sum_of_powers(x: &Scalar, n: usize) -> Scalar {
+    let mut sum = Scalar::ZERO;
+    let mut pow = Scalar::ONE;
+    for _ in 0..n {
+        sum += pow;
+        pow *= x;
+    }
+    sum
+}
+
+/// Range proof verification equations folded into one multiscalar check.
+/// `g`/`h` hold the coefficients of the party generators, party-major.
+struct RangeBatch {
+    n: usize,
+    b: Scalar,
+    b_blinding: Scalar,
+    g: Vec<Scalar>,
+    h: Vec<Scalar>,
+    scalars: Vec<Scalar>,
+    points: Vec<Option<RistrettoPoint>>,
+}
+
+impl RangeBatch {
+    fn new(n: usize) -> Self {
+        Self { n, b: Scalar::ZERO, b_blinding: Scalar::ZERO, g: Vec::new(), h: Vec::new(), scalars: Vec::new(), points: Vec::new() }
+    }
+
+    /// Replay `proof`'s transcript over `commitments` and add `weight` times
+    /// its verification equation. `None` if the proof is malformed.
+    fn add(&mut self, proof: &RangeProof, t: &mut Transcript, commitments: &[CompressedRistretto], weight: Scalar) -> Option<()> {
+        let (n, m) = (self.n, commitments.len());
+        let nm = n * m;
+        let lg_n = proof.l_r.len();
+        if 1usize << lg_n != nm { return None; }
+
+        t.append_message(b"dom-sep", b"rangeproof v1");
+        t.append_u64(b"n", n as u64);
+        t.append_u64(b"m", m as u64);
+        for v in commitments { t.append_message(b"V", v.as_bytes()); }
+        append_proof_point(t, b"A", &proof.a)?;
+        append_proof_point(t, b"S", &proof.s)?;
+        let y = transcript_scalar(t, b"y");
+        let z = transcript_scalar(t, b"z");
+        append_proof_point(t, b"T_1", &proof.t_1)?;
+        append_proof_point(t, b"T_2", &proof.t_2)?;
+        let x = transcript_scalar(t, b"x");
+        t.append_message(b"t_x", proof.t_x.as_bytes());
+        t.append_message(b"t_x_blinding", proof.t_x_blinding.as_bytes());
+        t.append_message(b"e_blinding", proof.e_blinding.as_bytes());
+        let w = transcript_scalar(t, b"w");
+
+        t.append_message(b"dom-sep", b"ipp v1");
+        t.append_u64(b"n", nm as u64);
+        let mut u = Vec::with_capacity(lg_n);
+        for (l, r) in proof.l_r.iter() {
+            append_proof_point(t, b"L", l)?;
+            append_proof_point(t, b"R", r)?;
+            u.push(transcript_scalar(t, b"u"));
+        }
+        t.append_message(b"a", proof.ipp_a.as_bytes());
+        t.append_message(b"b", proof.ipp_b.as_bytes());
+        let c = transcript_scalar(t, b"c");
+
+        let mut u_inv = u.clone();
+        let all_inv = Scalar::batch_invert(&mut u_inv);
+        let u_sq: Vec<Scalar> = u.iter().map(|u| u * u).collect();
+        let u_inv_sq: Vec<Scalar> = u_inv.iter().map(|u| u * u).collect();
+        let mut s = Vec::with_capacity(nm);
+        s.push(all_inv);
+        for i in 1..nm {
+            let lg_i = (usize::BITS - 1 - i.leading_zeros()) as usize;
+            s.push(s[i - (1 << lg_i)] * u_sq[(lg_n - 1) - lg_i]);
+        }
+
+        let (a, b, zz) = (proof.ipp_a, proof.ipp_b, z * z);
+        let cw = c * weight;
+        self.scalars.extend([weight, weight * x, cw * x, cw * x * x]);
+        self.points.extend([proof.a.decompress(), proof.s.decompress(), proof.t_1.decompress(), proof.t_2.decompress()]);
+        for ((l, r), (l_sq, r_sq)) in proof.l_r.iter().zip(u_sq.iter().zip(u_inv_sq.iter())) {
+            self.scalars.extend([weight * l_sq, weight * r_sq]);
+            self.points.extend([l.decompress(), r.decompress()]);
+        }
+        let mut z_j = Scalar::ONE;
+        for v in commitments {
+            self.scalars.push(cw * zz * z_j);
+            self.points.push(v.decompress());
+            z_j *= z;
+        }
+
+        let sum_2 = sum_of_powers(&Scalar::from(2u64), n);
+        let delta = (z - zz) * sum_of_powers(&y, nm) - zz * z * sum_2 * sum_of_powers(&z, m);
+        self.b += weight * (w * (proof.t_x - a * b) + c * (delta - proof.t_x));
+        self.b_blinding -= weight * (proof.e_blinding + c * proof.t_x_blinding);
+
+        if self.g.len() < nm {
+            self.g.resize(nm, Scalar::ZERO);
+            self.h.resize(nm, Scalar::ZERO);
+        }
+        let y_inv = y.invert();
+        let (mut y_inv_k, mut z_j) = (Scalar::ONE, Scalar::ONE);
+        for j in 0..m {
+            let mut two_i = Scalar::ONE;
+            for i in 0..n {
+                let k = j * n + i;
+                self.g[k] -= weight * (z + a * s[k]);
+                self.h[k] += weight * (z + y_inv_k * (zz * z_j * two_i - b * s[nm - 1 - k]));
+                y_inv_k *= y_inv;
+                two_i += two_i;
+            }
+            z_j *= z;
+        }
+        Some(())
+    }
+
+    /// Whether the folded equation holds.
+    fn verify(mut self) -> bool {
+        use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+        let parties = self.g.len() / self.n;
+        let derived: Vec<generators::PartyGens> =
+            (generators::MAX_RANGE_PARTIES..parties).map(generators::PartyGens::derive).collect();
+        for j in 0..parties {
+            let gens = generators::party_gens(j).unwrap_or_else(|| &derived[j - generators::MAX_RANGE_PARTIES]);
+            for i in 0..self.n {
+                self.scalars.extend([self.g[j * self.n + i], self.h[j * self.n + i]]);
+                self.points.extend([Some(gens.g[i]), Some(gens.h[i])]);
+            }
+        }
+        self.scalars.extend([self.b, self.b_blinding]);
+        self.points.extend([Some(G), Some(generator_h())]);
+        RistrettoPoint::optional_multiscalar_mul(self.scalars, self.points).is_some_and(|p| p.is_identity())
+    }
+}
+
+/// Parse one item, pad its commitments to a power of two with the
+/// zero-value commitments `PAD_R_i·H` the wallet pads with, and start its
+/// transcript bound to the public inputs.
+fn range_item(
     range_proof: &[u8],
     commitments: &[[u8; 32]],
     public_inputs: &[u8],
-    nbits: u32,
-) -> bool {
-    if commitments.is_empty() { return false; }
-    if nbits == 0 || nbits > 64 { return false; }
-    let mut cmts: alloc::vec::Vec<CompressedRistrettoNG> = alloc::vec::Vec::with_capacity(commitments.len());
-    for c in commitments.iter() { cmts.push(CompressedRistrettoNG(*c)); }
-    let rp = match RangeProof::from_bytes(range_proof) { Ok(p) => p, Err(_) => return false };
-    let h = {
-        let mut hasher = Sha512::new();
-        sha2::Digest::update(&mut hasher, b"VERIFIER_H_GENERATOR");
-        let out = hasher.finalize();
-        let mut bytes = [0u8; 64];
-        bytes.copy_from_slice(&out);
-        RistrettoPointNG::from_uniform_bytes(&bytes)
-    };
-    let pc_gens = PedersenGens { B: G_NG, B_blinding: h };
-    let m = cmts.len();
-    let party_capacity = m.next_power_of_two();
-    let bp_gens = BulletproofGens::new(nbits as usize, party_capacity);
+) -> Option<(RangeProof, Vec<CompressedRistretto>, Transcript)> {
+    if commitments.is_empty() { return None; }
+    let proof = RangeProof::from_bytes(range_proof)?;
     let pi_hash = blake2_256(public_inputs);
+    let mut cmts: Vec<CompressedRistretto> = commitments.iter().map(|c| CompressedRistretto(*c)).collect();
+    let h = generator_h();
+    for i in 0..(commitments.len().next_power_of_two() - commitments.len()) {
+        let mut hasher = Sha512::new();
+        sha2::Digest::update(&mut hasher, b"PAD_R");
+        sha2::Digest::update(&mut hasher, pi_hash);
+        sha2::Digest::update(&mut hasher, (i as u32).to_le_bytes());
+        let mut w = [0u8; 64];
+        w.copy_from_slice(&hasher.finalize());
+        cmts.push((Scalar::from_bytes_mod_order_wide(&w) * h).compress());
+    }
     let mut t = Transcript::new(b"NULLA_BULLETPROOF_RANGE");
     t.append_message(b"pi_hash", &pi_hash);
-    if party_capacity > m {
-        let pad = party_capacity - m;
-        for i in 0..pad {
-            let mut hasher = Sha512::new();
-            sha2::Digest::update(&mut hasher, b"PAD_R");
-            sha2::Digest::update(&mut hasher, &pi_hash);
-            sha2::Digest::update(&mut hasher, &(i as u32).to_le_bytes());
-            let out = hasher.finalize();
-            let mut w = [0u8; 64];
-            w.copy_from_slice(&out);
-            let r = curve25519_dalek_ng::scalar::Scalar::from_bytes_mod_order_wide(&w);
-            let h = {
-                let mut hasher = Sha512::new();
-                sha2::Digest::update(&mut hasher, b"VERIFIER_H_GENERATOR");
-                let out = hasher.finalize();
-                let mut bytes = [0u8; 64];
-                bytes.copy_from_slice(&out);
-                RistrettoPointNG::from_uniform_bytes(&bytes)
-            };
-            let c = r * h;
-            cmts.push(c.compress());
+    Some((proof, cmts, t))
+}
+
+pub fn verify_range_proof(
+    range_proof: &[u8],
+    commitments: &[[u8; 32]],
+    public_inputs: &[u8],
+    nbits: u32,
+) -> bool {
+    verify_range_proofs(&[(range_proof, commitments, public_inputs)], nbits)
+}
+
+/// Batched range verification over items sharing one generator table;
+/// true only if every item verifies. An empty batch is rejected.
+pub fn verify_range_proofs(items: &[RangeProofItem<'_>], nbits: u32) -> bool {
+    if !matches!(nbits, 8 | 16 | 32 | 64) || items.is_empty() { return false; }
+    let mut parsed = Vec::with_capacity(items.len());
+    for (range_proof, commitments, public_inputs) in items {
+        match range_item(range_proof, commitments, public_inputs) {
+            Some(item) => parsed.push(item),
+            None => return false,
         }
     }
-    let mut rng = DeterministicRng::new(pi_hash);
-    rp.verify_multiple_with_rng(&bp_gens, &pc_gens, &mut t, &cmts, nbits as usize, &mut rng).is_ok()
+    // Item weights are challenges over every item, so no item can be
+    // chosen to cancel another's equation.
+    let mut weights = Transcript::new(b"NULLA_BULLETPROOF_BATCH");
+    weights.append_u64(b"items", items.len() as u64);
+    for (range_proof, _, public_inputs) in items {
+        weights.append_message(b"proof", range_proof);
+        weights.append_message(b"pi_hash", &blake2_256(public_inputs));
+    }
+    for (_, cmts, _) in parsed.iter() {
+        for c in cmts { weights.append_message(b"V", c.as_bytes()); }
+    }
+    let mut batch = RangeBatch::new(nbits as usize);
+    for (k, (proof, cmts, t)) in parsed.iter_mut().enumerate() {
+        let weight = if k == 0 { Scalar::ONE } else { transcript_scalar(&mut weights, b"weight") };
+        if batch.add(proof, t, cmts, weight).is_none() { return false; }
+    }
+    batch.verify()
 }
 
 pub fn verify_commitment(value: u64, blinding: [u8; 32], commitment: [u8; 32]) -> bool {
@@ -239,6 +570,82 @@ pub fn pedersen_subtract(c_a: &[u8; 32], c_b: &[u8; 32]) -> Option<[u8; 32]> {
     Some((pa - pb).compress().to_bytes())
 }
 
+// ===================================================================
+//  Proof envelope — versioned one-of-many proofs
+//
+//  envelope = MAGIC (4) ‖ SCALE(ProofHeader) ‖ proof bytes
+//
+//  The header names the statement and its version (transcript domain,
+//  generators, serialization). `one_of_many::verify` looks it up in
+//  REGISTRY and dispatches on the version; the group size stays in the
+//  proof body. Proofs without the magic predate the envelope and are
+//  read as version 1: their first byte is m, never 'N'.
+//
+//  The runtime keeps a retired-version set on top of REGISTRY, letting
+//  governance drop a version once wallets have moved off it.
+// ===================================================================
+pub mod envelope {
+    use alloc::vec::Vec;
+    use parity_scale_codec::{Decode, Encode};
+
+    /// Envelope prefix, shared with the ProofHub verifier.
+    pub const MAGIC: [u8; 4] = *b"NZKP";
+
+    /// Version of proofs without an envelope.
+    pub const LEGACY_VERSION: u16 = 1;
+
+    /// Version `one_of_many::prove` emits.
+    pub const CURRENT_VERSION: u16 = 1;
+
+    /// The statement a proof is for. The discriminant is the on-chain key
+    /// of the runtime's retired-version set.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum AirId {
+        #[codec(index = 0)]
+        OneOfMany = 0,
+    }
+
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct ProofHeader {
+        pub air: AirId,
+        pub version: u16,
+    }
+
+    /// Supported (air, version) pairs. A version is added here together
+    /// with its dispatch arm in the verify function.
+    pub const REGISTRY: &[ProofHeader] = &[
+        ProofHeader { air: AirId::OneOfMany, version: 1 },
+    ];
+
+    /// Prefix `proof` with an envelope header.
+    pub fn seal(air: AirId, version: u16, proof: &[u8]) -> Vec<u8> {
+        let header = ProofHeader { air, version };
+        let mut out = Vec::with_capacity(MAGIC.len() + header.encoded_size() + proof.len());
+        out.extend_from_slice(&MAGIC);
+        header.encode_to(&mut out);
+        out.extend_from_slice(proof);
+        out
+    }
+
+    /// Split a proof for `air` into (version, proof bytes). None when the
+    /// header is malformed, names another statement or is not in REGISTRY.
+    pub fn open(proof: &[u8], air: AirId) -> Option<(u16, &[u8])> {
+        let mut rest = match proof.strip_prefix(&MAGIC[..]) {
+            Some(rest) => rest,
+            None => return Some((LEGACY_VERSION, proof)),
+        };
+        let header = ProofHeader::decode(&mut rest).ok()?;
+        if header.air != air || !REGISTRY.contains(&header) { return None; }
+        Some((header.version, rest))
+    }
+
+    /// Version of a proof for `air`, or None if it would be rejected by
+    /// `open`. Lets the runtime check its retired set before verifying.
+    pub fn version_of(proof: &[u8], air: AirId) -> Option<u16> {
+        open(proof, air).map(|(version, _)| version)
+    }
+}
+
 // ===================================================================
 //  Phase 10 — Lelantus-style one-of-many proofs (Groth–Kohlweiss 2015)
 //
@@ -270,12 +677,7 @@ pub mod one_of_many {
 
     /// Serial generator G1 = hash-to-point("NULLA_SERIAL_GENERATOR").
     pub fn generator_g1() -> RistrettoPoint {
-        let mut hasher = Sha512::new();
-        sha2::Digest::update(&mut hasher, b"NULLA_SERIAL_GENERATOR");
-        let out = hasher.finalize();
-        let mut bytes = [0u8; 64];
-        bytes.copy_from_slice(&out);
-        RistrettoPoint::from_uniform_bytes(&bytes)
+        generators::get().g1
     }
 
     /// Coin v2 commitment: C = s·G1 + v·G + r·H.
@@ -296,6 +698,7 @@ pub mod one_of_many {
         res
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn fs_challenge(
         ghash: &[u8; 32],
         serial: &[u8; 32],
@@ -359,8 +762,9 @@ pub mod one_of_many {
     /// `context` binds the proof to the spending transaction (e.g. BLAKE2-256
     /// of the SCALE-encoded public inputs) — prevents replay.
     ///
-    /// Returns the serialized proof, or None on bad inputs
+    /// Returns the serialized proof in a version-1 envelope, or None on bad inputs
     /// (group not a power of two, index out of range, undecodable coin).
+    #[allow(clippy::too_many_arguments)]
     pub fn prove(
         coins: &[[u8; 32]],
         index: usize,
@@ -414,7 +818,6 @@ pub mod one_of_many {
         for i in 0..n {
             let mut coeffs = alloc::vec![Scalar::ZERO; m + 1];
             coeffs[0] = Scalar::ONE;
-            let mut deg = 0usize;
             for j in 0..m {
                 let ij = (i >> j) & 1;
                 let lj = Scalar::from(bits[j]);
@@ -426,12 +829,12 @@ pub mod one_of_many {
                 };
                 // coeffs *= (c1·x + c0)
                 let mut next = alloc::vec![Scalar::ZERO; m + 1];
-                for k in 0..=deg {
+                // deg(coeffs) == j before this factor.
+                for k in 0..=j {
                     next[k] += coeffs[k] * c0;
                     next[k + 1] += coeffs[k] * c1;
                 }
                 coeffs = next;
-                deg += 1;
             }
             p.push(coeffs);
         }
@@ -462,8 +865,8 @@ pub mod one_of_many {
         for _ in 0..m { xm *= x; }
         let mut zd = r * xm;
         let mut xk = Scalar::ONE;
-        for k in 0..m {
-            zd -= rho[k] * xk;
+        for rho_k in rho.iter().take(m) {
+            zd -= *rho_k * xk;
             xk *= x;
         }
 
@@ -477,7 +880,7 @@ pub mod one_of_many {
             for s in v.iter() { out.extend_from_slice(&s.to_bytes()); }
         }
         out.extend_from_slice(&zd.to_bytes());
-        Some(out)
+        Some(envelope::seal(envelope::AirId::OneOfMany, envelope::CURRENT_VERSION, &out))
     }
 
     /// Verify a one-of-many spend proof against a coin group.
@@ -486,7 +889,10 @@ pub mod one_of_many {
     ///   1. x·cl_j + ca_j == f_j·G + za_j·H              (bit consistency)
     ///   2. (x−f_j)·cl_j + cb_j == zb_j·H                (bit is 0 or 1)
     ///   3. Σ_i (Π_j f'_{j,i_j})·D_i − Σ_k x^k·G_k == z_d·H   (membership)
+    ///
     /// where f'_{j,1} = f_j and f'_{j,0} = x − f_j.
+    ///
+    /// `proof` may be enveloped (see `envelope`) or raw (version 1).
     pub fn verify(
         proof: &[u8],
         coins: &[[u8; 32]],
@@ -494,6 +900,20 @@ pub mod one_of_many {
         price: u64,
         change: &[u8; 32],
         context: &[u8],
+    ) -> bool {
+        match envelope::open(proof, envelope::AirId::OneOfMany) {
+            Some((1, body)) => verify_v1(body, coins, serial, price, change, context),
+            _ => false,
+        }
+    }
+
+    fn verify_v1(
+        proof: &[u8],
+        coins: &[[u8; 32]],
+        serial: &[u8; 32],
+        price: u64,
+        change: &[u8; 32],
+        context: &[u8],
     ) -> bool {
         let n = coins.len();
         if n < 2 || !n.is_power_of_two() { return false; }
@@ -549,13 +969,12 @@ pub mod one_of_many {
         // exponent_i = Π_j f'_{j, i_j};  computed in O(N) by doubling table.
         let mut exps = alloc::vec![Scalar::ONE; n];
         let mut width = 1usize;
-        for j in 0..m {
-            let f1 = fj[j];
-            let f0 = x - fj[j];
+        for &f1 in fj.iter().take(m) {
+            let f0 = x - f1;
             // expand: indices with bit j set multiply by f1, others by f0
             for i in (0..width).rev() {
                 exps[i + width] = exps[i] * f1;
-                exps[i] = exps[i] * f0;
+                exps[i] *= f0;
             }
             width <<= 1;
         }
@@ -720,24 +1139,51 @@ pub mod one_of_many {
     pub fn pad_coin(group_id: u32, i: u32) -> [u8; 32] {
         let mut hasher = Sha512::new();
         sha2::Digest::update(&mut hasher, b"NULLA_GROUP_PAD");
-        sha2::Digest::update(&mut hasher, &group_id.to_le_bytes());
-        sha2::Digest::update(&mut hasher, &i.to_le_bytes());
+        sha2::Digest::update(&mut hasher, group_id.to_le_bytes());
+        sha2::Digest::update(&mut hasher, i.to_le_bytes());
         let out = hasher.finalize();
         let mut bytes = [0u8; 64];
         bytes.copy_from_slice(&out);
         RistrettoPoint::from_uniform_bytes(&bytes).compress().to_bytes()
     }
 
+    /// Pad coins of the last group padded: `coins[k]` is
+    /// `pad_coin(group_id, from + k)`. The open group is padded on every
+    /// spend while it fills, from an index that only grows, so successive
+    /// calls reuse this tail instead of hashing to the curve again.
+    struct PadCache {
+        group_id: u32,
+        from: u32,
+        coins: Vec<[u8; 32]>,
+    }
+
+    static PAD_CACHE: spin::Mutex<Option<PadCache>> = spin::Mutex::new(None);
+
     /// Pad a partial group to the next power of two (minimum 2) with
     /// unspendable pad coins derived from `group_id`.
     pub fn pad_group(coins: &[[u8; 32]], group_id: u32) -> Vec<[u8; 32]> {
         let n = core::cmp::max(coins.len(), 2).next_power_of_two();
         let mut out = Vec::with_capacity(n);
         out.extend_from_slice(coins);
-        let mut i = coins.len() as u32;
-        while out.len() < n {
-            out.push(pad_coin(group_id, i));
-            i += 1;
+        let (from, to) = (coins.len() as u32, n as u32);
+        if from == to { return out; }
+
+        let mut cache = PAD_CACHE.lock();
+        match &mut *cache {
+            Some(c) if c.group_id == group_id && c.from <= from => {
+                let mut i = c.from + c.coins.len() as u32;
+                while i < to {
+                    c.coins.push(pad_coin(group_id, i));
+                    i += 1;
+                }
+                let lo = (from - c.from) as usize;
+                out.extend_from_slice(&c.coins[lo..lo + (to - from) as usize]);
+            }
+            _ => {
+                let fresh: Vec<[u8; 32]> = (from..to).map(|i| pad_coin(group_id, i)).collect();
+                out.extend_from_slice(&fresh);
+                *cache = Some(PadCache { group_id, from, coins: fresh });
+            }
         }
         out
     }
@@ -795,6 +1241,40 @@ mod one_of_many_tests {
         assert!(prove(&coins, 8, &serial, price, &NO_CHANGE, &blinding, ctx, [0x42u8; 32]).is_none());
     }
 
+    #[test]
+    fn gk_envelope_versions() {
+        use super::envelope::{self, AirId};
+        let serial = [0x06u8; 32];
+        let blinding = [0x0au8; 32];
+        let coins = mk_group(8, 2, &serial, 700, &blinding);
+        let ctx = b"envelope-tx";
+        let sealed = prove(&coins, 2, &serial, 700, &NO_CHANGE, &blinding, ctx, [0x43u8; 32]).expect("prove");
+        assert_eq!(envelope::version_of(&sealed, AirId::OneOfMany), Some(envelope::CURRENT_VERSION));
+        // The bare proof is read as version 1.
+        let (_, raw) = envelope::open(&sealed, AirId::OneOfMany).expect("registered");
+        assert_eq!(raw.len(), 1 + (7 * 3 + 1) * 32);
+        assert!(verify(raw, &coins, &serial, 700, &NO_CHANGE, ctx));
+        // An unregistered version is rejected even with a valid body.
+        let v2 = envelope::seal(AirId::OneOfMany, 2, raw);
+        assert_eq!(envelope::version_of(&v2, AirId::OneOfMany), None);
+        assert!(!verify(&v2, &coins, &serial, 700, &NO_CHANGE, ctx));
+    }
+
+    #[test]
+    fn pad_group_cache_matches_pad_coin() {
+        let direct = |len: usize, group: u32| {
+            let n = core::cmp::max(len, 2).next_power_of_two();
+            (len as u32..n as u32).map(|i| pad_coin(group, i)).collect::<Vec<_>>()
+        };
+        let coins: Vec<[u8; 32]> = (0..9u8).map(|i| [i; 32]).collect();
+        // Growing group, a switch to another group, then an older snapshot.
+        for &(len, group) in &[(3, 7), (5, 7), (9, 7), (1, 8), (2, 7), (0, 7)] {
+            let padded = pad_group(&coins[..len], group);
+            assert_eq!(&padded[..len], &coins[..len]);
+            assert_eq!(padded[len..], direct(len, group)[..]);
+        }
+    }
+
     #[test]
     fn gk_change_flow() {
         // Coin v = 10_000, price = 6_000, change v' = 4_000.
@@ -852,3 +1332,111 @@ mod one_of_many_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod range_tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use bulletproofs::{BulletproofGens, PedersenGens};
+    use curve25519_dalek_ng::constants::RISTRETTO_BASEPOINT_POINT as G_NG;
+    use curve25519_dalek_ng::ristretto::CompressedRistretto as CompressedRistrettoNG;
+    use curve25519_dalek_ng::scalar::Scalar as ScalarNG;
+    use parity_scale_codec::Encode;
+
+    fn hash_to_point(label: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha512::new();
+        sha2::Digest::update(&mut hasher, label);
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&hasher.finalize());
+        RistrettoPoint::from_uniform_bytes(&bytes).compress().to_bytes()
+    }
+
+    /// Range proof over `values` bound to `public_inputs`, made by the
+    /// `bulletproofs` crate with the wallet-side transcript. Returns (proof
+    /// bytes, commitments).
+    fn prove_range(values: &[u64], blindings: &[[u8; 32]], public_inputs: &[u8]) -> (Vec<u8>, Vec<[u8; 32]>) {
+        let h = CompressedRistrettoNG(generators::H_COMPRESSED).decompress().unwrap();
+        let pc_gens = PedersenGens { B: G_NG, B_blinding: h };
+        let pi_hash = blake2_256(public_inputs);
+        let mut t = Transcript::new(b"NULLA_BULLETPROOF_RANGE");
+        t.append_message(b"pi_hash", &pi_hash);
+        let mut vs = values.to_vec();
+        let mut rs: Vec<ScalarNG> = blindings.iter().map(|b| ScalarNG::from_bytes_mod_order(*b)).collect();
+        // Pad to a power of two with zero values under the PAD_R blindings.
+        let parties = values.len().next_power_of_two();
+        for i in 0..(parties - values.len()) {
+            let mut hasher = Sha512::new();
+            sha2::Digest::update(&mut hasher, b"PAD_R");
+            sha2::Digest::update(&mut hasher, pi_hash);
+            sha2::Digest::update(&mut hasher, (i as u32).to_le_bytes());
+            let mut w = [0u8; 64];
+            w.copy_from_slice(&hasher.finalize());
+            vs.push(0);
+            rs.push(ScalarNG::from_bytes_mod_order_wide(&w));
+        }
+        let bp_gens = BulletproofGens::new(64, parties);
+        let mut rng = DeterministicRng::new([0x5au8; 32]);
+        let (proof, cmts) =
+            bulletproofs::RangeProof::prove_multiple_with_rng(&bp_gens, &pc_gens, &mut t, &vs, &rs, 64, &mut rng)
+                .expect("prove");
+        (proof.to_bytes(), cmts[..values.len()].iter().map(|c| c.to_bytes()).collect())
+    }
+
+    #[test]
+    fn baked_generators_match_hash_to_point() {
+        assert_eq!(generators::H_COMPRESSED, hash_to_point(b"VERIFIER_H_GENERATOR"));
+        assert_eq!(generators::G1_COMPRESSED, hash_to_point(b"NULLA_SERIAL_GENERATOR"));
+        assert_eq!(generator_h().compress().to_bytes(), generators::H_COMPRESSED);
+        assert_eq!(one_of_many::generator_g1().compress().to_bytes(), generators::G1_COMPRESSED);
+    }
+
+    #[test]
+    fn range_deposit_and_submit_shapes() {
+        // deposit_public shape: one commitment, empty public inputs.
+        let (p1, c1) = prove_range(&[1_000], &[[0x11u8; 32]], &[]);
+        assert!(verify_range_proof(&p1, &c1, &[], 64));
+        assert!(verify_commitment(1_000, [0x11u8; 32], c1[0]));
+
+        // submit_proof shape: three outputs padded to four, bound to the
+        // public inputs.
+        let pi = b"submit-proof-pi";
+        let (p2, c2) = prove_range(&[400, 500, 100], &[[0x22u8; 32], [0x33u8; 32], [0x44u8; 32]], pi);
+        assert!(verify_range_proof(&p2, &c2, pi, 64));
+        assert!(!verify_range_proof(&p2, &c2, b"other-pi", 64));
+        assert!(!verify_range_proof(&p2, &c2[..2], pi, 64));
+        assert!(!verify_range_proof(&p1, &c1, &[], 0));
+    }
+
+    #[test]
+    fn range_batch_rejects_any_bad_item() {
+        let (p1, c1) = prove_range(&[1_000], &[[0x11u8; 32]], &[]);
+        let pi = b"submit-proof-pi";
+        let (p2, c2) = prove_range(&[400, 500, 100], &[[0x22u8; 32], [0x33u8; 32], [0x44u8; 32]], pi);
+        assert!(verify_range_proofs(&[(&p1, &c1, &[]), (&p2, &c2, pi)], 64));
+        assert!(!verify_range_proofs(&[(&p1, &c1, &[]), (&p2, &c2, b"other-pi")], 64));
+        assert!(!verify_range_proofs(&[(&p1, &c2[..1], &[]), (&p2, &c2, pi)], 64));
+        let mut bad = p2.clone();
+        bad[4 * 32] ^= 1;
+        assert!(!verify_range_proofs(&[(&p1, &c1, &[]), (&bad, &c2, pi)], 64));
+        assert!(!verify_range_proofs(&[], 64));
+    }
+
+    #[test]
+    fn range_proof_past_the_baked_parties() {
+        let values: Vec<u64> = (1..=9).collect();
+        let blindings: Vec<[u8; 32]> = (1..=9u8).map(|b| [b; 32]).collect();
+        let (p, c) = prove_range(&values, &blindings, b"pi");
+        assert!(verify_range_proof(&p, &c, b"pi", 64));
+    }
+
+    #[test]
+    fn baked_party_tables_match_generators_chain() {
+        for party in 0..generators::MAX_RANGE_PARTIES {
+            let baked = generators::party_gens(party).unwrap();
+            let derived = generators::PartyGens::derive(party);
+            assert!(baked.g == derived.g && baked.h == derived.h);
+        }
+        assert!(generators::party_gens(generators::MAX_RANGE_PARTIES).is_none());
+        assert!(core::ptr::eq(generators::party_gens(2).unwrap(), generators::party_gens(2).unwrap()));
+    }
+}